@@ -0,0 +1,415 @@
+use zellij_tile::prelude::actions::{Action, SearchDirection};
+use zellij_tile::prelude::layout::RunPlugin;
+use zellij_tile::prelude::*;
+
+// the order in which keybinds are looked up, so that those of the normal mode come first
+const MODES: &[InputMode] = &[
+    InputMode::Normal,
+    InputMode::Locked,
+    InputMode::Pane,
+    InputMode::Tab,
+    InputMode::Resize,
+    InputMode::Move,
+    InputMode::Scroll,
+    InputMode::EnterSearch,
+    InputMode::Search,
+    InputMode::RenameTab,
+    InputMode::RenamePane,
+    InputMode::Session,
+    InputMode::Tmux,
+    InputMode::Prompt,
+];
+
+const DIRECTIONS: &[Direction] = &[
+    Direction::Left,
+    Direction::Right,
+    Direction::Up,
+    Direction::Down,
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EntryKind {
+    Action,
+    Plugin,
+    Layout,
+    Session,
+}
+
+impl EntryKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            EntryKind::Action => "Action",
+            EntryKind::Plugin => "Plugin",
+            EntryKind::Layout => "Layout",
+            EntryKind::Session => "Session",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Command {
+    Actions(Vec<Action>),
+    NewTabsWithLayout(String), // stringified layout
+    SwitchSession(String),     // session name
+}
+
+/// Something that can be run from the palette
+#[derive(Debug, Clone)]
+pub struct Entry {
+    pub kind: EntryKind,
+    pub name: String,
+    keybinds: Vec<(InputMode, Key)>,
+    command: Command,
+}
+
+impl Entry {
+    fn new(kind: EntryKind, name: String, command: Command) -> Self {
+        Entry {
+            kind,
+            name,
+            keybinds: vec![],
+            command,
+        }
+    }
+    pub fn run(&self) {
+        match &self.command {
+            Command::Actions(actions) => {
+                for action in actions {
+                    run_action(action.clone());
+                }
+            },
+            Command::NewTabsWithLayout(stringified_layout) => {
+                new_tabs_with_layout(stringified_layout)
+            },
+            Command::SwitchSession(session_name) => switch_session(Some(session_name)),
+        }
+    }
+    /// The keys bound to this entry, those of the normal mode without the name of the mode
+    pub fn keybinds(&self) -> String {
+        self.keybinds
+            .iter()
+            .map(|(mode, key)| match mode {
+                InputMode::Normal => key.to_string(),
+                _ => format!("{:?}: {}", mode, key),
+            })
+            .collect::<Vec<String>>()
+            .join(", ")
+    }
+    fn add_keybind(&mut self, mode: InputMode, key: Key) {
+        // keys shared by all modes are only listed once, for the normal mode
+        let is_bound_in_normal_mode = self
+            .keybinds
+            .iter()
+            .any(|(m, k)| *m == InputMode::Normal && *k == key);
+        if !is_bound_in_normal_mode {
+            self.keybinds.push((mode, key));
+        }
+    }
+}
+
+/// Every action of the catalog, launching every plugin alias and every other action bound to a
+/// key, with the keys bound to them in each mode
+pub fn action_entries(mode_info: &ModeInfo, plugin_aliases: &[String]) -> Vec<Entry> {
+    let mut entries: Vec<Entry> = catalog()
+        .into_iter()
+        .filter(|actions| can_run_from_palette(actions))
+        .map(|actions| {
+            Entry::new(
+                EntryKind::Action,
+                describe(&actions),
+                Command::Actions(actions),
+            )
+        })
+        .chain(
+            plugin_aliases
+                .iter()
+                .filter_map(|alias| plugin_entry(alias)),
+        )
+        .collect();
+    for mode in MODES {
+        for (key, actions) in mode_info.get_keybinds_for_mode(*mode) {
+            let actions = without_trailing_mode_switch(actions);
+            if !can_run_from_palette(&actions) {
+                continue;
+            }
+            let existing_entry = entries.iter_mut().find(|e| match &e.command {
+                Command::Actions(entry_actions) => *entry_actions == actions,
+                _ => false,
+            });
+            match existing_entry {
+                Some(entry) => entry.add_keybind(*mode, key),
+                None => {
+                    let mut entry = Entry::new(
+                        EntryKind::Action,
+                        describe(&actions),
+                        Command::Actions(actions),
+                    );
+                    entry.add_keybind(*mode, key);
+                    entries.push(entry);
+                },
+            }
+        }
+    }
+    entries
+}
+
+pub fn layout_entries(available_layouts: &[LayoutInfo]) -> Vec<Entry> {
+    available_layouts
+        .iter()
+        .map(|layout| {
+            Entry::new(
+                EntryKind::Layout,
+                format!("Open {} in new tabs", layout.name),
+                Command::NewTabsWithLayout(layout.stringified_layout.clone()),
+            )
+        })
+        .collect()
+}
+
+pub fn session_entries(
+    session_infos: &[SessionInfo],
+    resurrectable_sessions: &[(String, std::time::Duration)],
+) -> Vec<Entry> {
+    let running_sessions = session_infos
+        .iter()
+        .filter(|s| !s.is_current_session)
+        .map(|s| {
+            Entry::new(
+                EntryKind::Session,
+                format!("Switch to {}", s.name),
+                Command::SwitchSession(s.name.clone()),
+            )
+        });
+    let resurrectable_sessions = resurrectable_sessions.iter().map(|(name, _)| {
+        Entry::new(
+            EntryKind::Session,
+            format!("Resurrect {}", name),
+            Command::SwitchSession(name.clone()),
+        )
+    });
+    running_sessions.chain(resurrectable_sessions).collect()
+}
+
+fn plugin_entry(plugin_alias: &str) -> Option<Entry> {
+    let run_plugin = RunPlugin::from_url(&format!("zellij:{}", plugin_alias)).ok()?;
+    // opened like the session-manager is from the default keybinds
+    let action = Action::LaunchOrFocusPlugin(run_plugin, true, true, false, false);
+    Some(Entry::new(
+        EntryKind::Plugin,
+        format!("Launch {}", plugin_alias),
+        Command::Actions(vec![action]),
+    ))
+}
+
+// the actions that can be found without having them bound to a key
+fn catalog() -> Vec<Vec<Action>> {
+    let mut catalog: Vec<Vec<Action>> = [
+        InputMode::Normal,
+        InputMode::Locked,
+        InputMode::Pane,
+        InputMode::Tab,
+        InputMode::Resize,
+        InputMode::Move,
+        InputMode::Scroll,
+        InputMode::EnterSearch,
+        InputMode::Session,
+        InputMode::Tmux,
+    ]
+    .iter()
+    .map(|mode| vec![Action::SwitchToMode(*mode)])
+    .collect();
+    catalog.append(&mut vec![
+        vec![Action::NewPane(None, None)],
+        vec![Action::NewPane(Some(Direction::Down), None)],
+        vec![Action::NewPane(Some(Direction::Right), None)],
+        vec![Action::CloseFocus],
+        vec![
+            Action::SwitchToMode(InputMode::RenamePane),
+            Action::PaneNameInput(vec![0]),
+        ],
+        vec![Action::FocusNextPane],
+        vec![Action::FocusPreviousPane],
+    ]);
+    for direction in DIRECTIONS {
+        catalog.push(vec![Action::MoveFocus(*direction)]);
+    }
+    catalog.push(vec![Action::MovePane(None)]);
+    catalog.push(vec![Action::MovePaneBackwards]);
+    for direction in DIRECTIONS {
+        catalog.push(vec![Action::MovePane(Some(*direction))]);
+    }
+    catalog.push(vec![Action::Resize(Resize::Increase, None)]);
+    catalog.push(vec![Action::Resize(Resize::Decrease, None)]);
+    for resize in [Resize::Increase, Resize::Decrease] {
+        for direction in DIRECTIONS {
+            catalog.push(vec![Action::Resize(resize, Some(*direction))]);
+        }
+    }
+    catalog.append(&mut vec![
+        vec![Action::ToggleFocusFullscreen],
+        vec![Action::TogglePaneFrames],
+        vec![Action::ToggleFloatingPanes],
+        vec![Action::TogglePaneEmbedOrFloating],
+        vec![Action::PreviousSwapLayout],
+        vec![Action::NextSwapLayout],
+        vec![Action::ClearScreen],
+        vec![Action::EditScrollback],
+        vec![Action::ScrollUp],
+        vec![Action::ScrollDown],
+        vec![Action::PageScrollUp],
+        vec![Action::PageScrollDown],
+        vec![Action::HalfPageScrollUp],
+        vec![Action::HalfPageScrollDown],
+        vec![Action::ScrollToTop],
+        vec![Action::ScrollToBottom],
+        vec![Action::NewTab(None, vec![], None, None, None)],
+        vec![Action::CloseTab],
+        vec![
+            Action::SwitchToMode(InputMode::RenameTab),
+            Action::TabNameInput(vec![0]),
+        ],
+        vec![Action::GoToNextTab],
+        vec![Action::GoToPreviousTab],
+        vec![Action::ToggleTab],
+        vec![Action::MoveTab(Direction::Left)],
+        vec![Action::MoveTab(Direction::Right)],
+        vec![Action::ToggleActiveSyncTab],
+        vec![Action::BreakPane],
+        vec![Action::BreakPaneLeft],
+        vec![Action::BreakPaneRight],
+        vec![Action::ToggleMouseMode],
+        vec![Action::Detach],
+        vec![Action::Quit],
+    ]);
+    catalog
+}
+
+// keybinds usually end by going back to the normal mode, which the palette does not need
+fn without_trailing_mode_switch(mut actions: Vec<Action>) -> Vec<Action> {
+    if actions.len() > 1 {
+        if let Some(Action::SwitchToMode(_)) = actions.last() {
+            actions.pop();
+        }
+    }
+    actions
+}
+
+// actions that only make sense in the middle of typing something in a pane, or that the
+// palette cannot send to the host
+fn can_run_from_palette(actions: &[Action]) -> bool {
+    actions.iter().all(|action| action.can_be_run_by_plugins())
+        && !matches!(
+            actions.first(),
+            None | Some(Action::NoOp)
+                | Some(Action::Write(..))
+                | Some(Action::WriteChars(..))
+                | Some(Action::PaneNameInput(..))
+                | Some(Action::TabNameInput(..))
+                | Some(Action::SearchInput(..))
+                | Some(Action::UndoRenamePane)
+                | Some(Action::UndoRenameTab)
+                | Some(Action::Confirm)
+                | Some(Action::Deny)
+                | Some(Action::SkipConfirm(..))
+        )
+}
+
+fn describe(actions: &[Action]) -> String {
+    match actions {
+        [Action::SwitchToMode(InputMode::RenamePane), Action::PaneNameInput(_)] => {
+            "Rename pane".to_owned()
+        },
+        [Action::SwitchToMode(InputMode::RenameTab), Action::TabNameInput(_)] => {
+            "Rename tab".to_owned()
+        },
+        _ => actions
+            .iter()
+            .map(describe_action)
+            .collect::<Vec<String>>()
+            .join(", then "),
+    }
+}
+
+fn describe_action(action: &Action) -> String {
+    match action {
+        Action::Quit => "Quit Zellij".to_owned(),
+        Action::Detach => "Detach from session".to_owned(),
+        Action::SwitchToMode(mode) => format!("Switch to {:?} mode", mode),
+        Action::NewPane(None, _) => "New pane".to_owned(),
+        Action::NewPane(Some(direction), _) => {
+            format!("New pane {}", direction_name(direction))
+        },
+        Action::CloseFocus => "Close focused pane".to_owned(),
+        Action::FocusNextPane => "Focus next pane".to_owned(),
+        Action::FocusPreviousPane => "Focus previous pane".to_owned(),
+        Action::SwitchFocus => "Switch focus".to_owned(),
+        Action::MoveFocus(direction) => format!("Move focus {}", direction_name(direction)),
+        Action::MoveFocusOrTab(direction) => {
+            format!("Move focus or tab {}", direction_name(direction))
+        },
+        Action::MovePane(None) => "Move pane".to_owned(),
+        Action::MovePane(Some(direction)) => format!("Move pane {}", direction_name(direction)),
+        Action::MovePaneBackwards => "Move pane backwards".to_owned(),
+        Action::Resize(resize, None) => format!("{} pane size", resize_name(resize)),
+        Action::Resize(resize, Some(direction)) => format!(
+            "{} pane size {}",
+            resize_name(resize),
+            direction_name(direction)
+        ),
+        Action::ToggleFocusFullscreen => "Toggle fullscreen".to_owned(),
+        Action::TogglePaneFrames => "Toggle pane frames".to_owned(),
+        Action::ToggleFloatingPanes => "Toggle floating panes".to_owned(),
+        Action::TogglePaneEmbedOrFloating => "Embed or float pane".to_owned(),
+        Action::PreviousSwapLayout => "Previous swap layout".to_owned(),
+        Action::NextSwapLayout => "Next swap layout".to_owned(),
+        Action::ClearScreen => "Clear screen".to_owned(),
+        Action::EditScrollback => "Edit scrollback".to_owned(),
+        Action::ScrollUp => "Scroll up".to_owned(),
+        Action::ScrollDown => "Scroll down".to_owned(),
+        Action::PageScrollUp => "Scroll up a page".to_owned(),
+        Action::PageScrollDown => "Scroll down a page".to_owned(),
+        Action::HalfPageScrollUp => "Scroll up half a page".to_owned(),
+        Action::HalfPageScrollDown => "Scroll down half a page".to_owned(),
+        Action::ScrollToTop => "Scroll to top".to_owned(),
+        Action::ScrollToBottom => "Scroll to bottom".to_owned(),
+        Action::Search(SearchDirection::Down) => "Search down".to_owned(),
+        Action::Search(SearchDirection::Up) => "Search up".to_owned(),
+        Action::SearchToggleOption(option) => format!("Toggle search option {:?}", option),
+        Action::NewTab(..) => "New tab".to_owned(),
+        Action::CloseTab => "Close tab".to_owned(),
+        Action::GoToNextTab => "Go to next tab".to_owned(),
+        Action::GoToPreviousTab => "Go to previous tab".to_owned(),
+        Action::GoToTab(index) => format!("Go to tab {}", index),
+        Action::GoToTabName(name, _) => format!("Go to tab {}", name),
+        Action::ToggleTab => "Toggle between last tabs".to_owned(),
+        Action::MoveTab(direction) => format!("Move tab {}", direction_name(direction)),
+        Action::ToggleActiveSyncTab => "Toggle sync of tab".to_owned(),
+        Action::BreakPane => "Break pane to new tab".to_owned(),
+        Action::BreakPaneLeft => "Break pane to tab on the left".to_owned(),
+        Action::BreakPaneRight => "Break pane to tab on the right".to_owned(),
+        Action::ToggleMouseMode => "Toggle mouse mode".to_owned(),
+        Action::LaunchOrFocusPlugin(run_plugin, ..) | Action::LaunchPlugin(run_plugin, ..) => {
+            format!("Launch {}", run_plugin.location)
+        },
+        Action::Run(run_command_action) => {
+            format!("Run {}", run_command_action.command.display())
+        },
+        _ => format!("{:?}", action),
+    }
+}
+
+fn direction_name(direction: &Direction) -> &'static str {
+    match direction {
+        Direction::Left => "left",
+        Direction::Right => "right",
+        Direction::Up => "up",
+        Direction::Down => "down",
+    }
+}
+
+fn resize_name(resize: &Resize) -> &'static str {
+    match resize {
+        Resize::Increase => "Increase",
+        Resize::Decrease => "Decrease",
+    }
+}
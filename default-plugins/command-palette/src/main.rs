@@ -0,0 +1,120 @@
+mod entries;
+mod palette;
+use zellij_tile::prelude::*;
+
+use std::collections::BTreeMap;
+
+use entries::{action_entries, layout_entries, session_entries};
+use palette::Palette;
+
+const SEARCH_PROMPT: &str = "Search: ";
+
+#[derive(Default)]
+struct State {
+    palette: Palette,
+    mode_info: ModeInfo,
+    plugin_aliases: Vec<String>,
+    search_input: TextInput,
+    rows: usize,
+}
+
+register_plugin!(State);
+
+impl ZellijPlugin for State {
+    fn load(&mut self, _configuration: BTreeMap<String, String>) {
+        subscribe(&[
+            EventType::ModeUpdate,
+            EventType::SessionUpdate,
+            EventType::AvailableLayoutInfo,
+            EventType::AvailablePluginAliases,
+            EventType::Key,
+        ]);
+        self.search_input =
+            TextInput::new().with_placeholder("actions, plugins, layouts and sessions");
+    }
+
+    fn update(&mut self, event: Event) -> bool {
+        match event {
+            Event::ModeUpdate(mode_info) => {
+                // the keybinds might have changed with the configuration
+                self.mode_info = mode_info;
+                self.palette.actions = action_entries(&self.mode_info, &self.plugin_aliases);
+            },
+            Event::SessionUpdate(session_infos, resurrectable_sessions) => {
+                self.palette.sessions = session_entries(&session_infos, &resurrectable_sessions);
+            },
+            Event::AvailableLayoutInfo(available_layouts) => {
+                self.palette.layouts = layout_entries(&available_layouts);
+            },
+            Event::AvailablePluginAliases(plugin_aliases) => {
+                self.plugin_aliases = plugin_aliases;
+                self.palette.actions = action_entries(&self.mode_info, &self.plugin_aliases);
+            },
+            Event::Key(key) => {
+                self.handle_key(key);
+            },
+            _ => return false,
+        };
+        self.palette.update_search_term(self.search_input.text());
+        true
+    }
+
+    fn render(&mut self, rows: usize, cols: usize) {
+        self.rows = rows;
+        print_text_with_coordinates(
+            Text::new(SEARCH_PROMPT).color_range(2, ..),
+            0,
+            0,
+            None,
+            None,
+        );
+        self.search_input.render(
+            SEARCH_PROMPT.len(),
+            0,
+            cols.saturating_sub(SEARCH_PROMPT.len()),
+        );
+        // the search line and the controls line take two lines each
+        self.palette.render(2, self.list_rows(), cols);
+        let controls_line = Text::new("Help: <↓↑> - Select, <ENTER> - Run, <ESC> - Close")
+            .color_range(3, 6..10)
+            .color_range(3, 21..28)
+            .color_range(3, 36..41);
+        print_text_with_coordinates(controls_line, 0, rows.saturating_sub(1), None, None);
+    }
+}
+
+impl State {
+    fn list_rows(&self) -> usize {
+        self.rows.saturating_sub(4).max(1)
+    }
+    fn handle_key(&mut self, key: Key) {
+        match key {
+            Key::Char('\n') => {
+                if let Some(entry) = self.palette.selected_entry().cloned() {
+                    // hidden first, so that actions apply to the pane that was focused before
+                    self.close();
+                    entry.run();
+                }
+            },
+            Key::Esc => self.close(),
+            Key::Ctrl('c') => {
+                if self.search_input.is_empty() {
+                    self.close();
+                } else {
+                    self.search_input.clear();
+                }
+            },
+            Key::Down | Key::Ctrl('n') => self.palette.move_selection_down(1),
+            Key::Up | Key::Ctrl('p') => self.palette.move_selection_up(1),
+            Key::PageDown => self.palette.move_selection_down(self.list_rows()),
+            Key::PageUp => self.palette.move_selection_up(self.list_rows()),
+            _ => {
+                self.search_input.handle_key(&key);
+            },
+        }
+    }
+    fn close(&mut self) {
+        self.search_input.clear();
+        hide_self();
+    }
+}
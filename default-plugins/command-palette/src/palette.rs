@@ -0,0 +1,131 @@
+use fuzzy_matcher::skim::SkimMatcherV2;
+use fuzzy_matcher::FuzzyMatcher;
+use unicode_width::UnicodeWidthStr;
+use zellij_tile::prelude::*;
+
+use crate::entries::Entry;
+
+const KIND_COLUMN_WIDTH: usize = 8;
+
+#[derive(Debug)]
+struct Match {
+    index: usize, // in the entries
+    score: i64,
+    indices: Vec<usize>, // of the characters of the entry name matching the search term
+}
+
+/// The entries of the palette, fuzzy matched against a search term
+#[derive(Debug, Default)]
+pub struct Palette {
+    pub actions: Vec<Entry>, // and plugins
+    pub layouts: Vec<Entry>,
+    pub sessions: Vec<Entry>,
+    search_term: String,
+    matches: Vec<Match>,
+    selected: usize, // in the matches
+}
+
+impl Palette {
+    fn entries(&self) -> impl Iterator<Item = &Entry> {
+        self.actions
+            .iter()
+            .chain(self.layouts.iter())
+            .chain(self.sessions.iter())
+    }
+    /// Match the entries again, to be called after they or the search term changed
+    pub fn update_search_term(&mut self, search_term: &str) {
+        if search_term != self.search_term {
+            self.selected = 0;
+        }
+        self.search_term = search_term.to_owned();
+        let matcher = SkimMatcherV2::default().use_cache(true);
+        let mut matches: Vec<Match> = self
+            .entries()
+            .enumerate()
+            .filter_map(|(index, entry)| {
+                if self.search_term.is_empty() {
+                    return Some(Match {
+                        index,
+                        score: 0,
+                        indices: vec![],
+                    });
+                }
+                matcher
+                    .fuzzy_indices(&entry.name, &self.search_term)
+                    .map(|(score, indices)| Match {
+                        index,
+                        score,
+                        indices,
+                    })
+            })
+            .collect();
+        // stable, so that entries of the same score stay in the order they are listed in
+        matches.sort_by(|a, b| b.score.cmp(&a.score));
+        self.matches = matches;
+        self.selected = std::cmp::min(self.selected, self.matches.len().saturating_sub(1));
+    }
+    pub fn move_selection_down(&mut self, count: usize) {
+        self.selected = std::cmp::min(self.selected + count, self.matches.len().saturating_sub(1));
+    }
+    pub fn move_selection_up(&mut self, count: usize) {
+        self.selected = self.selected.saturating_sub(count);
+    }
+    pub fn selected_entry(&self) -> Option<&Entry> {
+        let index = self.matches.get(self.selected)?.index;
+        self.entries().nth(index)
+    }
+    pub fn render(&self, y: usize, rows: usize, columns: usize) {
+        if self.matches.is_empty() {
+            print_text_with_coordinates(
+                Text::new("No matching actions, plugins, layouts or sessions").color_range(3, ..),
+                0,
+                y,
+                Some(columns),
+                None,
+            );
+            return;
+        }
+        let first_index_to_render = if self.selected >= rows {
+            self.selected + 1 - rows
+        } else {
+            0
+        };
+        let entries: Vec<&Entry> = self.entries().collect();
+        for (line_index, (match_index, entry_match)) in self
+            .matches
+            .iter()
+            .enumerate()
+            .skip(first_index_to_render)
+            .take(rows)
+            .enumerate()
+        {
+            let entry = entries[entry_match.index];
+            let kind = format!("{:<width$}", entry.kind.label(), width = KIND_COLUMN_WIDTH);
+            let keybinds = entry.keybinds();
+            // the keybinds are right aligned, if there is room for them
+            let padding = columns
+                .saturating_sub(kind.width() + 1 + entry.name.width() + keybinds.width() + 1);
+            let show_keybinds = !keybinds.is_empty() && padding > 0;
+            let line = if show_keybinds {
+                format!("{} {}{}{}", kind, entry.name, " ".repeat(padding), keybinds)
+            } else {
+                format!("{} {}", kind, entry.name)
+            };
+            let name_start = kind.chars().count() + 1;
+            let mut text = Text::new(&line)
+                .color_range(1, ..KIND_COLUMN_WIDTH)
+                .color_indices(
+                    3,
+                    entry_match.indices.iter().map(|i| i + name_start).collect(),
+                );
+            if show_keybinds {
+                let keybinds_start = line.chars().count() - keybinds.chars().count();
+                text = text.color_range(2, keybinds_start..);
+            }
+            if match_index == self.selected {
+                text = text.selected();
+            }
+            print_text_with_coordinates(text, 0, y + line_index, Some(columns), None);
+        }
+    }
+}
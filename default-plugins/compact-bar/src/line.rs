@@ -135,6 +135,7 @@ fn left_more_message(
         part: more_styled_text,
         len: more_text_len,
         tab_index: Some(tab_index),
+        is_overflow_marker: true,
     }
 }
 
@@ -167,6 +168,7 @@ fn right_more_message(
         part: more_styled_text,
         len: more_text_len,
         tab_index: Some(tab_index),
+        is_overflow_marker: true,
     }
 }
 
@@ -197,6 +199,7 @@ fn tab_line_prefix(
         part: prefix_styled_text.to_string(),
         len: prefix_text_len,
         tab_index: None,
+        is_overflow_marker: false,
     }];
     if let Some(name) = session_name {
         let name_part = format!("({}) ", name);
@@ -211,6 +214,7 @@ fn tab_line_prefix(
                 part: name_part_styled_text.to_string(),
                 len: name_part_len,
                 tab_index: None,
+                is_overflow_marker: false,
             })
         }
     }
@@ -235,6 +239,7 @@ fn tab_line_prefix(
             part: format!("{}", mode_part_styled_text),
             len: mode_part_len,
             tab_index: None,
+            is_overflow_marker: false,
         })
     }
     parts
@@ -310,6 +315,7 @@ pub fn tab_line(
                 part: buffer,
                 len: remaining_space,
                 tab_index: None,
+                is_overflow_marker: false,
             });
             prefix.push(swap_layout_status);
         }
@@ -369,12 +375,14 @@ fn swap_layout_status(
                     part,
                     len: full_len,
                     tab_index: None,
+                    is_overflow_marker: false,
                 })
             } else if short_len <= max_len && input_mode != InputMode::Locked {
                 Some(LinePart {
                     part: swap_layout_indicator,
                     len: short_len,
                     tab_index: None,
+                    is_overflow_marker: false,
                 })
             } else {
                 None
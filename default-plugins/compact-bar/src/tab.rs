@@ -2,7 +2,7 @@ use crate::{line::tab_separator, LinePart};
 use ansi_term::{ANSIString, ANSIStrings};
 use unicode_width::UnicodeWidthStr;
 use zellij_tile::prelude::*;
-use zellij_tile_utils::style;
+use zellij_tile_utils::{style, tab_line::get_clicked_line_part};
 
 fn cursors(focused_clients: &[ClientId], palette: Palette) -> (Vec<ANSIString>, usize) {
     // cursor section, text length
@@ -33,6 +33,8 @@ pub fn render_tab(
     };
     let background_color = if tab.active {
         palette.green
+    } else if tab.is_requesting_attention {
+        palette.orange
     } else if is_alternate_tab {
         alternate_tab_color
     } else {
@@ -78,6 +80,7 @@ pub fn render_tab(
         part: tab_styled_text,
         len: tab_text_len,
         tab_index: Some(tab.position),
+        is_overflow_marker: false,
     }
 }
 
@@ -89,9 +92,7 @@ pub fn tab_style(
     capabilities: PluginCapabilities,
 ) -> LinePart {
     let separator = tab_separator(capabilities);
-    if tab.is_sync_panes_active {
-        tabname.push_str(" (Sync)");
-    }
+    tabname.push_str(&tab_indicators(tab, capabilities));
     // we only color alternate tabs differently if we can't use the arrow fonts to separate them
     if !capabilities.arrow_fonts {
         is_alternate_tab = false;
@@ -100,6 +101,27 @@ pub fn tab_style(
     render_tab(tabname, tab, is_alternate_tab, palette, separator)
 }
 
+fn tab_indicators(tab: &TabInfo, capabilities: PluginCapabilities) -> String {
+    let mut indicators = String::new();
+    // arrow_fonts is set with the simplified ui, in which case the font might be missing these
+    // symbols as well
+    let simplified_ui = capabilities.arrow_fonts;
+    if tab.is_sync_panes_active {
+        indicators.push_str(if simplified_ui { " (Sync)" } else { " ⇄" });
+    }
+    if tab.is_fullscreen_active {
+        indicators.push_str(if simplified_ui {
+            " (Fullscreen)"
+        } else {
+            " ⤢"
+        });
+    }
+    if tab.is_requesting_attention {
+        indicators.push_str(if simplified_ui { " (!)" } else { " ●" });
+    }
+    indicators
+}
+
 pub(crate) fn get_tab_to_focus(
     tab_line: &[LinePart],
     active_tab_idx: usize,
@@ -114,17 +136,3 @@ pub(crate) fn get_tab_to_focus(
     }
     None
 }
-
-pub(crate) fn get_clicked_line_part(
-    tab_line: &[LinePart],
-    mouse_click_col: usize,
-) -> Option<&LinePart> {
-    let mut len = 0;
-    for tab_line_part in tab_line {
-        if mouse_click_col >= len && mouse_click_col < len + tab_line_part.len {
-            return Some(tab_line_part);
-        }
-        len += tab_line_part.len;
-    }
-    None
-}
@@ -1,3 +1,4 @@
+mod new_session_settings;
 mod resurrectable_sessions;
 mod session_list;
 mod ui;
@@ -10,9 +11,11 @@ use ui::{
         render_controls_line, render_error, render_new_session_line, render_prompt,
         render_renaming_session_screen, render_resurrection_toggle, Colors,
     },
+    preview::MIN_COLUMNS_FOR_PREVIEW,
     SessionUiInfo,
 };
 
+use new_session_settings::{NewSessionSettings, NewSessionSettingsAction};
 use resurrectable_sessions::ResurrectableSessions;
 use session_list::SessionList;
 
@@ -23,6 +26,8 @@ struct State {
     resurrectable_sessions: ResurrectableSessions,
     search_term: String,
     new_session_name: Option<String>,
+    new_session_settings: Option<NewSessionSettings>,
+    available_layouts: Vec<LayoutInfo>,
     renaming_session_name: Option<String>,
     error: Option<String>,
     browsing_resurrection_sessions: bool,
@@ -36,6 +41,7 @@ impl ZellijPlugin for State {
         subscribe(&[
             EventType::ModeUpdate,
             EventType::SessionUpdate,
+            EventType::AvailableLayoutInfo,
             EventType::Key,
             EventType::RunCommandResult,
         ]);
@@ -60,14 +66,28 @@ impl ZellijPlugin for State {
                 self.update_session_infos(session_infos);
                 should_render = true;
             },
+            Event::AvailableLayoutInfo(available_layouts) => {
+                should_render = self.new_session_settings.is_some()
+                    && self.available_layouts != available_layouts;
+                self.available_layouts = available_layouts;
+            },
+            Event::RunCommandResult(exit_code, stdout, _stderr, context) => {
+                if let Some(new_session_settings) = self.new_session_settings.as_mut() {
+                    should_render =
+                        new_session_settings.handle_command_result(exit_code, &stdout, &context);
+                }
+            },
             _ => (),
         };
         should_render
     }
 
     fn render(&mut self, rows: usize, cols: usize) {
-        if self.browsing_resurrection_sessions {
-            self.resurrectable_sessions.render(rows, cols);
+        if let Some(new_session_settings) = self.new_session_settings.as_ref() {
+            new_session_settings.render(rows, cols, &self.available_layouts, self.colors);
+            return;
+        } else if self.browsing_resurrection_sessions {
+            self.resurrectable_sessions.render(rows, cols, self.colors);
             return;
         } else if let Some(new_session_name) = self.renaming_session_name.as_ref() {
             render_renaming_session_screen(&new_session_name, rows, cols);
@@ -80,10 +100,16 @@ impl ZellijPlugin for State {
             self.colors,
         );
         let room_for_list = rows.saturating_sub(5); // search line and controls
+        let preview_columns = if cols >= MIN_COLUMNS_FOR_PREVIEW {
+            cols / 2
+        } else {
+            0
+        };
+        let list_columns = cols.saturating_sub(preview_columns);
         self.sessions.update_rows(room_for_list);
         let list = self
             .sessions
-            .render(room_for_list, cols.saturating_sub(7), self.colors); // 7 for various ui
+            .render(room_for_list, list_columns.saturating_sub(7), self.colors); // 7 for various ui
         for line in list {
             println!("{}", line.render());
         }
@@ -97,6 +123,18 @@ impl ZellijPlugin for State {
         } else {
             render_controls_line(self.sessions.is_searching, rows, cols, self.colors);
         }
+        if preview_columns > 0 {
+            // the preview starts below the search line, next to the list
+            if let Some(preview) = self.sessions.render_preview(
+                list_columns,
+                2,
+                room_for_list,
+                preview_columns,
+                self.colors,
+            ) {
+                print!("{}", preview);
+            }
+        }
     }
 }
 
@@ -109,6 +147,23 @@ impl State {
             self.error = None;
             return true;
         }
+        if let Some(new_session_settings) = self.new_session_settings.as_mut() {
+            match new_session_settings.handle_key(key, &self.available_layouts) {
+                NewSessionSettingsAction::Back => {
+                    // back to typing the name of the new session
+                    self.new_session_name =
+                        self.new_session_settings.take().map(|s| s.session_name);
+                },
+                NewSessionSettingsAction::Start => {
+                    new_session_settings.start_session(&self.available_layouts);
+                    self.new_session_settings = None;
+                    self.reset_selected_index();
+                    hide_self();
+                },
+                NewSessionSettingsAction::None => {},
+            }
+            return true;
+        }
         let mut should_render = false;
         if let Key::Right = key {
             if self.new_session_name.is_none() {
@@ -246,13 +301,19 @@ impl State {
                 switch_session(Some(&session_name_to_resurrect));
             }
         } else if let Some(new_session_name) = &self.new_session_name {
-            if new_session_name.is_empty() {
-                switch_session(None);
-            } else if self.session_name.as_ref() == Some(new_session_name) {
+            if self.session_name.as_ref() == Some(new_session_name) {
                 // noop - we're already here!
                 self.new_session_name = None;
-            } else {
+            } else if self.sessions.has_session(new_session_name)
+                || self.resurrectable_sessions.has_session(new_session_name)
+            {
+                // an existing session is attached to or resurrected as it was
                 switch_session(Some(new_session_name));
+            } else {
+                // the layout, cwd and env of the new session are chosen before it is started
+                self.new_session_settings =
+                    self.new_session_name.take().map(NewSessionSettings::new);
+                return; // so that we don't hide self
             }
         } else if let Some(renaming_session_name) = &self.renaming_session_name.take() {
             if renaming_session_name.is_empty() {
@@ -0,0 +1,387 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use zellij_tile::prelude::*;
+
+use crate::ui::components::Colors;
+use crate::ui::preview::{render_preview, TabPreview, MIN_COLUMNS_FOR_PREVIEW};
+
+// the completion we are waiting for is kept in the context of run_command, so that results for a
+// path that has since been edited can be ignored
+const COMPLETION_PREFIX: &str = "cwd_completion_prefix";
+
+// lists the directories starting with the given prefix, one per line and with a trailing slash
+const LIST_DIRECTORIES_SCRIPT: &str =
+    r#"for path in "$1"*/; do [ -d "$path" ] && printf '%s\n' "$path"; done"#;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Step {
+    Layout,
+    Cwd,
+    Env,
+}
+
+pub enum NewSessionSettingsAction {
+    None,
+    Back,
+    Start,
+}
+
+/// The settings a new session is started with, chosen one step after the other once its name was
+/// typed
+#[derive(Debug)]
+pub struct NewSessionSettings {
+    pub session_name: String,
+    step: Step,
+    selected_layout_index: usize, // 0 is the default layout from the configuration
+    cwd: String,
+    cwd_completions: Vec<String>,
+    env: BTreeMap<String, String>,
+    env_input: String,
+    error: Option<String>,
+}
+
+impl NewSessionSettings {
+    pub fn new(session_name: String) -> Self {
+        NewSessionSettings {
+            session_name,
+            step: Step::Layout,
+            selected_layout_index: 0,
+            cwd: String::new(),
+            cwd_completions: vec![],
+            env: BTreeMap::new(),
+            env_input: String::new(),
+            error: None,
+        }
+    }
+    pub fn start_session(&self, available_layouts: &[LayoutInfo]) {
+        let session_name = Some(self.session_name.as_str()).filter(|n| !n.is_empty());
+        let layout = self
+            .selected_layout(available_layouts)
+            .map(|l| l.name.as_str());
+        let cwd = Some(self.cwd.trim())
+            .filter(|c| !c.is_empty())
+            .map(PathBuf::from);
+        switch_session_with_layout(session_name, layout, cwd, self.env.clone());
+    }
+    pub fn handle_key(
+        &mut self,
+        key: Key,
+        available_layouts: &[LayoutInfo],
+    ) -> NewSessionSettingsAction {
+        self.error = None;
+        match (self.step, key) {
+            (_, Key::Esc) => match self.step {
+                Step::Layout => return NewSessionSettingsAction::Back,
+                Step::Cwd => self.step = Step::Layout,
+                Step::Env => self.step = Step::Cwd,
+            },
+            (Step::Layout, Key::Down) => {
+                // the default layout comes before the available ones
+                if self.selected_layout_index >= available_layouts.len() {
+                    self.selected_layout_index = 0;
+                } else {
+                    self.selected_layout_index += 1;
+                }
+            },
+            (Step::Layout, Key::Up) => {
+                if self.selected_layout_index == 0 {
+                    self.selected_layout_index = available_layouts.len();
+                } else {
+                    self.selected_layout_index -= 1;
+                }
+            },
+            (Step::Layout, Key::Char('\n')) => self.step = Step::Cwd,
+            (Step::Cwd, Key::Char('\n')) => {
+                self.cwd_completions.clear();
+                self.step = Step::Env;
+            },
+            (Step::Cwd, Key::Char('\t')) => self.request_cwd_completions(),
+            (Step::Cwd, Key::Char(character)) => {
+                self.cwd.push(character);
+                self.cwd_completions.clear();
+            },
+            (Step::Cwd, Key::Backspace) => {
+                self.cwd.pop();
+                self.cwd_completions.clear();
+            },
+            (Step::Cwd, Key::Ctrl('c')) => {
+                self.cwd.clear();
+                self.cwd_completions.clear();
+            },
+            (Step::Env, Key::Char('\n')) => {
+                if self.env_input.is_empty() {
+                    return NewSessionSettingsAction::Start;
+                }
+                match self.env_input.split_once('=') {
+                    Some((key, value)) if !key.trim().is_empty() => {
+                        self.env.insert(key.trim().to_owned(), value.to_owned());
+                        self.env_input.clear();
+                    },
+                    _ => self.error = Some("Environment variables are set as KEY=VALUE".to_owned()),
+                }
+            },
+            (Step::Env, Key::Char(character)) => self.env_input.push(character),
+            (Step::Env, Key::Backspace) => {
+                if self.env_input.is_empty() {
+                    // remove the last variable to edit it
+                    if let Some(last_key) = self.env.keys().last().cloned() {
+                        if let Some(value) = self.env.remove(&last_key) {
+                            self.env_input = format!("{}={}", last_key, value);
+                        }
+                    }
+                } else {
+                    self.env_input.pop();
+                }
+            },
+            (Step::Env, Key::Ctrl('c')) => self.env_input.clear(),
+            _ => {},
+        }
+        NewSessionSettingsAction::None
+    }
+    /// Returns true if the result was for the cwd completion we are waiting for
+    pub fn handle_command_result(
+        &mut self,
+        exit_code: Option<i32>,
+        stdout: &[u8],
+        context: &BTreeMap<String, String>,
+    ) -> bool {
+        if context.get(COMPLETION_PREFIX) != Some(&self.cwd) || self.step != Step::Cwd {
+            return false;
+        }
+        if exit_code != Some(0) {
+            return false;
+        }
+        let completions: Vec<String> = String::from_utf8_lossy(stdout)
+            .lines()
+            .map(|l| l.to_owned())
+            .collect();
+        if let Some(common_prefix) = longest_common_prefix(&completions) {
+            if common_prefix.len() > self.cwd.len() {
+                self.cwd = common_prefix;
+            }
+        }
+        // a single completion was fully applied, there is nothing left to choose from
+        self.cwd_completions = if completions.len() > 1 {
+            completions
+        } else {
+            vec![]
+        };
+        true
+    }
+    pub fn render(
+        &self,
+        rows: usize,
+        columns: usize,
+        available_layouts: &[LayoutInfo],
+        colors: Colors,
+    ) {
+        let session_name = if self.session_name.is_empty() {
+            "New session".to_owned()
+        } else {
+            format!("New session: {}", self.session_name)
+        };
+        print_text_with_coordinates(Text::new(session_name).color_range(0, ..), 0, 0, None, None);
+        let preview_columns = if columns >= MIN_COLUMNS_FOR_PREVIEW {
+            columns / 2
+        } else {
+            0
+        };
+        let list_columns = columns.saturating_sub(preview_columns);
+        // title, cwd, its completions and env take two lines each, the controls one
+        let list_rows = rows.saturating_sub(10).max(1);
+        self.render_layout_list(2, list_rows, list_columns, available_layouts);
+        let cwd_y = 3 + list_rows + 1;
+        self.render_cwd(cwd_y, list_columns);
+        let env_y = cwd_y + 3;
+        self.render_env(env_y, list_columns);
+        self.render_controls_line(rows);
+        if preview_columns > 0 {
+            if let Some(layout) = self.selected_layout(available_layouts) {
+                let (tabs, focused_tab_index) =
+                    TabPreview::from_serialized_layout(&layout.stringified_layout);
+                print!(
+                    "{}",
+                    render_preview(
+                        &tabs,
+                        focused_tab_index,
+                        list_columns,
+                        2,
+                        rows.saturating_sub(3),
+                        preview_columns,
+                        colors
+                    )
+                );
+            }
+        }
+    }
+    fn selected_layout<'a>(&self, available_layouts: &'a [LayoutInfo]) -> Option<&'a LayoutInfo> {
+        self.selected_layout_index
+            .checked_sub(1)
+            .and_then(|i| available_layouts.get(i))
+    }
+    fn request_cwd_completions(&self) {
+        run_command(
+            &["sh", "-c", LIST_DIRECTORIES_SCRIPT, "sh", &self.cwd],
+            BTreeMap::from([(COMPLETION_PREFIX.to_owned(), self.cwd.clone())]),
+        );
+    }
+    fn render_layout_list(
+        &self,
+        y: usize,
+        list_rows: usize,
+        columns: usize,
+        available_layouts: &[LayoutInfo],
+    ) {
+        print_text_with_coordinates(
+            self.step_title("Layout", Step::Layout),
+            0,
+            y,
+            Some(columns),
+            None,
+        );
+        let entries: Vec<String> = std::iter::once("Default from configuration".to_owned())
+            .chain(available_layouts.iter().map(|l| {
+                if l.is_builtin {
+                    format!("{} (built-in)", l.name)
+                } else {
+                    l.name.clone()
+                }
+            }))
+            .collect();
+        let first_index_to_render = if self.selected_layout_index >= list_rows {
+            self.selected_layout_index + 1 - list_rows
+        } else {
+            0
+        };
+        for (line_index, (index, entry)) in entries
+            .iter()
+            .enumerate()
+            .skip(first_index_to_render)
+            .take(list_rows)
+            .enumerate()
+        {
+            let mut text = Text::new(format!("  {}", entry));
+            if index == self.selected_layout_index {
+                text = text.color_range(0, ..).selected();
+            }
+            print_text_with_coordinates(text, 0, y + 1 + line_index, Some(columns), None);
+        }
+    }
+    fn render_cwd(&self, y: usize, columns: usize) {
+        print_text_with_coordinates(
+            self.step_title("Directory", Step::Cwd),
+            0,
+            y,
+            Some(columns),
+            None,
+        );
+        let cwd = if self.step == Step::Cwd {
+            format!("  {}_", self.cwd)
+        } else if self.cwd.is_empty() {
+            "  Default".to_owned()
+        } else {
+            format!("  {}", self.cwd)
+        };
+        print_text_with_coordinates(
+            Text::new(cwd).color_range(3, 2..),
+            0,
+            y + 1,
+            Some(columns),
+            None,
+        );
+        if !self.cwd_completions.is_empty() {
+            let completions = self
+                .cwd_completions
+                .iter()
+                .map(|c| {
+                    // only the last component is interesting, the rest was typed
+                    let trimmed = c.trim_end_matches('/');
+                    match trimmed.rfind('/') {
+                        Some(index) => &c[index + 1..],
+                        None => c.as_str(),
+                    }
+                })
+                .collect::<Vec<&str>>()
+                .join(" ");
+            print_text_with_coordinates(
+                Text::new(format!("  {}", completions)),
+                0,
+                y + 2,
+                Some(columns),
+                None,
+            );
+        }
+    }
+    fn render_env(&self, y: usize, columns: usize) {
+        print_text_with_coordinates(
+            self.step_title("Environment variables", Step::Env),
+            0,
+            y,
+            Some(columns),
+            None,
+        );
+        let mut env: Vec<String> = self
+            .env
+            .iter()
+            .map(|(key, value)| format!("{}={}", key, value))
+            .collect();
+        if self.step == Step::Env {
+            env.push(format!("{}_", self.env_input));
+        } else if env.is_empty() {
+            env.push("None".to_owned());
+        }
+        print_text_with_coordinates(
+            Text::new(format!("  {}", env.join(" "))).color_range(3, 2..),
+            0,
+            y + 1,
+            Some(columns),
+            None,
+        );
+    }
+    fn step_title(&self, title: &str, step: Step) -> Text {
+        if self.step == step {
+            Text::new(title).color_range(2, ..)
+        } else {
+            Text::new(title)
+        }
+    }
+    fn render_controls_line(&self, rows: usize) {
+        let controls_line = match (&self.error, self.step) {
+            (Some(error), _) => Text::new(format!("Error: {}", error)).color_range(3, ..),
+            (None, Step::Layout) => {
+                Text::new("Help: <↓↑> - Choose layout, <ENTER> - Next, <ESC> - Back")
+                    .color_range(3, 6..10)
+                    .color_range(3, 28..35)
+                    .color_range(3, 44..49)
+            },
+            (None, Step::Cwd) => Text::new("Help: <TAB> - Complete, <ENTER> - Next, <ESC> - Back")
+                .color_range(3, 6..11)
+                .color_range(3, 24..31)
+                .color_range(3, 40..45),
+            (None, Step::Env) => Text::new(
+                "Help: KEY=VALUE <ENTER> - Add, <ENTER> when empty - Start session, <ESC> - Back",
+            )
+            .color_range(3, 16..23)
+            .color_range(3, 31..38)
+            .color_range(3, 67..72),
+        };
+        print_text_with_coordinates(controls_line, 0, rows.saturating_sub(1), None, None);
+    }
+}
+
+fn longest_common_prefix(strings: &[String]) -> Option<String> {
+    let first = strings.first()?;
+    let mut prefix_len = first.len();
+    for string in strings.iter().skip(1) {
+        prefix_len = first
+            .char_indices()
+            .zip(string.chars())
+            .take_while(|((_, a), b)| a == b)
+            .last()
+            .map(|((i, a), _)| i + a.len_utf8())
+            .unwrap_or(0)
+            .min(prefix_len);
+    }
+    Some(first[..prefix_len].to_owned())
+}
@@ -2,7 +2,8 @@ use fuzzy_matcher::skim::SkimMatcherV2;
 use fuzzy_matcher::FuzzyMatcher;
 use humantime::format_duration;
 
-use crate::ui::components::render_resurrection_toggle;
+use crate::ui::components::{render_resurrection_toggle, Colors};
+use crate::ui::preview::{render_preview, TabPreview, MIN_COLUMNS_FOR_PREVIEW};
 
 use std::time::Duration;
 
@@ -17,6 +18,7 @@ pub struct ResurrectableSessions {
     pub is_searching: bool,
     pub search_term: String,
     pub delete_all_dead_sessions_warning: bool,
+    selected_session_layout: Option<(String, Option<String>)>, // session name, serialized layout
 }
 
 impl ResurrectableSessions {
@@ -24,7 +26,7 @@ impl ResurrectableSessions {
         list.sort_by(|a, b| a.1.cmp(&b.1));
         self.all_resurrectable_sessions = list;
     }
-    pub fn render(&self, rows: usize, columns: usize) {
+    pub fn render(&mut self, rows: usize, columns: usize, colors: Colors) {
         if self.delete_all_dead_sessions_warning {
             self.render_delete_all_sessions_warning(rows, columns);
             return;
@@ -32,7 +34,12 @@ impl ResurrectableSessions {
         render_resurrection_toggle(columns, true);
         let search_indication = Text::new(format!("> {}_", self.search_term)).color_range(1, ..);
         let table_rows = rows.saturating_sub(3);
-        let table_columns = columns;
+        let preview_columns = if columns >= MIN_COLUMNS_FOR_PREVIEW {
+            columns / 2
+        } else {
+            0
+        };
+        let table_columns = columns.saturating_sub(preview_columns);
         let table = if self.is_searching {
             self.render_search_results(table_rows, columns)
         } else {
@@ -41,6 +48,39 @@ impl ResurrectableSessions {
         print_text_with_coordinates(search_indication, 0, 0, None, None);
         print_table_with_coordinates(table, 0, 1, Some(table_columns), Some(table_rows));
         self.render_controls_line(rows);
+        if preview_columns > 0 {
+            // the preview starts next to the first row of the table, below its empty title row
+            self.render_preview(
+                table_columns,
+                2,
+                table_rows.saturating_sub(1),
+                preview_columns,
+                colors,
+            );
+        }
+    }
+    fn render_preview(&mut self, x: usize, y: usize, rows: usize, columns: usize, colors: Colors) {
+        let selected_session_name = match self.get_selected_session_name() {
+            Some(selected_session_name) => selected_session_name,
+            None => return,
+        };
+        // the layout is only fetched when a session is selected, and kept until another one is
+        let is_cached = self
+            .selected_session_layout
+            .as_ref()
+            .map(|(session_name, _)| session_name == &selected_session_name)
+            .unwrap_or(false);
+        if !is_cached {
+            let serialized_layout = get_resurrectable_session_layout(&selected_session_name);
+            self.selected_session_layout = Some((selected_session_name, serialized_layout));
+        }
+        if let Some((_, Some(serialized_layout))) = self.selected_session_layout.as_ref() {
+            let (tabs, focused_tab_index) = TabPreview::from_serialized_layout(serialized_layout);
+            print!(
+                "{}",
+                render_preview(&tabs, focused_tab_index, x, y, rows, columns, colors)
+            );
+        }
     }
     fn render_search_results(&self, table_rows: usize, _table_columns: usize) -> Table {
         let mut table = Table::new().add_row(vec![" ", " ", " "]); // skip the title row
@@ -263,7 +303,7 @@ impl ResurrectableSessions {
         } else {
             self.selected_index
                 .and_then(|i| self.all_resurrectable_sessions.get(i))
-                .map(|session_name_and_creation_time| session_name_and_creation_time.0.clone())
+                .map(|session| session.0.clone())
         }
     }
     pub fn delete_selected_session(&mut self) {
@@ -281,9 +321,7 @@ impl ResurrectableSessions {
                     None
                 }
             })
-            .map(|session_name_and_creation_time| {
-                delete_dead_session(&session_name_and_creation_time.0)
-            });
+            .map(|session| delete_dead_session(&session.0));
     }
     fn delete_all_sessions(&mut self) {
         // optimistic update
@@ -17,13 +17,16 @@ pub struct SessionList {
 
 impl SessionList {
     pub fn set_sessions(&mut self, mut session_ui_infos: Vec<SessionUiInfo>) {
+        // sessions of the same workspace are listed together, after the ones without one
         session_ui_infos.sort_unstable_by(|a, b| {
             if a.is_current_session {
                 std::cmp::Ordering::Less
             } else if b.is_current_session {
                 std::cmp::Ordering::Greater
             } else {
-                a.name.cmp(&b.name)
+                a.workspace
+                    .cmp(&b.workspace)
+                    .then_with(|| a.name.cmp(&b.name))
             }
         });
         self.session_ui_infos = session_ui_infos;
@@ -375,6 +375,14 @@ pub fn build_session_ui_line(session_ui_info: &SessionUiInfo, colors: Colors) ->
         ),
     ]));
     ui_spans.push(session_bullet_span);
+    if let Some(workspace) = &session_ui_info.workspace {
+        let workspace_span =
+            UiSpan::UiSpanTelescope(UiSpanTelescope::new(vec![StringAndLength::new(
+                colors.cyan(&format!("[{}] ", workspace)),
+                workspace.width() + 3,
+            )]));
+        ui_spans.push(workspace_span);
+    }
     ui_spans.push(session_name_span);
     ui_spans.push(tab_and_pane_count);
     ui_spans.push(connected_users_count);
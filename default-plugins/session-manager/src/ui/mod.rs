@@ -1,4 +1,5 @@
 pub mod components;
+pub mod preview;
 use zellij_tile::prelude::*;
 
 use crate::session_list::{SelectedIndex, SessionList};
@@ -6,6 +7,7 @@ use components::{
     build_pane_ui_line, build_session_ui_line, build_tab_ui_line, minimize_lines, Colors,
     LineToRender,
 };
+use preview::{render_preview, TabPreview};
 
 macro_rules! render_assets {
     ($assets:expr, $line_count_to_remove:expr, $selected_index:expr, $to_render_until_selected: expr, $to_render_after_selected:expr, $has_deeper_selected_assets:expr, $max_cols:expr, $colors:expr) => {{
@@ -70,6 +72,43 @@ impl SessionList {
             self.render_list(max_rows, max_cols, colors)
         }
     }
+    pub fn render_preview(
+        &self,
+        x: usize,
+        y: usize,
+        rows: usize,
+        columns: usize,
+        colors: Colors,
+    ) -> Option<String> {
+        let selected_session_name = self.get_selected_session_name()?;
+        let session = self
+            .session_ui_infos
+            .iter()
+            .find(|s| s.name == selected_session_name)?;
+        // we preview the selected tab, or the active one if only the session is selected
+        let selected_tab_position = self.get_selected_tab_position();
+        let previewed_tab_index = session
+            .tabs
+            .iter()
+            .position(|t| Some(t.position) == selected_tab_position)
+            .or_else(|| session.tabs.iter().position(|t| t.is_active))
+            .unwrap_or(0);
+        let selected_pane = self.get_selected_pane_id();
+        let tabs: Vec<TabPreview> = session
+            .tabs
+            .iter()
+            .map(|t| TabPreview::from_tab_ui_info(t, selected_pane))
+            .collect();
+        Some(render_preview(
+            &tabs,
+            previewed_tab_index,
+            x,
+            y,
+            rows,
+            columns,
+            colors,
+        ))
+    }
     fn render_search_results(&self, max_rows: usize, max_cols: usize) -> Vec<LineToRender> {
         let mut lines_to_render = vec![];
         for (i, result) in self.search_results.iter().enumerate() {
@@ -224,6 +263,7 @@ pub struct SessionUiInfo {
     pub tabs: Vec<TabUiInfo>,
     pub connected_users: usize,
     pub is_current_session: bool,
+    pub workspace: Option<String>,
 }
 
 impl SessionUiInfo {
@@ -237,6 +277,7 @@ impl SessionUiInfo {
                 .collect(),
             connected_users: session_info.connected_clients,
             is_current_session: session_info.is_current_session,
+            workspace: session_info.workspace.clone(),
         }
     }
     pub fn line_count(&self, selected_index: &SelectedIndex) -> usize {
@@ -280,6 +321,7 @@ pub struct TabUiInfo {
     pub name: String,
     pub panes: Vec<PaneUiInfo>,
     pub position: usize,
+    pub is_active: bool,
 }
 
 impl TabUiInfo {
@@ -296,6 +338,17 @@ impl TabUiInfo {
                                 exit_code: pane_info.exit_status.clone(),
                                 pane_id: pane_info.id,
                                 is_plugin: pane_info.is_plugin,
+                                is_focused: pane_info.is_focused,
+                                is_floating: pane_info.is_floating,
+                                is_suppressed: pane_info.is_suppressed,
+                                command: pane_info
+                                    .terminal_command
+                                    .clone()
+                                    .or_else(|| pane_info.plugin_url.clone()),
+                                x: pane_info.pane_x,
+                                y: pane_info.pane_y,
+                                rows: pane_info.pane_rows,
+                                columns: pane_info.pane_columns,
                             })
                         } else {
                             None
@@ -308,6 +361,7 @@ impl TabUiInfo {
             name: tab_info.name.clone(),
             panes,
             position: tab_info.position,
+            is_active: tab_info.active,
         }
     }
     pub fn line_count(&self, selected_index: &SelectedIndex) -> usize {
@@ -338,6 +392,15 @@ pub struct PaneUiInfo {
     pub exit_code: Option<i32>,
     pub pane_id: u32,
     pub is_plugin: bool,
+    pub is_focused: bool,
+    pub is_floating: bool,
+    pub is_suppressed: bool,
+    pub command: Option<String>, // the command of a command pane or the url of a plugin
+    // the geometry of the pane in its tab, used to preview it
+    pub x: usize,
+    pub y: usize,
+    pub rows: usize,
+    pub columns: usize,
 }
 
 impl PaneUiInfo {
@@ -0,0 +1,270 @@
+use unicode_width::UnicodeWidthChar;
+use zellij_tile::prelude::layout::{Layout, Run};
+use zellij_tile::prelude::pane_size::{Dimension, PaneGeom};
+
+use crate::ui::{components::Colors, TabUiInfo};
+
+pub const MIN_COLUMNS_FOR_PREVIEW: usize = 100;
+
+// serialized layouts are positioned in a space the size of a common terminal before they are
+// scaled to the preview, so that their fixed size panes keep roughly the right proportions
+const LAYOUT_SPACE_COLUMNS: usize = 200;
+const LAYOUT_SPACE_ROWS: usize = 50;
+
+#[derive(Debug, Clone)]
+pub struct PreviewPane {
+    pub x: usize,
+    pub y: usize,
+    pub rows: usize,
+    pub columns: usize,
+    pub title: String,
+    pub command: Option<String>,
+    pub is_highlighted: bool,
+}
+
+/// The tiled panes of a tab, drawn as boxes in the space they take in the tab
+#[derive(Debug, Clone, Default)]
+pub struct TabPreview {
+    pub name: String,
+    pub panes: Vec<PreviewPane>,
+    pub floating_pane_count: usize,
+}
+
+impl TabPreview {
+    pub fn from_tab_ui_info(tab_ui_info: &TabUiInfo, selected_pane: Option<(u32, bool)>) -> Self {
+        let mut tab_preview = TabPreview {
+            name: tab_ui_info.name.clone(),
+            ..Default::default()
+        };
+        for pane in tab_ui_info.panes.iter().filter(|p| !p.is_suppressed) {
+            if pane.is_floating {
+                tab_preview.floating_pane_count += 1;
+                continue;
+            }
+            let is_highlighted = match selected_pane {
+                Some(selected_pane) => selected_pane == (pane.pane_id, pane.is_plugin),
+                None => pane.is_focused,
+            };
+            tab_preview.panes.push(PreviewPane {
+                x: pane.x,
+                y: pane.y,
+                rows: pane.rows,
+                columns: pane.columns,
+                title: pane.name.clone(),
+                command: pane.command.clone(),
+                is_highlighted,
+            });
+        }
+        tab_preview
+    }
+    /// Returns the tabs of the layout along with the index of its focused tab
+    pub fn from_serialized_layout(serialized_layout: &str) -> (Vec<TabPreview>, usize) {
+        let layout = match Layout::from_kdl(
+            serialized_layout,
+            "session-layout.kdl".to_owned(),
+            None,
+            None,
+        ) {
+            Ok(layout) => layout,
+            Err(_) => return (vec![], 0),
+        };
+        let mut tabs = layout.tabs();
+        if tabs.is_empty() {
+            // a layout without tabs describes the single tab it opens
+            let (tiled_panes, floating_panes) = layout.new_tab();
+            tabs.push((None, tiled_panes, floating_panes));
+        }
+        let space = PaneGeom {
+            x: 0,
+            y: 0,
+            rows: Dimension::fixed(LAYOUT_SPACE_ROWS),
+            cols: Dimension::fixed(LAYOUT_SPACE_COLUMNS),
+            is_stacked: false,
+        };
+        let tab_previews = tabs
+            .into_iter()
+            .enumerate()
+            .map(|(index, (tab_name, tiled_panes, floating_panes))| {
+                let panes = tiled_panes
+                    .position_panes_in_space(&space, None)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter(|(pane_layout, _)| !pane_layout.borderless)
+                    .map(|(pane_layout, pane_geom)| {
+                        let command = pane_layout.run.as_ref().and_then(run_description);
+                        PreviewPane {
+                            x: pane_geom.x,
+                            y: pane_geom.y,
+                            rows: pane_geom.rows.as_usize(),
+                            columns: pane_geom.cols.as_usize(),
+                            title: pane_layout
+                                .name
+                                .clone()
+                                .or_else(|| command.clone())
+                                .unwrap_or_else(|| "Pane".to_owned()),
+                            command,
+                            is_highlighted: pane_layout.focus.unwrap_or(false),
+                        }
+                    })
+                    .collect();
+                TabPreview {
+                    name: tab_name.unwrap_or_else(|| format!("Tab #{}", index + 1)),
+                    panes,
+                    floating_pane_count: floating_panes.len(),
+                }
+            })
+            .collect();
+        (tab_previews, layout.focused_tab_index().unwrap_or(0))
+    }
+}
+
+// the preview is drawn with absolute cursor positioning, so it can be placed next to a list after
+// the list is rendered
+pub fn render_preview(
+    tabs: &[TabPreview],
+    previewed_tab_index: usize,
+    x: usize,
+    y: usize,
+    rows: usize,
+    columns: usize,
+    colors: Colors,
+) -> String {
+    let mut grid = PreviewGrid::new(rows, columns);
+    let mut tab_line_x = 0;
+    for (index, tab) in tabs.iter().enumerate() {
+        let tab_name = format!(" {} ", tab.name);
+        grid.write(tab_line_x, 0, &tab_name, index == previewed_tab_index);
+        tab_line_x += tab_name.chars().count();
+    }
+    if let Some(tab) = tabs.get(previewed_tab_index) {
+        if tab.floating_pane_count > 0 {
+            let floating_panes = format!(" (+{} floating)", tab.floating_pane_count);
+            grid.write(tab_line_x, 0, &floating_panes, false);
+        }
+        grid.draw_panes(&tab.panes, 1);
+    }
+    grid.render(x, y, colors)
+}
+
+fn run_description(run: &Run) -> Option<String> {
+    match run {
+        Run::Command(run_command) => Some(run_command.to_string()),
+        Run::Plugin(run_plugin) => Some(run_plugin.location.to_string()),
+        Run::EditFile(path, _, _) => Some(path.display().to_string()),
+        Run::Cwd(_) | Run::Shell(_) => None,
+    }
+}
+
+struct PreviewGrid {
+    cells: Vec<Vec<(char, bool)>>, // character, is_highlighted
+    rows: usize,
+    columns: usize,
+}
+
+impl PreviewGrid {
+    pub fn new(rows: usize, columns: usize) -> Self {
+        PreviewGrid {
+            cells: vec![vec![(' ', false); columns]; rows],
+            rows,
+            columns,
+        }
+    }
+    pub fn write(&mut self, x: usize, y: usize, text: &str, is_highlighted: bool) {
+        if let Some(row) = self.cells.get_mut(y) {
+            // wide characters would misalign the cells after them
+            let characters = text.chars().filter(|c| !c.is_control()).map(|c| {
+                if c.width() == Some(1) {
+                    c
+                } else {
+                    '?'
+                }
+            });
+            for (cell, character) in row.iter_mut().skip(x).zip(characters) {
+                *cell = (character, is_highlighted);
+            }
+        }
+    }
+    pub fn draw_panes(&mut self, panes: &[PreviewPane], y_offset: usize) {
+        let min_x = panes.iter().map(|p| p.x).min().unwrap_or(0);
+        let min_y = panes.iter().map(|p| p.y).min().unwrap_or(0);
+        let max_x = panes.iter().map(|p| p.x + p.columns).max().unwrap_or(0);
+        let max_y = panes.iter().map(|p| p.y + p.rows).max().unwrap_or(0);
+        let tab_columns = max_x.saturating_sub(min_x);
+        let tab_rows = max_y.saturating_sub(min_y);
+        let available_columns = self.columns;
+        let available_rows = self.rows.saturating_sub(y_offset);
+        if tab_columns == 0 || tab_rows == 0 {
+            return;
+        }
+        let scale_x = |x: usize| (x - min_x) * available_columns / tab_columns;
+        let scale_y = |y: usize| (y - min_y) * available_rows / tab_rows;
+        // the highlighted pane is drawn last so that its borders are drawn over its neighbours'
+        let mut panes: Vec<&PreviewPane> = panes.iter().collect();
+        panes.sort_by_key(|p| p.is_highlighted);
+        for pane in panes {
+            let x = scale_x(pane.x);
+            let y = scale_y(pane.y) + y_offset;
+            let columns = scale_x(pane.x + pane.columns).saturating_sub(x);
+            let rows = (scale_y(pane.y + pane.rows) + y_offset).saturating_sub(y);
+            self.draw_pane(pane, x, y, rows, columns);
+        }
+    }
+    fn draw_pane(&mut self, pane: &PreviewPane, x: usize, y: usize, rows: usize, columns: usize) {
+        if rows < 2 || columns < 2 {
+            // too small to draw in the preview
+            return;
+        }
+        let is_highlighted = pane.is_highlighted;
+        let inner_columns = columns - 2;
+        let horizontal_border = "─".repeat(inner_columns);
+        let blank_line = " ".repeat(inner_columns);
+        self.write(x, y, &format!("┌{}┐", horizontal_border), is_highlighted);
+        for row in y + 1..y + rows - 1 {
+            self.write(x, row, &format!("│{}│", blank_line), is_highlighted);
+        }
+        self.write(
+            x,
+            y + rows - 1,
+            &format!("└{}┘", horizontal_border),
+            is_highlighted,
+        );
+        let title = truncate(&format!(" {} ", pane.title), inner_columns);
+        self.write(x + 1, y, &title, is_highlighted);
+        if let Some(command) = pane.command.as_ref().filter(|c| **c != pane.title) {
+            if rows > 2 {
+                self.write(x + 1, y + 1, &truncate(command, inner_columns), false);
+            }
+        }
+    }
+    pub fn render(&self, x: usize, y: usize, colors: Colors) -> String {
+        let mut rendered = String::new();
+        for (row_index, row) in self.cells.iter().enumerate() {
+            rendered.push_str(&format!("\u{1b}[{};{}H\u{1b}[m", y + row_index + 1, x + 1));
+            // consecutive cells with the same highlighting are styled together
+            let mut chunk = String::new();
+            let mut chunk_is_highlighted = false;
+            for (character, is_highlighted) in row {
+                if *is_highlighted != chunk_is_highlighted && !chunk.is_empty() {
+                    rendered.push_str(&style_chunk(&chunk, chunk_is_highlighted, colors));
+                    chunk.clear();
+                }
+                chunk_is_highlighted = *is_highlighted;
+                chunk.push(*character);
+            }
+            rendered.push_str(&style_chunk(&chunk, chunk_is_highlighted, colors));
+        }
+        rendered
+    }
+}
+
+fn style_chunk(chunk: &str, is_highlighted: bool, colors: Colors) -> String {
+    if is_highlighted {
+        colors.green(chunk)
+    } else {
+        chunk.to_owned()
+    }
+}
+
+fn truncate(text: &str, max_width: usize) -> String {
+    text.chars().take(max_width).collect()
+}
@@ -1,5 +1,6 @@
 mod first_line;
 mod second_line;
+mod segments;
 mod tip;
 
 use ansi_term::{
@@ -11,6 +12,7 @@ use ansi_term::{
 use std::collections::BTreeMap;
 use std::fmt::{Display, Error, Formatter};
 use zellij_tile::prelude::actions::Action;
+use zellij_tile::prelude::layout::RunPluginLocation;
 use zellij_tile::prelude::*;
 use zellij_tile_utils::{palette_match, style};
 
@@ -20,6 +22,7 @@ use second_line::{
     locked_floating_panes_are_visible, locked_fullscreen_panes_to_hide, system_clipboard_error,
     text_copied_hint,
 };
+use segments::StatusSegments;
 use tip::utils::get_cached_tip_name;
 
 // for more of these, copy paste from: https://en.wikipedia.org/wiki/Box-drawing_character
@@ -35,6 +38,7 @@ struct State {
     mode_info: ModeInfo,
     text_copy_destination: Option<CopyDestination>,
     display_system_clipboard_failure: bool,
+    segments: Option<StatusSegments>, // replaces the first line if configured
 }
 
 register_plugin!(State);
@@ -177,7 +181,7 @@ fn color_elements(palette: Palette, different_color_alternates: bool) -> Colored
 }
 
 impl ZellijPlugin for State {
-    fn load(&mut self, _configuration: BTreeMap<String, String>) {
+    fn load(&mut self, configuration: BTreeMap<String, String>) {
         // TODO: Should be able to choose whether to use the cache through config.
         self.tip_name = get_cached_tip_name();
         set_selectable(false);
@@ -188,6 +192,11 @@ impl ZellijPlugin for State {
             EventType::InputReceived,
             EventType::SystemClipboardFailure,
         ]);
+        self.segments = StatusSegments::from_configuration(&configuration);
+        if let Some(segments) = self.segments.as_mut() {
+            subscribe(&[EventType::Timer, EventType::RunCommandResult]);
+            segments.start();
+        }
     }
 
     fn update(&mut self, event: Event) -> bool {
@@ -231,11 +240,28 @@ impl ZellijPlugin for State {
                 self.text_copy_destination = None;
                 self.display_system_clipboard_failure = false;
             },
+            Event::Timer(elapsed) => {
+                if let Some(segments) = self.segments.as_mut() {
+                    segments.handle_timer(elapsed);
+                }
+            },
+            Event::RunCommandResult(exit_code, stdout, _stderr, context) => {
+                if let Some(segments) = self.segments.as_mut() {
+                    should_render = segments.handle_command_result(exit_code, &stdout, &context);
+                }
+            },
             _ => {},
         };
         should_render
     }
 
+    fn pipe(&mut self, pipe_message: PipeMessage) -> bool {
+        match self.segments.as_mut() {
+            Some(segments) => segments.handle_pipe_message(&pipe_message),
+            None => false,
+        }
+    }
+
     fn render(&mut self, rows: usize, cols: usize) {
         let supports_arrow_fonts = !self.mode_info.capabilities.arrow_fonts;
         let separator = if supports_arrow_fonts {
@@ -245,7 +271,10 @@ impl ZellijPlugin for State {
         };
 
         let active_tab = self.tabs.iter().find(|t| t.active);
-        let first_line = first_line(&self.mode_info, active_tab, cols, separator);
+        let first_line = match self.segments.as_ref() {
+            Some(segments) => segments.render(&self.mode_info, active_tab, cols, separator),
+            None => first_line(&self.mode_info, active_tab, cols, separator),
+        };
         let second_line = self.second_line(cols);
 
         let background = match self.mode_info.style.colors.theme_hue {
@@ -375,6 +404,25 @@ pub fn action_key(keymap: &[(Key, Vec<Action>)], action: &[Action]) -> Vec<Key>
         .collect::<Vec<Key>>()
 }
 
+/// Get the keys that launch or focus the built-in plugin with this alias.
+///
+/// Unlike [`action_key`], which matches any plugin, this tells the keys of different plugins
+/// apart.
+pub fn launch_plugin_key(keymap: &[(Key, Vec<Action>)], plugin_alias: &str) -> Vec<Key> {
+    let location = RunPluginLocation::Zellij(PluginTag::new(plugin_alias));
+    keymap
+        .iter()
+        .filter_map(|(key, acvec)| match acvec.first() {
+            Some(Action::LaunchOrFocusPlugin(run_plugin, ..))
+                if run_plugin.location == location =>
+            {
+                Some(*key)
+            },
+            _ => None,
+        })
+        .collect::<Vec<Key>>()
+}
+
 /// Get multiple keys for multiple actions.
 ///
 /// An extension of [`action_key`] that iterates over all action tuples and collects the results.
@@ -678,6 +726,24 @@ pub mod tests {
         assert_eq!(ret, vec![Key::Ctrl('d'), Key::Ctrl('b')]);
     }
 
+    #[test]
+    fn launch_plugin_key_tells_plugins_apart() {
+        let launch_plugin = |url: &str| {
+            let run_plugin = zellij_tile::prelude::layout::RunPlugin::from_url(url).unwrap();
+            vec![
+                Action::LaunchOrFocusPlugin(run_plugin, true, true, false, false),
+                TO_NORMAL,
+            ]
+        };
+        let keymap = &[
+            (Key::Char('w'), launch_plugin("zellij:session-manager")),
+            (Key::Char('p'), launch_plugin("zellij:command-palette")),
+            (Key::Char('d'), vec![Action::Detach]),
+        ];
+        let ret = launch_plugin_key(keymap, "command-palette");
+        assert_eq!(ret, vec![Key::Char('p')]);
+    }
+
     fn get_palette() -> Palette {
         Palette::default()
     }
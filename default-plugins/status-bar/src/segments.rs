@@ -0,0 +1,404 @@
+use ansi_term::{unstyled_len, ANSIString, ANSIStrings};
+use std::collections::BTreeMap;
+use zellij_tile::prelude::*;
+use zellij_tile_utils::style;
+
+use crate::LinePart;
+
+// the segment whose output a command is producing is kept in the context of run_command
+const SEGMENT_KEY: &str = "status_segment";
+const DEFAULT_CLOCK_FORMAT: &str = "%H:%M";
+const DEFAULT_INTERVAL: f64 = 10.0; // seconds
+const TICK: f64 = 1.0; // seconds
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SegmentKind {
+    Mode,
+    Session,
+    SwapLayout,
+    Clock { format: String },
+    Hostname,
+    Command { command: String },
+    Pipe { name: String },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Segment {
+    pub key: String, // identifies the segment in the configuration and in command contexts
+    pub kind: SegmentKind,
+    pub color: Option<String>, // the name of a color of the theme palette
+    pub interval: Option<f64>, // seconds between command runs, for segments that run one
+}
+
+/// A configurable first line of the status bar, made of segments chosen and ordered with the
+/// `segments` key of the plugin configuration, eg.
+///
+/// ```kdl
+/// plugin location="zellij:status-bar" {
+///     segments "mode session swap_layout clock hostname command:git pipe:build"
+///     clock_format "%H:%M:%S"
+///     clock_interval "1"
+///     command_git "git rev-parse --abbrev-ref HEAD"
+///     command_git_interval "5"
+///     command_git_color "orange"
+/// }
+/// ```
+///
+/// Pipe segments display the payload of the last message piped to the plugin with their name,
+/// eg. `zellij pipe --name build -- "passing"`.
+#[derive(Debug, Default)]
+pub struct StatusSegments {
+    segments: Vec<Segment>,
+    outputs: BTreeMap<String, String>, // segment key => last output
+    since_last_run: BTreeMap<String, f64>, // segment key => seconds
+}
+
+impl StatusSegments {
+    /// Returns None if no segments are configured, in which case the default first line is used
+    pub fn from_configuration(configuration: &BTreeMap<String, String>) -> Option<Self> {
+        let segments: Vec<Segment> = configuration
+            .get("segments")?
+            .split_whitespace()
+            .filter_map(|key| {
+                let kind = match key.split_once(':') {
+                    Some(("command", name)) => SegmentKind::Command {
+                        command: configuration.get(&format!("command_{}", name))?.clone(),
+                    },
+                    Some(("pipe", name)) => SegmentKind::Pipe {
+                        name: name.to_owned(),
+                    },
+                    Some(_) => return None,
+                    None => match key {
+                        "mode" => SegmentKind::Mode,
+                        "session" => SegmentKind::Session,
+                        "swap_layout" => SegmentKind::SwapLayout,
+                        "clock" => SegmentKind::Clock {
+                            format: configuration
+                                .get("clock_format")
+                                .cloned()
+                                .unwrap_or_else(|| DEFAULT_CLOCK_FORMAT.to_owned()),
+                        },
+                        "hostname" => SegmentKind::Hostname,
+                        _ => return None,
+                    },
+                };
+                let config_key = key.replace(':', "_");
+                let interval = match kind {
+                    SegmentKind::Clock { .. } | SegmentKind::Command { .. } => Some(
+                        configuration
+                            .get(&format!("{}_interval", config_key))
+                            .and_then(|i| i.parse::<f64>().ok())
+                            .filter(|i| *i > 0.0)
+                            .unwrap_or(DEFAULT_INTERVAL),
+                    ),
+                    _ => None,
+                };
+                Some(Segment {
+                    key: key.to_owned(),
+                    kind,
+                    color: configuration.get(&format!("{}_color", config_key)).cloned(),
+                    interval,
+                })
+            })
+            .collect();
+        Some(StatusSegments {
+            segments,
+            ..Default::default()
+        })
+    }
+    /// Runs the commands of all segments, to be called once the plugin is loaded
+    pub fn start(&mut self) {
+        for segment in &self.segments {
+            run_segment_command(segment);
+        }
+        if self.segments.iter().any(|s| s.interval.is_some()) {
+            set_timeout(TICK);
+        }
+    }
+    pub fn handle_timer(&mut self, elapsed: f64) {
+        for segment in self.segments.iter().filter(|s| s.interval.is_some()) {
+            let since_last_run = self
+                .since_last_run
+                .entry(segment.key.clone())
+                .or_insert(0.0);
+            *since_last_run += elapsed;
+            if segment
+                .interval
+                .map(|i| *since_last_run >= i)
+                .unwrap_or(false)
+            {
+                *since_last_run = 0.0;
+                run_segment_command(segment);
+            }
+        }
+        set_timeout(TICK);
+    }
+    /// Returns true if the output of a segment changed
+    pub fn handle_command_result(
+        &mut self,
+        exit_code: Option<i32>,
+        stdout: &[u8],
+        context: &BTreeMap<String, String>,
+    ) -> bool {
+        let segment_key = match context.get(SEGMENT_KEY) {
+            Some(segment_key) => segment_key,
+            None => return false,
+        };
+        // a failing command clears its segment rather than showing stale output
+        let output = if exit_code == Some(0) {
+            first_line_of(&String::from_utf8_lossy(stdout))
+        } else {
+            String::new()
+        };
+        self.set_output(segment_key, output)
+    }
+    /// Returns true if the message was for one of our pipe segments and changed its text
+    pub fn handle_pipe_message(&mut self, pipe_message: &PipeMessage) -> bool {
+        let segment_key = format!("pipe:{}", pipe_message.name);
+        if !self.segments.iter().any(|s| s.key == segment_key) {
+            return false;
+        }
+        let output = pipe_message
+            .payload
+            .as_deref()
+            .map(first_line_of)
+            .unwrap_or_default();
+        self.set_output(&segment_key, output)
+    }
+    pub fn render(
+        &self,
+        mode_info: &ModeInfo,
+        tab_info: Option<&TabInfo>,
+        max_len: usize,
+        separator: &str,
+    ) -> LinePart {
+        let palette = mode_info.style.colors;
+        let background = match palette.theme_hue {
+            ThemeHue::Dark => palette.black,
+            ThemeHue::Light => palette.white,
+        };
+        let mut line = LinePart::default();
+        for segment in &self.segments {
+            let text = match self.segment_text(segment, mode_info, tab_info) {
+                Some(text) if !text.is_empty() => text,
+                _ => continue,
+            };
+            let color = segment
+                .color
+                .as_deref()
+                .and_then(|c| palette_color(&palette, c))
+                .unwrap_or_else(|| default_color(&palette, &segment.kind));
+            let text = format!(" {} ", text);
+            let styled: Vec<ANSIString> = vec![
+                style!(background, color).paint(separator),
+                style!(background, color).bold().paint(text),
+                style!(color, background).paint(separator),
+            ];
+            let styled = ANSIStrings(&styled);
+            let len = unstyled_len(&styled);
+            if line.len + len > max_len {
+                // segments that do not fit are left out rather than cut
+                break;
+            }
+            line.append(&LinePart {
+                part: styled.to_string(),
+                len,
+            });
+        }
+        line
+    }
+    fn segment_text(
+        &self,
+        segment: &Segment,
+        mode_info: &ModeInfo,
+        tab_info: Option<&TabInfo>,
+    ) -> Option<String> {
+        match &segment.kind {
+            SegmentKind::Mode => Some(format!("{:?}", mode_info.mode).to_uppercase()),
+            SegmentKind::Session => mode_info.session_name.clone(),
+            SegmentKind::SwapLayout => tab_info.and_then(|tab_info| {
+                tab_info.active_swap_layout_name.as_ref().map(|name| {
+                    if tab_info.is_swap_layout_dirty {
+                        format!("{}*", name)
+                    } else {
+                        name.clone()
+                    }
+                })
+            }),
+            _ => self.outputs.get(&segment.key).cloned(),
+        }
+    }
+    fn set_output(&mut self, segment_key: &str, output: String) -> bool {
+        if self.outputs.get(segment_key) == Some(&output) {
+            return false;
+        }
+        self.outputs.insert(segment_key.to_owned(), output);
+        true
+    }
+}
+
+fn run_segment_command(segment: &Segment) {
+    let context = BTreeMap::from([(SEGMENT_KEY.to_owned(), segment.key.clone())]);
+    match &segment.kind {
+        SegmentKind::Clock { format } => run_command(&["date", &format!("+{}", format)], context),
+        SegmentKind::Hostname => run_command(&["uname", "-n"], context),
+        SegmentKind::Command { command } => run_command(&["sh", "-c", command], context),
+        _ => {},
+    }
+}
+
+fn first_line_of(text: &str) -> String {
+    text.lines().next().unwrap_or("").trim().to_owned()
+}
+
+fn palette_color(palette: &Palette, color_name: &str) -> Option<PaletteColor> {
+    match color_name {
+        "fg" => Some(palette.fg),
+        "bg" => Some(palette.bg),
+        "black" => Some(palette.black),
+        "red" => Some(palette.red),
+        "green" => Some(palette.green),
+        "yellow" => Some(palette.yellow),
+        "blue" => Some(palette.blue),
+        "magenta" => Some(palette.magenta),
+        "cyan" => Some(palette.cyan),
+        "white" => Some(palette.white),
+        "orange" => Some(palette.orange),
+        "gray" => Some(palette.gray),
+        "purple" => Some(palette.purple),
+        "gold" => Some(palette.gold),
+        "silver" => Some(palette.silver),
+        "pink" => Some(palette.pink),
+        "brown" => Some(palette.brown),
+        _ => None,
+    }
+}
+
+fn default_color(palette: &Palette, segment_kind: &SegmentKind) -> PaletteColor {
+    match segment_kind {
+        SegmentKind::Mode => palette.green,
+        SegmentKind::Session => palette.orange,
+        SegmentKind::SwapLayout => palette.blue,
+        SegmentKind::Clock { .. } => palette.cyan,
+        SegmentKind::Hostname => palette.magenta,
+        SegmentKind::Command { .. } => palette.yellow,
+        SegmentKind::Pipe { .. } => palette.fg,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn configuration(entries: &[(&str, &str)]) -> BTreeMap<String, String> {
+        entries
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    // Strip style information from `LinePart` and return a raw String instead
+    fn unstyle(line_part: LinePart) -> String {
+        let string = line_part.to_string();
+
+        let re = regex::Regex::new(r"\x1b\[[0-9;]*m").unwrap();
+        let string = re.replace_all(&string, "".to_string());
+
+        string.to_string()
+    }
+
+    #[test]
+    fn no_segments_configured() {
+        let status_segments = StatusSegments::from_configuration(&configuration(&[]));
+        assert!(status_segments.is_none());
+    }
+
+    #[test]
+    fn segments_are_parsed_in_order() {
+        let status_segments = StatusSegments::from_configuration(&configuration(&[
+            (
+                "segments",
+                "clock command:git mode unknown command:missing pipe:build",
+            ),
+            ("command_git", "git branch --show-current"),
+            ("command_git_interval", "5"),
+            ("command_git_color", "orange"),
+        ]))
+        .unwrap();
+        assert_eq!(
+            status_segments.segments,
+            vec![
+                Segment {
+                    key: "clock".to_owned(),
+                    kind: SegmentKind::Clock {
+                        format: DEFAULT_CLOCK_FORMAT.to_owned()
+                    },
+                    color: None,
+                    interval: Some(DEFAULT_INTERVAL),
+                },
+                Segment {
+                    key: "command:git".to_owned(),
+                    kind: SegmentKind::Command {
+                        command: "git branch --show-current".to_owned()
+                    },
+                    color: Some("orange".to_owned()),
+                    interval: Some(5.0),
+                },
+                Segment {
+                    key: "mode".to_owned(),
+                    kind: SegmentKind::Mode,
+                    color: None,
+                    interval: None,
+                },
+                Segment {
+                    key: "pipe:build".to_owned(),
+                    kind: SegmentKind::Pipe {
+                        name: "build".to_owned()
+                    },
+                    color: None,
+                    interval: None,
+                },
+            ],
+            "unknown segments and commands without a command line are left out"
+        );
+    }
+
+    #[test]
+    fn segments_are_rendered_with_their_outputs() {
+        let mut status_segments = StatusSegments::from_configuration(&configuration(&[
+            ("segments", "mode session command:git pipe:build"),
+            ("command_git", "git branch --show-current"),
+        ]))
+        .unwrap();
+        let mut context = BTreeMap::new();
+        context.insert(SEGMENT_KEY.to_owned(), "command:git".to_owned());
+        assert!(status_segments.handle_command_result(Some(0), b"main\n", &context));
+        let pipe_message = PipeMessage::new(
+            PipeSource::Cli("1".to_owned()),
+            "build",
+            &Some("passing\n".to_owned()),
+            &None,
+            false,
+        );
+        assert!(status_segments.handle_pipe_message(&pipe_message));
+        let mode_info = ModeInfo {
+            session_name: Some("my-session".to_owned()),
+            ..Default::default()
+        };
+        let line = status_segments.render(&mode_info, None, 100, "|");
+        assert_eq!(unstyle(line), "| NORMAL || my-session || main || passing |");
+    }
+
+    #[test]
+    fn segments_that_do_not_fit_are_left_out() {
+        let status_segments =
+            StatusSegments::from_configuration(&configuration(&[("segments", "mode session")]))
+                .unwrap();
+        let mode_info = ModeInfo {
+            session_name: Some("my-session".to_owned()),
+            ..Default::default()
+        };
+        let line = status_segments.render(&mode_info, None, 15, "|");
+        assert_eq!(unstyle(line), "| NORMAL |");
+    }
+}
@@ -0,0 +1,228 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// A file operation waiting for the user to type a name or confirm it
+#[derive(Debug, Clone)]
+pub enum Prompt {
+    NewFile(String),
+    NewDir(String),
+    Rename(PathBuf, String),
+    ConfirmDelete(Vec<PathBuf>),
+}
+
+impl Prompt {
+    pub fn name_mut(&mut self) -> Option<&mut String> {
+        match self {
+            Prompt::NewFile(name) | Prompt::NewDir(name) | Prompt::Rename(_, name) => Some(name),
+            Prompt::ConfirmDelete(_) => None,
+        }
+    }
+    pub fn render(&self) -> String {
+        match self {
+            Prompt::NewFile(name) => format!("New file: {}_", name),
+            Prompt::NewDir(name) => format!("New directory: {}_", name),
+            Prompt::Rename(path, name) => format!("Rename {} to: {}_", file_name(path), name),
+            Prompt::ConfirmDelete(paths) => {
+                if paths.len() == 1 {
+                    format!("Delete {}? (y/n)", file_name(&paths[0]))
+                } else {
+                    format!("Delete {} items? (y/n)", paths.len())
+                }
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClipboardOperation {
+    Copy,
+    Cut,
+}
+
+/// Files that were copied or cut, waiting to be pasted
+#[derive(Debug, Clone)]
+pub struct Clipboard {
+    pub operation: ClipboardOperation,
+    pub paths: Vec<PathBuf>,
+}
+
+impl Clipboard {
+    pub fn new(operation: ClipboardOperation, paths: Vec<PathBuf>) -> Self {
+        Clipboard { operation, paths }
+    }
+    /// Copies or moves the files into this folder, returning their new paths
+    pub fn paste(&self, destination_dir: &Path) -> io::Result<Vec<PathBuf>> {
+        let mut pasted = vec![];
+        for path in &self.paths {
+            let destination = unique_destination(destination_dir, path)?;
+            // copying a directory into itself would never stop copying the copies
+            if destination.starts_with(path) {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "cannot paste a directory into itself",
+                ));
+            }
+            match self.operation {
+                ClipboardOperation::Copy => copy_recursively(path, &destination)?,
+                ClipboardOperation::Cut => {
+                    // rename fails across filesystems, in which case we copy and delete instead
+                    if fs::rename(path, &destination).is_err() {
+                        copy_recursively(path, &destination)?;
+                        delete(path)?;
+                    }
+                },
+            }
+            pasted.push(destination);
+        }
+        Ok(pasted)
+    }
+}
+
+pub fn create_file(dir: &Path, name: &str) -> io::Result<PathBuf> {
+    let path = dir.join(validate_name(name)?);
+    fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&path)?;
+    Ok(path)
+}
+
+pub fn create_dir(dir: &Path, name: &str) -> io::Result<PathBuf> {
+    let path = dir.join(validate_name(name)?);
+    fs::create_dir(&path)?;
+    Ok(path)
+}
+
+pub fn rename(path: &Path, new_name: &str) -> io::Result<PathBuf> {
+    let new_path = path.with_file_name(validate_name(new_name)?);
+    if new_path.exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{} already exists", new_name),
+        ));
+    }
+    fs::rename(path, &new_path)?;
+    Ok(new_path)
+}
+
+pub fn delete(path: &Path) -> io::Result<()> {
+    if fs::symlink_metadata(path)?.is_dir() {
+        fs::remove_dir_all(path)
+    } else {
+        fs::remove_file(path)
+    }
+}
+
+fn copy_recursively(from: &Path, to: &Path) -> io::Result<()> {
+    if fs::symlink_metadata(from)?.is_dir() {
+        fs::create_dir(to)?;
+        for entry in fs::read_dir(from)? {
+            let entry = entry?;
+            copy_recursively(&entry.path(), &to.join(entry.file_name()))?;
+        }
+    } else {
+        fs::copy(from, to)?;
+    }
+    Ok(())
+}
+
+// pasting a file next to itself or over an existing one adds a suffix to its name rather than
+// overwriting anything
+fn unique_destination(destination_dir: &Path, path: &Path) -> io::Result<PathBuf> {
+    let name = path
+        .file_name()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "cannot paste the root"))?
+        .to_string_lossy()
+        .to_string();
+    let mut destination = destination_dir.join(&name);
+    let mut copy_index = 1;
+    while destination.exists() {
+        destination = destination_dir.join(format!("{}_{}", name, copy_index));
+        copy_index += 1;
+    }
+    Ok(destination)
+}
+
+fn validate_name(name: &str) -> io::Result<&str> {
+    let name = name.trim();
+    if name.is_empty() || name == "." || name == ".." || name.contains('/') {
+        Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("invalid name: \"{}\"", name),
+        ))
+    } else {
+        Ok(name)
+    }
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|f| f.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn copy_pastes_files_and_directories_recursively() {
+        let root = tempdir().unwrap();
+        let source_dir = create_dir(root.path(), "source").unwrap();
+        let nested_file = create_file(&create_dir(&source_dir, "nested").unwrap(), "file").unwrap();
+        fs::write(&nested_file, "contents").unwrap();
+        let destination_dir = create_dir(root.path(), "destination").unwrap();
+        let clipboard = Clipboard::new(ClipboardOperation::Copy, vec![source_dir.clone()]);
+        let pasted = clipboard.paste(&destination_dir).unwrap();
+        assert_eq!(pasted, vec![destination_dir.join("source")]);
+        assert_eq!(
+            fs::read_to_string(destination_dir.join("source/nested/file")).unwrap(),
+            "contents"
+        );
+        assert!(nested_file.exists(), "the copied files are kept");
+    }
+
+    #[test]
+    fn cut_moves_files() {
+        let root = tempdir().unwrap();
+        let file = create_file(root.path(), "file").unwrap();
+        let destination_dir = create_dir(root.path(), "destination").unwrap();
+        let clipboard = Clipboard::new(ClipboardOperation::Cut, vec![file.clone()]);
+        let pasted = clipboard.paste(&destination_dir).unwrap();
+        assert_eq!(pasted, vec![destination_dir.join("file")]);
+        assert!(destination_dir.join("file").exists());
+        assert!(!file.exists(), "the cut files are removed");
+    }
+
+    #[test]
+    fn pasting_over_an_existing_name_adds_a_suffix() {
+        let root = tempdir().unwrap();
+        let file = create_file(root.path(), "file").unwrap();
+        let clipboard = Clipboard::new(ClipboardOperation::Copy, vec![file.clone()]);
+        assert_eq!(
+            clipboard.paste(root.path()).unwrap(),
+            vec![root.path().join("file_1")]
+        );
+        assert_eq!(
+            clipboard.paste(root.path()).unwrap(),
+            vec![root.path().join("file_2")]
+        );
+        assert!(create_file(root.path(), "file").is_err());
+        assert!(rename(&file, "file_1").is_err());
+    }
+
+    #[test]
+    fn directories_cannot_be_pasted_into_themselves() {
+        let root = tempdir().unwrap();
+        let dir = create_dir(root.path(), "dir").unwrap();
+        let subdir = create_dir(&dir, "subdir").unwrap();
+        for operation in [ClipboardOperation::Copy, ClipboardOperation::Cut] {
+            let clipboard = Clipboard::new(operation, vec![dir.clone()]);
+            let e = clipboard.paste(&subdir).unwrap_err();
+            assert_eq!(e.kind(), io::ErrorKind::InvalidInput);
+        }
+        assert!(!subdir.join("dir").exists());
+    }
+}
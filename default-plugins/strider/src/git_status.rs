@@ -0,0 +1,162 @@
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use zellij_tile::prelude::*;
+
+// the command whose result we are waiting for is kept in the context of run_command
+const GIT_COMMAND: &str = "git_command";
+const SHOW_PREFIX: &str = "show_prefix";
+const STATUS: &str = "status";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum GitStatus {
+    // ordered by priority, a directory shows the highest status of its contents
+    Ignored,
+    Staged,
+    Untracked,
+    Modified,
+}
+
+impl GitStatus {
+    // parses the XY status code of `git status --porcelain`
+    fn from_porcelain(code: &str) -> Option<Self> {
+        let mut chars = code.chars();
+        let (index, worktree) = (chars.next()?, chars.next()?);
+        match (index, worktree) {
+            ('?', '?') => Some(GitStatus::Untracked),
+            ('!', '!') => Some(GitStatus::Ignored),
+            (_, 'M') | (_, 'D') | (_, 'T') | ('U', _) | (_, 'U') => Some(GitStatus::Modified),
+            (' ', _) => None,
+            _ => Some(GitStatus::Staged),
+        }
+    }
+}
+
+/// The git status of the files under the folder strider was opened in, refreshed in the
+/// background with `run_command`
+#[derive(Debug, Default)]
+pub struct GitState {
+    pub branch: Option<String>, // None if we're not in a git repository
+    statuses: HashMap<PathBuf, GitStatus>, // relative to the root folder
+    prefix: Option<String>,     // the path of the root folder relative to the repository
+    refresh_running: bool,
+    refresh_pending: bool,
+}
+
+impl GitState {
+    pub fn refresh(&mut self) {
+        if self.refresh_running {
+            // file system events come in bursts, so we refresh once more after the current one
+            self.refresh_pending = true;
+            return;
+        }
+        self.refresh_running = true;
+        match self.prefix {
+            Some(_) => run_git_status(),
+            None => run_command(
+                &["git", "rev-parse", "--show-prefix"],
+                BTreeMap::from([(GIT_COMMAND.to_owned(), SHOW_PREFIX.to_owned())]),
+            ),
+        }
+    }
+    pub fn status_of(&self, path: &Path) -> Option<GitStatus> {
+        self.statuses.get(path).copied()
+    }
+    /// Returns true if this was the result of one of our commands
+    pub fn handle_command_result(
+        &mut self,
+        exit_code: Option<i32>,
+        stdout: &[u8],
+        context: &BTreeMap<String, String>,
+    ) -> bool {
+        let stdout = String::from_utf8_lossy(stdout);
+        match context.get(GIT_COMMAND).map(|c| c.as_str()) {
+            Some(SHOW_PREFIX) => {
+                if exit_code == Some(0) {
+                    self.prefix = Some(stdout.trim().to_owned());
+                    run_git_status();
+                } else {
+                    // not a git repository, there's nothing to refresh until we know otherwise
+                    self.refresh_running = false;
+                    self.refresh_pending = false;
+                    self.branch = None;
+                    self.statuses.clear();
+                }
+                true
+            },
+            Some(STATUS) => {
+                self.refresh_running = false;
+                if exit_code == Some(0) {
+                    self.parse_status(&stdout);
+                }
+                if self.refresh_pending {
+                    self.refresh_pending = false;
+                    self.refresh();
+                }
+                true
+            },
+            _ => false,
+        }
+    }
+    fn parse_status(&mut self, porcelain: &str) {
+        let prefix = self.prefix.clone().unwrap_or_default();
+        self.statuses.clear();
+        for line in porcelain.lines() {
+            if let Some(branch_line) = line.strip_prefix("## ") {
+                self.branch = Some(parse_branch(branch_line));
+                continue;
+            }
+            if line.len() < 4 {
+                continue;
+            }
+            let (code, path) = line.split_at(3);
+            let status = match GitStatus::from_porcelain(code) {
+                Some(status) => status,
+                None => continue,
+            };
+            // renames are reported as "from -> to"
+            let path = path.rsplit(" -> ").next().unwrap_or(path);
+            let path = path.trim_matches('"').trim_end_matches('/');
+            // paths are relative to the repository, we only care about the ones under our folder
+            let path = match path.strip_prefix(prefix.as_str()) {
+                Some(path) => PathBuf::from(path),
+                None => continue,
+            };
+            // an ignored file does not make its folders ignored
+            let paths_with_status = if status == GitStatus::Ignored {
+                path.ancestors().take(1)
+            } else {
+                path.ancestors().take(usize::MAX)
+            };
+            for path in paths_with_status.filter(|p| !p.as_os_str().is_empty()) {
+                let entry = self.statuses.entry(path.to_path_buf()).or_insert(status);
+                if *entry < status {
+                    *entry = status;
+                }
+            }
+        }
+    }
+}
+
+fn run_git_status() {
+    run_command(
+        &["git", "status", "--porcelain=v1", "--branch", "--ignored"],
+        BTreeMap::from([(GIT_COMMAND.to_owned(), STATUS.to_owned())]),
+    );
+}
+
+// parses the branch line of `git status --porcelain --branch`, eg. "main...origin/main [ahead 1]"
+// or "No commits yet on main"
+fn parse_branch(branch_line: &str) -> String {
+    let branch_line = branch_line
+        .strip_prefix("No commits yet on ")
+        .or_else(|| branch_line.strip_prefix("Initial commit on "))
+        .unwrap_or(branch_line);
+    branch_line
+        .split("...")
+        .next()
+        .unwrap_or(branch_line)
+        .split(' ')
+        .next()
+        .unwrap_or(branch_line)
+        .to_owned()
+}
@@ -1,11 +1,15 @@
+mod file_operations;
+mod git_status;
 mod search;
 mod state;
 
 use colored::*;
+use file_operations::{ClipboardOperation, Prompt};
+use git_status::GitStatus;
 use search::{FileContentsWorker, FileNameWorker, MessageToSearch, ResultsOfSearch};
 use serde::{Deserialize, Serialize};
 use serde_json;
-use state::{refresh_directory, FsEntry, State};
+use state::{refresh_directory, FsEntry, State, ROOT};
 use std::collections::BTreeMap;
 use std::{cmp::min, time::Instant};
 use zellij_tile::prelude::*;
@@ -31,7 +35,9 @@ impl ZellijPlugin for State {
             EventType::FileSystemUpdate,
             EventType::FileSystemDelete,
             EventType::PermissionRequestResult,
+            EventType::RunCommandResult,
         ]);
+        self.git_state.refresh();
         post_message_to(PluginMessage {
             worker_name: Some("file_name_search".into()),
             name: serde_json::to_string(&MessageToSearch::ScanFolder).unwrap(),
@@ -54,10 +60,21 @@ impl ZellijPlugin for State {
             None
         };
         self.ev_history.push_back((event.clone(), Instant::now()));
+        if let Event::Key(_) = event {
+            // messages about file operations are shown until the next key press
+            if self.status_message.take().is_some() {
+                should_render = true;
+            }
+        }
         match event {
             Event::PermissionRequestResult(_) => {
                 should_render = true;
             },
+            Event::RunCommandResult(exit_code, stdout, _stderr, context) => {
+                should_render = self
+                    .git_state
+                    .handle_command_result(exit_code, &stdout, &context);
+            },
             Event::Timer(_elapsed) => {
                 if self.search_state.loading {
                     set_timeout(0.5);
@@ -101,15 +118,57 @@ impl ZellijPlugin for State {
                     self.search_state.handle_key(key);
                     should_render = true;
                 },
+                _ if self.prompt.is_some() => {
+                    self.handle_prompt_key(key);
+                    should_render = true;
+                },
                 Key::Char('/') => {
                     self.start_typing_search_term();
                     should_render = true;
                 },
+                Key::Esc if !self.marked_entries.is_empty() => {
+                    self.marked_entries.clear();
+                    should_render = true;
+                },
                 Key::Esc => {
                     self.stop_typing_search_term();
                     hide_self();
                     should_render = true;
                 },
+                Key::Char(' ') if !self.files.is_empty() => {
+                    self.toggle_mark_selected();
+                    let next = self.selected().saturating_add(1);
+                    *self.selected_mut() = min(self.files.len().saturating_sub(1), next);
+                    should_render = true;
+                },
+                Key::Char('n') => {
+                    self.start_prompt(Prompt::NewFile(String::new()));
+                    should_render = true;
+                },
+                Key::Char('N') => {
+                    self.start_prompt(Prompt::NewDir(String::new()));
+                    should_render = true;
+                },
+                Key::Char('r') => {
+                    self.start_rename();
+                    should_render = true;
+                },
+                Key::Char('x') | Key::Delete => {
+                    self.start_delete();
+                    should_render = true;
+                },
+                Key::Char('c') => {
+                    self.copy_to_clipboard(ClipboardOperation::Copy);
+                    should_render = true;
+                },
+                Key::Char('m') => {
+                    self.copy_to_clipboard(ClipboardOperation::Cut);
+                    should_render = true;
+                },
+                Key::Char('p') => {
+                    self.paste();
+                    should_render = true;
+                },
                 Key::Up | Key::Char('k') => {
                     let currently_selected = self.selected();
                     *self.selected_mut() = self.selected().saturating_sub(1);
@@ -205,6 +264,9 @@ impl ZellijPlugin for State {
                     name: serde_json::to_string(&MessageToSearch::FileSystemCreate).unwrap(),
                     payload: serde_json::to_string(&paths).unwrap(),
                 });
+                refresh_directory(self);
+                self.git_state.refresh();
+                should_render = true;
             },
             Event::FileSystemUpdate(paths) => {
                 let paths: Vec<String> = paths
@@ -221,6 +283,9 @@ impl ZellijPlugin for State {
                     name: serde_json::to_string(&MessageToSearch::FileSystemUpdate).unwrap(),
                     payload: serde_json::to_string(&paths).unwrap(),
                 });
+                refresh_directory(self);
+                self.git_state.refresh();
+                should_render = true;
             },
             Event::FileSystemDelete(paths) => {
                 let paths: Vec<String> = paths
@@ -237,6 +302,9 @@ impl ZellijPlugin for State {
                     name: serde_json::to_string(&MessageToSearch::FileSystemDelete).unwrap(),
                     payload: serde_json::to_string(&paths).unwrap(),
                 });
+                refresh_directory(self);
+                self.git_state.refresh();
+                should_render = true;
             },
             _ => {
                 dbg!("Unknown event {:?}", event);
@@ -252,6 +320,19 @@ impl ZellijPlugin for State {
             return;
         }
 
+        // the last row is taken by the prompt or message of a file operation, or the git branch
+        let bottom_line = self
+            .prompt
+            .as_ref()
+            .map(|p| p.render())
+            .or_else(|| self.status_message.clone())
+            .or_else(|| self.git_state.branch.as_ref().map(|b| format!(" {}", b)));
+        let rows = if bottom_line.is_some() {
+            rows.saturating_sub(1)
+        } else {
+            rows
+        };
+
         for i in 0..rows {
             if self.selected() < self.scroll() {
                 *self.scroll_mut() = self.selected();
@@ -263,12 +344,38 @@ impl ZellijPlugin for State {
             let is_last_row = i == rows.saturating_sub(1);
             let i = self.scroll() + i;
             if let Some(entry) = self.files.get(i) {
-                let mut path = entry.as_line(cols).normal();
+                // entries are prefixed with their git status inside git repositories
+                let git_status_indicator = self.git_state.branch.as_ref().map(|_| {
+                    let status = entry
+                        .path()
+                        .strip_prefix(ROOT)
+                        .ok()
+                        .and_then(|p| self.git_state.status_of(p));
+                    match status {
+                        Some(GitStatus::Modified) => "M ".red().bold(),
+                        Some(GitStatus::Staged) => "+ ".green().bold(),
+                        Some(GitStatus::Untracked) => "? ".cyan().bold(),
+                        Some(GitStatus::Ignored) => "! ".dimmed(),
+                        None => "  ".normal(),
+                    }
+                });
+                let line_width = if git_status_indicator.is_some() {
+                    cols.saturating_sub(2)
+                } else {
+                    cols
+                };
+                let mut path = entry.as_line(line_width).normal();
 
                 if let FsEntry::Dir(..) = entry {
                     path = path.dimmed().bold();
                 }
+                if self.marked_entries.contains(entry.path()) {
+                    path = path.yellow();
+                }
 
+                if let Some(git_status_indicator) = git_status_indicator {
+                    print!("{}", git_status_indicator);
+                }
                 if i == self.selected() {
                     if is_last_row {
                         print!("{}", path.clone().reversed());
@@ -286,6 +393,13 @@ impl ZellijPlugin for State {
                 println!();
             }
         }
+        if let Some(bottom_line) = bottom_line {
+            let mut bottom_line: String = bottom_line.chars().take(cols).collect();
+            if rows > 0 {
+                bottom_line.insert(0, '\n');
+            }
+            print!("{}", bottom_line.bold());
+        }
     }
 }
 
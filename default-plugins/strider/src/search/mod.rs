@@ -1,4 +1,5 @@
 pub mod controls_line;
+pub mod preview;
 pub mod search_results;
 pub mod search_state;
 pub mod selection_controls_area;
@@ -15,7 +16,8 @@ use zellij_tile::prelude::*;
 
 use fuzzy_matcher::skim::SkimMatcherV2;
 use fuzzy_matcher::FuzzyMatcher;
-use ignore::Walk;
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use ignore::WalkBuilder;
 use search_results::SearchResult;
 use serde::{Deserialize, Serialize};
 
@@ -28,6 +30,8 @@ pub struct Search {
     file_contents: BTreeMap<(String, usize), String>, // file_name, line_number, line
     cached_file_name_results: HashMap<String, Vec<SearchResult>>,
     cached_file_contents_results: HashMap<String, Vec<SearchResult>>,
+    #[serde(skip)]
+    ignore_rules: Option<Gitignore>, // of the root folder, for files changed after the scan
 }
 
 impl Search {
@@ -65,7 +69,9 @@ impl Search {
         }
     }
     pub fn scan_hd(&mut self) {
-        for result in Walk::new(ROOT) {
+        self.ignore_rules = Some(root_ignore_rules());
+        // respect .gitignore files even if the folder is not a git repository
+        for result in WalkBuilder::new(ROOT).require_git(false).build() {
             if let Ok(entry) = result {
                 self.add_file_entry(entry.path(), entry.metadata().ok());
             }
@@ -135,7 +141,11 @@ impl Search {
         match serde_json::from_str::<Vec<PathBuf>>(&paths) {
             Ok(paths) => {
                 for path in paths {
-                    self.add_file_entry(&path, path.metadata().ok());
+                    let metadata = path.metadata().ok();
+                    let is_dir = metadata.as_ref().map(|m| m.is_dir()).unwrap_or(false);
+                    if !self.is_ignored(&path, is_dir) {
+                        self.add_file_entry(&path, metadata);
+                    }
                 }
                 self.cached_file_name_results.clear();
                 self.cached_file_contents_results.clear();
@@ -218,6 +228,26 @@ impl Search {
         }
         matches
     }
+    // the scan skips hidden and ignored files, so we skip them when they change too
+    fn is_ignored(&self, path: &Path, is_dir: bool) -> bool {
+        let relative_path = match path.strip_prefix(ROOT) {
+            Ok(relative_path) => relative_path,
+            Err(_) => return true,
+        };
+        let is_hidden = relative_path
+            .components()
+            .any(|c| c.as_os_str().to_string_lossy().starts_with('.'));
+        is_hidden
+            || self
+                .ignore_rules
+                .as_ref()
+                .map(|ignore_rules| {
+                    ignore_rules
+                        .matched_path_or_any_parents(path, is_dir)
+                        .is_ignore()
+                })
+                .unwrap_or(false)
+    }
     fn strip_file_prefix(&self, file_name: &Path) -> String {
         let mut file_path_stripped_prefix = file_name.display().to_string().split_off(ROOT.width());
         if file_path_stripped_prefix.starts_with('/') {
@@ -244,6 +274,22 @@ impl Search {
     }
 }
 
+fn root_ignore_rules() -> Gitignore {
+    let mut builder = GitignoreBuilder::new(ROOT);
+    for ignore_file in [".gitignore", ".ignore"] {
+        let ignore_file = Path::new(ROOT).join(ignore_file);
+        if ignore_file.exists() {
+            if let Some(e) = builder.add(ignore_file) {
+                eprintln!("Failed to parse ignore file: {:?}", e);
+            }
+        }
+    }
+    builder.build().unwrap_or_else(|e| {
+        eprintln!("Failed to build ignore rules: {:?}", e);
+        Gitignore::empty()
+    })
+}
+
 #[derive(Serialize, Deserialize)]
 pub enum MessageToSearch {
     ScanFolder,
@@ -0,0 +1,141 @@
+use crate::search::search_results::SearchResult;
+use crate::search::ui::{bold, styled_text_foreground, GRAY_DARK, GREEN};
+use crate::state::ROOT;
+use std::io::{self, BufRead};
+use std::path::Path;
+use unicode_width::UnicodeWidthChar;
+
+pub const MIN_COLUMNS_FOR_PREVIEW: usize = 100;
+
+// the lines of the selected search result's file around its matched line
+#[derive(Debug, Clone, Default)]
+pub struct Preview {
+    path: String,
+    lines: Vec<(usize, String)>, // line number, line
+    matched_line_number: Option<usize>,
+    is_binary: bool,
+}
+
+impl Preview {
+    pub fn new(search_result: &SearchResult, rows: usize) -> Self {
+        let (path, matched_line_number) = match search_result {
+            SearchResult::File { path, .. } => (path, None),
+            SearchResult::LineInFile {
+                path, line_number, ..
+            } => (path, Some(*line_number)),
+        };
+        // the first row is the title
+        let rows_for_lines = rows.saturating_sub(1);
+        // place the matched line in the middle of the preview
+        let first_line_number = matched_line_number
+            .map(|line_number| line_number.saturating_sub(rows_for_lines / 2).max(1))
+            .unwrap_or(1);
+        let mut preview = Preview {
+            path: path.clone(),
+            matched_line_number,
+            ..Default::default()
+        };
+        if let Ok(file) = std::fs::File::open(Path::new(ROOT).join(path)) {
+            let lines = io::BufReader::new(file)
+                .lines()
+                .enumerate()
+                .skip(first_line_number - 1)
+                .take(rows_for_lines);
+            for (index, line) in lines {
+                match line {
+                    Ok(line) => {
+                        let line =
+                            String::from_utf8_lossy(&strip_ansi_escapes::strip(line).unwrap())
+                                .to_string();
+                        preview.lines.push((index + 1, line));
+                    },
+                    Err(_) => {
+                        preview.is_binary = true;
+                        preview.lines.clear();
+                        break;
+                    },
+                }
+            }
+        }
+        preview
+    }
+    pub fn is_for(&self, search_result: &SearchResult) -> bool {
+        match search_result {
+            SearchResult::File { path, .. } => {
+                &self.path == path && self.matched_line_number.is_none()
+            },
+            SearchResult::LineInFile {
+                path, line_number, ..
+            } => &self.path == path && self.matched_line_number == Some(*line_number),
+        }
+    }
+    // the preview is drawn with absolute cursor positioning, so it can be placed next to the
+    // search results after they are rendered
+    pub fn render(&self, x: usize, y: usize, rows: usize, columns: usize) -> String {
+        let mut to_render = String::new();
+        let separator = "│ ";
+        let columns = columns.saturating_sub(separator.chars().count());
+        let title = truncate_to_width(&self.path, columns);
+        to_render.push_str(&format!(
+            "\u{1b}[{};{}H{}{}",
+            y + 1,
+            x + 1,
+            separator,
+            styled_text_foreground(GREEN, &bold(&title))
+        ));
+        if self.is_binary {
+            to_render.push_str(&format!(
+                "\u{1b}[{};{}H{}{}",
+                y + 2,
+                x + 1,
+                separator,
+                styled_text_foreground(GRAY_DARK, "(binary file)")
+            ));
+            return to_render;
+        }
+        let line_number_width = self
+            .lines
+            .last()
+            .map(|(line_number, _)| line_number.to_string().len())
+            .unwrap_or(0);
+        for (i, (line_number, line)) in self.lines.iter().take(rows.saturating_sub(1)).enumerate() {
+            let line_number_prefix = format!("{:>width$} ", line_number, width = line_number_width);
+            let line = truncate_to_width(
+                &line.replace('\t', "    "),
+                columns.saturating_sub(line_number_prefix.len()),
+            );
+            let is_matched_line = Some(*line_number) == self.matched_line_number;
+            let (line_number_prefix, line) = if is_matched_line {
+                (
+                    styled_text_foreground(GREEN, &bold(&line_number_prefix)),
+                    styled_text_foreground(GREEN, &bold(&line)),
+                )
+            } else {
+                (styled_text_foreground(GRAY_DARK, &line_number_prefix), line)
+            };
+            to_render.push_str(&format!(
+                "\u{1b}[{};{}H{}{}{}",
+                y + i + 2,
+                x + 1,
+                separator,
+                line_number_prefix,
+                line
+            ));
+        }
+        to_render
+    }
+}
+
+fn truncate_to_width(text: &str, max_width: usize) -> String {
+    let mut truncated = String::new();
+    let mut width = 0;
+    for character in text.chars().filter(|c| !c.is_control()) {
+        let character_width = character.width().unwrap_or(0);
+        if width + character_width > max_width {
+            break;
+        }
+        width += character_width;
+        truncated.push(character);
+    }
+    truncated
+}
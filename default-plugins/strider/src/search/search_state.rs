@@ -1,3 +1,4 @@
+use crate::search::preview::{Preview, MIN_COLUMNS_FOR_PREVIEW};
 use crate::search::search_results::SearchResult;
 use crate::search::{MessageToSearch, ResultsOfSearch};
 use serde::{Deserialize, Serialize};
@@ -22,6 +23,7 @@ pub struct SearchState {
     pub display_rows: usize,
     pub display_columns: usize,
     pub displayed_search_results: (usize, Vec<SearchResult>), // usize is selected index
+    pub preview: Option<Preview>,
 }
 
 impl SearchState {
@@ -59,8 +61,13 @@ impl SearchState {
         }
     }
     pub fn change_size(&mut self, rows: usize, cols: usize) {
+        let size_changed = self.display_rows != rows || self.display_columns != cols;
         self.display_rows = rows;
         self.display_columns = cols;
+        if size_changed {
+            self.preview = None;
+            self.update_preview();
+        }
     }
     pub fn progress_animation(&mut self) {
         if self.loading_animation_offset == u8::MAX {
@@ -80,9 +87,11 @@ impl SearchState {
         if self.displayed_search_results.0 < self.max_search_selection_index() {
             self.displayed_search_results.0 += 1;
         }
+        self.update_preview();
     }
     fn move_search_selection_up(&mut self) {
         self.displayed_search_results.0 = self.displayed_search_results.0.saturating_sub(1);
+        self.update_preview();
     }
     fn open_search_result_in_editor(&mut self) {
         match self.selected_search_result_entry() {
@@ -146,6 +155,7 @@ impl SearchState {
         self.file_name_search_results.clear();
         self.file_contents_search_results.clear();
         self.displayed_search_results = (0, vec![]);
+        self.preview = None;
         self.search_term.clear();
     }
     fn append_to_search_term(&mut self, key: Key) {
@@ -222,6 +232,39 @@ impl SearchState {
             })
             .unwrap_or(0);
         self.displayed_search_results = (new_index, displayed_search_results);
+        self.update_preview();
+    }
+    fn update_preview(&mut self) {
+        if !self.should_show_preview() {
+            self.preview = None;
+            return;
+        }
+        match self.selected_search_result_entry() {
+            Some(selected_search_result) => {
+                let preview_is_up_to_date = self
+                    .preview
+                    .as_ref()
+                    .map(|p| p.is_for(&selected_search_result))
+                    .unwrap_or(false);
+                if !preview_is_up_to_date {
+                    self.preview = Some(Preview::new(
+                        &selected_search_result,
+                        self.rows_for_results(),
+                    ));
+                }
+            },
+            None => self.preview = None,
+        }
+    }
+    pub fn should_show_preview(&self) -> bool {
+        self.display_columns >= MIN_COLUMNS_FOR_PREVIEW
+    }
+    pub fn columns_for_results(&self) -> usize {
+        if self.should_show_preview() {
+            self.display_columns / 2
+        } else {
+            self.display_columns
+        }
     }
     fn selected_search_result_entry(&self) -> Option<SearchResult> {
         self.displayed_search_results
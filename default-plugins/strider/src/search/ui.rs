@@ -18,6 +18,7 @@ impl Display for SearchState {
         write!(f, "{}", self.render_search_results())?;
         write!(f, "{}", self.render_selection_control_area())?;
         write!(f, "{}", self.render_controls_line())?;
+        write!(f, "{}", self.render_preview())?;
         Ok(())
     }
 }
@@ -42,8 +43,11 @@ impl SearchState {
             let index_of_selected_result = self.displayed_search_results.0;
             let is_selected = i == index_of_selected_result;
             let is_below_search_result = i > index_of_selected_result;
-            let rendered_result =
-                search_result.render(self.display_columns, is_selected, is_below_search_result);
+            let rendered_result = search_result.render(
+                self.columns_for_results(),
+                is_selected,
+                is_below_search_result,
+            );
             to_render.push_str(&format!("{}", rendered_result));
             to_render.push('\n')
         }
@@ -65,6 +69,21 @@ impl SearchState {
             )
         }
     }
+    pub fn render_preview(&self) -> String {
+        match &self.preview {
+            Some(preview) => {
+                let x = self.columns_for_results();
+                let y = 1; // below the search line
+                preview.render(
+                    x,
+                    y,
+                    self.rows_for_results(),
+                    self.display_columns.saturating_sub(x),
+                )
+            },
+            None => String::new(),
+        }
+    }
     pub fn render_controls_line(&self) -> String {
         let has_results = !self.displayed_search_results.1.is_empty();
         let tiled_floating_control =
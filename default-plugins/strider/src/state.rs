@@ -1,10 +1,13 @@
+use crate::file_operations::{self, Clipboard, ClipboardOperation, Prompt};
+use crate::git_status::GitState;
 use crate::search::search_results::SearchResult;
 use crate::search::search_state::SearchState;
 use crate::search::search_state::SearchType;
 use pretty_bytes::converter as pb;
 use std::{
-    collections::{HashMap, VecDeque},
+    collections::{BTreeSet, HashMap, VecDeque},
     fs::read_dir,
+    io,
     path::{Path, PathBuf},
     time::Instant,
 };
@@ -32,6 +35,11 @@ pub struct State {
     pub processed_search_index: usize,
     pub should_open_floating: bool,
     pub search_filter: SearchType,
+    pub prompt: Option<Prompt>,
+    pub marked_entries: BTreeSet<PathBuf>,
+    pub clipboard: Option<Clipboard>,
+    pub status_message: Option<String>,
+    pub git_state: GitState,
 }
 
 impl State {
@@ -73,6 +81,133 @@ impl State {
             }
         }
     }
+    pub fn current_dir(&self) -> PathBuf {
+        Path::new(ROOT).join(&self.path)
+    }
+    pub fn toggle_mark_selected(&mut self) {
+        if let Some(path) = self.files.get(self.selected()).map(|f| f.path().clone()) {
+            if !self.marked_entries.remove(&path) {
+                self.marked_entries.insert(path);
+            }
+        }
+    }
+    pub fn start_prompt(&mut self, prompt: Prompt) {
+        self.status_message = None;
+        self.prompt = Some(prompt);
+    }
+    pub fn start_rename(&mut self) {
+        if let Some(entry) = self.files.get(self.selected()) {
+            let prompt = Prompt::Rename(entry.path().clone(), entry.name());
+            self.start_prompt(prompt);
+        }
+    }
+    pub fn start_delete(&mut self) {
+        let paths = self.paths_to_operate_on();
+        if !paths.is_empty() {
+            self.start_prompt(Prompt::ConfirmDelete(paths));
+        }
+    }
+    pub fn handle_prompt_key(&mut self, key: Key) {
+        let prompt = match self.prompt.as_mut() {
+            Some(prompt) => prompt,
+            None => return,
+        };
+        match prompt.name_mut() {
+            Some(name) => match key {
+                Key::Char('\n') => self.finish_prompt(),
+                Key::Char(c) => name.push(c),
+                Key::Backspace => {
+                    name.pop();
+                },
+                Key::Esc => self.prompt = None,
+                _ => {},
+            },
+            // anything other than 'y' cancels the deletion
+            None if key == Key::Char('y') => self.finish_prompt(),
+            None => self.prompt = None,
+        }
+    }
+    pub fn copy_to_clipboard(&mut self, operation: ClipboardOperation) {
+        let paths = self.paths_to_operate_on();
+        if paths.is_empty() {
+            return;
+        }
+        let verb = match operation {
+            ClipboardOperation::Copy => "Copied",
+            ClipboardOperation::Cut => "Cut",
+        };
+        self.status_message = Some(format!(
+            "{} {} item(s), press p to paste",
+            verb,
+            paths.len()
+        ));
+        self.clipboard = Some(Clipboard::new(operation, paths));
+        self.marked_entries.clear();
+    }
+    pub fn paste(&mut self) {
+        let clipboard = match self.clipboard.take() {
+            Some(clipboard) => clipboard,
+            None => return,
+        };
+        let result = clipboard.paste(&self.current_dir());
+        if clipboard.operation == ClipboardOperation::Copy {
+            // copied files can be pasted again
+            self.clipboard = Some(clipboard);
+        }
+        let last_pasted = result.map(|mut pasted| pasted.pop());
+        self.finish_operation(last_pasted);
+    }
+    fn finish_prompt(&mut self) {
+        let current_dir = self.current_dir();
+        let result = match self.prompt.take() {
+            Some(Prompt::NewFile(name)) => {
+                file_operations::create_file(&current_dir, &name).map(Some)
+            },
+            Some(Prompt::NewDir(name)) => {
+                file_operations::create_dir(&current_dir, &name).map(Some)
+            },
+            Some(Prompt::Rename(path, new_name)) => {
+                file_operations::rename(&path, &new_name).map(Some)
+            },
+            Some(Prompt::ConfirmDelete(paths)) => {
+                self.marked_entries.clear();
+                paths
+                    .iter()
+                    .map(|path| file_operations::delete(path))
+                    .collect::<io::Result<()>>()
+                    .map(|_| None)
+            },
+            None => return,
+        };
+        self.finish_operation(result);
+    }
+    // refreshes the directory right away rather than waiting for the file system events, and
+    // selects the created entry if there is one
+    fn finish_operation(&mut self, result: io::Result<Option<PathBuf>>) {
+        refresh_directory(self);
+        match result {
+            Ok(Some(path)) => {
+                if let Some(index) = self.files.iter().position(|f| f.path() == &path) {
+                    *self.selected_mut() = index;
+                }
+            },
+            Ok(None) => {
+                let last_index = self.files.len().saturating_sub(1);
+                *self.selected_mut() = std::cmp::min(self.selected(), last_index);
+            },
+            Err(e) => self.status_message = Some(format!("Error: {}", e)),
+        }
+    }
+    fn paths_to_operate_on(&self) -> Vec<PathBuf> {
+        if self.marked_entries.is_empty() {
+            self.files
+                .get(self.selected())
+                .map(|f| vec![f.path().clone()])
+                .unwrap_or_default()
+        } else {
+            self.marked_entries.iter().cloned().collect()
+        }
+    }
 }
 
 #[derive(PartialEq, Eq, PartialOrd, Ord, Clone)]
@@ -83,11 +218,18 @@ pub enum FsEntry {
 
 impl FsEntry {
     pub fn name(&self) -> String {
-        let path = match self {
+        self.path()
+            .file_name()
+            .unwrap()
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    pub fn path(&self) -> &PathBuf {
+        match self {
             FsEntry::Dir(p, _) => p,
             FsEntry::File(p, _) => p,
-        };
-        path.file_name().unwrap().to_string_lossy().into_owned()
+        }
     }
 
     pub fn as_line(&self, width: usize) -> String {
@@ -129,4 +271,5 @@ pub(crate) fn refresh_directory(state: &mut State) {
         .collect();
 
     state.files.sort_unstable();
+    state.marked_entries.retain(|p| p.exists());
 }
@@ -136,6 +136,7 @@ fn left_more_message(
         part: more_styled_text,
         len: more_text_len,
         tab_index: Some(tab_index),
+        is_overflow_marker: true,
     }
 }
 
@@ -168,6 +169,7 @@ fn right_more_message(
         part: more_styled_text,
         len: more_text_len,
         tab_index: Some(tab_index),
+        is_overflow_marker: true,
     }
 }
 
@@ -188,6 +190,7 @@ fn tab_line_prefix(session_name: Option<&str>, palette: Palette, cols: usize) ->
         part: prefix_styled_text.to_string(),
         len: prefix_text_len,
         tab_index: None,
+        is_overflow_marker: false,
     }];
     if let Some(name) = session_name {
         let name_part = format!("({}) ", name);
@@ -202,6 +205,7 @@ fn tab_line_prefix(session_name: Option<&str>, palette: Palette, cols: usize) ->
                 part: name_part_styled_text.to_string(),
                 len: name_part_len,
                 tab_index: None,
+                is_overflow_marker: false,
             })
         }
     }
@@ -3,20 +3,13 @@ mod tab;
 
 use std::cmp::{max, min};
 use std::collections::BTreeMap;
-use std::convert::TryInto;
 
 use tab::get_tab_to_focus;
 use zellij_tile::prelude::*;
+use zellij_tile_utils::tab_line::{LinePart, TabLineInteractions};
 
-use crate::line::tab_line;
-use crate::tab::tab_style;
-
-#[derive(Debug, Default)]
-pub struct LinePart {
-    part: String,
-    len: usize,
-    tab_index: Option<usize>,
-}
+use crate::line::{tab_line, tab_separator};
+use crate::tab::{render_tab, tab_style};
 
 #[derive(Default)]
 struct State {
@@ -24,6 +17,7 @@ struct State {
     active_tab_idx: usize,
     mode_info: ModeInfo,
     tab_line: Vec<LinePart>,
+    tab_line_interactions: TabLineInteractions,
 }
 
 static ARROW_SEPARATOR: &str = "";
@@ -37,6 +31,7 @@ impl ZellijPlugin for State {
             EventType::TabUpdate,
             EventType::ModeUpdate,
             EventType::Mouse,
+            EventType::Key,
         ]);
     }
 
@@ -66,9 +61,25 @@ impl ZellijPlugin for State {
             Event::Mouse(me) => match me {
                 Mouse::LeftClick(_, col) => {
                     let tab_to_focus = get_tab_to_focus(&self.tab_line, self.active_tab_idx, col);
-                    if let Some(idx) = tab_to_focus {
-                        switch_tab_to(idx.try_into().unwrap());
-                    }
+                    should_render = self.tab_line_interactions.handle_left_click(
+                        &self.tab_line,
+                        &self.tabs,
+                        col,
+                        tab_to_focus,
+                    );
+                },
+                Mouse::Hold(_, col) => {
+                    self.tab_line_interactions.handle_hold(&self.tab_line, col);
+                },
+                Mouse::Release(..) => {
+                    self.tab_line_interactions.handle_release();
+                },
+                Mouse::MiddleClick(_, col) => {
+                    self.tab_line_interactions.handle_middle_click(
+                        &self.tab_line,
+                        col,
+                        self.active_tab_idx.saturating_sub(1),
+                    );
                 },
                 Mouse::ScrollUp(_) => {
                     switch_tab_to(min(self.active_tab_idx + 1, self.tabs.len()) as u32);
@@ -78,6 +89,9 @@ impl ZellijPlugin for State {
                 },
                 _ => {},
             },
+            Event::Key(key) => {
+                should_render = self.tab_line_interactions.handle_key(key, &self.tabs);
+            },
             _ => {
                 eprintln!("Got unrecognized event: {:?}", event);
             },
@@ -89,6 +103,18 @@ impl ZellijPlugin for State {
         if self.tabs.is_empty() {
             return;
         }
+        let palette = self.mode_info.style.colors;
+        let separator = tab_separator(self.mode_info.capabilities);
+        if let Some(hidden_tabs_search) = self.tab_line_interactions.render_hidden_tabs_search(
+            &self.tabs,
+            cols.saturating_sub(1),
+            palette,
+            |tab| render_tab(tab.name.clone(), tab, false, palette, separator),
+        ) {
+            self.tab_line = hidden_tabs_search;
+            self.print_tab_line();
+            return;
+        }
         let mut all_tabs: Vec<LinePart> = vec![];
         let mut active_tab_index = 0;
         let mut is_alternate_tab = false;
@@ -121,7 +147,12 @@ impl ZellijPlugin for State {
             self.mode_info.capabilities,
             self.mode_info.style.hide_session_name,
         );
+        self.print_tab_line();
+    }
+}
 
+impl State {
+    fn print_tab_line(&self) {
         let output = self
             .tab_line
             .iter()
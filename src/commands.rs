@@ -2,11 +2,12 @@ use dialoguer::Confirm;
 use std::{fs::File, io::prelude::*, path::PathBuf, process, time::Duration};
 
 use crate::sessions::{
-    assert_dead_session, assert_session, assert_session_ne, delete_session as delete_session_impl,
-    get_active_session, get_name_generator, get_resurrectable_sessions, get_sessions,
-    get_sessions_sorted_by_mtime, kill_session as kill_session_impl, match_session_name,
-    print_sessions, print_sessions_with_index, resurrection_layout, session_exists, ActiveSession,
-    SessionNameMatch,
+    assert_dead_session, assert_session, assert_session_ne, copy_snapshot_to_session,
+    delete_session as delete_session_impl, delete_snapshot, get_active_session, get_name_generator,
+    get_resurrectable_sessions, get_session_snapshots, get_sessions, get_sessions_sorted_by_mtime,
+    kill_session as kill_session_impl, match_session_name, print_session_snapshots, print_sessions,
+    print_sessions_with_index, print_workspaces, resurrection_layout, session_exists,
+    snapshot_layout, ActiveSession, SessionNameMatch,
 };
 use zellij_client::{
     old_config_converter::{
@@ -19,18 +20,21 @@ use zellij_server::{os_input_output::get_server_os_input, start_server as start_
 use zellij_utils::{
     cli::{CliArgs, Command, SessionCommand, Sessions},
     data::ConnectToSession,
-    envs,
+    envs::{self, EnvironmentVariables},
+    home::{find_default_config_dir, get_layout_dir},
     input::{
-        actions::Action,
+        actions::{Action, ActionTarget},
         config::{Config, ConfigError},
-        options::Options,
+        layout::Layout,
+        options::{CliOptions, Options},
+        workspaces::{Workspaces, WORKSPACES_FILE_NAME},
     },
     miette::{Report, Result},
     nix,
     setup::Setup,
 };
 
-pub(crate) use crate::sessions::list_sessions;
+pub(crate) use crate::sessions::{list_sessions, resurrectable_session_ttl};
 
 pub(crate) fn kill_all_sessions(yes: bool) {
     match get_sessions() {
@@ -123,6 +127,113 @@ pub(crate) fn delete_session(target_session: &Option<String>, force: bool) {
     }
 }
 
+pub(crate) fn list_session_snapshots(session_name: Option<String>, no_formatting: bool) {
+    let snapshots = get_session_snapshots(session_name.as_deref());
+    if snapshots.is_empty() {
+        eprintln!("No snapshots found.");
+        process::exit(1);
+    }
+    print_session_snapshots(snapshots, no_formatting);
+    process::exit(0);
+}
+
+pub(crate) fn restore_session_snapshot(
+    mut opts: CliArgs,
+    snapshot: String,
+    session_name: Option<String>,
+    force_run_commands: bool,
+) {
+    if snapshot_layout(&snapshot).is_none() {
+        eprintln!("No snapshot named {:?} found.", snapshot);
+        process::exit(1);
+    }
+    let session_name = session_name.unwrap_or_else(|| snapshot.clone());
+    assert_session_ne(&session_name);
+    // the snapshot becomes a dead session, which is then resurrected by attaching to it
+    if let Err(e) = copy_snapshot_to_session(&snapshot, &session_name) {
+        eprintln!("Failed to restore snapshot {:?}: {}", snapshot, e);
+        process::exit(1);
+    }
+    opts.command = Some(Command::Sessions(Sessions::Attach {
+        session_name: Some(session_name),
+        create: true,
+        force_run_commands,
+        index: None,
+        options: None,
+        read_only: false,
+        remote: None,
+        token: None,
+        ca_cert: None,
+    }));
+    start_client(opts);
+}
+
+pub(crate) fn delete_session_snapshot(snapshot: &str) {
+    delete_snapshot(snapshot);
+    process::exit(0);
+}
+
+pub(crate) fn attach_to_workspace(mut opts: CliArgs, workspace_name: String) {
+    let workspaces = load_workspaces(&opts);
+    let workspace = match workspaces.get(&workspace_name) {
+        Some(workspace) => workspace,
+        None => {
+            eprintln!("No workspace named {:?} found.", workspace_name);
+            process::exit(1);
+        },
+    };
+    // the workspace itself is applied to the configuration when setting up the client
+    let workspace_options = CliOptions {
+        options: Options {
+            workspace: Some(workspace.name.clone()),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    opts.command = Some(Command::Sessions(Sessions::Attach {
+        session_name: Some(workspace.default_session_name()),
+        create: true,
+        force_run_commands: false,
+        index: None,
+        options: Some(Box::new(SessionCommand::Options(workspace_options))),
+        read_only: false,
+        remote: None,
+        token: None,
+        ca_cert: None,
+    }));
+    start_client(opts);
+}
+
+pub(crate) fn list_workspaces(opts: &CliArgs, no_formatting: bool) {
+    let workspaces = load_workspaces(opts);
+    if workspaces.workspaces.is_empty() {
+        eprintln!(
+            "No workspaces defined, they can be defined in a {} file next to the configuration file.",
+            WORKSPACES_FILE_NAME
+        );
+        process::exit(1);
+    }
+    print_workspaces(&workspaces, no_formatting, resurrectable_session_ttl(opts));
+    process::exit(0);
+}
+
+fn load_workspaces(opts: &CliArgs) -> Workspaces {
+    let workspaces =
+        Config::try_from(opts).and_then(|config| Setup::workspaces_from_cli_args(opts, &config));
+    match workspaces {
+        Ok(workspaces) => workspaces,
+        Err(e) => {
+            if let ConfigError::KdlError(error) = e {
+                let report: Report = error.into();
+                eprintln!("{:?}", report);
+            } else {
+                eprintln!("{}", e);
+            }
+            process::exit(1);
+        },
+    }
+}
+
 fn get_os_input<OsInputOutput>(
     fn_get_os_input: fn() -> Result<OsInputOutput, nix::Error>,
 ) -> OsInputOutput {
@@ -171,6 +282,7 @@ fn find_indexed_session(
 /// Checks session to send the action to and attaches with client
 pub(crate) fn send_action_to_session(
     cli_action: zellij_utils::cli::CliAction,
+    target: Option<ActionTarget>,
     requested_session_name: Option<String>,
     config: Option<Config>,
 ) {
@@ -190,7 +302,7 @@ pub(crate) fn send_action_to_session(
                     std::process::exit(1);
                 }
             }
-            attach_with_cli_client(cli_action, &session_name, config);
+            attach_with_cli_client(cli_action, target.clone(), &session_name, config);
         },
         ActiveSession::Many => {
             let existing_sessions: Vec<String> = get_sessions()
@@ -200,20 +312,20 @@ pub(crate) fn send_action_to_session(
                 .collect();
             if let Some(session_name) = requested_session_name {
                 if existing_sessions.contains(&session_name) {
-                    attach_with_cli_client(cli_action, &session_name, config);
+                    attach_with_cli_client(cli_action, target.clone(), &session_name, config);
                 } else {
                     eprintln!(
                         "Session '{}' not found. The following sessions are active:",
                         session_name
                     );
-                    list_sessions(false, false);
+                    list_sessions(false, false, false, None);
                     std::process::exit(1);
                 }
             } else if let Ok(session_name) = envs::get_session_name() {
-                attach_with_cli_client(cli_action, &session_name, config);
+                attach_with_cli_client(cli_action, target.clone(), &session_name, config);
             } else {
                 eprintln!("Please specify the session name to send actions to. The following sessions are active:");
-                list_sessions(false, false);
+                list_sessions(false, false, false, None);
                 std::process::exit(1);
             }
         },
@@ -290,13 +402,20 @@ pub(crate) fn convert_old_theme_file(old_theme_file: PathBuf) {
 
 fn attach_with_cli_client(
     cli_action: zellij_utils::cli::CliAction,
+    target: Option<ActionTarget>,
     session_name: &str,
     config: Option<Config>,
 ) {
     let os_input = get_os_input(zellij_client::os_input_output::get_cli_client_os_input);
     let get_current_dir = || std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
     match Action::actions_from_cli(cli_action, Box::new(get_current_dir), config) {
-        Ok(actions) => {
+        Ok(mut actions) => {
+            if let Some(target) = target {
+                actions = actions
+                    .drain(..)
+                    .map(|action| Action::Targeted(target.clone(), Box::new(action)))
+                    .collect();
+            }
             zellij_client::cli_client::start_cli_client(Box::new(os_input), session_name, actions);
             std::process::exit(0);
         },
@@ -352,10 +471,11 @@ fn attach_with_session_name(
                 print_sessions(
                     sessions
                         .iter()
-                        .map(|s| (s.clone(), Duration::default(), false))
+                        .map(|s| (s.clone(), Duration::default(), false, false))
                         .collect(),
                     false,
                     false,
+                    None,
                 );
                 process::exit(1);
             },
@@ -373,7 +493,7 @@ fn attach_with_session_name(
             ActiveSession::One(session_name) => ClientInfo::Attach(session_name, config_options),
             ActiveSession::Many => {
                 println!("Please specify the session to attach to, either by using the full name or a unique prefix.\nThe following sessions are active:");
-                list_sessions(false, false);
+                list_sessions(false, false, false, None);
                 process::exit(1);
             },
         },
@@ -399,12 +519,20 @@ pub(crate) fn start_client(opts: CliArgs) {
     let os_input = get_os_input(get_client_os_input);
     loop {
         let os_input = os_input.clone();
-        let config = config.clone();
-        let layout = layout.clone();
+        let mut config = config.clone();
+        let mut layout = layout.clone();
         let mut config_options = config_options.clone();
         let mut opts = opts.clone();
         let mut is_a_reconnect = false;
 
+        // a viewer stays read-only when it is moved to another session
+        let read_only = matches!(
+            opts.command,
+            Some(Command::Sessions(Sessions::Attach {
+                read_only: true,
+                ..
+            }))
+        );
         if let Some(reconnect_to_session) = &reconnect_to_session {
             // this is integration code to make session reconnects work with this existing,
             // untested and pretty involved function
@@ -417,12 +545,49 @@ pub(crate) fn start_client(opts: CliArgs) {
                     force_run_commands: false,
                     index: None,
                     options: None,
+                    read_only,
+                    remote: None,
+                    token: None,
+                    ca_cert: None,
                 }));
             } else {
                 opts.command = None;
                 opts.session = None;
                 config_options.attach_to_session = None;
             }
+            // these only take effect if the reconnect creates a new session
+            if let Some(layout_name) = &reconnect_to_session.layout {
+                let layout_dir = config_options
+                    .layout_dir
+                    .clone()
+                    .or_else(|| get_layout_dir(find_default_config_dir()));
+                // only layouts from the layout dir or built-in ones, the request can come from a
+                // plugin
+                if !Layout::is_layout_name(layout_name) {
+                    log::error!(
+                        "Refusing to load layout {:?}: not a layout name",
+                        layout_name
+                    );
+                } else {
+                    match Layout::from_path_or_default(
+                        Some(&PathBuf::from(layout_name)),
+                        layout_dir,
+                        config.clone(),
+                    ) {
+                        Ok((new_session_layout, new_session_config)) => {
+                            layout = new_session_layout;
+                            config = new_session_config;
+                        },
+                        Err(e) => log::error!("Failed to load layout {}: {}", layout_name, e),
+                    }
+                }
+            }
+            if reconnect_to_session.cwd.is_some() {
+                config_options.default_cwd = reconnect_to_session.cwd.clone();
+            }
+            config.env = config.env.merge(EnvironmentVariables::from_data(
+                reconnect_to_session.env.clone().into_iter().collect(),
+            ));
             is_a_reconnect = true;
         }
 
@@ -436,6 +601,10 @@ pub(crate) fn start_client(opts: CliArgs) {
             force_run_commands,
             index,
             options,
+            remote,
+            token,
+            ca_cert,
+            ..
         })) = opts.command.clone()
         {
             let config_options = match options.as_deref() {
@@ -445,7 +614,21 @@ pub(crate) fn start_client(opts: CliArgs) {
                 None => config_options,
             };
 
-            let client = if let Some(idx) = index {
+            let client = if let Some(address) = remote {
+                match (token, ca_cert) {
+                    (Some(token), Some(ca_cert)) => {
+                        ClientInfo::AttachRemote(address, token, ca_cert, config_options.clone())
+                    },
+                    (None, _) => {
+                        eprintln!("A token is required to attach to a remote session, please provide one with --token or ZELLIJ_REMOTE_ATTACH_TOKEN");
+                        process::exit(1);
+                    },
+                    (_, None) => {
+                        eprintln!("A certificate to trust is required to attach to a remote session, please provide one with --ca-cert or ZELLIJ_REMOTE_ATTACH_CA_CERT");
+                        process::exit(1);
+                    },
+                }
+            } else if let Some(idx) = index {
                 attach_with_session_index(config_options.clone(), idx, create)
             } else {
                 let session_exists = session_name
@@ -475,12 +658,14 @@ pub(crate) fn start_client(opts: CliArgs) {
             }
 
             let attach_layout = match &client {
-                ClientInfo::Attach(_, _) => None,
+                ClientInfo::Attach(_, _) | ClientInfo::AttachRemote(..) => None,
                 ClientInfo::New(_) => Some(layout),
                 ClientInfo::Resurrect(_session_name, layout_to_resurrect) => {
                     Some(layout_to_resurrect.clone())
                 },
             };
+            // sessions on another machine cannot be switched to locally
+            let is_remote = matches!(client, ClientInfo::AttachRemote(..));
 
             let tab_position_to_focus = reconnect_to_session
                 .as_ref()
@@ -499,6 +684,9 @@ pub(crate) fn start_client(opts: CliArgs) {
                 pane_id_to_focus,
                 is_a_reconnect,
             );
+            if is_remote {
+                reconnect_to_session = None;
+            }
         } else {
             if let Some(session_name) = opts.session.clone() {
                 start_client_plan(session_name.clone());
@@ -536,7 +724,7 @@ pub(crate) fn start_client(opts: CliArgs) {
                                 true,
                             );
                             let attach_layout = match &client {
-                                ClientInfo::Attach(_, _) => None,
+                                ClientInfo::Attach(_, _) | ClientInfo::AttachRemote(..) => None,
                                 ClientInfo::New(_) => Some(layout),
                                 ClientInfo::Resurrect(_, resurrection_layout) => {
                                     Some(resurrection_layout.clone())
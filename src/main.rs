@@ -5,8 +5,10 @@ mod tests;
 
 use zellij_utils::{
     clap::Parser,
-    cli::{CliAction, CliArgs, Command, Sessions},
-    input::config::Config,
+    cli::{
+        CliAction, CliArgs, Command, SessionSubcommand, Sessions, SnapshotCommand, WorkspaceCommand,
+    },
+    input::{actions::ActionTarget, config::Config},
     logging::*,
 };
 
@@ -16,8 +18,15 @@ fn main() {
 
     {
         let config = Config::try_from(&opts).ok();
-        if let Some(Command::Sessions(Sessions::Action(cli_action))) = opts.command {
-            commands::send_action_to_session(cli_action, opts.session, config);
+        if let Some(Command::Sessions(Sessions::Action {
+            pane_id,
+            tab_name,
+            tab_index,
+            action: cli_action,
+        })) = opts.command
+        {
+            let target = ActionTarget::new(pane_id, tab_name, tab_index);
+            commands::send_action_to_session(cli_action, target, opts.session, config);
             std::process::exit(0);
         }
         if let Some(Command::Sessions(Sessions::Run {
@@ -29,6 +38,9 @@ fn main() {
             name,
             close_on_exit,
             start_suspended,
+            restart,
+            max_restarts,
+            restart_backoff,
         })) = opts.command
         {
             let skip_plugin_cache = false; // N/A for this action
@@ -42,10 +54,13 @@ fn main() {
                 name,
                 close_on_exit,
                 start_suspended,
+                restart,
+                max_restarts,
+                restart_backoff,
                 configuration: None,
                 skip_plugin_cache,
             };
-            commands::send_action_to_session(command_cli_action, opts.session, config);
+            commands::send_action_to_session(command_cli_action, None, opts.session, config);
             std::process::exit(0);
         }
         if let Some(Command::Sessions(Sessions::Plugin {
@@ -66,10 +81,13 @@ fn main() {
                 name: None,
                 close_on_exit: false,
                 start_suspended: false,
+                restart: None,
+                max_restarts: None,
+                restart_backoff: None,
                 configuration,
                 skip_plugin_cache,
             };
-            commands::send_action_to_session(command_cli_action, opts.session, config);
+            commands::send_action_to_session(command_cli_action, None, opts.session, config);
             std::process::exit(0);
         }
         if let Some(Command::Sessions(Sessions::Edit {
@@ -96,7 +114,7 @@ fn main() {
                 in_place,
                 cwd,
             };
-            commands::send_action_to_session(command_cli_action, opts.session, config);
+            commands::send_action_to_session(command_cli_action, None, opts.session, config);
             std::process::exit(0);
         }
         if let Some(Command::Sessions(Sessions::ConvertConfig { old_config_file })) = opts.command {
@@ -133,7 +151,23 @@ fn main() {
                 plugin_cwd: None,
                 plugin_title: None,
             };
-            commands::send_action_to_session(command_cli_action, opts.session, config);
+            commands::send_action_to_session(command_cli_action, None, opts.session, config);
+            std::process::exit(0);
+        }
+        if let Some(Command::Sessions(Sessions::Subscribe { events, format })) = opts.command {
+            let command_cli_action = CliAction::Subscribe { events, format };
+            commands::send_action_to_session(command_cli_action, None, opts.session, config);
+            std::process::exit(0);
+        }
+        if let Some(Command::Sessions(Sessions::Session {
+            command:
+                SessionSubcommand::Snapshot {
+                    command: SnapshotCommand::Save { name },
+                },
+        })) = opts.command
+        {
+            let command_cli_action = CliAction::SaveSnapshot { name };
+            commands::send_action_to_session(command_cli_action, None, opts.session, config);
             std::process::exit(0);
         }
     }
@@ -141,9 +175,11 @@ fn main() {
     if let Some(Command::Sessions(Sessions::ListSessions {
         no_formatting,
         short,
+        json,
     })) = opts.command
     {
-        commands::list_sessions(no_formatting, short);
+        let resurrectable_session_ttl = commands::resurrectable_session_ttl(&opts);
+        commands::list_sessions(no_formatting, short, json, resurrectable_session_ttl);
     } else if let Some(Command::Sessions(Sessions::KillAllSessions { yes })) = opts.command {
         commands::kill_all_sessions(yes);
     } else if let Some(Command::Sessions(Sessions::KillSession { ref target_session })) =
@@ -159,6 +195,31 @@ fn main() {
     })) = opts.command
     {
         commands::delete_session(target_session, force);
+    } else if let Some(Command::Sessions(Sessions::Session {
+        command: SessionSubcommand::Snapshot { command },
+    })) = opts.command.clone()
+    {
+        match command {
+            SnapshotCommand::List {
+                session_name,
+                no_formatting,
+            } => commands::list_session_snapshots(session_name, no_formatting),
+            SnapshotCommand::Restore {
+                snapshot,
+                name,
+                force_run_commands,
+            } => commands::restore_session_snapshot(opts, snapshot, name, force_run_commands),
+            SnapshotCommand::Delete { snapshot } => commands::delete_session_snapshot(&snapshot),
+            // sent to the session above
+            SnapshotCommand::Save { .. } => {},
+        }
+    } else if let Some(Command::Sessions(Sessions::Workspace { command })) = opts.command.clone() {
+        match command {
+            WorkspaceCommand::Attach { name } => commands::attach_to_workspace(opts, name),
+            WorkspaceCommand::List { no_formatting } => {
+                commands::list_workspaces(&opts, no_formatting)
+            },
+        }
     } else if let Some(path) = opts.server {
         commands::start_server(path, opts.debug);
     } else {
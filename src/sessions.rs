@@ -1,22 +1,41 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::os::unix::fs::FileTypeExt;
 use std::time::{Duration, SystemTime};
 use std::{fs, io, process};
 use suggest::Suggest;
 use zellij_utils::{
     anyhow,
+    cli::CliArgs,
     consts::{
-        session_info_folder_for_session, session_layout_cache_file_name,
-        ZELLIJ_SESSION_INFO_CACHE_DIR, ZELLIJ_SOCK_DIR,
+        remove_session_snapshot_folder, session_idle_deadline_file_name,
+        session_info_folder_for_session, session_layout_cache_file_name, session_snapshot_folder,
+        session_snapshot_id, session_snapshot_layout_file_name,
+        session_snapshots_folder_for_session, session_workspace_file_name,
+        split_session_snapshot_id, SNAPSHOT_ID_SEPARATOR, ZELLIJ_SESSION_INFO_CACHE_DIR,
+        ZELLIJ_SOCK_DIR,
     },
     envs,
     humantime::format_duration,
-    input::layout::Layout,
+    input::{config::Config, layout::Layout, workspaces::Workspaces},
     interprocess::local_socket::LocalSocketStream,
-    ipc::{ClientToServerMsg, IpcReceiverWithContext, IpcSenderWithContext, ServerToClientMsg},
+    ipc::{
+        handshake_with_server, set_read_timeout, ClientToServerMsg, IpcReceiverWithContext,
+        IpcSenderWithContext, ProtocolInfo, ServerToClientMsg, IPC_HANDSHAKE_TIMEOUT,
+    },
+    serde_json,
 };
 
 pub(crate) fn get_sessions() -> Result<Vec<(String, Duration)>, io::ErrorKind> {
+    get_sessions_with_compatibility().map(|sessions| {
+        sessions
+            .into_iter()
+            .map(|(session_name, duration, _is_compatible)| (session_name, duration))
+            .collect()
+    })
+}
+
+/// The running sessions, along with whether they speak the same protocol as this client
+fn get_sessions_with_compatibility() -> Result<Vec<(String, Duration, bool)>, io::ErrorKind> {
     match fs::read_dir(&*ZELLIJ_SOCK_DIR) {
         Ok(files) => {
             let mut sessions = Vec::new();
@@ -29,8 +48,11 @@ pub(crate) fn get_sessions() -> Result<Vec<(String, Duration)>, io::ErrorKind> {
                     .and_then(|d| d.elapsed().ok())
                     .unwrap_or_default();
                 let duration = Duration::from_secs(ctime.as_secs());
-                if file.file_type().unwrap().is_socket() && assert_socket(&file_name) {
-                    sessions.push((file_name, duration));
+                if file.file_type().unwrap().is_socket() {
+                    if let Some(status) = probe_socket(&file_name) {
+                        let is_compatible = status == SocketStatus::Compatible;
+                        sessions.push((file_name, duration, is_compatible));
+                    }
                 }
             });
             Ok(sessions)
@@ -126,67 +148,279 @@ pub(crate) fn get_sessions_sorted_by_mtime() -> anyhow::Result<Vec<String>> {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SocketStatus {
+    Compatible,
+    /// a server is listening, but it speaks another protocol or doesn't answer the handshake (eg.
+    /// because it predates it), attaching to it will tell the user which version it runs
+    Incompatible,
+}
+
 fn assert_socket(name: &str) -> bool {
+    probe_socket(name).is_some()
+}
+
+/// Returns the status of the server listening on the given socket, or `None` if there is none
+fn probe_socket(name: &str) -> Option<SocketStatus> {
     let path = &*ZELLIJ_SOCK_DIR.join(name);
     match LocalSocketStream::connect(path) {
-        Ok(stream) => {
+        Ok(mut stream) => {
+            match handshake_with_server(&mut stream) {
+                Ok(server_protocol)
+                    if ProtocolInfo::current().is_compatible_with(&server_protocol) => {},
+                Ok(_) | Err(_) => return Some(SocketStatus::Incompatible),
+            }
+            let _ = set_read_timeout(&stream, Some(IPC_HANDSHAKE_TIMEOUT));
             let mut sender = IpcSenderWithContext::new(stream);
             let _ = sender.send(ClientToServerMsg::ConnStatus);
             let mut receiver: IpcReceiverWithContext<ServerToClientMsg> = sender.get_receiver();
             match receiver.recv() {
-                Some((ServerToClientMsg::Connected, _)) => true,
-                None | Some((_, _)) => false,
+                Some((ServerToClientMsg::Connected, _)) => Some(SocketStatus::Compatible),
+                None | Some((_, _)) => None,
             }
         },
         Err(e) if e.kind() == io::ErrorKind::ConnectionRefused => {
             drop(fs::remove_file(path));
-            false
+            None
         },
-        Err(_) => false,
+        Err(_) => None,
     }
 }
 
+/// (session_name, timestamp, is_dead, is_incompatible)
+pub(crate) type SessionListing = (String, Duration, bool, bool);
+
 pub(crate) fn print_sessions(
-    mut sessions: Vec<(String, Duration, bool)>,
+    mut sessions: Vec<SessionListing>,
     no_formatting: bool,
     short: bool,
+    resurrectable_session_ttl: Option<Duration>,
 ) {
-    // (session_name, timestamp, is_dead)
-    let curr_session = envs::get_session_name().unwrap_or_else(|_| "".into());
+    // (session_name, timestamp, is_dead, is_incompatible)
     sessions.sort_by(|a, b| a.1.cmp(&b.1));
-    sessions
-        .iter()
-        .for_each(|(session_name, timestamp, is_dead)| {
-            if short {
-                println!("{}", session_name);
-                return;
-            }
-            if no_formatting {
-                let suffix = if curr_session == *session_name {
-                    format!("(current)")
-                } else if *is_dead {
-                    format!("(EXITED - attach to resurrect)")
-                } else {
-                    String::new()
-                };
-                let timestamp = format!("[Created {} ago]", format_duration(*timestamp));
-                println!("{} {} {}", session_name, timestamp, suffix);
-            } else {
-                let formatted_session_name = format!("\u{1b}[32;1m{}\u{1b}[m", session_name);
-                let suffix = if curr_session == *session_name {
-                    format!("(current)")
-                } else if *is_dead {
-                    format!("(\u{1b}[31;1mEXITED\u{1b}[m - attach to resurrect)")
+    if short {
+        for (session_name, _timestamp, _is_dead, _is_incompatible) in sessions {
+            println!("{}", session_name);
+        }
+        return;
+    }
+    // sessions that belong to a workspace are listed under it, after the ones that don't
+    let mut sessions_by_workspace: BTreeMap<Option<String>, Vec<SessionListing>> = BTreeMap::new();
+    for session in sessions {
+        sessions_by_workspace
+            .entry(get_session_workspace(&session.0))
+            .or_default()
+            .push(session);
+    }
+    for (workspace, sessions) in sessions_by_workspace {
+        let indentation = match workspace {
+            Some(workspace) => {
+                if no_formatting {
+                    println!("{}:", workspace);
                 } else {
-                    String::new()
-                };
-                let timestamp = format!(
-                    "[Created \u{1b}[35;1m{}\u{1b}[m ago]",
-                    format_duration(*timestamp)
+                    println!("\u{1b}[36;1m{}\u{1b}[m:", workspace);
+                }
+                "  "
+            },
+            None => "",
+        };
+        for session in sessions {
+            print_session(
+                session,
+                indentation,
+                no_formatting,
+                resurrectable_session_ttl,
+            );
+        }
+    }
+}
+
+fn print_session(
+    (session_name, timestamp, is_dead, is_incompatible): SessionListing,
+    indentation: &str,
+    no_formatting: bool,
+    resurrectable_session_ttl: Option<Duration>,
+) {
+    let curr_session = envs::get_session_name().unwrap_or_else(|_| "".into());
+    let expires_in = session_expires_in(&session_name, is_dead, resurrectable_session_ttl)
+        .map(|expires_in| format_duration(expires_in).to_string());
+    if no_formatting {
+        let suffix = if curr_session == session_name {
+            "(current)".to_string()
+        } else if is_incompatible {
+            "(INCOMPATIBLE VERSION)".to_string()
+        } else if is_dead {
+            match expires_in {
+                Some(expires_in) => {
+                    format!("(EXITED - attach to resurrect, deleted in {})", expires_in)
+                },
+                None => "(EXITED - attach to resurrect)".to_string(),
+            }
+        } else {
+            match expires_in {
+                Some(expires_in) => format!("(no clients - killed in {})", expires_in),
+                None => String::new(),
+            }
+        };
+        let timestamp = format!("[Created {} ago]", format_duration(timestamp));
+        println!("{}{} {} {}", indentation, session_name, timestamp, suffix);
+    } else {
+        let formatted_session_name = format!("\u{1b}[32;1m{}\u{1b}[m", session_name);
+        let suffix = if curr_session == session_name {
+            "(current)".to_string()
+        } else if is_incompatible {
+            "(\u{1b}[31;1mINCOMPATIBLE VERSION\u{1b}[m)".to_string()
+        } else if is_dead {
+            match expires_in {
+                Some(expires_in) => format!(
+                    "(\u{1b}[31;1mEXITED\u{1b}[m - attach to resurrect, deleted in \u{1b}[35;1m{}\u{1b}[m)",
+                    expires_in
+                ),
+                None => "(\u{1b}[31;1mEXITED\u{1b}[m - attach to resurrect)".to_string(),
+            }
+        } else {
+            match expires_in {
+                Some(expires_in) => format!(
+                    "(no clients - killed in \u{1b}[35;1m{}\u{1b}[m)",
+                    expires_in
+                ),
+                None => String::new(),
+            }
+        };
+        let timestamp = format!(
+            "[Created \u{1b}[35;1m{}\u{1b}[m ago]",
+            format_duration(timestamp)
+        );
+        println!(
+            "{}{} {} {}",
+            indentation, formatted_session_name, timestamp, suffix
+        );
+    }
+}
+
+pub(crate) fn print_workspaces(
+    workspaces: &Workspaces,
+    no_formatting: bool,
+    resurrectable_session_ttl: Option<Duration>,
+) {
+    let mut sessions = get_all_sessions().unwrap_or_default();
+    sessions.sort_by(|a, b| a.1.cmp(&b.1));
+    for workspace in workspaces.workspaces.values() {
+        let mut details = vec![format!("session: {}", workspace.default_session_name())];
+        if let Some(layout) = &workspace.layout {
+            details.push(format!("layout: {}", layout.display()));
+        }
+        if let Some(cwd) = &workspace.cwd {
+            details.push(format!("cwd: {}", cwd.display()));
+        }
+        if no_formatting {
+            println!("{} [{}]", workspace.name, details.join(", "));
+        } else {
+            println!(
+                "\u{1b}[36;1m{}\u{1b}[m [{}]",
+                workspace.name,
+                details.join(", ")
+            );
+        }
+        for session in &sessions {
+            if get_session_workspace(&session.0).as_ref() == Some(&workspace.name) {
+                print_session(
+                    session.clone(),
+                    "  ",
+                    no_formatting,
+                    resurrectable_session_ttl,
                 );
-                println!("{} {} {}", formatted_session_name, timestamp, suffix);
             }
+        }
+    }
+}
+
+/// How long until the session is deleted (if it is resurrectable and there is a ttl for
+/// resurrectable sessions) or killed (if it is running without any clients and has an idle timeout)
+fn session_expires_in(
+    session_name: &str,
+    is_dead: bool,
+    resurrectable_session_ttl: Option<Duration>,
+) -> Option<Duration> {
+    if is_dead {
+        let exited_ago = fs::metadata(session_layout_cache_file_name(session_name))
+            .and_then(|metadata| metadata.modified())
+            .ok()?
+            .elapsed()
+            .unwrap_or_default();
+        resurrectable_session_ttl
+            .map(|ttl| Duration::from_secs(ttl.saturating_sub(exited_ago).as_secs()))
+    } else {
+        let idle_deadline: u64 = fs::read_to_string(session_idle_deadline_file_name(session_name))
+            .ok()?
+            .trim()
+            .parse()
+            .ok()?;
+        let now = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .ok()?
+            .as_secs();
+        Some(Duration::from_secs(idle_deadline.saturating_sub(now)))
+    }
+}
+
+/// Resurrectable sessions are deleted by running sessions according to this option, we assume
+/// they share the configuration of the cli
+pub(crate) fn resurrectable_session_ttl(opts: &CliArgs) -> Option<Duration> {
+    Config::try_from(opts)
+        .ok()?
+        .options
+        .resurrectable_session_ttl
+        .filter(|ttl| *ttl > 0)
+        .map(|ttl| Duration::from_secs(ttl * 60 * 60))
+}
+
+/// The workspace a running or resurrectable session belongs to, if any
+pub(crate) fn get_session_workspace(session_name: &str) -> Option<String> {
+    fs::read_to_string(session_workspace_file_name(session_name))
+        .ok()
+        .map(|workspace| workspace.trim().to_owned())
+        .filter(|workspace| !workspace.is_empty())
+}
+
+pub(crate) fn print_sessions_as_json(
+    mut sessions: Vec<SessionListing>,
+    resurrectable_session_ttl: Option<Duration>,
+) {
+    // (session_name, timestamp, is_dead, is_incompatible)
+    let curr_session = envs::get_session_name().unwrap_or_else(|_| "".into());
+    sessions.sort_by(|a, b| a.1.cmp(&b.1));
+    let sessions: Vec<serde_json::Value> = sessions
+        .iter()
+        .map(|(session_name, timestamp, is_dead, is_incompatible)| {
+            serde_json::json!({
+                "name": session_name,
+                "created_secs_ago": timestamp.as_secs(),
+                "is_current": curr_session == *session_name,
+                "exited": is_dead,
+                "incompatible_version": is_incompatible,
+                "workspace": get_session_workspace(session_name),
+                "expires_in_secs": session_expires_in(session_name, *is_dead, resurrectable_session_ttl)
+                    .map(|expires_in| expires_in.as_secs()),
+            })
         })
+        .collect();
+    println!("{}", serde_json::Value::Array(sessions));
+}
+
+pub(crate) fn print_session_snapshots(snapshots: Vec<(String, Duration)>, no_formatting: bool) {
+    // (snapshot_id, saved_ago)
+    for (snapshot_id, saved_ago) in snapshots {
+        if no_formatting {
+            println!("{} [Saved {} ago]", snapshot_id, format_duration(saved_ago));
+        } else {
+            println!(
+                "\u{1b}[32;1m{}\u{1b}[m [Saved \u{1b}[35;1m{}\u{1b}[m ago]",
+                snapshot_id,
+                format_duration(saved_ago)
+            );
+        }
+    }
 }
 
 pub(crate) fn print_sessions_with_index(sessions: Vec<String>) {
@@ -222,7 +456,8 @@ pub(crate) fn get_active_session() -> ActiveSession {
 pub(crate) fn kill_session(name: &str) {
     let path = &*ZELLIJ_SOCK_DIR.join(name);
     match LocalSocketStream::connect(path) {
-        Ok(stream) => {
+        Ok(mut stream) => {
+            let _ = handshake_with_server(&mut stream);
             let _ = IpcSenderWithContext::new(stream).send(ClientToServerMsg::KillSession);
         },
         Err(e) => {
@@ -235,7 +470,8 @@ pub(crate) fn kill_session(name: &str) {
 pub(crate) fn delete_session(name: &str, force: bool) {
     if force {
         let path = &*ZELLIJ_SOCK_DIR.join(name);
-        let _ = LocalSocketStream::connect(path).map(|stream| {
+        let _ = LocalSocketStream::connect(path).map(|mut stream| {
+            let _ = handshake_with_server(&mut stream);
             IpcSenderWithContext::new(stream)
                 .send(ClientToServerMsg::KillSession)
                 .ok();
@@ -253,30 +489,46 @@ pub(crate) fn delete_session(name: &str, force: bool) {
     }
 }
 
-pub(crate) fn list_sessions(no_formatting: bool, short: bool) {
-    let exit_code = match get_sessions() {
-        Ok(running_sessions) => {
-            let resurrectable_sessions = get_resurrectable_sessions();
-            let mut all_sessions: HashMap<String, (Duration, bool)> = resurrectable_sessions
-                .iter()
-                .map(|(name, timestamp, _layout)| (name.clone(), (timestamp.clone(), true)))
-                .collect();
-            for (session_name, duration) in running_sessions {
-                all_sessions.insert(session_name.clone(), (duration, false));
-            }
-            if all_sessions.is_empty() {
+/// Both the running and the resurrectable sessions, as (session_name, timestamp, is_dead,
+/// is_incompatible)
+fn get_all_sessions() -> Result<Vec<SessionListing>, io::ErrorKind> {
+    let running_sessions = get_sessions_with_compatibility()?;
+    let resurrectable_sessions = get_resurrectable_sessions();
+    let mut all_sessions: HashMap<String, (Duration, bool, bool)> = resurrectable_sessions
+        .iter()
+        .map(|(name, timestamp, _layout)| (name.clone(), (*timestamp, true, false)))
+        .collect();
+    for (session_name, duration, is_compatible) in running_sessions {
+        all_sessions.insert(session_name.clone(), (duration, false, !is_compatible));
+    }
+    Ok(all_sessions
+        .into_iter()
+        .map(|(name, (timestamp, is_dead, is_incompatible))| {
+            (name, timestamp, is_dead, is_incompatible)
+        })
+        .collect())
+}
+
+pub(crate) fn list_sessions(
+    no_formatting: bool,
+    short: bool,
+    json: bool,
+    resurrectable_session_ttl: Option<Duration>,
+) {
+    let exit_code = match get_all_sessions() {
+        Ok(all_sessions) => {
+            if json {
+                print_sessions_as_json(all_sessions, resurrectable_session_ttl);
+                0
+            } else if all_sessions.is_empty() {
                 eprintln!("No active zellij sessions found.");
                 1
             } else {
                 print_sessions(
-                    all_sessions
-                        .iter()
-                        .map(|(name, (timestamp, is_dead))| {
-                            (name.clone(), timestamp.clone(), *is_dead)
-                        })
-                        .collect(),
+                    all_sessions,
                     no_formatting,
                     short,
+                    resurrectable_session_ttl,
                 );
                 0
             }
@@ -340,6 +592,106 @@ pub(crate) fn resurrection_layout(session_name_to_resurrect: &str) -> Option<Lay
                 None
             }
         })
+        // snapshots are resurrected as a new session named after them
+        .or_else(|| snapshot_layout(session_name_to_resurrect))
+}
+
+// (snapshot_id, time since it was saved), newest first
+pub(crate) fn get_session_snapshots(session_name: Option<&str>) -> Vec<(String, Duration)> {
+    let session_names: Vec<String> = match session_name {
+        Some(session_name) => vec![session_name.to_owned()],
+        None => match fs::read_dir(&*ZELLIJ_SESSION_INFO_CACHE_DIR) {
+            Ok(files_in_session_info_folder) => files_in_session_info_folder
+                .filter_map(|f| f.ok())
+                .filter(|f| f.path().is_dir())
+                .filter_map(|f| f.file_name().into_string().ok())
+                .collect(),
+            Err(_) => vec![],
+        },
+    };
+    let mut snapshots = vec![];
+    for session_name in session_names {
+        if let Ok(snapshot_folders) =
+            fs::read_dir(session_snapshots_folder_for_session(&session_name))
+        {
+            for snapshot_name in
+                snapshot_folders.filter_map(|f| f.ok()?.file_name().into_string().ok())
+            {
+                let saved_ago = std::fs::metadata(session_snapshot_layout_file_name(
+                    &session_name,
+                    &snapshot_name,
+                ))
+                .and_then(|metadata| metadata.created())
+                .ok()
+                .and_then(|created| created.elapsed().ok())
+                .map(|elapsed| Duration::from_secs(elapsed.as_secs()))
+                .unwrap_or_default();
+                snapshots.push((
+                    session_snapshot_id(&session_name, &snapshot_name),
+                    saved_ago,
+                ));
+            }
+        }
+    }
+    snapshots.sort_by(|a, b| a.1.cmp(&b.1));
+    snapshots
+}
+
+pub(crate) fn snapshot_layout(snapshot_id: &str) -> Option<Layout> {
+    let (session_name, snapshot_name) = split_session_snapshot_id(snapshot_id)?;
+    let layout_file_name = session_snapshot_layout_file_name(session_name, snapshot_name);
+    let raw_layout = std::fs::read_to_string(&layout_file_name).ok()?;
+    match Layout::from_kdl(
+        &raw_layout,
+        layout_file_name.display().to_string(),
+        None,
+        None,
+    ) {
+        Ok(layout) => Some(layout),
+        Err(e) => {
+            log::error!("Failed to parse snapshot layout file: {}", e);
+            None
+        },
+    }
+}
+
+// copies the snapshot to be resurrected as a dead session by this name
+pub(crate) fn copy_snapshot_to_session(snapshot_id: &str, session_name: &str) -> io::Result<()> {
+    let (snapshot_session_name, snapshot_name) = split_session_snapshot_id(snapshot_id)
+        .ok_or_else(|| io::Error::from(io::ErrorKind::NotFound))?;
+    let snapshot_folder = session_snapshot_folder(snapshot_session_name, snapshot_name);
+    let session_folder = session_info_folder_for_session(session_name);
+    fs::create_dir_all(&session_folder)?;
+    for file in fs::read_dir(snapshot_folder)? {
+        let file = file?;
+        if file.file_type()?.is_file() {
+            fs::copy(file.path(), session_folder.join(file.file_name()))?;
+        }
+    }
+    Ok(())
+}
+
+pub(crate) fn delete_snapshot(snapshot_id: &str) {
+    let removed =
+        snapshot_id
+            .rsplit_once(SNAPSHOT_ID_SEPARATOR)
+            .map(|(session_name, snapshot_name)| {
+                remove_session_snapshot_folder(session_name, snapshot_name)
+            });
+    match removed {
+        Some(Ok(())) => println!("Snapshot: {:?} successfully deleted.", snapshot_id),
+        Some(Err(e)) if e.kind() == io::ErrorKind::InvalidInput => {
+            eprintln!("{}", e);
+            process::exit(2);
+        },
+        Some(Err(e)) if e.kind() != io::ErrorKind::NotFound => {
+            log::error!("Failed to remove snapshot {:?}: {:?}", snapshot_id, e);
+        },
+        _ => {
+            eprintln!("Snapshot: {:?} not found.", snapshot_id);
+            process::exit(2);
+        },
+    }
 }
 
 pub(crate) fn assert_session(name: &str) {
@@ -7,9 +7,11 @@ use std::{fs, path::PathBuf};
 
 use crate::os_input_output::ClientOsApi;
 use zellij_utils::{
+    cli::EventStreamFormat,
+    data::EventType,
     errors::prelude::*,
     input::actions::Action,
-    ipc::{ClientToServerMsg, ExitReason, ServerToClientMsg},
+    ipc::{ClientToServerMsg, ExitReason, ProtocolInfo, ServerToClientMsg},
     uuid::Uuid,
 };
 
@@ -25,7 +27,17 @@ pub fn start_cli_client(
         sock_dir.push(session_name);
         sock_dir
     };
-    os_input.connect_to_server(&*zellij_ipc_pipe);
+    match os_input.connect_to_server(&zellij_ipc_pipe) {
+        Ok(server_protocol) if !ProtocolInfo::current().is_compatible_with(&server_protocol) => {
+            eprintln!("{}", ExitReason::IncompatibleVersion(Some(server_protocol)));
+            process::exit(2);
+        },
+        Ok(_) => {},
+        Err(e) => {
+            eprintln!("{:#}", e);
+            process::exit(2);
+        },
+    }
     let pane_id = os_input
         .env_variable("ZELLIJ_PANE_ID")
         .and_then(|e| e.trim().parse().ok());
@@ -63,6 +75,9 @@ pub fn start_cli_client(
                     pane_title,
                 );
             },
+            Action::Subscribe { events, format } => {
+                subscribe_client(&mut os_input, events, format, pane_id);
+            },
             action => {
                 single_message_client(&mut os_input, action, pane_id);
             },
@@ -182,6 +197,57 @@ fn pipe_client(
     }
 }
 
+fn subscribe_client(
+    os_input: &mut Box<dyn ClientOsApi>,
+    events: Vec<EventType>,
+    format: EventStreamFormat,
+    pane_id: Option<u32>,
+) {
+    let msg = ClientToServerMsg::Action(Action::Subscribe { events, format }, pane_id, None);
+    os_input.send_to_server(msg);
+    loop {
+        match os_input.recv_from_server() {
+            Some((ServerToClientMsg::SubscribedEvent(event), _)) => {
+                let line = match format {
+                    EventStreamFormat::Json => match serde_json::to_string(&event) {
+                        Ok(line) => line,
+                        Err(e) => {
+                            eprintln!("Failed to serialize event {}: {}", event.to_string(), e);
+                            continue;
+                        },
+                    },
+                    EventStreamFormat::Plain => event.to_string(),
+                };
+                let mut stdout = os_input.get_stdout_writer();
+                if let Err(e) = writeln!(stdout, "{}", line).and_then(|_| stdout.flush()) {
+                    // most likely the reading end of our STDOUT went away (eg. `| head -n 1`)
+                    log::error!("Failed to write to stdout: {}", e);
+                    os_input.send_to_server(ClientToServerMsg::ClientExited);
+                    process::exit(0);
+                }
+            },
+            Some((ServerToClientMsg::LogError(log_lines), _)) => {
+                log_lines.iter().for_each(|line| eprintln!("{line}"));
+                process::exit(2);
+            },
+            Some((ServerToClientMsg::Exit(exit_reason), _)) => match exit_reason {
+                ExitReason::Error(e) => {
+                    eprintln!("{}", e);
+                    process::exit(2);
+                },
+                _ => {
+                    process::exit(0);
+                },
+            },
+            None => {
+                // the session has ended
+                process::exit(0);
+            },
+            _ => {},
+        }
+    }
+}
+
 fn single_message_client(
     os_input: &mut Box<dyn ClientOsApi>,
     action: Action,
@@ -80,7 +80,9 @@ impl InputHandler {
         let bracketed_paste_start = vec![27, 91, 50, 48, 48, 126]; // \u{1b}[200~
         let bracketed_paste_end = vec![27, 91, 50, 48, 49, 126]; // \u{1b}[201~
         if self.options.mouse_mode.unwrap_or(true) {
-            self.os_input.enable_mouse().non_fatal();
+            self.os_input
+                .enable_mouse(self.options.mouse_hover_events.unwrap_or(false))
+                .non_fatal();
             self.mouse_mode_active = true;
         }
         loop {
@@ -239,7 +241,16 @@ impl InputHandler {
                 },
             },
             MouseEvent::Release(point) => {
-                let button_released = self.holding_mouse.unwrap_or_default();
+                // the terminal reports mouse motion without any button held the same way it
+                // reports a release, the two can only be told apart by whether we hold a button
+                let button_released = match self.holding_mouse {
+                    Some(button_released) => button_released,
+                    None if self.options.mouse_hover_events.unwrap_or(false) => {
+                        self.dispatch_action(Action::MouseMotion(point), None);
+                        return;
+                    },
+                    None => HeldMouseButton::default(),
+                };
                 match button_released {
                     HeldMouseButton::Left => {
                         self.dispatch_action(Action::LeftMouseRelease(point), None)
@@ -320,6 +331,7 @@ impl InputHandler {
             | Action::GoToTab(_)
             | Action::GoToTabName(_, _)
             | Action::ToggleTab
+            | Action::MoveTab(_)
             | Action::MoveFocusOrTab(_) => {
                 self.command_is_executing.blocking_input_thread();
                 self.os_input
@@ -327,12 +339,21 @@ impl InputHandler {
                 self.command_is_executing
                     .wait_until_input_thread_is_unblocked();
             },
+            Action::Write(ref bytes) if self.options.local_echo_prediction.unwrap_or(false) => {
+                self.send_client_instructions
+                    .send(ClientInstruction::PredictLocalEcho(bytes.clone()))
+                    .unwrap();
+                self.os_input
+                    .send_to_server(ClientToServerMsg::Action(action, None, client_id));
+            },
             Action::ToggleMouseMode => {
                 if self.mouse_mode_active {
                     self.os_input.disable_mouse().non_fatal();
                     self.mouse_mode_active = false;
                 } else {
-                    self.os_input.enable_mouse().non_fatal();
+                    self.os_input
+                        .enable_mouse(self.options.mouse_hover_events.unwrap_or(false))
+                        .non_fatal();
                     self.mouse_mode_active = true;
                 }
             },
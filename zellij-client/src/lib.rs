@@ -3,6 +3,7 @@ pub mod os_input_output;
 pub mod cli_client;
 mod command_is_executing;
 mod input_handler;
+mod local_echo;
 pub mod old_config_converter;
 mod stdin_ansi_parser;
 mod stdin_handler;
@@ -10,7 +11,7 @@ mod stdin_handler;
 use log::info;
 use std::env::current_exe;
 use std::io::{self, Write};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::sync::{Arc, Mutex};
 use std::thread;
@@ -18,20 +19,29 @@ use zellij_utils::errors::FatalError;
 
 use crate::stdin_ansi_parser::{AnsiStdinInstruction, StdinAnsiParser, SyncOutput};
 use crate::{
-    command_is_executing::CommandIsExecuting, input_handler::input_loop,
-    os_input_output::ClientOsApi, stdin_handler::stdin_loop,
+    command_is_executing::CommandIsExecuting,
+    input_handler::input_loop,
+    local_echo::{LocalEchoPredictor, LocalEchoUpdate},
+    os_input_output::ClientOsApi,
+    stdin_handler::stdin_loop,
 };
 use zellij_utils::{
-    channels::{self, ChannelWithContext, SenderWithContext},
+    channels::{self, ChannelWithContext, RecvTimeoutError, SenderWithContext},
     consts::{set_permissions, ZELLIJ_SOCK_DIR},
-    data::{ClientId, ConnectToSession, InputMode, Style},
+    data::{ClientId, ConnectToSession, Event, InputMode, Style},
     envs,
     errors::{ClientContext, ContextType, ErrorInstruction},
     input::{config::Config, options::Options},
-    ipc::{ClientAttributes, ClientToServerMsg, ExitReason, ServerToClientMsg},
+    ipc::{
+        ClientAttributes, ClientToServerMsg, ExitReason, ProtocolInfo, ServerToClientMsg,
+        IPC_CAPABILITY_LOCAL_ECHO, IPC_CAPABILITY_READ_ONLY_CLIENTS,
+    },
     termwiz::input::InputEvent,
 };
-use zellij_utils::{cli::CliArgs, input::layout::Layout};
+use zellij_utils::{
+    cli::{CliArgs, Command as CliCommand, Sessions},
+    input::layout::Layout,
+};
 
 /// Instructions related to the client-side application
 #[derive(Debug, Clone)]
@@ -51,6 +61,8 @@ pub(crate) enum ClientInstruction {
     SetSynchronizedOutput(Option<SyncOutput>),
     UnblockCliPipeInput(String),   // String -> pipe name
     CliPipeOutput(String, String), // String -> pipe name, String -> output
+    SubscribedEvent(Event),
+    PredictLocalEcho(Vec<u8>),
 }
 
 impl From<ServerToClientMsg> for ClientInstruction {
@@ -75,6 +87,7 @@ impl From<ServerToClientMsg> for ClientInstruction {
             ServerToClientMsg::CliPipeOutput(pipe_name, output) => {
                 ClientInstruction::CliPipeOutput(pipe_name, output)
             },
+            ServerToClientMsg::SubscribedEvent(event) => ClientInstruction::SubscribedEvent(event),
         }
     }
 }
@@ -97,6 +110,8 @@ impl From<&ClientInstruction> for ClientContext {
             ClientInstruction::SetSynchronizedOutput(..) => ClientContext::SetSynchronisedOutput,
             ClientInstruction::UnblockCliPipeInput(..) => ClientContext::UnblockCliPipeInput,
             ClientInstruction::CliPipeOutput(..) => ClientContext::CliPipeOutput,
+            ClientInstruction::SubscribedEvent(..) => ClientContext::SubscribedEvent,
+            ClientInstruction::PredictLocalEcho(..) => ClientContext::PredictLocalEcho,
         }
     }
 }
@@ -128,9 +143,20 @@ fn spawn_server(socket_path: &Path, debug: bool) -> io::Result<()> {
     }
 }
 
+fn exit_before_attaching(os_input: &dyn ClientOsApi, message: &str) -> ! {
+    let restore_snapshot = "\u{1b}[?1049l";
+    let _ = os_input
+        .get_stdout_writer()
+        .write(restore_snapshot.as_bytes())
+        .unwrap();
+    eprintln!("{}", message);
+    std::process::exit(1);
+}
+
 #[derive(Debug, Clone)]
 pub enum ClientInfo {
     Attach(String, Options),
+    AttachRemote(String, String, PathBuf, Options), // address, token, ca certificate, options
     New(String),
     Resurrect(String, Layout),
 }
@@ -139,6 +165,7 @@ impl ClientInfo {
     pub fn get_session_name(&self) -> &str {
         match self {
             Self::Attach(ref name, _) => name,
+            Self::AttachRemote(ref address, ..) => address,
             Self::New(ref name) => name,
             Self::Resurrect(ref name, _) => name,
         }
@@ -195,14 +222,28 @@ pub fn start_client(
         .unwrap_or_else(|| os_input.load_palette());
 
     let full_screen_ws = os_input.get_terminal_size_using_fd(0);
+    let local_echo_prediction = config_options.local_echo_prediction.unwrap_or(false);
+    // only an existing session can be attached to as a viewer
+    let read_only = matches!(info, ClientInfo::Attach(..) | ClientInfo::AttachRemote(..))
+        && matches!(
+            opts.command,
+            Some(CliCommand::Sessions(Sessions::Attach {
+                read_only: true,
+                ..
+            }))
+        );
     let client_attributes = ClientAttributes {
         size: full_screen_ws,
         style: Style {
             colors: palette,
             rounded_corners: config.ui.pane_frames.rounded_corners,
             hide_session_name: config.ui.pane_frames.hide_session_name,
+            show_foreground_process: config.ui.pane_frames.show_foreground_process,
+            show_git_branch: config.ui.pane_frames.show_git_branch,
         },
         keybinds: config.keybinds.clone(),
+        read_only,
+        diff_rendering: config_options.diff_rendering.unwrap_or(false) || local_echo_prediction,
     };
 
     let create_ipc_pipe = || -> std::path::PathBuf {
@@ -213,7 +254,7 @@ pub fn start_client(
         sock_dir
     };
 
-    let (first_msg, ipc_pipe) = match info {
+    let (first_msg, server_protocol) = match info {
         ClientInfo::Attach(name, config_options) => {
             envs::set_session_name(name.clone());
             os_input.update_session_name(name);
@@ -226,9 +267,18 @@ pub fn start_client(
                     tab_position_to_focus,
                     pane_id_to_focus,
                 ),
-                ipc_pipe,
+                os_input.connect_to_server(&ipc_pipe),
             )
         },
+        ClientInfo::AttachRemote(address, token, ca_cert, config_options) => (
+            ClientToServerMsg::AttachClient(
+                client_attributes,
+                config_options,
+                tab_position_to_focus,
+                pane_id_to_focus,
+            ),
+            os_input.connect_to_remote_server(&address, &token, &ca_cert),
+        ),
         ClientInfo::New(name) | ClientInfo::Resurrect(name, _) => {
             envs::set_session_name(name.clone());
             os_input.update_session_name(name);
@@ -244,12 +294,33 @@ pub fn start_client(
                     Box::new(layout.unwrap()),
                     Some(config.plugins.clone()),
                 ),
-                ipc_pipe,
+                os_input.connect_to_server(&ipc_pipe),
             )
         },
     };
 
-    os_input.connect_to_server(&*ipc_pipe);
+    let server_supports_local_echo = server_protocol
+        .as_ref()
+        .map(|server_protocol| server_protocol.supports(IPC_CAPABILITY_LOCAL_ECHO))
+        .unwrap_or(false);
+    match server_protocol {
+        Ok(server_protocol) if !ProtocolInfo::current().is_compatible_with(&server_protocol) => {
+            let reason = ExitReason::IncompatibleVersion(Some(server_protocol));
+            exit_before_attaching(&*os_input, &reason.to_string());
+        },
+        Ok(server_protocol)
+            if read_only && !server_protocol.supports(IPC_CAPABILITY_READ_ONLY_CLIENTS) =>
+        {
+            exit_before_attaching(
+                &*os_input,
+                "This session does not support read-only clients, please upgrade it.",
+            );
+        },
+        Ok(_) => {},
+        Err(e) => {
+            exit_before_attaching(&*os_input, &format!("{:#}", e));
+        },
+    }
     os_input.send_to_server(first_msg);
 
     let mut command_is_executing = CommandIsExecuting::new();
@@ -401,6 +472,29 @@ pub fn start_client(
         _ => None,
     };
 
+    let mut local_echo_predictor = if local_echo_prediction && server_supports_local_echo {
+        Some(LocalEchoPredictor::new(full_screen_ws.cols))
+    } else {
+        None
+    };
+    let handle_local_echo_update = |update: LocalEchoUpdate| {
+        if let Some(vte_output) = update.vte_output {
+            let mut stdout = os_input.get_stdout_writer();
+            stdout
+                .write_all(vte_output.as_bytes())
+                .expect("cannot write to stdout");
+            stdout.flush().expect("could not flush");
+        }
+        if !update.predicted_cells.is_empty() {
+            os_input.send_to_server(ClientToServerMsg::LocalEchoPredicted(
+                update.predicted_cells,
+            ));
+        }
+        if update.predictions_done {
+            os_input.send_to_server(ClientToServerMsg::LocalEchoExpired);
+        }
+    };
+
     let mut stdout = os_input.get_stdout_writer();
     stdout
         .write_all("\u{1b}[1m\u{1b}[HLoading Zellij\u{1b}[m\n\r".as_bytes())
@@ -412,6 +506,22 @@ pub fn start_client(
             // there are buffered instructions, we need to go through them before processing the
             // new ones
             pending_instructions.remove(0)
+        } else if let Some(time_until_expiry) = local_echo_predictor
+            .as_ref()
+            .and_then(|predictor| predictor.time_until_expiry())
+        {
+            match receive_client_instructions.recv_timeout(time_until_expiry) {
+                Ok(instruction) => instruction,
+                Err(RecvTimeoutError::Timeout) => {
+                    if let Some(predictor) = local_echo_predictor.as_mut() {
+                        handle_local_echo_update(predictor.expire());
+                    }
+                    continue;
+                },
+                Err(RecvTimeoutError::Disconnected) => {
+                    panic!("failed to receive app instruction on channel")
+                },
+            }
         } else {
             receive_client_instructions
                 .recv()
@@ -472,6 +582,15 @@ pub fn start_client(
                         .expect("cannot write to stdout");
                 }
                 stdout.flush().expect("could not flush");
+                if let Some(predictor) = local_echo_predictor.as_mut() {
+                    handle_local_echo_update(predictor.handle_render(&output));
+                }
+            },
+            ClientInstruction::PredictLocalEcho(bytes) => {
+                if let Some(predictor) = local_echo_predictor.as_mut() {
+                    predictor.change_columns(os_input.get_terminal_size_using_fd(0).cols);
+                    handle_local_echo_update(predictor.predict(&bytes));
+                }
             },
             ClientInstruction::UnblockInputThread => {
                 command_is_executing.unblock_input_thread();
@@ -536,3 +655,7 @@ pub fn start_client(
 #[cfg(test)]
 #[path = "./unit/stdin_tests.rs"]
 mod stdin_tests;
+
+#[cfg(test)]
+#[path = "./unit/local_echo_tests.rs"]
+mod local_echo_tests;
@@ -0,0 +1,191 @@
+//! Predicts the echo of characters typed into the focused terminal pane, drawing them before the
+//! server renders them (see the `local_echo_prediction` option).
+use std::time::{Duration, Instant};
+
+// predictions that were not echoed by the server within this time are considered wrong
+const PREDICTION_TIMEOUT: Duration = Duration::from_millis(1000);
+
+const SHOW_CURSOR: &str = "\u{1b}[?25h";
+const HIDE_CURSOR: &str = "\u{1b}[?25l";
+
+#[derive(Debug, Default)]
+pub(crate) struct LocalEchoPredictor {
+    cursor_position: Option<(usize, usize)>, // (x, y), None if the cursor is hidden
+    columns: usize,
+    predictions: Vec<Prediction>,
+    oldest_prediction: Option<Instant>,
+    paused: bool,
+    // predictions are only drawn once the server echoed one we made without drawing it, so that
+    // input that is not echoed (eg. passwords) is never painted
+    echo_confirmed: bool,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Prediction {
+    x: usize,
+    y: usize,
+    drawn: bool,
+}
+
+/// What the client needs to do after the predictor handled something
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(crate) struct LocalEchoUpdate {
+    /// Draw this to the terminal
+    pub vte_output: Option<String>,
+    /// Let the server know predictions were drawn over these cells (x, y)
+    pub predicted_cells: Vec<(usize, usize)>,
+    /// Let the server know predictions are no longer drawn, so it renders their cells again
+    pub predictions_done: bool,
+}
+
+impl LocalEchoPredictor {
+    pub fn new(columns: usize) -> Self {
+        LocalEchoPredictor {
+            columns,
+            ..Default::default()
+        }
+    }
+    pub fn change_columns(&mut self, columns: usize) {
+        self.columns = columns;
+    }
+    /// Predict the echo of these bytes typed by the user, drawing the prediction only if the
+    /// server has been echoing our previous ones
+    pub fn predict(&mut self, bytes: &[u8]) -> LocalEchoUpdate {
+        let mut update = LocalEchoUpdate::default();
+        if self.paused {
+            return update;
+        }
+        let is_printable = |byte: &u8| (0x20..0x7f).contains(byte);
+        if bytes.is_empty() || !bytes.iter().all(is_printable) {
+            // we cannot tell where the cursor will be after eg. a newline or an escape sequence,
+            // so stop predicting until the server renders again, and do not trust the echo of
+            // whatever comes next (eg. a password prompt) until it is confirmed again
+            self.paused = true;
+            self.echo_confirmed = false;
+            return update;
+        }
+        let (mut x, y) = match (self.predictions.last(), self.cursor_position) {
+            (Some(last), _) => (last.x + 1, last.y),
+            (None, Some(cursor_position)) => cursor_position,
+            (None, None) => return update,
+        };
+        let start_x = x;
+        let drawn = self.echo_confirmed;
+        let mut text = String::new();
+        for byte in bytes {
+            if x >= self.columns {
+                self.paused = true;
+                break;
+            }
+            text.push(*byte as char);
+            self.predictions.push(Prediction { x, y, drawn });
+            if drawn {
+                update.predicted_cells.push((x, y));
+            }
+            x += 1;
+        }
+        if !text.is_empty() {
+            if drawn {
+                // predictions are underlined so that they can be told apart from confirmed text
+                update.vte_output = Some(format!(
+                    "\u{1b}[{};{}H\u{1b}[4m{}\u{1b}[m",
+                    y + 1,
+                    start_x + 1,
+                    text
+                ));
+            }
+            self.oldest_prediction.get_or_insert_with(Instant::now);
+        }
+        update
+    }
+    /// Follow the server's cursor through its renders, and drop the predictions it has confirmed
+    /// or contradicted
+    pub fn handle_render(&mut self, render: &str) -> LocalEchoUpdate {
+        let mut update = LocalEchoUpdate::default();
+        self.paused = false;
+        let previous_cursor_position = self.cursor_position;
+        if let Some(cursor_position) = cursor_position_after_render(render) {
+            self.cursor_position = cursor_position;
+        }
+        if self.predictions.is_empty() {
+            if self.cursor_position != previous_cursor_position {
+                // something other than our echo moved the cursor (eg. output or focusing another
+                // pane), so the echo has to be confirmed again
+                self.echo_confirmed = false;
+            }
+            return update;
+        }
+        let had_drawn_predictions = self.predictions.iter().any(|p| p.drawn);
+        match self.cursor_position {
+            Some((cursor_x, cursor_y)) => {
+                if self.predictions.iter().all(|p| p.y == cursor_y) {
+                    // predictions the cursor moved past were echoed
+                    let prediction_count = self.predictions.len();
+                    self.predictions.retain(|p| p.x >= cursor_x);
+                    if self.predictions.len() < prediction_count {
+                        self.echo_confirmed = true;
+                    }
+                } else {
+                    self.predictions.clear();
+                    self.echo_confirmed = false;
+                }
+            },
+            None => {
+                self.predictions.clear();
+                self.echo_confirmed = false;
+            },
+        }
+        if self.predictions.is_empty() {
+            self.oldest_prediction = None;
+        }
+        update.predictions_done =
+            had_drawn_predictions && !self.predictions.iter().any(|p| p.drawn);
+        update
+    }
+    /// How long until the pending predictions expire, if there are any
+    pub fn time_until_expiry(&self) -> Option<Duration> {
+        self.oldest_prediction
+            .map(|oldest_prediction| PREDICTION_TIMEOUT.saturating_sub(oldest_prediction.elapsed()))
+    }
+    /// Give up on the pending predictions
+    pub fn expire(&mut self) -> LocalEchoUpdate {
+        let predictions_done = self.predictions.iter().any(|p| p.drawn);
+        if !self.predictions.is_empty() {
+            // the server did not echo what was typed, so stop drawing predictions until it does
+            self.echo_confirmed = false;
+        }
+        self.predictions.clear();
+        self.oldest_prediction = None;
+        LocalEchoUpdate {
+            predictions_done,
+            ..Default::default()
+        }
+    }
+}
+
+// returns Some(None) if the render hid the cursor, Some(Some((x, y))) if it showed it and None if
+// it did not touch it
+fn cursor_position_after_render(render: &str) -> Option<Option<(usize, usize)>> {
+    let last_show = render.rfind(SHOW_CURSOR);
+    let last_hide = render.rfind(HIDE_CURSOR);
+    match (last_show, last_hide) {
+        (Some(last_show), Some(last_hide)) if last_hide > last_show => Some(None),
+        (Some(last_show), _) => {
+            // the server moves the cursor right after showing it
+            let goto = &render[last_show + SHOW_CURSOR.len()..];
+            Some(parse_goto(goto))
+        },
+        (None, Some(_)) => Some(None),
+        (None, None) => None,
+    }
+}
+
+// parses "\u{1b}[{row};{col}H" at the start of this str into a zero based (x, y)
+fn parse_goto(vte: &str) -> Option<(usize, usize)> {
+    let params = vte.strip_prefix("\u{1b}[")?;
+    let end = params.find('H')?;
+    let (row, col) = params[..end].split_once(';')?;
+    let row: usize = row.parse().ok()?;
+    let col: usize = col.parse().ok()?;
+    Some((col.checked_sub(1)?, row.checked_sub(1)?))
+}
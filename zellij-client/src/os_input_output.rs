@@ -15,14 +15,21 @@ use std::{io, thread, time};
 use zellij_utils::{
     data::Palette,
     errors::ErrorContext,
-    ipc::{ClientToServerMsg, IpcReceiverWithContext, IpcSenderWithContext, ServerToClientMsg},
+    ipc::{
+        connect_to_remote_server, handshake_with_server, remote_attach_client_config,
+        ClientToServerMsg, IpcReceiverWithContext, IpcSenderWithContext, ProtocolInfo,
+        ServerToClientMsg,
+    },
     shared::default_palette,
 };
 
 const SIGWINCH_CB_THROTTLE_DURATION: time::Duration = time::Duration::from_millis(50);
 
 const ENABLE_MOUSE_SUPPORT: &str = "\u{1b}[?1000h\u{1b}[?1002h\u{1b}[?1015h\u{1b}[?1006h";
-const DISABLE_MOUSE_SUPPORT: &str = "\u{1b}[?1006l\u{1b}[?1015l\u{1b}[?1002l\u{1b}[?1000l";
+const DISABLE_MOUSE_SUPPORT: &str =
+    "\u{1b}[?1006l\u{1b}[?1015l\u{1b}[?1003l\u{1b}[?1002l\u{1b}[?1000l";
+// reports every movement of the mouse, not only those while a button is held
+const ENABLE_MOUSE_MOTION_SUPPORT: &str = "\u{1b}[?1003h";
 
 fn into_raw_mode(pid: RawFd) {
     let mut tio = termios::tcgetattr(pid).expect("could not get terminal attribute");
@@ -108,10 +115,22 @@ pub trait ClientOsApi: Send + Sync {
     // This should be called from the client-side router thread only.
     fn recv_from_server(&self) -> Option<(ServerToClientMsg, ErrorContext)>;
     fn handle_signals(&self, sigwinch_cb: Box<dyn Fn()>, quit_cb: Box<dyn Fn()>);
-    /// Establish a connection with the server socket.
-    fn connect_to_server(&self, path: &Path);
+    /// Establish a connection with the server socket, returning the protocol the server speaks.
+    fn connect_to_server(&self, path: &Path) -> Result<ProtocolInfo>;
+    /// Establish a connection with a server accepting remote clients on `address`, trusting the
+    /// certificates in `ca_cert`, returning the protocol the server speaks.
+    fn connect_to_remote_server(
+        &self,
+        _address: &str,
+        _token: &str,
+        _ca_cert: &Path,
+    ) -> Result<ProtocolInfo> {
+        Ok(ProtocolInfo::current())
+    }
     fn load_palette(&self) -> Palette;
-    fn enable_mouse(&self) -> Result<()>;
+    /// Enable mouse support, also reporting the mouse moving without any button held if
+    /// `report_motion` is true
+    fn enable_mouse(&self, report_motion: bool) -> Result<()>;
     fn disable_mouse(&self) -> Result<()>;
     // Repeatedly send action, until stdin is readable again
     fn stdin_poller(&self) -> StdinPoller;
@@ -232,8 +251,8 @@ impl ClientOsApi for ClientOsInputOutput {
             }
         }
     }
-    fn connect_to_server(&self, path: &Path) {
-        let socket;
+    fn connect_to_server(&self, path: &Path) -> Result<ProtocolInfo> {
+        let mut socket;
         loop {
             match LocalSocketStream::connect(path) {
                 Ok(sock) => {
@@ -245,10 +264,27 @@ impl ClientOsApi for ClientOsInputOutput {
                 },
             }
         }
+        let server_protocol = handshake_with_server(&mut socket)?;
         let sender = IpcSenderWithContext::new(socket);
         let receiver = sender.get_receiver();
         *self.send_instructions_to_server.lock().unwrap() = Some(sender);
         *self.receive_instructions_from_server.lock().unwrap() = Some(receiver);
+        Ok(server_protocol)
+    }
+    fn connect_to_remote_server(
+        &self,
+        address: &str,
+        token: &str,
+        ca_cert: &Path,
+    ) -> Result<ProtocolInfo> {
+        let tls_config = remote_attach_client_config(ca_cert)?;
+        let mut socket = connect_to_remote_server(address, tls_config, token)?;
+        let server_protocol = handshake_with_server(&mut socket)?;
+        let sender = IpcSenderWithContext::new(socket);
+        let receiver = sender.get_receiver();
+        *self.send_instructions_to_server.lock().unwrap() = Some(sender);
+        *self.receive_instructions_from_server.lock().unwrap() = Some(receiver);
+        Ok(server_protocol)
     }
     fn load_palette(&self) -> Palette {
         // this was removed because termbg doesn't release stdin in certain scenarios (we know of
@@ -264,12 +300,17 @@ impl ClientOsApi for ClientOsInputOutput {
         // };
         default_palette()
     }
-    fn enable_mouse(&self) -> Result<()> {
+    fn enable_mouse(&self, report_motion: bool) -> Result<()> {
         let err_context = "failed to enable mouse mode";
         let mut stdout = self.get_stdout_writer();
         stdout
             .write_all(ENABLE_MOUSE_SUPPORT.as_bytes())
             .context(err_context)?;
+        if report_motion {
+            stdout
+                .write_all(ENABLE_MOUSE_MOTION_SUPPORT.as_bytes())
+                .context(err_context)?;
+        }
         stdout.flush().context(err_context)?;
         Ok(())
     }
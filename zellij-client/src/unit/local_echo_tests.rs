@@ -0,0 +1,137 @@
+use crate::local_echo::{LocalEchoPredictor, LocalEchoUpdate};
+
+// what the server sends when the cursor is shown at this zero based position
+fn render_with_cursor_at(x: usize, y: usize) -> String {
+    format!(
+        "\u{1b}[?25lsome output\u{1b}[?25h\u{1b}[{};{}H\u{1b}[m",
+        y + 1,
+        x + 1
+    )
+}
+
+// a predictor whose echo the server confirmed, with the cursor at this zero based position
+fn predictor_with_confirmed_echo_at(columns: usize, x: usize, y: usize) -> LocalEchoPredictor {
+    let mut predictor = LocalEchoPredictor::new(columns);
+    predictor.handle_render(&render_with_cursor_at(x - 1, y));
+    predictor.predict(b"a");
+    predictor.handle_render(&render_with_cursor_at(x, y));
+    predictor
+}
+
+#[test]
+fn predicts_printable_characters_at_the_cursor() {
+    let mut predictor = predictor_with_confirmed_echo_at(80, 4, 2);
+    let update = predictor.predict(b"ls");
+    assert_eq!(
+        update,
+        LocalEchoUpdate {
+            vte_output: Some("\u{1b}[3;5H\u{1b}[4mls\u{1b}[m".to_owned()),
+            predicted_cells: vec![(4, 2), (5, 2)],
+            predictions_done: false,
+        }
+    );
+    let update = predictor.predict(b" ");
+    assert_eq!(update.predicted_cells, vec![(6, 2)]);
+}
+
+#[test]
+fn does_not_predict_without_a_visible_cursor() {
+    let mut predictor = LocalEchoPredictor::new(80);
+    assert_eq!(predictor.predict(b"a"), LocalEchoUpdate::default());
+    predictor.handle_render(&render_with_cursor_at(4, 2));
+    predictor.handle_render("\u{1b}[?25lsome output");
+    assert_eq!(predictor.predict(b"a"), LocalEchoUpdate::default());
+}
+
+#[test]
+fn stops_predicting_after_non_printable_input_until_the_echo_is_confirmed_again() {
+    let mut predictor = predictor_with_confirmed_echo_at(80, 1, 0);
+    assert_eq!(predictor.predict(b"\r"), LocalEchoUpdate::default());
+    assert_eq!(predictor.predict(b"a"), LocalEchoUpdate::default());
+    predictor.handle_render(&render_with_cursor_at(0, 1));
+    assert_eq!(predictor.predict(b"a"), LocalEchoUpdate::default());
+    predictor.handle_render(&render_with_cursor_at(1, 1));
+    assert_eq!(predictor.predict(b"b").predicted_cells, vec![(1, 1)]);
+}
+
+#[test]
+fn does_not_draw_predictions_before_the_echo_is_confirmed() {
+    let mut predictor = LocalEchoPredictor::new(80);
+    predictor.handle_render(&render_with_cursor_at(0, 0));
+    assert_eq!(predictor.predict(b"a"), LocalEchoUpdate::default());
+    assert_eq!(predictor.predict(b"b"), LocalEchoUpdate::default());
+    predictor.handle_render(&render_with_cursor_at(1, 0));
+    assert_eq!(predictor.predict(b"c").predicted_cells, vec![(2, 0)]);
+}
+
+#[test]
+fn stops_drawing_predictions_once_one_is_not_echoed() {
+    let mut predictor = predictor_with_confirmed_echo_at(80, 1, 0);
+    predictor.predict(b"a");
+    assert!(predictor.expire().predictions_done);
+    assert_eq!(predictor.predict(b"b"), LocalEchoUpdate::default());
+}
+
+#[test]
+fn echo_has_to_be_confirmed_again_after_the_cursor_moves_by_itself() {
+    let mut predictor = predictor_with_confirmed_echo_at(80, 1, 0);
+    // eg. focusing another pane
+    predictor.handle_render(&render_with_cursor_at(7, 3));
+    assert_eq!(predictor.predict(b"a"), LocalEchoUpdate::default());
+}
+
+#[test]
+fn does_not_predict_past_the_last_column() {
+    let mut predictor = predictor_with_confirmed_echo_at(4, 2, 0);
+    assert_eq!(
+        predictor.predict(b"abc").predicted_cells,
+        vec![(2, 0), (3, 0)]
+    );
+}
+
+#[test]
+fn predictions_are_done_once_the_cursor_moves_past_them() {
+    let mut predictor = predictor_with_confirmed_echo_at(80, 1, 0);
+    predictor.predict(b"abc");
+    assert!(
+        !predictor
+            .handle_render(&render_with_cursor_at(3, 0))
+            .predictions_done
+    );
+    assert!(predictor.time_until_expiry().is_some());
+    assert!(
+        predictor
+            .handle_render(&render_with_cursor_at(4, 0))
+            .predictions_done
+    );
+    assert!(predictor.time_until_expiry().is_none());
+}
+
+#[test]
+fn predictions_are_done_when_the_cursor_leaves_their_line() {
+    let mut predictor = predictor_with_confirmed_echo_at(80, 1, 0);
+    predictor.predict(b"abc");
+    assert!(
+        predictor
+            .handle_render(&render_with_cursor_at(0, 5))
+            .predictions_done
+    );
+}
+
+#[test]
+fn render_without_cursor_instructions_keeps_predictions() {
+    let mut predictor = predictor_with_confirmed_echo_at(80, 1, 0);
+    predictor.predict(b"abc");
+    assert!(!predictor.handle_render("some output").predictions_done);
+    assert_eq!(predictor.predict(b"d").predicted_cells, vec![(4, 0)]);
+}
+
+#[test]
+fn expiring_pending_predictions() {
+    let mut predictor = LocalEchoPredictor::new(80);
+    assert!(!predictor.expire().predictions_done);
+    let mut predictor = predictor_with_confirmed_echo_at(80, 1, 0);
+    predictor.predict(b"abc");
+    assert!(predictor.expire().predictions_done);
+    assert!(predictor.time_until_expiry().is_none());
+}
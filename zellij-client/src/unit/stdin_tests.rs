@@ -8,7 +8,10 @@ use zellij_utils::input::config::Config;
 use zellij_utils::input::options::Options;
 use zellij_utils::nix;
 use zellij_utils::pane_size::Size;
-use zellij_utils::termwiz::input::{InputEvent, KeyCode, KeyEvent, Modifiers};
+use zellij_utils::position::Position;
+use zellij_utils::termwiz::input::{
+    InputEvent, KeyCode, KeyEvent, Modifiers, MouseButtons, MouseEvent,
+};
 
 use crate::InputInstruction;
 use crate::{
@@ -25,7 +28,7 @@ use std::sync::{Arc, Mutex};
 use std::thread;
 use zellij_utils::{
     errors::ErrorContext,
-    ipc::{ClientToServerMsg, ServerToClientMsg},
+    ipc::{ClientToServerMsg, ProtocolInfo, ServerToClientMsg},
 };
 
 use zellij_utils::channels::{self, ChannelWithContext, SenderWithContext};
@@ -177,13 +180,13 @@ impl ClientOsApi for FakeClientOsApi {
     fn handle_signals(&self, _sigwinch_cb: Box<dyn Fn()>, _quit_cb: Box<dyn Fn()>) {
         unimplemented!()
     }
-    fn connect_to_server(&self, _path: &Path) {
+    fn connect_to_server(&self, _path: &Path) -> Result<ProtocolInfo> {
         unimplemented!()
     }
     fn load_palette(&self) -> Palette {
         unimplemented!()
     }
-    fn enable_mouse(&self) -> Result<()> {
+    fn enable_mouse(&self, _report_motion: bool) -> Result<()> {
         Ok(())
     }
     fn disable_mouse(&self) -> Result<()> {
@@ -318,3 +321,77 @@ pub fn move_focus_left_in_normal_mode() {
         "All actions sent to server properly"
     );
 }
+
+fn actions_for_mouse_moving_without_a_button_held(options: Options) -> Vec<Action> {
+    let stdin_events = vec![
+        (
+            vec![],
+            InputEvent::Mouse(MouseEvent {
+                x: 3,
+                y: 2,
+                mouse_buttons: MouseButtons::NONE,
+                modifiers: Modifiers::NONE,
+            }),
+        ),
+        (
+            commands::QUIT.to_vec(),
+            InputEvent::Key(KeyEvent {
+                key: KeyCode::Char('q'),
+                modifiers: Modifiers::CTRL,
+            }),
+        ),
+    ];
+
+    let events_sent_to_server = Arc::new(Mutex::new(vec![]));
+    let command_is_executing = CommandIsExecuting::new();
+    let client_os_api = Box::new(FakeClientOsApi::new(
+        events_sent_to_server.clone(),
+        command_is_executing.clone(),
+    ));
+    let config = Config::from_default_assets().unwrap();
+
+    let (send_client_instructions, _receive_client_instructions): ChannelWithContext<
+        ClientInstruction,
+    > = channels::bounded(50);
+    let send_client_instructions = SenderWithContext::new(send_client_instructions);
+
+    let (send_input_instructions, receive_input_instructions): ChannelWithContext<
+        InputInstruction,
+    > = channels::bounded(50);
+    let send_input_instructions = SenderWithContext::new(send_input_instructions);
+    for event in stdin_events {
+        send_input_instructions
+            .send(InputInstruction::KeyEvent(event.1, event.0))
+            .unwrap();
+    }
+
+    let default_mode = InputMode::Normal;
+    input_loop(
+        client_os_api,
+        config,
+        options,
+        command_is_executing,
+        send_client_instructions,
+        default_mode,
+        receive_input_instructions,
+    );
+    extract_actions_sent_to_server(events_sent_to_server)
+}
+
+#[test]
+pub fn mouse_motion_is_only_sent_with_mouse_hover_events() {
+    let position = Position::new(1, 2);
+    assert_eq!(
+        actions_for_mouse_moving_without_a_button_held(Options::default()),
+        vec![Action::LeftMouseRelease(position), Action::Quit],
+        "without hover events this is treated as a release, like before"
+    );
+    let options = Options {
+        mouse_hover_events: Some(true),
+        ..Default::default()
+    };
+    assert_eq!(
+        actions_for_mouse_moving_without_a_button_held(options),
+        vec![Action::MouseMotion(position), Action::Quit],
+    );
+}
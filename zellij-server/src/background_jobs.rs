@@ -1,10 +1,14 @@
 use zellij_utils::async_std::task;
 use zellij_utils::consts::{
-    session_info_cache_file_name, session_info_folder_for_session, session_layout_cache_file_name,
-    ZELLIJ_SESSION_INFO_CACHE_DIR, ZELLIJ_SOCK_DIR,
+    remove_session_snapshot_folder, session_idle_deadline_file_name, session_info_cache_file_name,
+    session_info_folder_for_session, session_layout_cache_file_name, session_snapshot_folder,
+    session_snapshot_id, session_snapshot_layout_file_name, session_snapshots_folder_for_session,
+    session_workspace_file_name, validate_snapshot_path_component, AUTO_SNAPSHOT_PREFIX,
+    SESSION_LAYOUT_FILE_NAME, ZELLIJ_SESSION_INFO_CACHE_DIR, ZELLIJ_SOCK_DIR,
 };
 use zellij_utils::data::{Event, HttpVerb, SessionInfo};
 use zellij_utils::errors::{prelude::*, BackgroundJobContext, ContextType};
+use zellij_utils::input::{layout::Layout, options::Options};
 use zellij_utils::surf::{
     http::{Method, Url},
     RequestBuilder,
@@ -14,27 +18,30 @@ use std::collections::{BTreeMap, HashMap};
 use std::fs;
 use std::io::Write;
 use std::os::unix::fs::FileTypeExt;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::{
     atomic::{AtomicBool, Ordering},
     Arc, Mutex,
 };
-use std::time::{Duration, Instant};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 use crate::panes::PaneId;
 use crate::plugins::{PluginId, PluginInstruction};
+use crate::pty::PtyInstruction;
 use crate::screen::ScreenInstruction;
 use crate::thread_bus::Bus;
-use crate::ClientId;
+use crate::{ClientId, ServerInstruction};
 
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub enum BackgroundJob {
     DisplayPaneError(Vec<PaneId>, String),
-    AnimatePluginLoading(u32),                            // u32 - plugin_id
-    StopPluginLoadingAnimation(u32),                      // u32 - plugin_id
-    ReadAllSessionInfosOnMachine,                         // u32 - plugin_id
-    ReportSessionInfo(String, SessionInfo),               // String - session name
+    AnimatePluginLoading(u32),       // u32 - plugin_id
+    StopPluginLoadingAnimation(u32), // u32 - plugin_id
+    ReadAllSessionInfosOnMachine,    // u32 - plugin_id
+    SamplePaneProcesses,
+    ReportSessionInfo(String, SessionInfo), // String - session name
     ReportLayoutInfo((String, BTreeMap<String, String>)), // BTreeMap<file_name, pane_contents>
+    SaveSnapshot(String, (String, BTreeMap<String, String>), ClientId), // String - snapshot name
     RunCommand(
         PluginId,
         ClientId,
@@ -67,8 +74,10 @@ impl From<&BackgroundJob> for BackgroundJobContext {
             BackgroundJob::ReadAllSessionInfosOnMachine => {
                 BackgroundJobContext::ReadAllSessionInfosOnMachine
             },
+            BackgroundJob::SamplePaneProcesses => BackgroundJobContext::SamplePaneProcesses,
             BackgroundJob::ReportSessionInfo(..) => BackgroundJobContext::ReportSessionInfo,
             BackgroundJob::ReportLayoutInfo(..) => BackgroundJobContext::ReportLayoutInfo,
+            BackgroundJob::SaveSnapshot(..) => BackgroundJobContext::SaveSnapshot,
             BackgroundJob::RunCommand(..) => BackgroundJobContext::RunCommand,
             BackgroundJob::WebRequest(..) => BackgroundJobContext::WebRequest,
             BackgroundJob::Exit => BackgroundJobContext::Exit,
@@ -79,11 +88,44 @@ impl From<&BackgroundJob> for BackgroundJobContext {
 static FLASH_DURATION_MS: u64 = 1000;
 static PLUGIN_ANIMATION_OFFSET_DURATION_MD: u64 = 500;
 static SESSION_READ_DURATION: u64 = 1000;
+static PANE_PROCESS_SAMPLE_DURATION: u64 = 2000;
 static DEFAULT_SERIALIZATION_INTERVAL: u64 = 60000;
 
+/// Limits on how long sessions are kept around, enforced periodically along with reading the
+/// session infos on the machine
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct SessionPolicies {
+    /// Kill this session after it has had no connected clients for this long
+    pub idle_session_timeout: Option<Duration>,
+    /// Delete resurrectable sessions that exited longer ago than this
+    pub resurrectable_session_ttl: Option<Duration>,
+    /// Delete the resurrectable sessions that exited longest ago beyond this number
+    pub max_resurrectable_sessions: Option<usize>,
+}
+
+impl SessionPolicies {
+    pub fn from_options(options: &Options) -> Self {
+        let hours = |hours: u64| Duration::from_secs(hours * 60 * 60);
+        SessionPolicies {
+            idle_session_timeout: options
+                .idle_session_timeout
+                .filter(|timeout| *timeout > 0)
+                .map(hours),
+            resurrectable_session_ttl: options
+                .resurrectable_session_ttl
+                .filter(|ttl| *ttl > 0)
+                .map(hours),
+            max_resurrectable_sessions: options.max_resurrectable_sessions,
+        }
+    }
+}
+
 pub(crate) fn background_jobs_main(
     bus: Bus<BackgroundJob>,
     serialization_interval: Option<u64>,
+    auto_snapshot_history: usize,
+    session_policies: SessionPolicies,
+    layout_dir: Option<PathBuf>,
 ) -> Result<()> {
     let err_context = || "failed to write to pty".to_string();
     let mut running_jobs: HashMap<BackgroundJob, Instant> = HashMap::new();
@@ -92,6 +134,7 @@ pub(crate) fn background_jobs_main(
     let current_session_info = Arc::new(Mutex::new(SessionInfo::default()));
     let current_session_layout = Arc::new(Mutex::new((String::new(), BTreeMap::new())));
     let last_serialization_time = Arc::new(Mutex::new(Instant::now()));
+    let mut last_auto_snapshot_layout = String::new();
     let serialization_interval = serialization_interval.map(|s| s * 1000); // convert to
                                                                            // milliseconds
 
@@ -152,8 +195,51 @@ pub(crate) fn background_jobs_main(
                 *current_session_info.lock().unwrap() = session_info;
             },
             BackgroundJob::ReportLayoutInfo(session_layout) => {
+                let session_name = current_session_name.lock().unwrap().to_string();
+                if auto_snapshot_history > 0
+                    && !session_name.is_empty()
+                    && !session_layout.0.is_empty()
+                    && session_layout.0 != last_auto_snapshot_layout
+                {
+                    take_auto_snapshot(&session_name, &session_layout, auto_snapshot_history);
+                    last_auto_snapshot_layout = session_layout.0.clone();
+                }
                 *current_session_layout.lock().unwrap() = session_layout;
             },
+            BackgroundJob::SaveSnapshot(snapshot_name, session_layout, client_id) => {
+                let session_name = current_session_name.lock().unwrap().to_string();
+                let message = if session_name.is_empty() {
+                    ServerInstruction::LogError(
+                        vec!["The session is not ready to be snapshotted yet".to_owned()],
+                        client_id,
+                    )
+                } else if let Err(e) = validate_snapshot_path_component(&session_name)
+                    .and_then(|_| validate_snapshot_path_component(&snapshot_name))
+                {
+                    ServerInstruction::LogError(
+                        vec![format!("Failed to save snapshot: {}", e)],
+                        client_id,
+                    )
+                } else {
+                    // a snapshot by the same name is replaced rather than merged with this one
+                    let _ = remove_session_snapshot_folder(&session_name, &snapshot_name);
+                    let snapshot_folder = session_snapshot_folder(&session_name, &snapshot_name);
+                    match write_session_layout(&snapshot_folder, session_layout) {
+                        Ok(()) => ServerInstruction::Log(
+                            vec![format!(
+                                "Saved snapshot: {}",
+                                session_snapshot_id(&session_name, &snapshot_name)
+                            )],
+                            client_id,
+                        ),
+                        Err(e) => ServerInstruction::LogError(
+                            vec![format!("Failed to save snapshot: {}", e)],
+                            client_id,
+                        ),
+                    }
+                };
+                let _ = bus.senders.send_to_server(message);
+            },
             BackgroundJob::ReadAllSessionInfosOnMachine => {
                 // this job should only be run once and it keeps track of other sessions (as well
                 // as this one's) infos (metadata mostly) and sends it to the screen which in turn
@@ -168,18 +254,43 @@ pub(crate) fn background_jobs_main(
                     let current_session_name = current_session_name.clone();
                     let current_session_layout = current_session_layout.clone();
                     let last_serialization_time = last_serialization_time.clone();
+                    let layout_dir = layout_dir.clone();
                     async move {
+                        let mut idle_since = None;
+                        let mut last_layout_dir_state = None;
                         loop {
                             let current_session_name =
                                 current_session_name.lock().unwrap().to_string();
                             let current_session_info = current_session_info.lock().unwrap().clone();
                             let current_session_layout =
                                 current_session_layout.lock().unwrap().clone();
+                            let connected_clients = current_session_info.connected_clients;
                             write_session_state_to_disk(
                                 current_session_name.clone(),
                                 current_session_info,
                                 current_session_layout,
                             );
+                            if let Some(idle_session_timeout) =
+                                session_policies.idle_session_timeout
+                            {
+                                if !current_session_name.is_empty()
+                                    && idle_session_timeout_reached(
+                                        &current_session_name,
+                                        connected_clients,
+                                        idle_session_timeout,
+                                        &mut idle_since,
+                                    )
+                                {
+                                    log::info!(
+                                        "Killing session {} after {:?} without clients",
+                                        current_session_name,
+                                        idle_session_timeout
+                                    );
+                                    let _ = senders.send_to_server(ServerInstruction::KillSession);
+                                    break;
+                                }
+                            }
+                            prune_resurrectable_sessions(&session_policies);
                             let session_infos_on_machine =
                                 read_other_live_session_states(&current_session_name);
                             let resurrectable_sessions =
@@ -188,6 +299,17 @@ pub(crate) fn background_jobs_main(
                                 session_infos_on_machine,
                                 resurrectable_sessions,
                             ));
+                            // reading and parsing every layout on each tick is wasteful, so
+                            // they are only listed again when the layout dir changes
+                            let layout_dir_state = read_layout_dir_state(layout_dir.as_deref());
+                            if last_layout_dir_state.as_ref() != Some(&layout_dir_state) {
+                                let available_layouts =
+                                    Layout::list_available_layouts(layout_dir.as_deref());
+                                let _ = senders.send_to_plugin(
+                                    PluginInstruction::UpdateAvailableLayouts(available_layouts),
+                                );
+                                last_layout_dir_state = Some(layout_dir_state);
+                            }
                             if last_serialization_time
                                 .lock()
                                 .unwrap()
@@ -206,6 +328,26 @@ pub(crate) fn background_jobs_main(
                     }
                 });
             },
+            BackgroundJob::SamplePaneProcesses => {
+                // this job should only be run once, it periodically asks the pty thread to sample
+                // the processes running in each terminal pane
+                if running_jobs.get(&job).is_some() {
+                    continue;
+                }
+                running_jobs.insert(job, Instant::now());
+                task::spawn({
+                    let senders = bus.senders.clone();
+                    async move {
+                        loop {
+                            let _ = senders.send_to_pty(PtyInstruction::SamplePaneProcesses);
+                            task::sleep(std::time::Duration::from_millis(
+                                PANE_PROCESS_SAMPLE_DURATION,
+                            ))
+                            .await;
+                        }
+                    }
+                });
+            },
             BackgroundJob::RunCommand(
                 plugin_id,
                 client_id,
@@ -320,9 +462,10 @@ pub(crate) fn background_jobs_main(
                     loading_plugin.store(false, Ordering::SeqCst);
                 }
 
-                let cache_file_name =
-                    session_info_cache_file_name(&current_session_name.lock().unwrap().to_owned());
-                let _ = std::fs::remove_file(cache_file_name);
+                let current_session_name = current_session_name.lock().unwrap().to_owned();
+                let _ = std::fs::remove_file(session_info_cache_file_name(&current_session_name));
+                let _ =
+                    std::fs::remove_file(session_idle_deadline_file_name(&current_session_name));
                 return Ok(());
             },
         }
@@ -360,25 +503,82 @@ fn write_session_state_to_disk(
         std::fs::create_dir_all(session_info_folder_for_session(&current_session_name).as_path())
             .and_then(|_| std::fs::File::create(metadata_cache_file_name))
             .and_then(|mut f| write!(f, "{}", current_session_info.to_string()));
+    if let Some(workspace) = &current_session_info.workspace {
+        let _wrote_workspace_file = std::fs::write(
+            session_workspace_file_name(&current_session_name),
+            workspace,
+        );
+    }
 
     if !current_session_layout.is_empty() {
-        let layout_cache_file_name = session_layout_cache_file_name(&current_session_name);
-        let _wrote_layout_file = std::fs::create_dir_all(
-            session_info_folder_for_session(&current_session_name).as_path(),
-        )
-        .and_then(|_| std::fs::File::create(layout_cache_file_name))
-        .and_then(|mut f| write!(f, "{}", current_session_layout))
-        .and_then(|_| {
-            let session_info_folder = session_info_folder_for_session(&current_session_name);
-            for (external_file_name, external_file_contents) in layout_files_to_write {
-                std::fs::File::create(session_info_folder.join(external_file_name))
-                    .and_then(|mut f| write!(f, "{}", external_file_contents))
-                    .unwrap_or_else(|e| {
-                        log::error!("Failed to write layout metadata file: {:?}", e);
-                    });
-            }
-            Ok(())
-        });
+        if let Err(e) = write_session_layout(
+            &session_info_folder_for_session(&current_session_name),
+            (current_session_layout, layout_files_to_write),
+        ) {
+            log::error!("Failed to write session layout: {:?}", e);
+        }
+    }
+}
+
+// writes the serialized layout along with the files holding its pane contents, which are looked
+// up relative to it when it is resurrected
+fn write_session_layout(
+    folder: &Path,
+    session_layout: (String, BTreeMap<String, String>),
+) -> std::io::Result<()> {
+    let (layout, layout_files_to_write) = session_layout;
+    std::fs::create_dir_all(folder)?;
+    std::fs::File::create(folder.join(SESSION_LAYOUT_FILE_NAME))
+        .and_then(|mut f| write!(f, "{}", layout))?;
+    for (external_file_name, external_file_contents) in layout_files_to_write {
+        std::fs::File::create(folder.join(external_file_name))
+            .and_then(|mut f| write!(f, "{}", external_file_contents))?;
+    }
+    Ok(())
+}
+
+fn take_auto_snapshot(
+    session_name: &str,
+    session_layout: &(String, BTreeMap<String, String>),
+    auto_snapshot_history: usize,
+) {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|since_epoch| since_epoch.as_secs())
+        .unwrap_or_default();
+    let snapshot_name = format!("{}{}", AUTO_SNAPSHOT_PREFIX, timestamp);
+    if let Err(e) = write_session_layout(
+        &session_snapshot_folder(session_name, &snapshot_name),
+        session_layout.clone(),
+    ) {
+        log::error!("Failed to write automatic snapshot: {:?}", e);
+        return;
+    }
+    let mut auto_snapshot_timestamps: Vec<u64> =
+        match fs::read_dir(session_snapshots_folder_for_session(session_name)) {
+            Ok(snapshot_folders) => snapshot_folders
+                .filter_map(|f| f.ok()?.file_name().into_string().ok())
+                .filter_map(|snapshot_name| {
+                    snapshot_name
+                        .strip_prefix(AUTO_SNAPSHOT_PREFIX)?
+                        .parse::<u64>()
+                        .ok()
+                })
+                .collect(),
+            Err(e) => {
+                log::error!("Failed to read snapshots folder: {:?}", e);
+                return;
+            },
+        };
+    auto_snapshot_timestamps.sort_unstable();
+    let excess_snapshot_count = auto_snapshot_timestamps
+        .len()
+        .saturating_sub(auto_snapshot_history);
+    for timestamp in auto_snapshot_timestamps.iter().take(excess_snapshot_count) {
+        let snapshot_name = format!("{}{}", AUTO_SNAPSHOT_PREFIX, timestamp);
+        if let Err(e) = remove_session_snapshot_folder(session_name, &snapshot_name) {
+            log::error!("Failed to remove old automatic snapshot: {:?}", e);
+        }
     }
 }
 
@@ -417,42 +617,27 @@ fn find_resurrectable_sessions(
 ) -> BTreeMap<String, Duration> {
     match fs::read_dir(&*ZELLIJ_SESSION_INFO_CACHE_DIR) {
         Ok(files_in_session_info_folder) => {
-            let files_that_are_folders = files_in_session_info_folder
+            let session_names = files_in_session_info_folder
                 .filter_map(|f| f.ok().map(|f| f.path()))
-                .filter(|f| f.is_dir());
-            files_that_are_folders
-                .filter_map(|folder_name| {
-                    let session_name = folder_name.file_name()?.to_str()?.to_owned();
-                    if session_infos_on_machine.contains_key(&session_name) {
-                        // this is not a dead session...
-                        return None;
-                    }
-                    let layout_file_name = session_layout_cache_file_name(&session_name);
-                    let ctime = match std::fs::metadata(&layout_file_name)
-                        .and_then(|metadata| metadata.created())
-                    {
-                        Ok(created) => Some(created),
-                        Err(e) => {
-                            if e.kind() != std::io::ErrorKind::NotFound {
-                                // let's not spam the
-                                // logs if serialization
-                                // is disabled
-                                log::error!(
-                                    "Failed to read created stamp of resurrection file: {:?}",
-                                    e
-                                );
-                            }
-                            None
-                        },
-                    };
-                    let elapsed_duration = ctime
-                        .map(|ctime| {
-                            Duration::from_secs(ctime.elapsed().ok().unwrap_or_default().as_secs())
-                        })
-                        .unwrap_or_default();
-                    Some((session_name, elapsed_duration))
-                })
-                .collect()
+                .filter(|f| f.is_dir())
+                .filter_map(|folder_name| Some(folder_name.file_name()?.to_str()?.to_owned()));
+            let mut resurrectable_sessions = BTreeMap::new();
+            for session_name in session_names {
+                // snapshots can be resurrected as new sessions whether their session is running
+                // or not
+                resurrectable_sessions.extend(find_session_snapshots(&session_name));
+                if session_infos_on_machine.contains_key(&session_name) {
+                    // this is not a dead session...
+                    continue;
+                }
+                let layout_file_name = session_layout_cache_file_name(&session_name);
+                if !layout_file_name.exists() {
+                    // ...and neither is a folder only holding snapshots or metadata
+                    continue;
+                }
+                resurrectable_sessions.insert(session_name, layout_file_age(&layout_file_name));
+            }
+            resurrectable_sessions
         },
         Err(e) => {
             log::error!("Failed to read session info cache dir: {:?}", e);
@@ -460,3 +645,158 @@ fn find_resurrectable_sessions(
         },
     }
 }
+
+// returns true once the session has had no connected clients for the whole timeout, the time at
+// which it will be killed is written to disk in the meantime so that it can be shown in `zellij ls`
+fn idle_session_timeout_reached(
+    session_name: &str,
+    connected_clients: usize,
+    idle_session_timeout: Duration,
+    idle_since: &mut Option<Instant>,
+) -> bool {
+    let idle_deadline_file_name = session_idle_deadline_file_name(session_name);
+    if connected_clients > 0 {
+        if idle_since.take().is_some() {
+            let _ = fs::remove_file(idle_deadline_file_name);
+        }
+        return false;
+    }
+    match idle_since {
+        Some(idle_since) => idle_since.elapsed() >= idle_session_timeout,
+        None => {
+            *idle_since = Some(Instant::now());
+            let idle_deadline = (SystemTime::now() + idle_session_timeout)
+                .duration_since(UNIX_EPOCH)
+                .map(|since_epoch| since_epoch.as_secs())
+                .unwrap_or_default();
+            if let Err(e) = fs::write(idle_deadline_file_name, idle_deadline.to_string()) {
+                log::error!("Failed to write idle session deadline: {:?}", e);
+            }
+            false
+        },
+    }
+}
+
+// deletes the resurrectable sessions that exited longer ago than the ttl, as well as the ones that
+// exited longest ago beyond the maximum number of sessions to keep
+fn prune_resurrectable_sessions(session_policies: &SessionPolicies) {
+    if session_policies.resurrectable_session_ttl.is_none()
+        && session_policies.max_resurrectable_sessions.is_none()
+    {
+        return;
+    }
+    let mut dead_sessions = find_dead_sessions();
+    dead_sessions.sort_by_key(|(_session_name, exited_ago)| *exited_ago);
+    for (index, (session_name, exited_ago)) in dead_sessions.iter().enumerate() {
+        let expired = session_policies
+            .resurrectable_session_ttl
+            .map(|ttl| *exited_ago > ttl)
+            .unwrap_or(false);
+        let over_capacity = session_policies
+            .max_resurrectable_sessions
+            .map(|max_sessions| index >= max_sessions)
+            .unwrap_or(false);
+        if expired || over_capacity {
+            log::info!("Deleting resurrectable session {}", session_name);
+            delete_dead_session(session_name);
+        }
+    }
+}
+
+// returns the sessions that can be resurrected along with how long ago they exited (ie. when their
+// layout was last serialized)
+fn find_dead_sessions() -> Vec<(String, Duration)> {
+    let session_folders = match fs::read_dir(&*ZELLIJ_SESSION_INFO_CACHE_DIR) {
+        Ok(session_folders) => session_folders,
+        Err(_) => return vec![],
+    };
+    session_folders
+        .filter_map(|f| f.ok()?.file_name().into_string().ok())
+        .filter(|session_name| !ZELLIJ_SOCK_DIR.join(session_name).exists())
+        .filter_map(|session_name| {
+            let exited_ago = fs::metadata(session_layout_cache_file_name(&session_name))
+                .and_then(|metadata| metadata.modified())
+                .ok()?
+                .elapsed()
+                .unwrap_or_default();
+            Some((session_name, exited_ago))
+        })
+        .collect()
+}
+
+// the snapshots of the session are kept, they can still be resurrected as new sessions
+fn delete_dead_session(session_name: &str) {
+    let session_folder = session_info_folder_for_session(session_name);
+    let snapshots_folder = session_snapshots_folder_for_session(session_name);
+    if let Ok(files) = fs::read_dir(&session_folder) {
+        for path in files.filter_map(|f| f.ok().map(|f| f.path())) {
+            if path == snapshots_folder {
+                continue;
+            }
+            let deleted = if path.is_dir() {
+                fs::remove_dir_all(&path)
+            } else {
+                fs::remove_file(&path)
+            };
+            if let Err(e) = deleted {
+                log::error!("Failed to delete {:?}: {:?}", path, e);
+            }
+        }
+    }
+    // only succeeds if there are no snapshots left
+    let _ = fs::remove_dir(session_folder);
+}
+
+fn find_session_snapshots(session_name: &str) -> BTreeMap<String, Duration> {
+    // a session without snapshots has no snapshots folder
+    let snapshot_folders = match fs::read_dir(session_snapshots_folder_for_session(session_name)) {
+        Ok(snapshot_folders) => snapshot_folders,
+        Err(_) => return BTreeMap::new(),
+    };
+    snapshot_folders
+        .filter_map(|f| f.ok()?.file_name().into_string().ok())
+        .map(|snapshot_name| {
+            let layout_file_name = session_snapshot_layout_file_name(session_name, &snapshot_name);
+            (
+                session_snapshot_id(session_name, &snapshot_name),
+                layout_file_age(&layout_file_name),
+            )
+        })
+        .collect()
+}
+
+// the names and modification times of the files in the layout dir
+fn read_layout_dir_state(layout_dir: Option<&Path>) -> Vec<(PathBuf, Option<SystemTime>)> {
+    let mut layout_dir_state: Vec<(PathBuf, Option<SystemTime>)> = layout_dir
+        .and_then(|layout_dir| fs::read_dir(layout_dir).ok())
+        .map(|entries| {
+            entries
+                .filter_map(|entry| {
+                    let entry = entry.ok()?;
+                    let modified = entry.metadata().and_then(|m| m.modified()).ok();
+                    Some((entry.path(), modified))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    layout_dir_state.sort();
+    layout_dir_state
+}
+
+fn layout_file_age(layout_file_name: &Path) -> Duration {
+    let ctime = match std::fs::metadata(layout_file_name).and_then(|metadata| metadata.created()) {
+        Ok(created) => Some(created),
+        Err(e) => {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                // let's not spam the
+                // logs if serialization
+                // is disabled
+                log::error!("Failed to read created stamp of resurrection file: {:?}", e);
+            }
+            None
+        },
+    };
+    ctime
+        .map(|ctime| Duration::from_secs(ctime.elapsed().ok().unwrap_or_default().as_secs()))
+        .unwrap_or_default()
+}
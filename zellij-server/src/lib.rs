@@ -15,16 +15,18 @@ mod terminal_bytes;
 mod thread_bus;
 mod ui;
 
-use background_jobs::{background_jobs_main, BackgroundJob};
+use background_jobs::{background_jobs_main, BackgroundJob, SessionPolicies};
 use log::info;
 use pty_writer::{pty_writer_main, PtyWriteInstruction};
 use std::collections::{HashMap, HashSet};
 use std::{
+    net::TcpListener,
     path::PathBuf,
     sync::{Arc, RwLock},
     thread,
 };
 use zellij_utils::envs;
+use zellij_utils::interprocess::local_socket::LocalSocketStream;
 use zellij_utils::nix::sys::stat::{umask, Mode};
 use zellij_utils::pane_size::Size;
 
@@ -41,10 +43,10 @@ use route::route_thread_main;
 use zellij_utils::{
     channels::{self, ChannelWithContext, SenderWithContext},
     cli::CliArgs,
-    consts::{DEFAULT_SCROLL_BUFFER_SIZE, SCROLL_BUFFER_SIZE},
+    consts::{DEFAULT_AUTO_SNAPSHOT_HISTORY, DEFAULT_SCROLL_BUFFER_SIZE, SCROLL_BUFFER_SIZE},
     data::{ConnectToSession, Event, PluginCapabilities},
     errors::{prelude::*, ContextType, ErrorInstruction, FatalError, ServerContext},
-    home::get_default_data_dir,
+    home::{find_default_config_dir, get_default_data_dir, get_layout_dir},
     input::{
         command::{RunCommand, TerminalAction},
         get_mode_info,
@@ -52,7 +54,10 @@ use zellij_utils::{
         options::Options,
         plugins::PluginsConfig,
     },
-    ipc::{ClientAttributes, ExitReason, ServerToClientMsg},
+    ipc::{
+        accept_remote_client, handshake_with_client, remote_attach_listen_address,
+        remote_attach_server_config, ClientAttributes, ExitReason, ProtocolInfo, ServerToClientMsg,
+    },
 };
 
 pub type ClientId = u16;
@@ -93,6 +98,10 @@ pub enum ServerInstruction {
         pipe_id: String,
         client_id: ClientId,
     },
+    ListClients(ClientId),
+    KickClient(ClientId, ClientId), // (client to kick, client requesting it)
+    SubscribedEvent(Event, ClientId),
+    ClientMissedRenders(ClientId),
 }
 
 impl From<&ServerInstruction> for ServerContext {
@@ -117,6 +126,10 @@ impl From<&ServerInstruction> for ServerContext {
             ServerInstruction::AssociatePipeWithClient { .. } => {
                 ServerContext::AssociatePipeWithClient
             },
+            ServerInstruction::ListClients(..) => ServerContext::ListClients,
+            ServerInstruction::KickClient(..) => ServerContext::KickClient,
+            ServerInstruction::SubscribedEvent(..) => ServerContext::SubscribedEvent,
+            ServerInstruction::ClientMissedRenders(..) => ServerContext::ClientMissedRenders,
         }
     }
 }
@@ -200,6 +213,8 @@ macro_rules! send_to_client {
 pub(crate) struct SessionState {
     clients: HashMap<ClientId, Option<Size>>,
     pipes: HashMap<String, ClientId>, // String => pipe_id
+    read_only_clients: HashSet<ClientId>,
+    remote_clients: HashSet<ClientId>,
 }
 
 impl SessionState {
@@ -207,6 +222,8 @@ impl SessionState {
         SessionState {
             clients: HashMap::new(),
             pipes: HashMap::new(),
+            read_only_clients: HashSet::new(),
+            remote_clients: HashSet::new(),
         }
     }
     pub fn new_client(&mut self) -> ClientId {
@@ -228,23 +245,53 @@ impl SessionState {
     pub fn remove_client(&mut self, client_id: ClientId) {
         self.clients.remove(&client_id);
         self.pipes.retain(|_p_id, c_id| c_id != &client_id);
+        self.read_only_clients.remove(&client_id);
+        self.remote_clients.remove(&client_id);
+    }
+    pub fn set_client_read_only(&mut self, client_id: ClientId) {
+        self.read_only_clients.insert(client_id);
+    }
+    pub fn client_is_read_only(&self, client_id: ClientId) -> bool {
+        self.read_only_clients.contains(&client_id)
+    }
+    pub fn set_client_remote(&mut self, client_id: ClientId) {
+        self.remote_clients.insert(client_id);
+    }
+    pub fn client_is_remote(&self, client_id: ClientId) -> bool {
+        self.remote_clients.contains(&client_id)
+    }
+    pub fn attached_clients(&self) -> Vec<(ClientId, bool)> {
+        // (client_id, is_read_only) for every client rendering the session, cli clients and pipes
+        // never report a terminal size
+        let mut attached_clients: Vec<(ClientId, bool)> = self
+            .clients
+            .iter()
+            .filter(|(_client_id, size)| size.is_some())
+            .map(|(client_id, _size)| (*client_id, self.client_is_read_only(*client_id)))
+            .collect();
+        attached_clients.sort_unstable();
+        attached_clients
     }
     pub fn set_client_size(&mut self, client_id: ClientId, size: Size) {
         self.clients.insert(client_id, Some(size));
     }
     pub fn min_client_terminal_size(&self) -> Option<Size> {
         // None if there are no client sizes
-        let mut rows: Vec<usize> = self
+        // viewers do not resize the session, unless it has no other clients to take its size from
+        let client_sizes: Vec<Size> = self
             .clients
-            .values()
-            .filter_map(|size| size.map(|size| size.rows))
+            .iter()
+            .filter(|(client_id, _size)| !self.read_only_clients.contains(client_id))
+            .filter_map(|(_client_id, size)| *size)
             .collect();
+        let client_sizes = if client_sizes.is_empty() {
+            self.clients.values().filter_map(|size| *size).collect()
+        } else {
+            client_sizes
+        };
+        let mut rows: Vec<usize> = client_sizes.iter().map(|size| size.rows).collect();
         rows.sort_unstable();
-        let mut cols: Vec<usize> = self
-            .clients
-            .values()
-            .filter_map(|size| size.map(|size| size.cols))
-            .collect();
+        let mut cols: Vec<usize> = client_sizes.iter().map(|size| size.cols).collect();
         cols.sort_unstable();
         let min_rows = rows.first();
         let min_cols = cols.first();
@@ -266,12 +313,140 @@ impl SessionState {
             if ids_of_pipe_clients.contains(client_id) {
                 continue;
             }
+            if self.read_only_clients.contains(client_id) {
+                // viewers do not keep a session alive on their own
+                continue;
+            }
             active_clients_connected = true;
         }
         active_clients_connected
     }
 }
 
+fn spawn_client_router(
+    mut stream: LocalSocketStream,
+    client_id: ClientId,
+    mut os_input: Box<dyn ServerOsApi>,
+    session_data: Arc<RwLock<Option<SessionMetaData>>>,
+    session_state: Arc<RwLock<SessionState>>,
+    to_server: SenderWithContext<ServerInstruction>,
+) {
+    thread::Builder::new()
+        .name("server_router".to_string())
+        .spawn(move || {
+            // the client learns about our protocol during the handshake, so it is able to explain
+            // why it was refused on its own
+            match handshake_with_client(&mut stream) {
+                Ok(client_protocol)
+                    if ProtocolInfo::current().is_compatible_with(&client_protocol) => {},
+                Ok(client_protocol) => {
+                    log::error!(
+                        "Refusing client {} with incompatible protocol: {:?}",
+                        client_id,
+                        client_protocol
+                    );
+                    session_state.write().unwrap().remove_client(client_id);
+                    return;
+                },
+                Err(e) => {
+                    log::error!("Refusing client {}: {:?}", client_id, e);
+                    session_state.write().unwrap().remove_client(client_id);
+                    return;
+                },
+            }
+            let receiver = os_input
+                .new_client(client_id, stream, to_server.clone())
+                .unwrap();
+            route_thread_main(
+                session_data,
+                session_state,
+                os_input,
+                to_server,
+                receiver,
+                client_id,
+            )
+            .fatal()
+        })
+        .unwrap();
+}
+
+fn spawn_remote_listener(
+    address: String,
+    token: String,
+    cert: PathBuf,
+    key: PathBuf,
+    os_input: Box<dyn ServerOsApi>,
+    session_data: Arc<RwLock<Option<SessionMetaData>>>,
+    session_state: Arc<RwLock<SessionState>>,
+    to_server: SenderWithContext<ServerInstruction>,
+) {
+    let _ = thread::Builder::new()
+        .name("server_remote_listener".to_string())
+        .spawn(move || {
+            let bound = remote_attach_server_config(&cert, &key).and_then(|tls_config| {
+                let listener = remote_attach_listen_address(&address).and_then(|address| {
+                    TcpListener::bind(address)
+                        .with_context(|| format!("failed to accept remote clients on {address}"))
+                })?;
+                Ok((listener, tls_config))
+            });
+            let (listener, tls_config) = match bound {
+                Ok(bound) => bound,
+                Err(e) => {
+                    log::error!("{:?}", e);
+                    return;
+                },
+            };
+            log::info!("Accepting remote clients on {}", address);
+            for stream in listener.incoming() {
+                let stream = match stream {
+                    Ok(stream) => stream,
+                    Err(e) => {
+                        log::error!("Failed to accept remote client: {}", e);
+                        continue;
+                    },
+                };
+                let peer_address = stream
+                    .peer_addr()
+                    .map(|a| a.to_string())
+                    .unwrap_or_default();
+                // authenticate in a separate thread so that a slow client cannot block others
+                let token = token.clone();
+                let tls_config = tls_config.clone();
+                let os_input = os_input.clone();
+                let session_data = session_data.clone();
+                let session_state = session_state.clone();
+                let to_server = to_server.clone();
+                let _ = thread::Builder::new()
+                    .name("server_remote_handshake".to_string())
+                    .spawn(
+                        move || match accept_remote_client(stream, tls_config, &token) {
+                            Ok(stream) => {
+                                log::info!("Remote client connected from {}", peer_address);
+                                let client_id = {
+                                    let mut session_state = session_state.write().unwrap();
+                                    let client_id = session_state.new_client();
+                                    session_state.set_client_remote(client_id);
+                                    client_id
+                                };
+                                spawn_client_router(
+                                    stream,
+                                    client_id,
+                                    os_input,
+                                    session_data,
+                                    session_state,
+                                    to_server,
+                                );
+                            },
+                            Err(e) => {
+                                log::error!("Refused remote client from {}: {:?}", peer_address, e);
+                            },
+                        },
+                    );
+            }
+        });
+}
+
 pub fn start_server(mut os_input: Box<dyn ServerOsApi>, socket_path: PathBuf) {
     info!("Starting Zellij server!");
 
@@ -322,26 +497,15 @@ pub fn start_server(mut os_input: Box<dyn ServerOsApi>, socket_path: PathBuf) {
                 for stream in listener.incoming() {
                     match stream {
                         Ok(stream) => {
-                            let mut os_input = os_input.clone();
                             let client_id = session_state.write().unwrap().new_client();
-                            let receiver = os_input.new_client(client_id, stream).unwrap();
-                            let session_data = session_data.clone();
-                            let session_state = session_state.clone();
-                            let to_server = to_server.clone();
-                            thread::Builder::new()
-                                .name("server_router".to_string())
-                                .spawn(move || {
-                                    route_thread_main(
-                                        session_data,
-                                        session_state,
-                                        os_input,
-                                        to_server,
-                                        receiver,
-                                        client_id,
-                                    )
-                                    .fatal()
-                                })
-                                .unwrap();
+                            spawn_client_router(
+                                stream,
+                                client_id,
+                                os_input.clone(),
+                                session_data.clone(),
+                                session_state.clone(),
+                                to_server.clone(),
+                            );
                         },
                         Err(err) => {
                             panic!("err {:?}", err);
@@ -379,6 +543,49 @@ pub fn start_server(mut os_input: Box<dyn ServerOsApi>, socket_path: PathBuf) {
                     .write()
                     .unwrap()
                     .set_client_size(client_id, client_attributes.size);
+                if client_attributes.diff_rendering {
+                    session_data
+                        .read()
+                        .unwrap()
+                        .as_ref()
+                        .unwrap()
+                        .senders
+                        .send_to_screen(ScreenInstruction::EnableDiffRendering(client_id))
+                        .unwrap();
+                }
+
+                match (
+                    config_options.remote_attach_address.clone(),
+                    config_options.remote_attach_token.clone(),
+                    config_options.remote_attach_cert.clone(),
+                    config_options.remote_attach_key.clone(),
+                ) {
+                    (Some(address), Some(token), Some(cert), Some(key)) if !token.is_empty() => {
+                        spawn_remote_listener(
+                            address,
+                            token,
+                            cert,
+                            key,
+                            os_input.clone(),
+                            session_data.clone(),
+                            session_state.clone(),
+                            to_server.clone(),
+                        )
+                    },
+                    (Some(address), Some(token), ..) if !token.is_empty() => {
+                        log::error!(
+                            "Not accepting remote clients on {}: remote_attach_cert and remote_attach_key are not set",
+                            address
+                        );
+                    },
+                    (Some(address), ..) => {
+                        log::error!(
+                            "Not accepting remote clients on {}: remote_attach_token is not set",
+                            address
+                        );
+                    },
+                    _ => {},
+                }
 
                 let default_shell = config_options.default_shell.map(|shell| {
                     TerminalAction::RunCommand(RunCommand {
@@ -467,6 +674,12 @@ pub fn start_server(mut os_input: Box<dyn ServerOsApi>, socket_path: PathBuf) {
                     .write()
                     .unwrap()
                     .set_client_size(client_id, attrs.size);
+                if attrs.read_only {
+                    session_state
+                        .write()
+                        .unwrap()
+                        .set_client_read_only(client_id);
+                }
                 let min_size = session_state
                     .read()
                     .unwrap()
@@ -476,6 +689,12 @@ pub fn start_server(mut os_input: Box<dyn ServerOsApi>, socket_path: PathBuf) {
                     .senders
                     .send_to_screen(ScreenInstruction::TerminalResize(min_size))
                     .unwrap();
+                if attrs.diff_rendering {
+                    session_data
+                        .senders
+                        .send_to_screen(ScreenInstruction::EnableDiffRendering(client_id))
+                        .unwrap();
+                }
                 session_data
                     .senders
                     .send_to_screen(ScreenInstruction::AddClient(
@@ -566,6 +785,14 @@ pub fn start_server(mut os_input: Box<dyn ServerOsApi>, socket_path: PathBuf) {
                     },
                 }
             },
+            ServerInstruction::SubscribedEvent(event, client_id) => {
+                send_to_client!(
+                    client_id,
+                    os_input,
+                    ServerToClientMsg::SubscribedEvent(event),
+                    session_state
+                );
+            },
             ServerInstruction::ClientExit(client_id) => {
                 let _ =
                     os_input.send_to_client(client_id, ServerToClientMsg::Exit(ExitReason::Normal));
@@ -605,8 +832,14 @@ pub fn start_server(mut os_input: Box<dyn ServerOsApi>, socket_path: PathBuf) {
                         .keys()
                         .copied()
                         .collect();
-                    // these are just the pipes
+                    // these are just the pipes and read-only viewers
                     for client_id in client_ids_to_cleanup {
+                        if session_state.read().unwrap().client_is_read_only(client_id) {
+                            let _ = os_input.send_to_client(
+                                client_id,
+                                ServerToClientMsg::Exit(ExitReason::Normal),
+                            );
+                        }
                         remove_client!(client_id, os_input, session_state);
                     }
                     break;
@@ -690,9 +923,8 @@ pub fn start_server(mut os_input: Box<dyn ServerOsApi>, socket_path: PathBuf) {
                 // If `None`- Send an exit instruction. This is the case when a user closes the last Tab/Pane.
                 if let Some(output) = &serialized_output {
                     for (client_id, client_render_instruction) in output.iter() {
-                        // TODO: When a client is too slow or unresponsive, the channel fills up
-                        // and this call will disconnect the client in turn. Should this be
-                        // changed?
+                        // When a client is too slow to keep up, renders are dropped until it
+                        // catches up (see ServerInstruction::ClientMissedRenders)
                         send_to_client!(
                             *client_id,
                             os_input,
@@ -720,6 +952,14 @@ pub fn start_server(mut os_input: Box<dyn ServerOsApi>, socket_path: PathBuf) {
                 }
                 break;
             },
+            ServerInstruction::ClientMissedRenders(client_id) => {
+                if let Some(session_data) = session_data.read().unwrap().as_ref() {
+                    session_data
+                        .senders
+                        .send_to_screen(ScreenInstruction::ClientMissedRenders(client_id))
+                        .unwrap();
+                }
+            },
             ServerInstruction::ConnStatus(client_id) => {
                 let _ = os_input.send_to_client(client_id, ServerToClientMsg::Connected);
                 remove_client!(client_id, os_input, session_state);
@@ -733,6 +973,51 @@ pub fn start_server(mut os_input: Box<dyn ServerOsApi>, socket_path: PathBuf) {
                     session_state
                 );
             },
+            ServerInstruction::ListClients(client_id) => {
+                let client_list = session_state
+                    .read()
+                    .unwrap()
+                    .attached_clients()
+                    .iter()
+                    .map(|(attached_client_id, is_read_only)| {
+                        if *is_read_only {
+                            format!("{} (read-only)", attached_client_id)
+                        } else {
+                            format!("{}", attached_client_id)
+                        }
+                    })
+                    .collect();
+                send_to_client!(
+                    client_id,
+                    os_input,
+                    ServerToClientMsg::Log(client_list),
+                    session_state
+                );
+            },
+            ServerInstruction::KickClient(client_id_to_kick, client_id) => {
+                if session_state
+                    .read()
+                    .unwrap()
+                    .client_is_read_only(client_id_to_kick)
+                {
+                    let _ =
+                        to_server.send(ServerInstruction::DetachSession(vec![client_id_to_kick]));
+                    send_to_client!(
+                        client_id,
+                        os_input,
+                        ServerToClientMsg::UnblockInputThread,
+                        session_state
+                    );
+                } else {
+                    let error = format!("Client {} is not a read-only viewer", client_id_to_kick);
+                    send_to_client!(
+                        client_id,
+                        os_input,
+                        ServerToClientMsg::LogError(vec![error]),
+                        session_state
+                    );
+                }
+            },
             ServerInstruction::Log(lines_to_log, client_id) => {
                 send_to_client!(
                     client_id,
@@ -853,6 +1138,14 @@ fn init_session(
     };
 
     let serialization_interval = config_options.serialization_interval;
+    let layout_dir = config_options
+        .layout_dir
+        .clone()
+        .or_else(|| get_layout_dir(find_default_config_dir()));
+    let session_policies = SessionPolicies::from_options(&config_options);
+    let auto_snapshot_history = config_options
+        .auto_snapshot_history
+        .unwrap_or(DEFAULT_AUTO_SNAPSHOT_HISTORY);
 
     let default_shell = config_options.default_shell.clone().map(|command| {
         TerminalAction::RunCommand(RunCommand {
@@ -882,6 +1175,7 @@ fn init_session(
                 ),
                 opts.debug,
                 config_options.scrollback_editor.clone(),
+                client_attributes.style.show_git_branch,
             );
 
             move || pty_thread_main(pty, layout.clone()).fatal()
@@ -988,7 +1282,16 @@ fn init_session(
                 None,
                 Some(os_input.clone()),
             );
-            move || background_jobs_main(background_jobs_bus, serialization_interval).fatal()
+            move || {
+                background_jobs_main(
+                    background_jobs_bus,
+                    serialization_interval,
+                    auto_snapshot_history,
+                    session_policies,
+                    layout_dir,
+                )
+                .fatal()
+            }
         })
         .unwrap();
 
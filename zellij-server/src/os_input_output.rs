@@ -1,11 +1,11 @@
-use crate::{panes::PaneId, ClientId};
+use crate::{panes::PaneId, ClientId, ServerInstruction};
 
 use async_std::{fs::File as AsyncFile, io::ReadExt, os::unix::io::FromRawFd};
 use interprocess::local_socket::LocalSocketStream;
 use nix::{
     pty::{openpty, OpenptyResult, Winsize},
     sys::{
-        signal::{kill, Signal},
+        signal::{kill, killpg, Signal},
         termios,
     },
     unistd,
@@ -15,8 +15,8 @@ use signal_hook::consts::*;
 use sysinfo::{ProcessExt, ProcessRefreshKind, System, SystemExt};
 use zellij_utils::{
     async_std, channels,
-    channels::TrySendError,
-    data::Palette,
+    channels::{SenderWithContext, TrySendError},
+    data::{Palette, PaneProcessInfo, PaneSignal},
     errors::prelude::*,
     input::command::{RunCommand, TerminalAction},
     interprocess,
@@ -36,9 +36,12 @@ use std::{
     fs::File,
     io::Write,
     os::unix::{io::RawFd, process::CommandExt},
-    path::PathBuf,
+    path::{Path, PathBuf},
     process::{Child, Command},
-    sync::{Arc, Mutex},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
 };
 
 pub use async_trait::async_trait;
@@ -170,6 +173,7 @@ fn handle_openpty(
     if command_exists(&cmd) {
         let mut child = unsafe {
             let cmd = cmd.clone();
+            let env_vars = cmd.env_vars();
             let command = &mut Command::new(cmd.command);
             if let Some(current_dir) = cmd.cwd {
                 if current_dir.exists() && current_dir.is_dir() {
@@ -183,6 +187,7 @@ fn handle_openpty(
             }
             command
                 .args(&cmd.args)
+                .envs(env_vars)
                 .env("ZELLIJ_PANE_ID", &format!("{}", terminal_id))
                 .pre_exec(move || -> std::io::Result<()> {
                     if libc::login_tty(pid_secondary) != 0 {
@@ -337,6 +342,7 @@ fn spawn_terminal(
                 cwd,
                 hold_on_close: false,
                 hold_on_start: false,
+                ..Default::default()
             }
         },
         TerminalAction::RunCommand(command) => command,
@@ -357,18 +363,25 @@ fn spawn_terminal(
 // thread
 // When the above happens, the ClientSender buffers messages in hopes that the congestion will be
 // freed until we runs out of buffer space.
-// If we run out of buffer space, we bubble up an error sot hat the router thread will give up on
-// this client and we'll stop sending messages to it.
+// If we run out of buffer space with a render, we drop it and render everything for this client
+// again once it has caught up, as renders only ever contain what changed since the previous one.
+// If we run out of buffer space with any other message, we bubble up an error sot hat the router
+// thread will give up on this client and we'll stop sending messages to it.
 // If the client ever becomes responsive again, we'll send one final "Buffer full" message so it
 // knows what happened.
 #[derive(Clone)]
 struct ClientSender {
     client_id: ClientId,
     client_buffer_sender: channels::Sender<ServerToClientMsg>,
+    missed_renders: Arc<AtomicBool>,
 }
 
 impl ClientSender {
-    pub fn new(client_id: ClientId, mut sender: IpcSenderWithContext<ServerToClientMsg>) -> Self {
+    pub fn new(
+        client_id: ClientId,
+        mut sender: IpcSenderWithContext<ServerToClientMsg>,
+        to_server: SenderWithContext<ServerInstruction>,
+    ) -> Self {
         // FIXME(hartan): This queue is responsible for buffering messages between server and
         // client. If it fills up, the client is disconnected with a "Buffer full" sort of error
         // message. It was previously found to be too small (with depth 50), so it was increased to
@@ -380,17 +393,28 @@ impl ClientSender {
         // queue for the time being because we want to prevent e.g. the whole session being killed
         // (by OOM-killers or some other mechanism) just because a single client doesn't respond.
         let (client_buffer_sender, client_buffer_receiver) = channels::bounded(5000);
-        std::thread::spawn(move || {
-            let err_context = || format!("failed to send message to client {client_id}");
-            for msg in client_buffer_receiver.iter() {
-                sender.send(msg).with_context(err_context).non_fatal();
+        let missed_renders = Arc::new(AtomicBool::new(false));
+        std::thread::spawn({
+            let missed_renders = missed_renders.clone();
+            move || {
+                let err_context = || format!("failed to send message to client {client_id}");
+                for msg in client_buffer_receiver.iter() {
+                    sender.send(msg).with_context(err_context).non_fatal();
+                    if client_buffer_receiver.is_empty()
+                        && missed_renders.swap(false, Ordering::SeqCst)
+                    {
+                        log::info!("client {} caught up, rendering everything again", client_id);
+                        let _ = to_server.send(ServerInstruction::ClientMissedRenders(client_id));
+                    }
+                }
+                // If we're here, the message buffer is broken for some reason
+                let _ = sender.send(ServerToClientMsg::Exit(ExitReason::Disconnect));
             }
-            // If we're here, the message buffer is broken for some reason
-            let _ = sender.send(ServerToClientMsg::Exit(ExitReason::Disconnect));
         });
         ClientSender {
             client_id,
             client_buffer_sender,
+            missed_renders,
         }
     }
     pub fn send_or_buffer(&self, msg: ServerToClientMsg) -> Result<()> {
@@ -401,16 +425,27 @@ impl ClientSender {
             )
         };
 
-        self.client_buffer_sender
-            .try_send(msg)
-            .or_else(|err| {
+        let result = match self.client_buffer_sender.try_send(msg) {
+            Err(TrySendError::Full(ServerToClientMsg::Render(_))) => {
+                if !self.missed_renders.swap(true, Ordering::SeqCst) {
+                    log::warn!(
+                        "client {} is processing server messages too slow, skipping renders until it catches up",
+                        self.client_id
+                    );
+                }
+                return Ok(());
+            },
+            result => result,
+        };
+        result
+            .map_err(|err| {
                 if let TrySendError::Full(_) = err {
                     log::warn!(
                         "client {} is processing server messages too slow",
                         self.client_id
                     );
                 }
-                Err(err)
+                err
             })
             .with_context(err_context)
     }
@@ -426,6 +461,7 @@ pub struct ServerOsInputOutput {
     // a command pane with a
     // non-existing command)
     cached_resizes: Arc<Mutex<Option<BTreeMap<u32, (u16, u16, Option<u16>, Option<u16>)>>>>, // <terminal_id, (cols, rows, width_in_pixels, height_in_pixels)>
+    process_sampler: Arc<Mutex<System>>,
 }
 
 // async fn in traits is not supported by rust, so dtolnay's excellent async_trait macro is being
@@ -492,13 +528,24 @@ pub trait ServerOsApi: Send + Sync {
     fn kill(&self, pid: Pid) -> Result<()>;
     /// Terminate the process with process ID `pid`. (SIGKILL)
     fn force_kill(&self, pid: Pid) -> Result<()>;
+    /// Send `signal` to the foreground process group of the terminal with id `terminal_id`,
+    /// falling back to the process group of its child process `pid`
+    fn signal_terminal(&self, terminal_id: u32, _pid: Pid, _signal: PaneSignal) -> Result<()> {
+        Err(anyhow!(
+            "cannot signal terminal {}, this os api does not support signals",
+            terminal_id
+        ))
+    }
     /// Returns a [`Box`] pointer to this [`ServerOsApi`] struct.
     fn box_clone(&self) -> Box<dyn ServerOsApi>;
     fn send_to_client(&self, client_id: ClientId, msg: ServerToClientMsg) -> Result<()>;
+    /// Returns the receiving end of a newly connected client, `to_server` is used to render
+    /// everything for it again if it misses renders because it is too slow to process them
     fn new_client(
         &mut self,
         client_id: ClientId,
         stream: LocalSocketStream,
+        to_server: SenderWithContext<ServerInstruction>,
     ) -> Result<IpcReceiverWithContext<ClientToServerMsg>>;
     fn remove_client(&mut self, client_id: ClientId) -> Result<()>;
     fn load_palette(&self) -> Palette;
@@ -512,6 +559,16 @@ pub trait ServerOsApi: Send + Sync {
     fn get_all_cmds_by_ppid(&self) -> HashMap<String, Vec<String>> {
         HashMap::new()
     }
+    /// Samples the process tree of each terminal (given as its id and the pid of its child
+    /// process), returning the resources it uses and its foreground process - as well as the git
+    /// branch of its cwd if `with_git_branch` is true
+    fn get_pane_process_infos(
+        &self,
+        _terminals: Vec<(u32, Pid)>,
+        _with_git_branch: bool,
+    ) -> HashMap<u32, PaneProcessInfo> {
+        HashMap::new()
+    }
     /// Writes the given buffer to a string
     fn write_to_file(&mut self, buf: String, file: Option<String>) -> Result<()>;
 
@@ -689,6 +746,32 @@ impl ServerOsApi for ServerOsInputOutput {
         let _ = kill(pid, Some(Signal::SIGKILL));
         Ok(())
     }
+    fn signal_terminal(&self, terminal_id: u32, pid: Pid, signal: PaneSignal) -> Result<()> {
+        let err_context = || format!("failed to send {} to terminal {}", signal, terminal_id);
+
+        let signal = match signal {
+            PaneSignal::Interrupt => Signal::SIGINT,
+            PaneSignal::Terminate => Signal::SIGTERM,
+            PaneSignal::Kill => Signal::SIGKILL,
+            PaneSignal::Hangup => Signal::SIGHUP,
+            PaneSignal::Stop => Signal::SIGSTOP,
+            PaneSignal::Continue => Signal::SIGCONT,
+            PaneSignal::Other(signal_number) => {
+                Signal::try_from(signal_number).with_context(err_context)?
+            },
+        };
+        // the foreground process group is the one the user is interacting with (eg. a build
+        // started from a shell), rather than the shell itself
+        let foreground_process_group = self
+            .terminal_id_to_raw_fd
+            .lock()
+            .to_anyhow()
+            .with_context(err_context)?
+            .get(&terminal_id)
+            .and_then(|fd| *fd)
+            .and_then(|fd| unistd::tcgetpgrp(fd).ok());
+        killpg(foreground_process_group.unwrap_or(pid), signal).with_context(err_context)
+    }
     fn send_to_client(&self, client_id: ClientId, msg: ServerToClientMsg) -> Result<()> {
         let err_context = || format!("failed to send message to client {client_id}");
 
@@ -709,9 +792,10 @@ impl ServerOsApi for ServerOsInputOutput {
         &mut self,
         client_id: ClientId,
         stream: LocalSocketStream,
+        to_server: SenderWithContext<ServerInstruction>,
     ) -> Result<IpcReceiverWithContext<ClientToServerMsg>> {
         let receiver = IpcReceiverWithContext::new(stream);
-        let sender = ClientSender::new(client_id, receiver.get_sender());
+        let sender = ClientSender::new(client_id, receiver.get_sender(), to_server);
         self.client_senders
             .lock()
             .to_anyhow()
@@ -799,6 +883,82 @@ impl ServerOsApi for ServerOsInputOutput {
         }
         cmds
     }
+    fn get_pane_process_infos(
+        &self,
+        terminals: Vec<(u32, Pid)>,
+        with_git_branch: bool,
+    ) -> HashMap<u32, PaneProcessInfo> {
+        let mut process_infos = HashMap::new();
+        let mut system_info = match self.process_sampler.lock() {
+            Ok(system_info) => system_info,
+            Err(e) => {
+                log::error!("Failed to sample pane processes: {}", e);
+                return process_infos;
+            },
+        };
+        // the cpu usage of a process is measured between two refreshes, which is why the same
+        // System is kept around between samples
+        system_info.refresh_processes_specifics(ProcessRefreshKind::new().with_cpu());
+        let mut children_by_ppid: HashMap<sysinfo::Pid, Vec<sysinfo::Pid>> = HashMap::new();
+        for (pid, process) in system_info.processes() {
+            if let Some(ppid) = process.parent() {
+                children_by_ppid.entry(ppid).or_default().push(*pid);
+            }
+        }
+        for children in children_by_ppid.values_mut() {
+            children.sort();
+        }
+        let terminal_id_to_raw_fd = self
+            .terminal_id_to_raw_fd
+            .lock()
+            .map(|terminal_id_to_raw_fd| terminal_id_to_raw_fd.clone())
+            .unwrap_or_default();
+        // the sampler only reads the cwd of a process when it first sees it, so we get them
+        // separately to follow the user changing directories
+        let cwds = if with_git_branch {
+            self.get_cwds(terminals.iter().map(|(_terminal_id, pid)| *pid).collect())
+        } else {
+            HashMap::new()
+        };
+
+        for (terminal_id, pid) in terminals {
+            let root_pid: sysinfo::Pid = pid.into();
+            if system_info.process(root_pid).is_none() {
+                continue;
+            }
+            let mut process_info = PaneProcessInfo::default();
+            let mut cpu_usage = 0.0;
+            let mut pids_to_visit = vec![root_pid];
+            while let Some(pid) = pids_to_visit.pop() {
+                if let Some(process) = system_info.process(pid) {
+                    cpu_usage += process.cpu_usage();
+                    process_info.memory_usage += process.memory() * 1024; // sysinfo reports KB
+                    if pid != root_pid && !process.cmd().is_empty() {
+                        process_info.child_commands.push(process.cmd().join(" "));
+                    }
+                }
+                if let Some(children) = children_by_ppid.get(&pid) {
+                    pids_to_visit.extend(children.iter().rev());
+                }
+            }
+            process_info.cpu_usage = cpu_usage.round() as u32;
+            // the foreground process group is led by whatever the user is currently running
+            // in the terminal, or by the shell itself if it is waiting for input
+            let foreground_pid: sysinfo::Pid = terminal_id_to_raw_fd
+                .get(&terminal_id)
+                .and_then(|fd| *fd)
+                .and_then(|fd| unistd::tcgetpgrp(fd).ok())
+                .map(|pgid| pgid.into())
+                .unwrap_or(root_pid);
+            process_info.foreground_process = system_info
+                .process(foreground_pid)
+                .or_else(|| system_info.process(root_pid))
+                .map(|process| process.name().to_owned());
+            process_info.git_branch = cwds.get(&pid).and_then(|cwd| git_branch(cwd));
+            process_infos.insert(terminal_id, process_info);
+        }
+        process_infos
+    }
 
     fn write_to_file(&mut self, buf: String, name: Option<String>) -> Result<()> {
         let err_context = || "failed to write to file".to_string();
@@ -883,6 +1043,7 @@ pub fn get_server_os_input() -> Result<ServerOsInputOutput, nix::Error> {
         client_senders: Arc::new(Mutex::new(HashMap::new())),
         terminal_id_to_raw_fd: Arc::new(Mutex::new(BTreeMap::new())),
         cached_resizes: Arc::new(Mutex::new(None)),
+        process_sampler: Arc::new(Mutex::new(System::new())),
     })
 }
 
@@ -924,6 +1085,34 @@ pub struct ChildId {
     pub shell: Option<Pid>,
 }
 
+/// The current branch of the git repository this folder is in, or the short hash of the checked
+/// out commit if the HEAD is detached
+fn git_branch(cwd: &Path) -> Option<String> {
+    let dot_git = cwd
+        .ancestors()
+        .map(|a| a.join(".git"))
+        .find(|a| a.exists())?;
+    let git_dir = if dot_git.is_dir() {
+        dot_git
+    } else {
+        // worktrees and submodules have a .git file pointing to their git dir
+        let dot_git_file = std::fs::read_to_string(&dot_git).ok()?;
+        let git_dir = PathBuf::from(dot_git_file.trim().strip_prefix("gitdir: ")?);
+        dot_git.parent()?.join(git_dir)
+    };
+    let head = std::fs::read_to_string(git_dir.join("HEAD")).ok()?;
+    let head = head.trim();
+    match head.strip_prefix("ref: ") {
+        Some(reference) => Some(
+            reference
+                .strip_prefix("refs/heads/")
+                .unwrap_or(reference)
+                .to_owned(),
+        ),
+        None => Some(head.chars().take(7).collect()),
+    }
+}
+
 #[cfg(test)]
 #[path = "./unit/os_input_output_tests.rs"]
 mod os_input_output_tests;
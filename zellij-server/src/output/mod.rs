@@ -5,7 +5,7 @@ use crate::panes::Row;
 
 use crate::{
     panes::sixel::SixelImageStore,
-    panes::terminal_character::{AnsiCode, CharacterStyles},
+    panes::terminal_character::{AnsiCode, CharacterStyles, RESET_STYLES},
     panes::{LinkHandler, TerminalCharacter, EMPTY_TERMINAL_CHARACTER},
     ClientId,
 };
@@ -250,6 +250,8 @@ pub struct Output {
     character_cell_size: Rc<RefCell<Option<SizeInPixels>>>,
     floating_panes_stack: Option<FloatingPanesStack>,
     styled_underlines: bool,
+    rendered_frames: Option<Rc<RefCell<HashMap<ClientId, RenderedFrame>>>>,
+    cells_drawn_by_vte_instructions: HashMap<ClientId, Vec<(usize, usize)>>, // (x, y)
 }
 
 impl Output {
@@ -277,6 +279,22 @@ impl Output {
             self.client_character_chunks.insert(*client_id, vec![]);
         }
     }
+    /// Only serialize the cells that changed since the last render for the clients that have a
+    /// [`RenderedFrame`]
+    pub fn diff_against_rendered_frames(
+        &mut self,
+        rendered_frames: Rc<RefCell<HashMap<ClientId, RenderedFrame>>>,
+    ) {
+        self.rendered_frames = Some(rendered_frames);
+    }
+    /// A vte instruction drew this cell outside of the character chunks (eg. a fake cursor), so it
+    /// has to be sent again the next time it is rendered
+    pub fn add_cell_drawn_by_vte_instruction(&mut self, client_id: ClientId, x: usize, y: usize) {
+        self.cells_drawn_by_vte_instructions
+            .entry(client_id)
+            .or_insert_with(Vec::new)
+            .push((x, y));
+    }
     pub fn add_character_chunks_to_client(
         &mut self,
         client_id: ClientId,
@@ -404,19 +422,51 @@ impl Output {
         let err_context = || "failed to serialize output to clients".to_string();
 
         let mut serialized_render_instructions = HashMap::new();
+        let rendered_frames = self.rendered_frames.clone();
+        let mut rendered_frames = rendered_frames
+            .as_ref()
+            .map(|rendered_frames| rendered_frames.borrow_mut());
 
         for (client_id, client_character_chunks) in self.client_character_chunks.drain() {
             let mut client_serialized_render_instructions = String::new();
+            let mut clears_display = false;
 
             // append pre-vte instructions for this client
             if let Some(pre_vte_instructions_for_client) =
                 self.pre_vte_instructions.remove(&client_id)
             {
                 for vte_instruction in pre_vte_instructions_for_client {
+                    clears_display = clears_display || vte_instruction.contains("\u{1b}[2J");
                     client_serialized_render_instructions.push_str(&vte_instruction);
                 }
             }
 
+            let client_character_chunks = match rendered_frames
+                .as_mut()
+                .and_then(|rendered_frames| rendered_frames.get_mut(&client_id))
+            {
+                Some(rendered_frame) => {
+                    if clears_display {
+                        rendered_frame.clear();
+                    }
+                    let changed_chunks = rendered_frame.changed_chunks(client_character_chunks);
+                    if let Some(cells) = self.cells_drawn_by_vte_instructions.remove(&client_id) {
+                        rendered_frame.forget_cells(cells.into_iter());
+                    }
+                    if self
+                        .sixel_chunks
+                        .get(&client_id)
+                        .map(|sixel_chunks| !sixel_chunks.is_empty())
+                        .unwrap_or(false)
+                    {
+                        // images are drawn over the cells without us knowing which ones
+                        rendered_frame.clear();
+                    }
+                    changed_chunks
+                },
+                None => client_character_chunks,
+            };
+
             // append the actual vte
             client_serialized_render_instructions.push_str(
                 &serialize_chunks(
@@ -455,6 +505,118 @@ impl Output {
     }
 }
 
+// a wide character also takes up the cells to its right, which hold this in a RenderedFrame
+const WIDE_CHARACTER_CONTINUATION: TerminalCharacter = TerminalCharacter {
+    character: ' ',
+    width: 0,
+    styles: RESET_STYLES,
+};
+
+/// The cells last sent to a client, so that only the ones that changed since are sent to it again
+/// (see the `diff_rendering` option)
+#[derive(Clone, Debug, Default)]
+pub struct RenderedFrame {
+    cells: HashMap<(usize, usize), TerminalCharacter>, // (x, y)
+}
+
+impl RenderedFrame {
+    /// Forget everything that was sent, eg. because the client's display was cleared
+    pub fn clear(&mut self) {
+        self.cells.clear();
+    }
+    /// Forget these cells, so that they are sent again the next time they are rendered even if
+    /// they did not change
+    pub fn forget_cells(&mut self, cells: impl Iterator<Item = (usize, usize)>) {
+        for (x, y) in cells {
+            self.forget_cell(x, y);
+        }
+    }
+    /// Returns only the parts of these chunks that changed since they were last sent, remembering
+    /// them as sent
+    pub fn changed_chunks(&mut self, character_chunks: Vec<CharacterChunk>) -> Vec<CharacterChunk> {
+        let mut changed_chunks = vec![];
+        for character_chunk in character_chunks {
+            let y = character_chunk.y;
+            if character_chunk.changed_colors.is_some() {
+                // these are drawn with a different palette, so we cannot tell whether they changed
+                let mut x = character_chunk.x;
+                for t_character in &character_chunk.terminal_characters {
+                    self.forget_cell(x, y);
+                    x += t_character.width;
+                }
+                changed_chunks.push(character_chunk);
+                continue;
+            }
+            let selection_and_colors = character_chunk.selection_and_colors();
+            let mut changed_part: Option<CharacterChunk> = None;
+            let mut x = character_chunk.x;
+            for t_character in &character_chunk.terminal_characters {
+                let mut rendered_character = *t_character;
+                rendered_character.styles = adjust_styles_for_possible_selection(
+                    selection_and_colors.clone(),
+                    t_character.styles,
+                    y,
+                    x,
+                );
+                if self.was_rendered(x, y, &rendered_character) {
+                    if let Some(changed_part) = changed_part.take() {
+                        changed_chunks.push(changed_part);
+                    }
+                } else {
+                    self.remember(x, y, rendered_character);
+                    changed_part
+                        .get_or_insert_with(|| CharacterChunk {
+                            terminal_characters: vec![],
+                            x,
+                            y,
+                            changed_colors: None,
+                            selection_and_colors: selection_and_colors.clone(),
+                        })
+                        .terminal_characters
+                        .push(*t_character);
+                }
+                x += t_character.width;
+            }
+            if let Some(changed_part) = changed_part.take() {
+                changed_chunks.push(changed_part);
+            }
+        }
+        changed_chunks
+    }
+    fn was_rendered(&self, x: usize, y: usize, rendered_character: &TerminalCharacter) -> bool {
+        self.cells.get(&(x, y)) == Some(rendered_character)
+            && (1..rendered_character.width).all(|offset| {
+                self.cells.get(&(x + offset, y)) == Some(&WIDE_CHARACTER_CONTINUATION)
+            })
+    }
+    fn remember(&mut self, x: usize, y: usize, rendered_character: TerminalCharacter) {
+        // drawing over part of a wide character erases all of it
+        for offset in 0..rendered_character.width.max(1) {
+            self.forget_cell(x + offset, y);
+        }
+        self.cells.insert((x, y), rendered_character);
+        for offset in 1..rendered_character.width {
+            self.cells
+                .insert((x + offset, y), WIDE_CHARACTER_CONTINUATION);
+        }
+    }
+    fn forget_cell(&mut self, x: usize, y: usize) {
+        // forget the whole character occupying this cell
+        let mut start_x = x;
+        while start_x > 0 && self.cells.get(&(start_x, y)) == Some(&WIDE_CHARACTER_CONTINUATION) {
+            start_x -= 1;
+        }
+        let width = self
+            .cells
+            .get(&(start_x, y))
+            .map(|t_character| t_character.width.max(1))
+            .unwrap_or(1);
+        for x in start_x..(start_x + width).max(x + 1) {
+            self.cells.remove(&(x, y));
+        }
+    }
+}
+
 // this struct represents the geometry of a group of floating panes
 // we use it to filter out CharacterChunks who are behind these geometries
 // and so would not be visible. If a chunk is partially covered, it is adjusted
@@ -1031,3 +1193,7 @@ impl OutputBuffer {
         changed_rects
     }
 }
+
+#[cfg(test)]
+#[path = "./unit/output_tests.rs"]
+mod output_tests;
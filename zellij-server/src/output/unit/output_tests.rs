@@ -0,0 +1,87 @@
+use super::{CharacterChunk, RenderedFrame};
+use crate::panes::TerminalCharacter;
+
+fn chunk(text: &str, x: usize, y: usize) -> CharacterChunk {
+    CharacterChunk::new(text.chars().map(TerminalCharacter::new).collect(), x, y)
+}
+
+fn chunks_to_strings(chunks: Vec<CharacterChunk>) -> Vec<(String, usize, usize)> {
+    chunks
+        .into_iter()
+        .map(|chunk| {
+            let text = chunk
+                .terminal_characters
+                .iter()
+                .map(|t_character| t_character.character)
+                .collect();
+            (text, chunk.x, chunk.y)
+        })
+        .collect()
+}
+
+#[test]
+fn rendered_frame_sends_everything_the_first_time() {
+    let mut rendered_frame = RenderedFrame::default();
+    let changed_chunks =
+        rendered_frame.changed_chunks(vec![chunk("hello", 0, 0), chunk("world", 2, 1)]);
+    assert_eq!(
+        chunks_to_strings(changed_chunks),
+        vec![("hello".to_owned(), 0, 0), ("world".to_owned(), 2, 1)]
+    );
+}
+
+#[test]
+fn rendered_frame_only_sends_changed_cells() {
+    let mut rendered_frame = RenderedFrame::default();
+    rendered_frame.changed_chunks(vec![chunk("hello world", 0, 0)]);
+    let changed_chunks = rendered_frame.changed_chunks(vec![chunk("hallo wirld", 0, 0)]);
+    assert_eq!(
+        chunks_to_strings(changed_chunks),
+        vec![("a".to_owned(), 1, 0), ("i".to_owned(), 7, 0)]
+    );
+}
+
+#[test]
+fn rendered_frame_sends_nothing_if_nothing_changed() {
+    let mut rendered_frame = RenderedFrame::default();
+    rendered_frame.changed_chunks(vec![chunk("hello", 0, 0)]);
+    let changed_chunks = rendered_frame.changed_chunks(vec![chunk("hello", 0, 0)]);
+    assert!(changed_chunks.is_empty());
+}
+
+#[test]
+fn rendered_frame_sends_forgotten_cells_again() {
+    let mut rendered_frame = RenderedFrame::default();
+    rendered_frame.changed_chunks(vec![chunk("hello", 0, 0)]);
+    rendered_frame.forget_cells(vec![(1, 0), (2, 0)].into_iter());
+    let changed_chunks = rendered_frame.changed_chunks(vec![chunk("hello", 0, 0)]);
+    assert_eq!(
+        chunks_to_strings(changed_chunks),
+        vec![("el".to_owned(), 1, 0)]
+    );
+}
+
+#[test]
+fn rendered_frame_sends_everything_again_after_clear() {
+    let mut rendered_frame = RenderedFrame::default();
+    rendered_frame.changed_chunks(vec![chunk("hello", 0, 0)]);
+    rendered_frame.clear();
+    let changed_chunks = rendered_frame.changed_chunks(vec![chunk("hello", 0, 0)]);
+    assert_eq!(
+        chunks_to_strings(changed_chunks),
+        vec![("hello".to_owned(), 0, 0)]
+    );
+}
+
+#[test]
+fn rendered_frame_sends_wide_character_again_when_partly_overwritten() {
+    let mut rendered_frame = RenderedFrame::default();
+    rendered_frame.changed_chunks(vec![chunk("a世b", 0, 0)]);
+    // the narrow character drawn over the second half of the wide one erases all of it
+    rendered_frame.changed_chunks(vec![chunk("x", 2, 0)]);
+    let changed_chunks = rendered_frame.changed_chunks(vec![chunk("a世b", 0, 0)]);
+    assert_eq!(
+        chunks_to_strings(changed_chunks),
+        vec![("世".to_owned(), 1, 0)]
+    );
+}
@@ -38,7 +38,7 @@ use crate::panes::terminal_character::{
     AnsiCode, CharacterStyles, CharsetIndex, Cursor, CursorShape, StandardCharset,
     TerminalCharacter, EMPTY_TERMINAL_CHARACTER,
 };
-use crate::ui::components::UiComponentParser;
+use crate::ui::components::{UiComponentParser, UiComponentRegion};
 
 fn get_top_non_canonical_rows(rows: &mut Vec<Row>) -> Vec<Row> {
     let mut index_of_last_non_canonical_row = None;
@@ -359,6 +359,7 @@ pub struct Grid {
     pub search_results: SearchResult,
     pub pending_clipboard_update: Option<String>,
     ui_component_bytes: Option<Vec<u8>>,
+    ui_component_regions: Vec<UiComponentRegion>,
     style: Style,
     debug: bool,
     arrow_fonts: bool,
@@ -504,6 +505,7 @@ impl Grid {
             sixel_grid,
             pending_clipboard_update: None,
             ui_component_bytes: None,
+            ui_component_regions: vec![],
             style,
             debug,
             arrow_fonts,
@@ -1132,6 +1134,25 @@ impl Grid {
             Some((self.cursor.x, self.cursor.y))
         }
     }
+    /// The cursor position even if it is hidden (eg. in plugin panes)
+    pub fn cursor_position(&self) -> (usize, usize) {
+        (self.cursor.x, self.cursor.y)
+    }
+    pub fn add_ui_component_region(&mut self, ui_component_region: UiComponentRegion) {
+        self.ui_component_regions.push(ui_component_region);
+    }
+    pub fn clear_ui_component_regions(&mut self) {
+        self.ui_component_regions.clear();
+    }
+    /// The id of the UI component at these viewport coordinates, along with the row and col
+    /// relative to it
+    pub fn ui_component_at(&self, x: usize, y: usize) -> Option<(String, usize, usize)> {
+        // components rendered last are drawn over the ones before them
+        self.ui_component_regions
+            .iter()
+            .rev()
+            .find_map(|region| region.relative_position(x, y))
+    }
     /// Clears all buffers with text for a current screen
     pub fn clear_screen(&mut self) {
         if self.alternate_screen_state.is_some() {
@@ -79,6 +79,8 @@ pub(crate) struct PluginPane {
     character_cell_size: Rc<RefCell<Option<SizeInPixels>>>,
     vte_parsers: HashMap<ClientId, vte::Parser>,
     grids: HashMap<ClientId, Grid>,
+    hovered_components: HashMap<ClientId, String>, // id of the UI component each client's mouse
+    // is over
     prev_pane_name: String,
     frame: HashMap<ClientId, PaneFrame>,
     borderless: bool,
@@ -116,6 +118,7 @@ impl PluginPane {
         let mut plugin = PluginPane {
             pid,
             should_render: HashMap::new(),
+            hovered_components: HashMap::new(),
             selectable: true,
             geom: position_and_size,
             geom_override: None,
@@ -217,6 +220,7 @@ impl Pane for PluginPane {
         grid.delete_viewport_and_scroll();
         grid.reset_cursor_position();
         grid.render_full_viewport();
+        grid.clear_ui_component_regions();
 
         let vte_parser = self
             .vte_parsers
@@ -500,23 +504,58 @@ impl Pane for PluginPane {
         // noop
     }
     fn start_selection(&mut self, start: &Position, client_id: ClientId) {
-        self.send_plugin_instructions
-            .send(PluginInstruction::Update(vec![(
+        let mut events = vec![(
+            Some(self.pid),
+            Some(client_id),
+            Event::Mouse(Mouse::LeftClick(start.line.0, start.column.0)),
+        )];
+        if let Some((id, row, col)) = self.ui_component_at(start, client_id) {
+            events.push((
                 Some(self.pid),
                 Some(client_id),
-                Event::Mouse(Mouse::LeftClick(start.line.0, start.column.0)),
-            )]))
+                Event::ComponentClicked { id, row, col },
+            ));
+        }
+        self.send_plugin_instructions
+            .send(PluginInstruction::Update(events))
             .unwrap();
     }
     fn update_selection(&mut self, position: &Position, client_id: ClientId) {
-        self.send_plugin_instructions
-            .send(PluginInstruction::Update(vec![(
+        let mut events = vec![(
+            Some(self.pid),
+            Some(client_id),
+            Event::Mouse(Mouse::Hold(position.line.0, position.column.0)),
+        )];
+        if let Some((id, row, col)) = self.ui_component_at(position, client_id) {
+            events.push((
                 Some(self.pid),
                 Some(client_id),
-                Event::Mouse(Mouse::Hold(position.line.0, position.column.0)),
-            )]))
+                Event::ComponentHovered { id, row, col },
+            ));
+        }
+        self.send_plugin_instructions
+            .send(PluginInstruction::Update(events))
             .unwrap();
     }
+    fn mouse_motion(&mut self, position: &Position, client_id: ClientId) {
+        match self.ui_component_at(position, client_id) {
+            // the plugin is only told when the mouse moves onto a different component
+            Some((id, row, col)) if self.hovered_components.get(&client_id) != Some(&id) => {
+                self.hovered_components.insert(client_id, id.clone());
+                self.send_plugin_instructions
+                    .send(PluginInstruction::Update(vec![(
+                        Some(self.pid),
+                        Some(client_id),
+                        Event::ComponentHovered { id, row, col },
+                    )]))
+                    .unwrap();
+            },
+            Some(_) => {},
+            None => {
+                self.hovered_components.remove(&client_id);
+            },
+        }
+    }
     fn end_selection(&mut self, end: &Position, client_id: ClientId) {
         self.send_plugin_instructions
             .send(PluginInstruction::Update(vec![(
@@ -577,6 +616,15 @@ impl Pane for PluginPane {
             )]))
             .unwrap();
     }
+    fn handle_middle_click(&mut self, to: &Position, client_id: ClientId) {
+        self.send_plugin_instructions
+            .send(PluginInstruction::Update(vec![(
+                Some(self.pid),
+                Some(client_id),
+                Event::Mouse(Mouse::MiddleClick(to.line.0, to.column.0)),
+            )]))
+            .unwrap();
+    }
     fn add_red_pane_frame_color_override(&mut self, error_text: Option<String>) {
         self.pane_frame_color_override = Some((self.style.colors.red, error_text));
     }
@@ -650,6 +698,16 @@ impl PluginPane {
     fn set_client_should_render(&mut self, client_id: ClientId, should_render: bool) {
         self.should_render.insert(client_id, should_render);
     }
+    fn ui_component_at(
+        &self,
+        position: &Position,
+        client_id: ClientId,
+    ) -> Option<(String, usize, usize)> {
+        let line = usize::try_from(position.line.0).ok()?;
+        self.grids
+            .get(&client_id)
+            .and_then(|grid| grid.ui_component_at(position.column.0, line))
+    }
     fn handle_plugin_bytes_for_all_clients(&mut self, bytes: VteBytes) {
         let client_ids: Vec<ClientId> = self.grids.keys().copied().collect();
         for client_id in client_ids {
@@ -16,7 +16,7 @@ use std::time::{self, Instant};
 use zellij_utils::input::command::RunCommand;
 use zellij_utils::pane_size::Offset;
 use zellij_utils::{
-    data::{InputMode, Palette, PaletteColor, PaneId as ZellijUtilsPaneId, Style},
+    data::{InputMode, Palette, PaletteColor, PaneId as ZellijUtilsPaneId, PaneProcessInfo, Style},
     errors::prelude::*,
     input::layout::Run,
     pane_size::PaneGeom,
@@ -125,6 +125,10 @@ pub struct TerminalPane {
     // held on startup and can possibly be used to display some errors
     pane_frame_color_override: Option<(PaletteColor, Option<String>)>,
     invoked_with: Option<Run>,
+    restarts: Option<(usize, Option<i32>)>, // (restart count, last exit status) for panes whose
+    // command was restarted by its restart policy
+    blocked_on: Option<String>, // the pane this held pane is waiting for before it can be started
+    process_info: Option<PaneProcessInfo>, // the last sample of the processes running in this pane
     #[allow(dead_code)]
     arrow_fonts: bool,
 }
@@ -197,18 +201,14 @@ impl Pane for TerminalPane {
         // we send back the original input
         if let Some((_exit_status, _is_first_run, run_command)) = &self.is_held {
             match input_bytes.as_slice() {
-                ENTER_CARRIAGE_RETURN | ENTER_NEWLINE | SPACE => {
-                    let run_command = run_command.clone();
-                    self.is_held = None;
-                    self.grid.reset_terminal_state();
-                    self.set_should_render(true);
-                    self.remove_banner();
-                    Some(AdjustedInput::ReRunCommandInThisPane(run_command))
-                },
+                ENTER_CARRIAGE_RETURN | ENTER_NEWLINE | SPACE => self
+                    .run_held_command()
+                    .map(AdjustedInput::ReRunCommandInThisPane),
                 ESC => {
                     // Drop to shell in the same working directory as the command was run
                     let working_dir = run_command.cwd.clone();
                     self.is_held = None;
+                    self.blocked_on = None;
                     self.grid.reset_terminal_state();
                     self.set_should_render(true);
                     self.remove_banner();
@@ -373,12 +373,14 @@ impl Pane for TerminalPane {
             }
             format!("SEARCHING: {}{}", self.search_term, modifier_text)
         } else if self.pane_name.is_empty() {
-            self.grid
-                .title
-                .clone()
-                .unwrap_or_else(|| self.pane_title.clone())
+            self.with_process_info(
+                self.grid
+                    .title
+                    .clone()
+                    .unwrap_or_else(|| self.pane_title.clone()),
+            )
         } else {
-            self.pane_name.clone()
+            self.with_process_info(self.pane_name.clone())
         };
 
         let frame_geom = self.current_geom();
@@ -391,10 +393,16 @@ impl Pane for TerminalPane {
         if let Some((exit_status, is_first_run, _run_command)) = &self.is_held {
             if *is_first_run {
                 frame.indicate_first_run();
+                if let Some(blocked_on) = &self.blocked_on {
+                    frame.add_blocked_on(blocked_on.clone());
+                }
             } else {
                 frame.add_exit_status(exit_status.as_ref().copied());
             }
         }
+        if let Some((restart_count, last_exit_status)) = self.restarts {
+            frame.add_restarts(restart_count, last_exit_status);
+        }
         if let Some((frame_color_override, _text)) = self.pane_frame_color_override.as_ref() {
             frame.override_color(*frame_color_override);
         }
@@ -633,6 +641,10 @@ impl Pane for TerminalPane {
         self.exclude_from_sync
     }
 
+    fn has_pending_bell(&self) -> bool {
+        self.grid.ring_bell
+    }
+
     fn mouse_left_click(&self, position: &Position, is_held: bool) -> Option<String> {
         self.grid.mouse_left_click_signal(position, is_held)
     }
@@ -769,6 +781,52 @@ impl Pane for TerminalPane {
     fn is_held(&self) -> bool {
         self.is_held.is_some()
     }
+    fn set_restarts(&mut self, restart_count: usize, last_exit_status: Option<i32>) {
+        self.restarts = Some((restart_count, last_exit_status));
+        self.set_should_render(true);
+    }
+    fn restarts(&self) -> Option<(usize, Option<i32>)> {
+        self.restarts
+    }
+    fn set_blocked_on(&mut self, blocked_on: Option<String>) {
+        self.blocked_on = blocked_on;
+        self.set_should_render(true);
+    }
+    fn set_process_info(&mut self, process_info: Option<PaneProcessInfo>) -> bool {
+        let foreground_process_changed = self
+            .process_info
+            .as_ref()
+            .and_then(|p| p.foreground_process.as_ref())
+            != process_info
+                .as_ref()
+                .and_then(|p| p.foreground_process.as_ref());
+        let git_branch_changed = self
+            .process_info
+            .as_ref()
+            .and_then(|p| p.git_branch.as_ref())
+            != process_info.as_ref().and_then(|p| p.git_branch.as_ref());
+        self.process_info = process_info;
+        // only the foreground process and git branch are displayed, so there's no need to
+        // re-render when nothing but the resource usage changed
+        let needs_render = (foreground_process_changed && self.style.show_foreground_process)
+            || (git_branch_changed && self.style.show_git_branch);
+        if needs_render {
+            self.set_should_render(true);
+        }
+        needs_render
+    }
+    fn process_info(&self) -> Option<PaneProcessInfo> {
+        self.process_info.clone()
+    }
+    fn run_held_command(&mut self) -> Option<RunCommand> {
+        let (_exit_status, _is_first_run, run_command) = self.is_held.take()?;
+        self.restarts = None;
+        self.blocked_on = None;
+        self.grid.reset_terminal_state();
+        self.set_should_render(true);
+        self.remove_banner();
+        Some(run_command)
+    }
     fn exited(&self) -> bool {
         match self.is_held {
             Some((_, is_first_run, _)) => !is_first_run,
@@ -838,6 +896,9 @@ impl TerminalPane {
             fake_cursor_locations: HashSet::new(),
             search_term: String::new(),
             is_held: None,
+            restarts: None,
+            blocked_on: None,
+            process_info: None,
             banner: None,
             pane_frame_color_override: None,
             invoked_with,
@@ -908,6 +969,29 @@ impl TerminalPane {
             self.banner = None;
         }
     }
+    fn with_process_info(&self, title: String) -> String {
+        let foreground_process = self
+            .process_info
+            .as_ref()
+            .and_then(|p| p.foreground_process.as_ref())
+            .filter(|_| self.style.show_foreground_process);
+        let title = match foreground_process {
+            // many shells already set the terminal title to the running command
+            Some(foreground_process) if !title.contains(foreground_process.as_str()) => {
+                format!("{} ({})", title, foreground_process)
+            },
+            _ => title,
+        };
+        let git_branch = self
+            .process_info
+            .as_ref()
+            .and_then(|p| p.git_branch.as_ref())
+            .filter(|_| self.style.show_git_branch);
+        match git_branch {
+            Some(git_branch) => format!("{} [{}]", title, git_branch),
+            None => title,
+        }
+    }
 }
 
 #[cfg(test)]
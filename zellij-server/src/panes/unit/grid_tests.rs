@@ -5,8 +5,9 @@ use ::insta::assert_snapshot;
 use std::cell::RefCell;
 use std::collections::HashMap;
 use std::rc::Rc;
+use zellij_tile::ui_components::{Checkbox, ProgressBar, SelectableList, Tabs, TextInput};
 use zellij_utils::{
-    data::{Palette, Style},
+    data::{CharOrArrow, Key, Palette, Style},
     pane_size::SizeInPixels,
     position::Position,
     vte,
@@ -3515,3 +3516,220 @@ fn text_ui_component_with_coordinates() {
     }
     assert_snapshot!(format!("{:?}", grid));
 }
+
+#[test]
+fn input_ui_component() {
+    let mut vte_parser = vte::Parser::new();
+    let sixel_image_store = Rc::new(RefCell::new(SixelImageStore::default()));
+    let terminal_emulator_color_codes = Rc::new(RefCell::new(HashMap::new()));
+    let debug = false;
+    let arrow_fonts = true;
+    let styled_underlines = true;
+    let mut grid = Grid::new(
+        41,
+        120,
+        Rc::new(RefCell::new(Palette::default())),
+        terminal_emulator_color_codes,
+        Rc::new(RefCell::new(LinkHandler::new())),
+        Rc::new(RefCell::new(None)),
+        sixel_image_store,
+        Style::default(),
+        debug,
+        arrow_fonts,
+        styled_underlines,
+    );
+    let fixture_name = "input-ui-component";
+    let content = read_fixture(fixture_name);
+    for byte in content {
+        vte_parser.advance(&mut grid, byte);
+    }
+    assert_snapshot!(format!("{:?}", grid));
+}
+
+#[test]
+fn tabs_ui_component() {
+    let mut vte_parser = vte::Parser::new();
+    let sixel_image_store = Rc::new(RefCell::new(SixelImageStore::default()));
+    let terminal_emulator_color_codes = Rc::new(RefCell::new(HashMap::new()));
+    let debug = false;
+    let arrow_fonts = true;
+    let styled_underlines = true;
+    let mut grid = Grid::new(
+        41,
+        120,
+        Rc::new(RefCell::new(Palette::default())),
+        terminal_emulator_color_codes,
+        Rc::new(RefCell::new(LinkHandler::new())),
+        Rc::new(RefCell::new(None)),
+        sixel_image_store,
+        Style::default(),
+        debug,
+        arrow_fonts,
+        styled_underlines,
+    );
+    let fixture_name = "tabs-ui-component";
+    let content = read_fixture(fixture_name);
+    for byte in content {
+        vte_parser.advance(&mut grid, byte);
+    }
+    assert_snapshot!(format!("{:?}", grid));
+}
+
+#[test]
+fn checkbox_ui_component() {
+    let mut vte_parser = vte::Parser::new();
+    let sixel_image_store = Rc::new(RefCell::new(SixelImageStore::default()));
+    let terminal_emulator_color_codes = Rc::new(RefCell::new(HashMap::new()));
+    let debug = false;
+    let arrow_fonts = true;
+    let styled_underlines = true;
+    let mut grid = Grid::new(
+        41,
+        120,
+        Rc::new(RefCell::new(Palette::default())),
+        terminal_emulator_color_codes,
+        Rc::new(RefCell::new(LinkHandler::new())),
+        Rc::new(RefCell::new(None)),
+        sixel_image_store,
+        Style::default(),
+        debug,
+        arrow_fonts,
+        styled_underlines,
+    );
+    let fixture_name = "checkbox-ui-component";
+    let content = read_fixture(fixture_name);
+    for byte in content {
+        vte_parser.advance(&mut grid, byte);
+    }
+    assert_snapshot!(format!("{:?}", grid));
+}
+
+#[test]
+fn progress_bar_ui_component() {
+    let mut vte_parser = vte::Parser::new();
+    let sixel_image_store = Rc::new(RefCell::new(SixelImageStore::default()));
+    let terminal_emulator_color_codes = Rc::new(RefCell::new(HashMap::new()));
+    let debug = false;
+    let arrow_fonts = true;
+    let styled_underlines = true;
+    let mut grid = Grid::new(
+        41,
+        120,
+        Rc::new(RefCell::new(Palette::default())),
+        terminal_emulator_color_codes,
+        Rc::new(RefCell::new(LinkHandler::new())),
+        Rc::new(RefCell::new(None)),
+        sixel_image_store,
+        Style::default(),
+        debug,
+        arrow_fonts,
+        styled_underlines,
+    );
+    let fixture_name = "progress-bar-ui-component";
+    let content = read_fixture(fixture_name);
+    for byte in content {
+        vte_parser.advance(&mut grid, byte);
+    }
+    assert_snapshot!(format!("{:?}", grid));
+}
+
+fn grid_with_serialized_ui_components(serialized_components: &[String]) -> Grid {
+    // renders the components the way plugins serialize them with zellij-tile
+    let mut vte_parser = vte::Parser::new();
+    let sixel_image_store = Rc::new(RefCell::new(SixelImageStore::default()));
+    let terminal_emulator_color_codes = Rc::new(RefCell::new(HashMap::new()));
+    let debug = false;
+    let arrow_fonts = true;
+    let styled_underlines = true;
+    let mut grid = Grid::new(
+        10,
+        60,
+        Rc::new(RefCell::new(Palette::default())),
+        terminal_emulator_color_codes,
+        Rc::new(RefCell::new(LinkHandler::new())),
+        Rc::new(RefCell::new(None)),
+        sixel_image_store,
+        Style::default(),
+        debug,
+        arrow_fonts,
+        styled_underlines,
+    );
+    for byte in serialized_components.concat().as_bytes() {
+        vte_parser.advance(&mut grid, *byte);
+    }
+    grid
+}
+
+#[test]
+fn text_input_ui_component_serialization_round_trip() {
+    let mut scrolled_input = TextInput::new().with_text("a text longer than the input");
+    scrolled_input.handle_key(&Key::Ctrl('w'));
+    let mut input_with_cursor_in_the_middle = TextInput::new().with_text("hello world");
+    input_with_cursor_in_the_middle.handle_key(&Key::Alt(CharOrArrow::Char('b')));
+    let mut unfocused_input = TextInput::new().with_placeholder("type here...");
+    unfocused_input.set_focused(false);
+    let grid = grid_with_serialized_ui_components(&[
+        scrolled_input.serialize(0, 0, 12),
+        input_with_cursor_in_the_middle.serialize(0, 1, 20),
+        unfocused_input.serialize(0, 2, 20),
+    ]);
+    assert_snapshot!(format!("{:?}", grid));
+}
+
+#[test]
+fn selectable_list_ui_component_serialization_round_trip() {
+    let mut list = SelectableList::new(vec!["apple", "banana", "cherry", "grape", "pineapple"]);
+    list.set_filter("ap");
+    list.handle_key(&Key::Down);
+    let mut scrolled_list = SelectableList::new((1..=10).map(|i| format!("item {}", i)).collect());
+    scrolled_list.select(7);
+    let grid = grid_with_serialized_ui_components(&[
+        list.serialize(0, 0, 20, 5),
+        scrolled_list.serialize(30, 0, 20, 3),
+    ]);
+    assert_snapshot!(format!("{:?}", grid));
+}
+
+#[test]
+fn tabs_ui_component_serialization_round_trip() {
+    let mut tabs = Tabs::new(vec!["first", "second", "third"]);
+    tabs.handle_key(&Key::BackTab);
+    let mut truncated_tabs = Tabs::new(vec!["first", "second", "third"]);
+    truncated_tabs.select_next();
+    let grid = grid_with_serialized_ui_components(&[
+        tabs.serialize(0, 0, 40),
+        truncated_tabs.serialize(0, 1, 16),
+    ]);
+    assert_snapshot!(format!("{:?}", grid));
+}
+
+#[test]
+fn checkbox_ui_component_serialization_round_trip() {
+    let mut checked = Checkbox::new("enabled").checked();
+    let mut toggled = Checkbox::new("toggled");
+    toggled.set_focused(true);
+    toggled.handle_key(&Key::Char(' '));
+    let mut unchecked = Checkbox::new("disabled");
+    let grid = grid_with_serialized_ui_components(&[
+        checked.serialize(0, 0),
+        toggled.serialize(0, 1),
+        unchecked.serialize(0, 2),
+    ]);
+    assert_snapshot!(format!("{:?}", grid));
+}
+
+#[test]
+fn progress_bar_ui_component_serialization_round_trip() {
+    let mut progress_bar = ProgressBar::new("downloading");
+    progress_bar.set_progress(3, 4);
+    let mut capped_progress_bar = ProgressBar::new("done");
+    capped_progress_bar.set_progress(12, 10);
+    let mut empty_progress_bar = ProgressBar::new("waiting");
+    empty_progress_bar.set_progress(0, 0);
+    let grid = grid_with_serialized_ui_components(&[
+        progress_bar.serialize(0, 0, 40),
+        capped_progress_bar.serialize(0, 1, 40),
+        empty_progress_bar.serialize(0, 2, 40),
+    ]);
+    assert_snapshot!(format!("{:?}", grid));
+}
@@ -26,8 +26,8 @@ use wasm_bridge::WasmBridge;
 use zellij_utils::{
     async_std::{channel, future::timeout, task},
     data::{
-        Event, EventType, MessageToPlugin, PermissionStatus, PermissionType, PipeMessage,
-        PipeSource, PluginCapabilities,
+        Event, EventType, LayoutInfo, MessageToPlugin, PermissionStatus, PermissionType,
+        PipeMessage, PipeSource, PluginCapabilities,
     },
     errors::{prelude::*, ContextType, PluginContext},
     input::{
@@ -108,6 +108,7 @@ pub enum PluginInstruction {
     ),
     DumpLayout(SessionLayoutMetadata, ClientId),
     LogLayoutToHd(SessionLayoutMetadata),
+    SaveSnapshot(SessionLayoutMetadata, String, ClientId), // String is the name of the snapshot
     CliPipe {
         pipe_id: String,
         name: String,
@@ -130,6 +131,11 @@ pub enum PluginInstruction {
         message: MessageToPlugin,
     },
     UnblockCliPipes(Vec<PluginRenderAsset>),
+    CliSubscribe {
+        event_types: HashSet<EventType>,
+        cli_client_id: ClientId,
+    },
+    UpdateAvailableLayouts(Vec<LayoutInfo>),
     Exit,
 }
 
@@ -161,10 +167,13 @@ impl From<&PluginInstruction> for PluginContext {
             },
             PluginInstruction::DumpLayout(..) => PluginContext::DumpLayout,
             PluginInstruction::LogLayoutToHd(..) => PluginContext::LogLayoutToHd,
+            PluginInstruction::SaveSnapshot(..) => PluginContext::SaveSnapshot,
             PluginInstruction::CliPipe { .. } => PluginContext::CliPipe,
             PluginInstruction::CachePluginEvents { .. } => PluginContext::CachePluginEvents,
             PluginInstruction::MessageFromPlugin { .. } => PluginContext::MessageFromPlugin,
             PluginInstruction::UnblockCliPipes { .. } => PluginContext::UnblockCliPipes,
+            PluginInstruction::CliSubscribe { .. } => PluginContext::CliSubscribe,
+            PluginInstruction::UpdateAvailableLayouts(..) => PluginContext::UpdateAvailableLayouts,
         }
     }
 }
@@ -398,7 +407,7 @@ pub(crate) fn plugin_thread_main(
                 )];
                 wasm_bridge.update_plugins(updates, shutdown_send.clone())?;
             },
-            PluginInstruction::PluginSubscribedToEvents(_plugin_id, _client_id, events) => {
+            PluginInstruction::PluginSubscribedToEvents(plugin_id, client_id, events) => {
                 for event in events {
                     if let EventType::FileSystemCreate
                     | EventType::FileSystemRead
@@ -406,6 +415,22 @@ pub(crate) fn plugin_thread_main(
                     | EventType::FileSystemDelete = event
                     {
                         wasm_bridge.start_fs_watcher_if_not_started();
+                    } else if let EventType::AvailablePluginAliases = event {
+                        // the aliases only change with the config, so they are sent once
+                        let updates = vec![(
+                            Some(plugin_id),
+                            Some(client_id),
+                            Event::AvailablePluginAliases(wasm_bridge.plugin_aliases()),
+                        )];
+                        wasm_bridge.update_plugins(updates, shutdown_send.clone())?;
+                    } else if let EventType::AvailableLayoutInfo = event {
+                        // the layouts are only sent again when they change
+                        let updates = vec![(
+                            Some(plugin_id),
+                            Some(client_id),
+                            Event::AvailableLayoutInfo(wasm_bridge.available_layouts()),
+                        )];
+                        wasm_bridge.update_plugins(updates, shutdown_send.clone())?;
                     }
                 }
             },
@@ -453,6 +478,18 @@ pub(crate) fn plugin_thread_main(
                         .send_to_pty(PtyInstruction::LogLayoutToHd(session_layout_metadata)),
                 );
             },
+            PluginInstruction::SaveSnapshot(
+                mut session_layout_metadata,
+                snapshot_name,
+                client_id,
+            ) => {
+                populate_session_layout_metadata(&mut session_layout_metadata, &wasm_bridge);
+                drop(bus.senders.send_to_pty(PtyInstruction::SaveSnapshot(
+                    session_layout_metadata,
+                    snapshot_name,
+                    client_id,
+                )));
+            },
             PluginInstruction::CliPipe {
                 pipe_id,
                 name,
@@ -575,6 +612,22 @@ pub(crate) fn plugin_thread_main(
                         .context("failed to unblock input pipe");
                 }
             },
+            PluginInstruction::CliSubscribe {
+                event_types,
+                cli_client_id,
+            } => {
+                wasm_bridge.subscribe_cli_client(cli_client_id, event_types);
+            },
+            PluginInstruction::UpdateAvailableLayouts(available_layouts) => {
+                if wasm_bridge.update_available_layouts(available_layouts) {
+                    let updates = vec![(
+                        None,
+                        None,
+                        Event::AvailableLayoutInfo(wasm_bridge.available_layouts()),
+                    )];
+                    wasm_bridge.update_plugins(updates, shutdown_send.clone())?;
+                }
+            },
             PluginInstruction::Exit => {
                 break;
             },
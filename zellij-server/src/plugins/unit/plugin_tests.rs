@@ -2,11 +2,13 @@ use super::plugin_thread_main;
 use crate::screen::ScreenInstruction;
 use crate::{channels::SenderWithContext, thread_bus::Bus, ServerInstruction};
 use insta::assert_snapshot;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashSet};
 use std::path::PathBuf;
 use tempfile::tempdir;
 use wasmer::Store;
-use zellij_utils::data::{Event, Key, PermissionStatus, PermissionType, PluginCapabilities};
+use zellij_utils::data::{
+    Event, EventType, Key, PermissionStatus, PermissionType, PluginCapabilities, TabInfo,
+};
 use zellij_utils::errors::ErrorContext;
 use zellij_utils::input::layout::{Layout, PluginUserConfiguration, RunPlugin, RunPluginLocation};
 use zellij_utils::input::permission::PermissionCache;
@@ -5970,3 +5972,224 @@ pub fn pipe_message_to_plugin_plugin_command() {
         });
     assert_snapshot!(format!("{:#?}", plugin_bytes_event));
 }
+
+#[test]
+pub fn cli_client_subscribes_to_events() {
+    let (plugin_thread_sender, server_receiver, _screen_receiver, teardown) =
+        create_plugin_thread_with_server_receiver(None);
+    let cli_client_id = 10;
+    let tab_update = |name: &str| {
+        Event::TabUpdate(vec![TabInfo {
+            name: name.to_owned(),
+            ..Default::default()
+        }])
+    };
+    let received_server_instruction = Arc::new(Mutex::new(vec![]));
+    let server_thread = log_actions_in_thread!(
+        received_server_instruction,
+        ServerInstruction::SubscribedEvent,
+        server_receiver,
+        2
+    );
+    let _ = plugin_thread_sender.send(PluginInstruction::Update(vec![(
+        None,
+        Some(1),
+        tab_update("first"),
+    )]));
+    let _ = plugin_thread_sender.send(PluginInstruction::CliSubscribe {
+        event_types: HashSet::from([EventType::TabUpdate]),
+        cli_client_id,
+    });
+    let _ = plugin_thread_sender.send(PluginInstruction::Update(vec![
+        (None, Some(1), tab_update("first")),  // unchanged
+        (None, Some(2), tab_update("second")), // same update for another client
+        (None, None, Event::InputReceived),    // not subscribed to
+    ]));
+    let _ = plugin_thread_sender.send(PluginInstruction::Update(vec![(
+        None,
+        Some(1),
+        tab_update("third"),
+    )]));
+    server_thread.join().unwrap();
+    teardown();
+    let subscribed_events: Vec<(Event, u16)> = received_server_instruction
+        .lock()
+        .unwrap()
+        .iter()
+        .filter_map(|i| match i {
+            ServerInstruction::SubscribedEvent(event, client_id) => {
+                Some((event.clone(), *client_id))
+            },
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        subscribed_events,
+        vec![
+            (tab_update("first"), cli_client_id),
+            (tab_update("third"), cli_client_id)
+        ],
+        "cli client receives the current state and then only changes"
+    );
+}
+
+#[test]
+pub fn run_action_permissions() {
+    use super::zellij_exports::action_permission;
+    use zellij_utils::input::actions::Action;
+    use zellij_utils::input::command::{RunCommand, RunCommandAction};
+    use zellij_utils::input::layout::{FloatingPaneLayout, Run, RunShell, TiledPaneLayout};
+    let run_command = RunCommandAction {
+        command: PathBuf::from("htop"),
+        ..Default::default()
+    };
+    let layout_with_command = TiledPaneLayout {
+        children: vec![
+            TiledPaneLayout::default(),
+            TiledPaneLayout {
+                run: Some(Run::Command(RunCommand {
+                    command: PathBuf::from("htop"),
+                    ..Default::default()
+                })),
+                ..Default::default()
+            },
+        ],
+        ..Default::default()
+    };
+    let floating_pane_with_file = FloatingPaneLayout {
+        run: Some(Run::EditFile(PathBuf::from("/some/file"), None, None)),
+        ..Default::default()
+    };
+    let shell_pane_with_env =
+        |env: BTreeMap<String, String>, env_file: Option<PathBuf>| TiledPaneLayout {
+            run: Some(Run::Shell(RunShell {
+                env,
+                env_file,
+                ..Default::default()
+            })),
+            ..Default::default()
+        };
+    let preloaded_env: BTreeMap<String, String> =
+        [("LD_PRELOAD".to_owned(), "/tmp/evil.so".to_owned())].into();
+    let run_plugin = RunPlugin::from_url("zellij:tab-bar").unwrap();
+    let permissions = [
+        (
+            Action::Run(run_command.clone()),
+            PermissionType::RunCommands,
+        ),
+        (
+            Action::NewFloatingPane(Some(run_command.clone()), None),
+            PermissionType::RunCommands,
+        ),
+        (
+            Action::NewTiledPane(None, Some(run_command.clone()), None),
+            PermissionType::RunCommands,
+        ),
+        (
+            Action::NewInPlacePane(Some(run_command.clone()), None),
+            PermissionType::RunCommands,
+        ),
+        (
+            Action::NewTab(Some(layout_with_command), vec![], None, None, None),
+            PermissionType::RunCommands,
+        ),
+        (
+            Action::Targeted(
+                Default::default(),
+                Box::new(Action::Run(run_command.clone())),
+            ),
+            PermissionType::RunCommands,
+        ),
+        (
+            Action::DumpScreen("/some/file".to_owned(), false),
+            PermissionType::RunCommands,
+        ),
+        (
+            Action::EditFile(PathBuf::from("/some/file"), None, None, None, false, false),
+            PermissionType::OpenFiles,
+        ),
+        (
+            Action::NewTab(
+                Some(shell_pane_with_env(preloaded_env, None)),
+                vec![],
+                None,
+                None,
+                None,
+            ),
+            PermissionType::RunCommands,
+        ),
+        (
+            Action::NewTab(
+                Some(shell_pane_with_env(
+                    BTreeMap::new(),
+                    Some(PathBuf::from(".env")),
+                )),
+                vec![],
+                None,
+                None,
+                None,
+            ),
+            PermissionType::RunCommands,
+        ),
+        (
+            Action::NewTab(None, vec![floating_pane_with_file], None, None, None),
+            PermissionType::OpenFiles,
+        ),
+        (
+            Action::NewFloatingPane(None, None),
+            PermissionType::OpenTerminalsOrPlugins,
+        ),
+        (
+            Action::NewTab(
+                Some(shell_pane_with_env(BTreeMap::new(), None)),
+                vec![],
+                None,
+                None,
+                None,
+            ),
+            PermissionType::OpenTerminalsOrPlugins,
+        ),
+        (
+            Action::LaunchOrFocusPlugin(run_plugin, true, true, false, false),
+            PermissionType::OpenTerminalsOrPlugins,
+        ),
+        (
+            Action::WriteChars("ls".to_owned()),
+            PermissionType::WriteToStdin,
+        ),
+        (
+            Action::ListPanes(true),
+            PermissionType::ReadApplicationState,
+        ),
+        (
+            Action::CliPipe {
+                pipe_id: "pipe".to_owned(),
+                name: None,
+                payload: None,
+                args: None,
+                plugin: None,
+                configuration: None,
+                launch_new: false,
+                skip_cache: false,
+                floating: None,
+                in_place: None,
+                cwd: None,
+                pane_title: None,
+            },
+            PermissionType::MessageAndLaunchOtherPlugins,
+        ),
+        (
+            Action::NewTab(None, vec![], None, None, None),
+            PermissionType::ChangeApplicationState,
+        ),
+        (Action::GoToNextTab, PermissionType::ChangeApplicationState),
+    ];
+    for (action, permission) in permissions {
+        assert_eq!(
+            action_permission(&action),
+            permission,
+            "permission of {:?}",
+            action
+        );
+    }
+}
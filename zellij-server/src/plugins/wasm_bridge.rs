@@ -19,7 +19,7 @@ use wasmer::{Module, Store, Value};
 use zellij_utils::async_channel::Sender;
 use zellij_utils::async_std::task::{self, JoinHandle};
 use zellij_utils::consts::ZELLIJ_CACHE_DIR;
-use zellij_utils::data::{PermissionStatus, PermissionType, PipeMessage, PipeSource};
+use zellij_utils::data::{LayoutInfo, PermissionStatus, PermissionType, PipeMessage, PipeSource};
 use zellij_utils::downloader::Downloader;
 use zellij_utils::input::permission::PermissionCache;
 use zellij_utils::notify_debouncer_full::{notify::RecommendedWatcher, Debouncer, FileIdMap};
@@ -102,6 +102,9 @@ pub struct WasmBridge {
     cached_plugin_map:
         HashMap<RunPluginLocation, HashMap<PluginUserConfiguration, Vec<(PluginId, ClientId)>>>,
     pending_pipes: PendingPipes,
+    cli_subscriptions: HashMap<ClientId, HashSet<EventType>>,
+    latest_state_events: HashMap<EventType, Event>,
+    available_layouts: Vec<LayoutInfo>,
 }
 
 impl WasmBridge {
@@ -146,6 +149,9 @@ impl WasmBridge {
             default_layout,
             cached_plugin_map: HashMap::new(),
             pending_pipes: Default::default(),
+            cli_subscriptions: HashMap::new(),
+            latest_state_events: HashMap::new(),
+            available_layouts: vec![],
         }
     }
     pub fn load_plugin(
@@ -525,6 +531,8 @@ impl WasmBridge {
     ) -> Result<()> {
         let err_context = || "failed to update plugin state".to_string();
 
+        self.update_cli_subscriptions(&updates)
+            .with_context(err_context)?;
         let plugins_to_update: Vec<(
             PluginId,
             ClientId,
@@ -743,6 +751,65 @@ impl WasmBridge {
             .lock()
             .unwrap()
             .retain(|c| c != &client_id);
+        self.cli_subscriptions.remove(&client_id);
+    }
+    pub fn subscribe_cli_client(
+        &mut self,
+        cli_client_id: ClientId,
+        event_types: HashSet<EventType>,
+    ) {
+        // send the current state right away so that subscribers do not have to wait for the
+        // next change in order to know where they stand
+        for event_type in &event_types {
+            if let Some(event) = self.latest_state_events.get(event_type) {
+                let _ = self
+                    .senders
+                    .send_to_server(ServerInstruction::SubscribedEvent(
+                        event.clone(),
+                        cli_client_id,
+                    ));
+            }
+        }
+        self.cli_subscriptions
+            .entry(cli_client_id)
+            .or_default()
+            .extend(event_types);
+    }
+    fn update_cli_subscriptions(
+        &mut self,
+        updates: &[(Option<PluginId>, Option<ClientId>, Event)],
+    ) -> Result<()> {
+        let mut seen_client_specific_events = HashSet::new();
+        for (pid, cid, event) in updates {
+            if pid.is_some() {
+                // events directed at a specific plugin are of no interest to cli clients
+                continue;
+            }
+            let event_type = EventType::from_str(&event.to_string())?;
+            if cid.is_some() && !seen_client_specific_events.insert(event_type) {
+                // some events (eg. TabUpdate) are sent once per connected client, cli clients are
+                // not connected so they get the one meant for the first client
+                continue;
+            }
+            if is_state_event(&event_type) {
+                if self.latest_state_events.get(&event_type) == Some(event) {
+                    // nothing changed
+                    continue;
+                }
+                self.latest_state_events.insert(event_type, event.clone());
+            }
+            for (cli_client_id, event_types) in &self.cli_subscriptions {
+                if event_types.contains(&event_type) {
+                    let _ = self
+                        .senders
+                        .send_to_server(ServerInstruction::SubscribedEvent(
+                            event.clone(),
+                            *cli_client_id,
+                        ));
+                }
+            }
+        }
+        Ok(())
     }
     pub fn cleanup(&mut self) {
         for (_plugin_id, loading_plugin_task) in self.loading_plugins.drain() {
@@ -1027,6 +1094,23 @@ impl WasmBridge {
             };
         }
     }
+    pub fn plugin_aliases(&self) -> Vec<String> {
+        let mut plugin_aliases: Vec<String> =
+            self.plugins.0.keys().map(|tag| tag.to_string()).collect();
+        plugin_aliases.sort();
+        plugin_aliases
+    }
+    pub fn available_layouts(&self) -> Vec<LayoutInfo> {
+        self.available_layouts.clone()
+    }
+    /// Returns true if the layouts are not the ones we already had
+    pub fn update_available_layouts(&mut self, available_layouts: Vec<LayoutInfo>) -> bool {
+        if self.available_layouts == available_layouts {
+            return false;
+        }
+        self.available_layouts = available_layouts;
+        true
+    }
     pub fn cache_plugin_permissions(
         &mut self,
         plugin_id: PluginId,
@@ -1204,6 +1288,17 @@ fn handle_plugin_loading_failure(
     }
 }
 
+fn is_state_event(event_type: &EventType) -> bool {
+    // events that describe the current state of the session rather than something that happened
+    matches!(
+        event_type,
+        EventType::ModeUpdate
+            | EventType::TabUpdate
+            | EventType::PaneUpdate
+            | EventType::SessionUpdate
+    )
+}
+
 // TODO: move to permissions?
 fn check_event_permission(
     plugin_env: &PluginEnv,
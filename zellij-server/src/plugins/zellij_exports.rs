@@ -18,8 +18,8 @@ use std::{
 use wasmer::{imports, AsStoreMut, Function, FunctionEnv, FunctionEnvMut, Imports};
 use wasmer_wasi::WasiEnv;
 use zellij_utils::data::{
-    CommandType, ConnectToSession, HttpVerb, MessageToPlugin, PermissionStatus, PermissionType,
-    PluginPermission,
+    CommandType, ConnectToSession, HttpVerb, MessageToPlugin, PaneSignal, PermissionStatus,
+    PermissionType, PluginPermission,
 };
 use zellij_utils::input::permission::PermissionCache;
 
@@ -28,7 +28,11 @@ use url::Url;
 use crate::{panes::PaneId, screen::ScreenInstruction};
 
 use zellij_utils::{
-    consts::{VERSION, ZELLIJ_SESSION_INFO_CACHE_DIR, ZELLIJ_SOCK_DIR},
+    consts::{
+        remove_session_snapshot_folder, resurrectable_session_layout_file_name,
+        split_session_snapshot_id, validate_snapshot_path_component, VERSION,
+        ZELLIJ_SESSION_INFO_CACHE_DIR, ZELLIJ_SOCK_DIR,
+    },
     data::{
         CommandToRun, Direction, Event, EventType, FileToOpen, InputMode, PluginCommand, PluginIds,
         PluginMessage, Resize, ResizeStrategy,
@@ -37,11 +41,11 @@ use zellij_utils::{
     input::{
         actions::Action,
         command::{RunCommand, RunCommandAction, TerminalAction},
-        layout::{Layout, PluginUserConfiguration, RunPlugin, RunPluginLocation},
+        layout::{Layout, PluginUserConfiguration, Run, RunPlugin, RunPluginLocation},
         plugins::PluginType,
     },
     plugin_api::{
-        plugin_command::ProtobufPluginCommand,
+        plugin_command::{ProtobufPluginCommand, ProtobufResurrectableSessionLayout},
         plugin_ids::{ProtobufPluginIds, ProtobufZellijVersion},
     },
     prost::Message,
@@ -220,12 +224,9 @@ fn host_run_plugin_command(env: FunctionEnvMut<ForeignFunctionEnv>) {
                     PluginCommand::RequestPluginPermissions(permissions) => {
                         request_permission(env, permissions)?
                     },
-                    PluginCommand::SwitchSession(connect_to_session) => switch_session(
-                        env,
-                        connect_to_session.name,
-                        connect_to_session.tab_position,
-                        connect_to_session.pane_id,
-                    )?,
+                    PluginCommand::SwitchSession(connect_to_session) => {
+                        switch_session(env, connect_to_session)?
+                    },
                     PluginCommand::DeleteDeadSession(session_name) => {
                         delete_dead_session(session_name)?
                     },
@@ -252,6 +253,14 @@ fn host_run_plugin_command(env: FunctionEnvMut<ForeignFunctionEnv>) {
                         cli_pipe_output(env, pipe_name, output)?
                     },
                     PluginCommand::MessageToPlugin(message) => message_to_plugin(env, message)?,
+                    PluginCommand::SignalTerminalPane(terminal_pane_id, signal) => {
+                        signal_terminal_pane(env, terminal_pane_id, signal)
+                    },
+                    PluginCommand::MoveTab(direction) => move_tab(env, direction),
+                    PluginCommand::RunAction(action) => run_action(env, action),
+                    PluginCommand::GetResurrectableSessionLayout(session_name) => {
+                        get_resurrectable_session_layout(env, &session_name)
+                    },
                 },
                 (PermissionStatus::Denied, permission) => {
                     log::error!(
@@ -424,6 +433,36 @@ fn get_zellij_version(env: &ForeignFunctionEnv) {
     .non_fatal();
 }
 
+// the layout is read when asked for rather than with every session update, since plugins only need
+// it to preview the session they are about to resurrect
+fn get_resurrectable_session_layout(env: &ForeignFunctionEnv, session_name: &str) {
+    let serialized_layout =
+        resurrectable_session_layout_file_name(session_name).and_then(|layout_file_name| {
+            match std::fs::read_to_string(layout_file_name) {
+                Ok(serialized_layout) => Some(serialized_layout),
+                Err(e) => {
+                    if e.kind() != std::io::ErrorKind::NotFound {
+                        log::error!("Failed to read resurrection layout file: {:?}", e);
+                    }
+                    None
+                },
+            }
+        });
+    let protobuf_resurrectable_session_layout =
+        ProtobufResurrectableSessionLayout { serialized_layout };
+    wasi_write_object(
+        &env.plugin_env.wasi_env,
+        &protobuf_resurrectable_session_layout.encode_to_vec(),
+    )
+    .with_context(|| {
+        format!(
+            "failed to send resurrectable session layout to plugin {}",
+            env.plugin_env.name()
+        )
+    })
+    .non_fatal();
+}
+
 fn open_file(env: &ForeignFunctionEnv, file_to_open: FileToOpen) {
     let error_msg = || format!("failed to open file in plugin {}", env.plugin_env.name());
     let floating = false;
@@ -550,6 +589,7 @@ fn open_command_pane(env: &ForeignFunctionEnv, command_to_run: CommandToRun) {
         direction,
         hold_on_close,
         hold_on_start,
+        ..Default::default()
     };
     let action = Action::NewTiledPane(direction, Some(run_command_action), name);
     apply_action!(action, error_msg, env);
@@ -573,6 +613,7 @@ fn open_command_pane_floating(env: &ForeignFunctionEnv, command_to_run: CommandT
         direction,
         hold_on_close,
         hold_on_start,
+        ..Default::default()
     };
     let action = Action::NewFloatingPane(Some(run_command_action), name);
     apply_action!(action, error_msg, env);
@@ -596,6 +637,7 @@ fn open_command_pane_in_place(env: &ForeignFunctionEnv, command_to_run: CommandT
         direction,
         hold_on_close,
         hold_on_start,
+        ..Default::default()
     };
     let action = Action::NewInPlacePane(Some(run_command_action), name);
     apply_action!(action, error_msg, env);
@@ -889,26 +931,38 @@ fn move_focus_or_tab(env: &ForeignFunctionEnv, direction: Direction) {
     apply_action!(action, error_msg, env);
 }
 
+fn move_tab(env: &ForeignFunctionEnv, direction: Direction) {
+    let error_msg = || format!("failed to move tab in plugin {}", env.plugin_env.name());
+    let action = Action::MoveTab(direction);
+    apply_action!(action, error_msg, env);
+}
+
+fn run_action(env: &ForeignFunctionEnv, action: Action) {
+    let error_msg = || format!("failed to run action in plugin {}", env.plugin_env.name());
+    apply_action!(action, error_msg, env);
+}
+
 fn detach(env: &ForeignFunctionEnv) {
     let action = Action::Detach;
     let error_msg = || format!("Failed to detach");
     apply_action!(action, error_msg, env);
 }
 
-fn switch_session(
-    env: &ForeignFunctionEnv,
-    session_name: Option<String>,
-    tab_position: Option<usize>,
-    pane_id: Option<(u32, bool)>,
-) -> Result<()> {
-    // pane_id is (id, is_plugin)
+fn switch_session(env: &ForeignFunctionEnv, connect_to_session: ConnectToSession) -> Result<()> {
     let err_context = || format!("Failed to switch session");
     let client_id = env.plugin_env.client_id;
-    let tab_position = tab_position.map(|p| p + 1); // ¯\_()_/¯
+    if let Some(layout_name) = &connect_to_session.layout {
+        if !Layout::is_layout_name(layout_name) {
+            return Err(anyhow!(
+                "{:?} is not the name of a layout in the layout dir or of a built-in one",
+                layout_name
+            ))
+            .with_context(err_context);
+        }
+    }
     let connect_to_session = ConnectToSession {
-        name: session_name,
-        tab_position,
-        pane_id,
+        tab_position: connect_to_session.tab_position.map(|p| p + 1), // ¯\_()_/¯
+        ..connect_to_session
     };
     env.plugin_env
         .senders
@@ -921,8 +975,21 @@ fn switch_session(
 }
 
 fn delete_dead_session(session_name: String) -> Result<()> {
-    std::fs::remove_dir_all(&*ZELLIJ_SESSION_INFO_CACHE_DIR.join(&session_name))
-        .with_context(|| format!("Failed to delete dead session: {:?}", &session_name))
+    let err_context = || format!("Failed to delete dead session: {:?}", &session_name);
+    let session_folder = ZELLIJ_SESSION_INFO_CACHE_DIR.join(&session_name);
+    // resurrectable sessions include the snapshots of all sessions
+    match split_session_snapshot_id(&session_name) {
+        Some((snapshot_session_name, snapshot_name)) if !session_folder.exists() => {
+            remove_session_snapshot_folder(snapshot_session_name, snapshot_name)
+                .with_context(err_context)
+        },
+        _ => {
+            validate_snapshot_path_component(&session_name)
+                .map_err(|e| anyhow!(e))
+                .with_context(err_context)?;
+            std::fs::remove_dir_all(session_folder).with_context(err_context)
+        },
+    }
 }
 
 fn delete_all_dead_sessions() -> Result<()> {
@@ -947,8 +1014,8 @@ fn delete_all_dead_sessions() -> Result<()> {
             files_that_are_folders
                 .filter_map(|folder_name| {
                     let session_name = folder_name.file_name()?.to_str()?.to_owned();
+                    // the snapshots of live sessions are kept, they are not dead sessions
                     if live_sessions.contains(&session_name) {
-                        // this is not a dead session...
                         return None;
                     }
                     Some(session_name)
@@ -1221,6 +1288,17 @@ fn close_terminal_pane(env: &ForeignFunctionEnv, terminal_pane_id: u32) {
     apply_action!(action, error_msg, env);
 }
 
+fn signal_terminal_pane(env: &ForeignFunctionEnv, terminal_pane_id: u32, signal: PaneSignal) {
+    let error_msg = || {
+        format!(
+            "failed to signal terminal pane in plugin {}",
+            env.plugin_env.name()
+        )
+    };
+    let action = Action::SignalPane(Some(terminal_pane_id), signal);
+    apply_action!(action, error_msg, env);
+}
+
 fn close_plugin_pane(env: &ForeignFunctionEnv, plugin_pane_id: u32) {
     let error_msg = || {
         format!(
@@ -1335,6 +1413,156 @@ pub fn wasi_read_bytes(wasi_env: &WasiEnv) -> Result<Vec<u8>> {
 }
 
 // TODO: move to permissions?
+// actions are granted the permission of the plugin command that does the same thing, they are all
+// listed so that new actions have to be given a permission deliberately
+pub(crate) fn action_permission(action: &Action) -> PermissionType {
+    match action {
+        Action::NewFloatingPane(run_command, _)
+        | Action::NewTiledPane(_, run_command, _)
+        | Action::NewInPlacePane(run_command, _) => match run_command {
+            Some(_) => PermissionType::RunCommands,
+            None => PermissionType::OpenTerminalsOrPlugins,
+        },
+        Action::NewTab(
+            tiled_layout,
+            floating_layouts,
+            swap_tiled_layouts,
+            swap_floating_layouts,
+            _,
+        ) => {
+            let swap_tiled_layouts = swap_tiled_layouts.iter().flatten();
+            let swap_floating_layouts = swap_floating_layouts.iter().flatten();
+            let runs: Vec<Run> = tiled_layout
+                .iter()
+                .chain(swap_tiled_layouts.flat_map(|(layouts, _)| layouts.values()))
+                .flat_map(|layout| layout.extract_run_instructions())
+                .flatten()
+                .chain(
+                    floating_layouts
+                        .iter()
+                        .chain(
+                            swap_floating_layouts
+                                .flat_map(|(layouts, _)| layouts.values().flatten()),
+                        )
+                        .filter_map(|floating_layout| floating_layout.run.clone()),
+                )
+                .collect();
+            // a shell started with an env of its own (eg. LD_PRELOAD) is as good as a command
+            let runs_command = |run: &Run| match run {
+                Run::Command(..) => true,
+                Run::Shell(run_shell) => !run_shell.env.is_empty() || run_shell.env_file.is_some(),
+                _ => false,
+            };
+            if runs.iter().any(runs_command) {
+                PermissionType::RunCommands
+            } else if runs.iter().any(|run| matches!(run, Run::EditFile(..))) {
+                PermissionType::OpenFiles
+            } else if runs
+                .iter()
+                .any(|run| matches!(run, Run::Plugin(..) | Run::Shell(..)))
+            {
+                PermissionType::OpenTerminalsOrPlugins
+            } else {
+                PermissionType::ChangeApplicationState
+            }
+        },
+        // the dump can be written to any path, which is as good as running a command
+        Action::Run(..) | Action::DumpScreen(..) => PermissionType::RunCommands,
+        Action::EditFile(..) => PermissionType::OpenFiles,
+        Action::NewPane(..)
+        | Action::NewTiledPluginPane(..)
+        | Action::NewFloatingPluginPane(..)
+        | Action::NewInPlacePluginPane(..)
+        | Action::LaunchOrFocusPlugin(..)
+        | Action::LaunchPlugin(..)
+        | Action::StartOrReloadPlugin(..) => PermissionType::OpenTerminalsOrPlugins,
+        Action::Write(..) | Action::WriteChars(..) => PermissionType::WriteToStdin,
+        Action::CliPipe { .. } => PermissionType::MessageAndLaunchOtherPlugins,
+        Action::DumpLayout
+        | Action::QueryTabNames
+        | Action::ListClients
+        | Action::ListPanes(..)
+        | Action::ListTabs(..)
+        | Action::Subscribe { .. } => PermissionType::ReadApplicationState,
+        Action::SkipConfirm(action) | Action::Targeted(_, action) => action_permission(action),
+        Action::Quit
+        | Action::SwitchToMode(..)
+        | Action::SwitchModeForAllClients(..)
+        | Action::Resize(..)
+        | Action::FocusNextPane
+        | Action::FocusPreviousPane
+        | Action::SwitchFocus
+        | Action::MoveFocus(..)
+        | Action::MoveFocusOrTab(..)
+        | Action::MovePane(..)
+        | Action::MovePaneBackwards
+        | Action::ClearScreen
+        | Action::EditScrollback
+        | Action::ScrollUp
+        | Action::ScrollUpAt(..)
+        | Action::ScrollDown
+        | Action::ScrollDownAt(..)
+        | Action::ScrollToBottom
+        | Action::ScrollToTop
+        | Action::PageScrollUp
+        | Action::PageScrollDown
+        | Action::HalfPageScrollUp
+        | Action::HalfPageScrollDown
+        | Action::ToggleFocusFullscreen
+        | Action::TogglePaneFrames
+        | Action::ToggleActiveSyncTab
+        | Action::TogglePaneEmbedOrFloating
+        | Action::ToggleFloatingPanes
+        | Action::CloseFocus
+        | Action::PaneNameInput(..)
+        | Action::UndoRenamePane
+        | Action::NoOp
+        | Action::GoToNextTab
+        | Action::GoToPreviousTab
+        | Action::CloseTab
+        | Action::GoToTab(..)
+        | Action::GoToTabName(..)
+        | Action::ToggleTab
+        | Action::MoveTab(..)
+        | Action::TabNameInput(..)
+        | Action::UndoRenameTab
+        | Action::Detach
+        | Action::LeftClick(..)
+        | Action::RightClick(..)
+        | Action::MiddleClick(..)
+        | Action::LeftMouseRelease(..)
+        | Action::RightMouseRelease(..)
+        | Action::MiddleMouseRelease(..)
+        | Action::MouseHoldLeft(..)
+        | Action::MouseHoldRight(..)
+        | Action::MouseHoldMiddle(..)
+        | Action::MouseMotion(..)
+        | Action::Copy
+        | Action::Confirm
+        | Action::Deny
+        | Action::SearchInput(..)
+        | Action::Search(..)
+        | Action::SearchToggleOption(..)
+        | Action::ToggleMouseMode
+        | Action::PreviousSwapLayout
+        | Action::NextSwapLayout
+        | Action::CloseTerminalPane(..)
+        | Action::ClosePluginPane(..)
+        | Action::FocusTerminalPaneWithId(..)
+        | Action::FocusPluginPaneWithId(..)
+        | Action::RenameTerminalPane(..)
+        | Action::RenamePluginPane(..)
+        | Action::RenameTab(..)
+        | Action::BreakPane
+        | Action::BreakPaneRight
+        | Action::BreakPaneLeft
+        | Action::RenameSession(..)
+        | Action::SignalPane(..)
+        | Action::KickClient(..)
+        | Action::SaveSnapshot(..) => PermissionType::ChangeApplicationState,
+    }
+}
+
 fn check_command_permission(
     plugin_env: &PluginEnv,
     command: &PluginCommand,
@@ -1357,6 +1585,12 @@ fn check_command_permission(
         | PluginCommand::OpenCommandPaneInPlace(..)
         | PluginCommand::RunCommand(..)
         | PluginCommand::ExecCmd(..) => PermissionType::RunCommands,
+        // a new session's layout can run commands and its env is given to all of them
+        PluginCommand::SwitchSession(connect_to_session)
+            if connect_to_session.layout.is_some() || !connect_to_session.env.is_empty() =>
+        {
+            PermissionType::RunCommands
+        },
         PluginCommand::WebRequest(..) => PermissionType::WebAccess,
         PluginCommand::Write(..) | PluginCommand::WriteChars(..) => PermissionType::WriteToStdin,
         PluginCommand::SwitchTabTo(..)
@@ -1406,11 +1640,15 @@ fn check_command_permission(
         | PluginCommand::DeleteDeadSession(..)
         | PluginCommand::DeleteAllDeadSessions
         | PluginCommand::RenameSession(..)
+        | PluginCommand::SignalTerminalPane(..)
+        | PluginCommand::MoveTab(..)
         | PluginCommand::RenameTab(..) => PermissionType::ChangeApplicationState,
+        PluginCommand::GetResurrectableSessionLayout(..) => PermissionType::ReadApplicationState,
         PluginCommand::UnblockCliPipeInput(..)
         | PluginCommand::BlockCliPipeInput(..)
         | PluginCommand::CliPipeOutput(..) => PermissionType::ReadCliPipes,
         PluginCommand::MessageToPlugin(..) => PermissionType::MessageAndLaunchOtherPlugins,
+        PluginCommand::RunAction(action) => action_permission(action),
         _ => return (PermissionStatus::Granted, None),
     };
 
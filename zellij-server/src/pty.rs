@@ -9,26 +9,89 @@ use crate::{
     ClientId, ServerInstruction,
 };
 use async_std::task::{self, JoinHandle};
-use std::{collections::HashMap, os::unix::io::RawFd, path::PathBuf};
+use serde::Serialize;
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    os::unix::io::RawFd,
+    path::PathBuf,
+};
 use zellij_utils::nix::unistd::Pid;
 use zellij_utils::{
     async_std,
+    data::{PaneInfo, PaneSignal},
     errors::prelude::*,
     errors::{ContextType, PtyContext},
     input::{
         command::{RunCommand, TerminalAction},
         layout::{
-            FloatingPaneLayout, Layout, PluginUserConfiguration, Run, RunPlugin, RunPluginLocation,
-            TiledPaneLayout,
+            FloatingPaneLayout, Layout, PaneDependency, PluginUserConfiguration, ReadyCondition,
+            Run, RunPlugin, RunPluginLocation, TiledPaneLayout,
         },
     },
     pane_size::Size,
-    session_serialization,
+    serde, session_serialization,
 };
 
 pub type VteBytes = Vec<u8>;
 pub type TabIndex = u32;
 
+/// A pane as listed by `zellij action list-panes`
+#[derive(Clone, Debug, Serialize)]
+#[serde(crate = "self::serde")]
+pub struct PaneListing {
+    pub tab_position: usize,
+    pub tab_name: String,
+    #[serde(flatten)]
+    pub pane: PaneInfo,
+    pub cwd: Option<PathBuf>,
+}
+
+impl fmt::Display for PaneListing {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let pane_id = if self.pane.is_plugin {
+            format!("plugin_{}", self.pane.id)
+        } else {
+            format!("terminal_{}", self.pane.id)
+        };
+        let command = self
+            .pane
+            .terminal_command
+            .as_ref()
+            .or(self.pane.plugin_url.as_ref())
+            .map(|c| c.as_str())
+            .unwrap_or("-");
+        let cwd = self
+            .cwd
+            .as_ref()
+            .map(|c| c.display().to_string())
+            .unwrap_or_else(|| "-".to_owned());
+        let mut flags = vec![];
+        if self.pane.is_focused {
+            flags.push("focused".to_owned());
+        }
+        if self.pane.is_floating {
+            flags.push("floating".to_owned());
+        }
+        if self.pane.exited {
+            match self.pane.exit_status {
+                Some(exit_status) => flags.push(format!("exited({})", exit_status)),
+                None => flags.push("exited".to_owned()),
+            }
+        }
+        write!(
+            f,
+            "{}\t{}\t{}\t{}\t{}\t{}",
+            pane_id,
+            self.tab_name,
+            self.pane.title,
+            command,
+            cwd,
+            flags.join(",")
+        )
+    }
+}
+
 #[derive(Clone, Copy, Debug)]
 pub enum ClientTabIndexOrPaneId {
     ClientId(ClientId),
@@ -67,6 +130,13 @@ pub enum PtyInstruction {
     ClosePane(PaneId),
     CloseTab(Vec<PaneId>),
     ReRunCommandInPane(PaneId, RunCommand),
+    CommandExited(PaneId, Option<i32>, RunCommand, Option<usize>), // Option<i32> is the exit
+    // status, Option<usize> is the tab index
+    RestartCommandInPane(PaneId, RunCommand),
+    PaneOutputMatched(u32, u32), // terminal id whose output matched the pattern the pane with the
+    // second terminal id is waiting for
+    SignalPane(u32, PaneSignal), // u32 is the terminal id
+    SamplePaneProcesses,
     DropToShellInPane {
         pane_id: PaneId,
         shell: Option<PathBuf>,
@@ -79,6 +149,8 @@ pub enum PtyInstruction {
     ), // String is an optional pane name
     DumpLayout(SessionLayoutMetadata, ClientId),
     LogLayoutToHd(SessionLayoutMetadata),
+    SaveSnapshot(SessionLayoutMetadata, String, ClientId), // String is the name of the snapshot
+    ListPanes(Vec<PaneListing>, bool, ClientId),           // bool is whether to list them as JSON
     FillPluginCwd(
         Option<bool>,   // should float
         bool,           // should be opened in place
@@ -106,11 +178,18 @@ impl From<&PtyInstruction> for PtyContext {
             PtyInstruction::CloseTab(_) => PtyContext::CloseTab,
             PtyInstruction::NewTab(..) => PtyContext::NewTab,
             PtyInstruction::ReRunCommandInPane(..) => PtyContext::ReRunCommandInPane,
+            PtyInstruction::CommandExited(..) => PtyContext::CommandExited,
+            PtyInstruction::RestartCommandInPane(..) => PtyContext::RestartCommandInPane,
+            PtyInstruction::PaneOutputMatched(..) => PtyContext::PaneOutputMatched,
+            PtyInstruction::SignalPane(..) => PtyContext::SignalPane,
+            PtyInstruction::SamplePaneProcesses => PtyContext::SamplePaneProcesses,
             PtyInstruction::DropToShellInPane { .. } => PtyContext::DropToShellInPane,
             PtyInstruction::SpawnInPlaceTerminal(..) => PtyContext::SpawnInPlaceTerminal,
             PtyInstruction::DumpLayout(..) => PtyContext::DumpLayout,
             PtyInstruction::LogLayoutToHd(..) => PtyContext::LogLayoutToHd,
+            PtyInstruction::SaveSnapshot(..) => PtyContext::SaveSnapshot,
             PtyInstruction::FillPluginCwd(..) => PtyContext::FillPluginCwd,
+            PtyInstruction::ListPanes(..) => PtyContext::ListPanes,
             PtyInstruction::Exit => PtyContext::Exit,
         }
     }
@@ -123,6 +202,11 @@ pub(crate) struct Pty {
     debug_to_file: bool,
     task_handles: HashMap<u32, JoinHandle<()>>, // terminal_id to join-handle
     default_editor: Option<PathBuf>,
+    // git branches are only looked up when they are shown in the pane frames
+    sample_git_branches: bool,
+    restart_counts: HashMap<u32, usize>, // terminal_id => times its command was restarted
+    pane_dependencies: HashMap<u32, (u32, ReadyCondition)>, // terminal_id => (terminal_id it is
+                                         // waiting for, readiness condition)
 }
 
 pub(crate) fn pty_thread_main(mut pty: Pty, layout: Box<Layout>) -> Result<()> {
@@ -513,44 +597,51 @@ pub(crate) fn pty_thread_main(mut pty: Pty, layout: Box<Layout>) -> Result<()> {
                     .context("failed to close tabs")?;
             },
             PtyInstruction::ReRunCommandInPane(pane_id, run_command) => {
-                let err_context = || format!("failed to rerun command in pane {:?}", pane_id);
-
-                match pty
-                    .rerun_command_in_pane(pane_id, run_command.clone())
-                    .with_context(err_context)
-                {
-                    Ok(..) => {},
-                    Err(err) => match err.downcast_ref::<ZellijError>() {
-                        Some(ZellijError::CommandNotFound { terminal_id, .. }) => {
-                            if run_command.hold_on_close {
-                                pty.bus
-                                    .senders
-                                    .send_to_screen(ScreenInstruction::PtyBytes(
-                                        *terminal_id,
-                                        format!(
-                                            "Command not found: {}",
-                                            run_command.command.display()
-                                        )
-                                        .as_bytes()
-                                        .to_vec(),
-                                    ))
-                                    .with_context(err_context)?;
-                                pty.bus
-                                    .senders
-                                    .send_to_screen(ScreenInstruction::HoldPane(
-                                        PaneId::Terminal(*terminal_id),
-                                        Some(2), // exit status
-                                        run_command,
-                                        None,
-                                        None,
-                                    ))
-                                    .with_context(err_context)?;
-                            }
-                        },
-                        _ => Err::<(), _>(err).non_fatal(),
-                    },
+                rerun_command_in_pane_or_report_error(&mut pty, pane_id, run_command)?;
+            },
+            PtyInstruction::CommandExited(pane_id, exit_status, run_command, tab_index) => {
+                pty.handle_command_exit(pane_id, exit_status, run_command, tab_index)
+                    .with_context(|| format!("failed to handle exit of pane {:?}", pane_id))?;
+            },
+            PtyInstruction::RestartCommandInPane(pane_id, run_command) => {
+                // the pane might have been closed while we were waiting to restart it
+                if pty.is_pending_restart(pane_id) {
+                    rerun_command_in_pane_or_report_error(&mut pty, pane_id, run_command)?;
                 }
             },
+            PtyInstruction::PaneOutputMatched(terminal_id, dependent_terminal_id) => {
+                pty.start_panes_waiting_for(terminal_id, |waiting_terminal_id, ready_when| {
+                    waiting_terminal_id == dependent_terminal_id
+                        && matches!(ready_when, ReadyCondition::OutputMatches(..))
+                })
+                .with_context(|| format!("failed to start panes waiting for {terminal_id}"))?;
+            },
+            PtyInstruction::SignalPane(terminal_id, signal) => {
+                pty.signal_pane(terminal_id, signal)
+                    .with_context(|| format!("failed to signal pane {terminal_id}"))
+                    .non_fatal();
+            },
+            PtyInstruction::SamplePaneProcesses => {
+                pty.sample_pane_processes()
+                    .with_context(|| "failed to sample pane processes".to_string())
+                    .non_fatal();
+            },
+            PtyInstruction::ListPanes(mut pane_listings, json, client_id) => {
+                pty.populate_pane_listing_cwds(&mut pane_listings);
+                let lines = if json {
+                    match serde_json::to_string(&pane_listings) {
+                        Ok(json) => vec![json],
+                        Err(e) => vec![format!("Failed to serialize panes: {}", e)],
+                    }
+                } else {
+                    pane_listings.iter().map(|p| p.to_string()).collect()
+                };
+                pty.bus
+                    .senders
+                    .send_to_server(ServerInstruction::Log(lines, client_id))
+                    .with_context(|| "failed to list panes".to_string())
+                    .non_fatal();
+            },
             PtyInstruction::DropToShellInPane {
                 pane_id,
                 shell,
@@ -645,6 +736,34 @@ pub(crate) fn pty_thread_main(mut pty: Pty, layout: Box<Layout>) -> Result<()> {
                     },
                 }
             },
+            PtyInstruction::SaveSnapshot(mut session_layout_metadata, snapshot_name, client_id) => {
+                let err_context = || "Failed to save snapshot".to_string();
+                pty.populate_session_layout_metadata(&mut session_layout_metadata);
+                match session_serialization::serialize_session_layout(
+                    session_layout_metadata.into(),
+                ) {
+                    Ok(kdl_layout_and_pane_contents) => {
+                        pty.bus
+                            .senders
+                            .send_to_background_jobs(BackgroundJob::SaveSnapshot(
+                                snapshot_name,
+                                kdl_layout_and_pane_contents,
+                                client_id,
+                            ))
+                            .with_context(err_context)?;
+                    },
+                    Err(e) => {
+                        pty.bus
+                            .senders
+                            .send_to_server(ServerInstruction::LogError(
+                                vec![e.to_owned()],
+                                client_id,
+                            ))
+                            .with_context(err_context)
+                            .non_fatal();
+                    },
+                }
+            },
             PtyInstruction::FillPluginCwd(
                 should_float,
                 should_be_open_in_place,
@@ -679,6 +798,7 @@ impl Pty {
         bus: Bus<PtyInstruction>,
         debug_to_file: bool,
         default_editor: Option<PathBuf>,
+        sample_git_branches: bool,
     ) -> Self {
         Pty {
             active_panes: HashMap::new(),
@@ -687,6 +807,9 @@ impl Pty {
             debug_to_file,
             task_handles: HashMap::new(),
             default_editor,
+            restart_counts: HashMap::new(),
+            pane_dependencies: HashMap::new(),
+            sample_git_branches,
         }
     }
     pub fn get_default_terminal(
@@ -723,6 +846,7 @@ impl Pty {
                     cwd, // note: this might also be filled by the calling function, eg. spawn_terminal
                     hold_on_close: false,
                     hold_on_start: false,
+                    ..Default::default()
                 })
             },
         }
@@ -786,11 +910,9 @@ impl Pty {
                 terminal_action
             },
         };
-        let (hold_on_start, hold_on_close) = match &terminal_action {
-            TerminalAction::RunCommand(run_command) => {
-                (run_command.hold_on_start, run_command.hold_on_close)
-            },
-            _ => (false, false),
+        let hold_on_start = match &terminal_action {
+            TerminalAction::RunCommand(run_command) => run_command.hold_on_start,
+            _ => false,
         };
 
         if hold_on_start {
@@ -806,22 +928,7 @@ impl Pty {
             return Ok((terminal_id, starts_held));
         }
 
-        let quit_cb = Box::new({
-            let senders = self.bus.senders.clone();
-            move |pane_id, exit_status, command| {
-                if hold_on_close {
-                    let _ = senders.send_to_screen(ScreenInstruction::HoldPane(
-                        pane_id,
-                        exit_status,
-                        command,
-                        None,
-                        None,
-                    ));
-                } else {
-                    let _ = senders.send_to_screen(ScreenInstruction::ClosePane(pane_id, None));
-                }
-            }
-        });
+        let quit_cb = command_exit_callback(self.bus.senders.clone(), None);
         let (terminal_id, pid_primary, child_fd): (u32, RawFd, RawFd) = self
             .bus
             .os_input
@@ -872,11 +979,21 @@ impl Pty {
         let mut default_shell =
             default_shell.unwrap_or_else(|| self.get_default_terminal(cwd, None));
         self.fill_cwd(&mut default_shell, client_id);
-        let extracted_run_instructions = layout.extract_run_instructions();
-        let extracted_floating_run_instructions = floating_panes_layout
+        let extracted_run_instructions = layout.extract_run_instructions_and_dependencies();
+        let extracted_floating_run_instructions: Vec<_> = floating_panes_layout
             .iter()
             .filter(|f| !f.already_running)
-            .map(|f| f.run.clone());
+            .map(|f| (f.run.clone(), f.name.clone(), f.depends_on.clone()))
+            .collect();
+        // panes can only wait for named command panes in the same layout
+        let command_pane_names: HashSet<String> = extracted_run_instructions
+            .iter()
+            .chain(extracted_floating_run_instructions.iter())
+            .filter(|(run, _, _)| matches!(run, Some(Run::Command(_))))
+            .filter_map(|(_, name, _)| name.clone())
+            .collect();
+        let mut command_pane_ids: HashMap<String, u32> = HashMap::new();
+        let mut pane_dependencies: Vec<(u32, PaneDependency)> = vec![];
         let mut new_pane_pids: Vec<(u32, bool, Option<RunCommand>, Result<RawFd>)> = vec![]; // (terminal_id,
                                                                                              // starts_held,
                                                                                              // run_command,
@@ -885,17 +1002,47 @@ impl Pty {
             vec![]; // same
                     // as
                     // new_pane_pids
-        for run_instruction in extracted_run_instructions {
-            if let Some(new_pane_data) =
-                self.apply_run_instruction(run_instruction, default_shell.clone(), tab_index)?
-            {
+        for (run_instruction, pane_name, pane_dependency) in extracted_run_instructions {
+            let pane_dependency = pane_dependency.filter(|d| {
+                command_pane_names.contains(&d.pane_name)
+                    && Some(&d.pane_name) != pane_name.as_ref()
+            });
+            let is_command = matches!(run_instruction, Some(Run::Command(_)));
+            if let Some(new_pane_data) = self.apply_run_instruction(
+                run_instruction,
+                default_shell.clone(),
+                tab_index,
+                pane_dependency.is_some(),
+            )? {
+                let terminal_id = new_pane_data.0;
+                if let (Some(pane_name), true) = (pane_name, is_command) {
+                    command_pane_ids.insert(pane_name, terminal_id);
+                }
+                if let Some(pane_dependency) = pane_dependency {
+                    pane_dependencies.push((terminal_id, pane_dependency));
+                }
                 new_pane_pids.push(new_pane_data);
             }
         }
-        for run_instruction in extracted_floating_run_instructions {
-            if let Some(new_pane_data) =
-                self.apply_run_instruction(run_instruction, default_shell.clone(), tab_index)?
-            {
+        for (run_instruction, pane_name, pane_dependency) in extracted_floating_run_instructions {
+            let pane_dependency = pane_dependency.filter(|d| {
+                command_pane_names.contains(&d.pane_name)
+                    && Some(&d.pane_name) != pane_name.as_ref()
+            });
+            let is_command = matches!(run_instruction, Some(Run::Command(_)));
+            if let Some(new_pane_data) = self.apply_run_instruction(
+                run_instruction,
+                default_shell.clone(),
+                tab_index,
+                pane_dependency.is_some(),
+            )? {
+                let terminal_id = new_pane_data.0;
+                if let (Some(pane_name), true) = (pane_name, is_command) {
+                    command_pane_ids.insert(pane_name, terminal_id);
+                }
+                if let Some(pane_dependency) = pane_dependency {
+                    pane_dependencies.push((terminal_id, pane_dependency));
+                }
                 new_floating_panes_pids.push(new_pane_data);
             }
         }
@@ -932,9 +1079,16 @@ impl Pty {
                 client_id,
             ))
             .with_context(err_context)?;
+        for (terminal_id, pane_dependency) in pane_dependencies {
+            if let Some(waiting_for) = command_pane_ids.get(&pane_dependency.pane_name) {
+                self.wait_for_pane(terminal_id, *waiting_for, pane_dependency)
+                    .with_context(err_context)?;
+            }
+        }
         let mut terminals_to_start = vec![];
         terminals_to_start.append(&mut new_pane_pids);
         terminals_to_start.append(&mut new_floating_panes_pids);
+        let mut started_terminals = vec![];
         for (terminal_id, starts_held, run_command, pid_primary) in terminals_to_start {
             if starts_held {
                 // we do not run a command or start listening for bytes on held panes
@@ -942,6 +1096,7 @@ impl Pty {
             }
             match pid_primary {
                 Ok(pid_primary) => {
+                    started_terminals.push(terminal_id);
                     let terminal_bytes = task::spawn({
                         let senders = self.bus.senders.clone();
                         let os_input = self
@@ -989,6 +1144,12 @@ impl Pty {
                 },
             }
         }
+        for terminal_id in started_terminals {
+            self.start_panes_waiting_for(terminal_id, |_, ready_when| {
+                ready_when == &ReadyCondition::Started
+            })
+            .with_context(err_context)?;
+        }
         Ok(())
     }
     fn apply_run_instruction(
@@ -996,6 +1157,7 @@ impl Pty {
         run_instruction: Option<Run>,
         default_shell: TerminalAction,
         tab_index: usize,
+        waits_for_dependency: bool,
     ) -> Result<Option<(u32, bool, Option<RunCommand>, Result<i32>)>> {
         // terminal_id,
         // starts_held,
@@ -1010,25 +1172,9 @@ impl Pty {
         });
         match run_instruction {
             Some(Run::Command(mut command)) => {
-                let starts_held = command.hold_on_start;
-                let hold_on_close = command.hold_on_close;
-                let quit_cb = Box::new({
-                    let senders = self.bus.senders.clone();
-                    move |pane_id, exit_status, command| {
-                        if hold_on_close {
-                            let _ = senders.send_to_screen(ScreenInstruction::HoldPane(
-                                pane_id,
-                                exit_status,
-                                command,
-                                Some(tab_index),
-                                None,
-                            ));
-                        } else {
-                            let _ =
-                                senders.send_to_screen(ScreenInstruction::ClosePane(pane_id, None));
-                        }
-                    }
-                });
+                // panes waiting for another pane are started by us once it is ready
+                let starts_held = command.hold_on_start || waits_for_dependency;
+                let quit_cb = command_exit_callback(self.bus.senders.clone(), Some(tab_index));
                 if command.cwd.is_none() {
                     if let TerminalAction::RunCommand(cmd) = default_shell {
                         command.cwd = cmd.cwd;
@@ -1110,6 +1256,35 @@ impl Pty {
                     },
                 }
             },
+            Some(Run::Shell(run_shell)) => {
+                let starts_held = false; // we do not hold shell panes
+                let mut shell =
+                    self.get_default_terminal(run_shell.cwd, Some(default_shell.clone()));
+                if let TerminalAction::RunCommand(command) = &mut shell {
+                    command.env = run_shell.env;
+                    command.env_file = run_shell.env_file;
+                }
+                match self
+                    .bus
+                    .os_input
+                    .as_mut()
+                    .context("no OS I/O interface found")
+                    .with_context(err_context)?
+                    .spawn_terminal(shell, quit_cb, self.default_editor.clone())
+                    .with_context(err_context)
+                {
+                    Ok((terminal_id, pid_primary, child_fd)) => {
+                        self.id_to_child_pid.insert(terminal_id, child_fd);
+                        Ok(Some((terminal_id, starts_held, None, Ok(pid_primary))))
+                    },
+                    Err(err) => match err.downcast_ref::<ZellijError>() {
+                        Some(ZellijError::CommandNotFound { terminal_id, .. }) => {
+                            Ok(Some((*terminal_id, starts_held, None, Err(err))))
+                        },
+                        _ => Err(err),
+                    },
+                }
+            },
             Some(Run::EditFile(path_to_file, line_number, cwd)) => {
                 let starts_held = false; // we do not hold edit panes (for now?)
                 match self
@@ -1169,6 +1344,8 @@ impl Pty {
         match id {
             PaneId::Terminal(id) => {
                 self.task_handles.remove(&id);
+                self.restart_counts.remove(&id);
+                self.pane_dependencies.remove(&id);
                 if let Some(child_fd) = self.id_to_child_pid.remove(&id) {
                     task::block_on(async {
                         let err_context = || format!("failed to run async task for pane {id}");
@@ -1220,25 +1397,10 @@ impl Pty {
             PaneId::Terminal(id) => {
                 let _ = self.task_handles.remove(&id); // if all is well, this shouldn't be here
                 let _ = self.id_to_child_pid.remove(&id); // if all is wlel, this shouldn't be here
+                                                          // if this pane was waiting for another pane, it was started manually
+                let _ = self.pane_dependencies.remove(&id);
 
-                let hold_on_close = run_command.hold_on_close;
-                let quit_cb = Box::new({
-                    let senders = self.bus.senders.clone();
-                    move |pane_id, exit_status, command| {
-                        if hold_on_close {
-                            let _ = senders.send_to_screen(ScreenInstruction::HoldPane(
-                                pane_id,
-                                exit_status,
-                                command,
-                                None,
-                                None,
-                            ));
-                        } else {
-                            let _ =
-                                senders.send_to_screen(ScreenInstruction::ClosePane(pane_id, None));
-                        }
-                    }
-                });
+                let quit_cb = command_exit_callback(self.bus.senders.clone(), None);
                 let (pid_primary, child_fd): (RawFd, RawFd) = self
                     .bus
                     .os_input
@@ -1271,11 +1433,217 @@ impl Pty {
 
                 self.task_handles.insert(id, terminal_bytes);
                 self.id_to_child_pid.insert(id, child_fd);
+                self.start_panes_waiting_for(id, |_, ready_when| {
+                    ready_when == &ReadyCondition::Started
+                })
+                .with_context(err_context)?;
                 Ok(())
             },
             _ => Err(anyhow!("cannot respawn plugin panes")).with_context(err_context),
         }
     }
+    pub fn handle_command_exit(
+        &mut self,
+        pane_id: PaneId,
+        exit_status: Option<i32>,
+        run_command: RunCommand,
+        tab_index: Option<usize>,
+    ) -> Result<()> {
+        let err_context = || format!("failed to handle command exit in pane {:?}", pane_id);
+        let terminal_id = match pane_id {
+            PaneId::Terminal(terminal_id) => terminal_id,
+            PaneId::Plugin(_) => return Ok(()),
+        };
+        if exit_status == Some(0) {
+            self.start_panes_waiting_for(terminal_id, |_, ready_when| {
+                ready_when == &ReadyCondition::ExitedSuccessfully
+            })
+            .with_context(err_context)?;
+        }
+        let restart_count = self.restart_counts.get(&terminal_id).copied().unwrap_or(0);
+        if run_command.should_restart(exit_status, restart_count) {
+            let delay = run_command.restart_delay(restart_count);
+            let restart_count = restart_count + 1;
+            self.restart_counts.insert(terminal_id, restart_count);
+            self.bus
+                .senders
+                .send_to_screen(ScreenInstruction::PtyBytes(
+                    terminal_id,
+                    format!(
+                        "\n\r[{}, restarting in {:.1}s]\n\r",
+                        exit_status
+                            .map(|e| format!("Exited with code {}", e))
+                            .unwrap_or_else(|| "Exited".to_owned()),
+                        delay.as_secs_f64()
+                    )
+                    .as_bytes()
+                    .to_vec(),
+                ))
+                .with_context(err_context)?;
+            self.bus
+                .senders
+                .send_to_screen(ScreenInstruction::UpdatePaneRestarts(
+                    pane_id,
+                    restart_count,
+                    exit_status,
+                ))
+                .with_context(err_context)?;
+            task::spawn({
+                let senders = self.bus.senders.clone();
+                async move {
+                    task::sleep(delay).await;
+                    let _ = senders
+                        .send_to_pty(PtyInstruction::RestartCommandInPane(pane_id, run_command));
+                }
+            });
+        } else {
+            // we're done restarting, if the user re-runs this command manually it starts with a
+            // clean slate
+            self.restart_counts.remove(&terminal_id);
+            if run_command.hold_on_close {
+                self.bus
+                    .senders
+                    .send_to_screen(ScreenInstruction::HoldPane(
+                        pane_id,
+                        exit_status,
+                        run_command,
+                        tab_index,
+                        None,
+                    ))
+                    .with_context(err_context)?;
+            } else {
+                self.bus
+                    .senders
+                    .send_to_screen(ScreenInstruction::ClosePane(pane_id, None))
+                    .with_context(err_context)?;
+            }
+        }
+        Ok(())
+    }
+    pub fn wait_for_pane(
+        &mut self,
+        terminal_id: u32,
+        waiting_for: u32,
+        pane_dependency: PaneDependency,
+    ) -> Result<()> {
+        let err_context = || format!("failed to make pane {terminal_id} wait for {waiting_for}");
+        if let ReadyCondition::OutputMatches(pattern) = &pane_dependency.ready_when {
+            self.bus
+                .senders
+                .send_to_screen(ScreenInstruction::WatchPaneOutput(
+                    waiting_for,
+                    terminal_id,
+                    pattern.clone(),
+                ))
+                .with_context(err_context)?;
+        }
+        self.bus
+            .senders
+            .send_to_screen(ScreenInstruction::UpdatePaneBlockedOn(
+                PaneId::Terminal(terminal_id),
+                Some(pane_dependency.to_string()),
+            ))
+            .with_context(err_context)?;
+        self.pane_dependencies
+            .insert(terminal_id, (waiting_for, pane_dependency.ready_when));
+        Ok(())
+    }
+    pub fn start_panes_waiting_for(
+        &mut self,
+        terminal_id: u32,
+        is_ready: impl Fn(u32, &ReadyCondition) -> bool,
+    ) -> Result<()> {
+        let err_context = || format!("failed to start panes waiting for {terminal_id}");
+        let ready_panes: Vec<u32> = self
+            .pane_dependencies
+            .iter()
+            .filter(|(dependent_terminal_id, (waiting_for, ready_when))| {
+                *waiting_for == terminal_id && is_ready(**dependent_terminal_id, ready_when)
+            })
+            .map(|(dependent_terminal_id, _)| *dependent_terminal_id)
+            .collect();
+        for dependent_terminal_id in ready_panes {
+            self.pane_dependencies.remove(&dependent_terminal_id);
+            // the screen un-holds the pane and asks us to run its command
+            self.bus
+                .senders
+                .send_to_screen(ScreenInstruction::RunHeldPane(PaneId::Terminal(
+                    dependent_terminal_id,
+                )))
+                .with_context(err_context)?;
+        }
+        Ok(())
+    }
+    pub fn signal_pane(&self, terminal_id: u32, signal: PaneSignal) -> Result<()> {
+        let err_context = || format!("failed to send {signal} to pane {terminal_id}");
+
+        // a held command pane has no running process to signal
+        let child_fd = self
+            .id_to_child_pid
+            .get(&terminal_id)
+            .with_context(|| format!("no running process found for terminal {terminal_id}"))
+            .with_context(err_context)?;
+        self.bus
+            .os_input
+            .as_ref()
+            .context("no OS I/O interface found")
+            .and_then(|os_input| {
+                os_input.signal_terminal(terminal_id, Pid::from_raw(*child_fd), signal)
+            })
+            .with_context(err_context)
+    }
+    pub fn sample_pane_processes(&self) -> Result<()> {
+        let terminals: Vec<(u32, Pid)> = self
+            .id_to_child_pid
+            .iter()
+            .map(|(terminal_id, child_fd)| (*terminal_id, Pid::from_raw(*child_fd)))
+            .collect();
+        if terminals.is_empty() {
+            return Ok(());
+        }
+        let process_infos = self
+            .bus
+            .os_input
+            .as_ref()
+            .map(|os_input| os_input.get_pane_process_infos(terminals, self.sample_git_branches))
+            .unwrap_or_default();
+        self.bus
+            .senders
+            .send_to_screen(ScreenInstruction::UpdatePaneProcessInfos(process_infos))
+            .with_context(|| "failed to report pane process infos".to_string())
+    }
+    pub fn populate_pane_listing_cwds(&self, pane_listings: &mut [PaneListing]) {
+        let terminal_pids: HashMap<u32, Pid> = pane_listings
+            .iter()
+            .filter(|p| !p.pane.is_plugin)
+            .filter_map(|p| {
+                self.id_to_child_pid
+                    .get(&p.pane.id)
+                    .map(|pid| (p.pane.id, Pid::from_raw(*pid)))
+            })
+            .collect();
+        let pids_to_cwds = self
+            .bus
+            .os_input
+            .as_ref()
+            .map(|os_input| os_input.get_cwds(terminal_pids.values().copied().collect()))
+            .unwrap_or_default();
+        for pane_listing in pane_listings.iter_mut() {
+            if pane_listing.pane.is_plugin {
+                continue;
+            }
+            pane_listing.cwd = terminal_pids
+                .get(&pane_listing.pane.id)
+                .and_then(|pid| pids_to_cwds.get(pid))
+                .cloned();
+        }
+    }
+    pub fn is_pending_restart(&self, pane_id: PaneId) -> bool {
+        match pane_id {
+            PaneId::Terminal(terminal_id) => self.restart_counts.contains_key(&terminal_id),
+            PaneId::Plugin(_) => false,
+        }
+    }
     pub fn populate_session_layout_metadata(
         &self,
         session_layout_metadata: &mut SessionLayoutMetadata,
@@ -1374,6 +1742,64 @@ impl Drop for Pty {
     }
 }
 
+fn rerun_command_in_pane_or_report_error(
+    pty: &mut Pty,
+    pane_id: PaneId,
+    run_command: RunCommand,
+) -> Result<()> {
+    let err_context = || format!("failed to rerun command in pane {:?}", pane_id);
+
+    match pty
+        .rerun_command_in_pane(pane_id, run_command.clone())
+        .with_context(err_context)
+    {
+        Ok(..) => {},
+        Err(err) => match err.downcast_ref::<ZellijError>() {
+            Some(ZellijError::CommandNotFound { terminal_id, .. }) => {
+                if run_command.hold_on_close {
+                    pty.bus
+                        .senders
+                        .send_to_screen(ScreenInstruction::PtyBytes(
+                            *terminal_id,
+                            format!("Command not found: {}", run_command.command.display())
+                                .as_bytes()
+                                .to_vec(),
+                        ))
+                        .with_context(err_context)?;
+                    pty.bus
+                        .senders
+                        .send_to_screen(ScreenInstruction::HoldPane(
+                            PaneId::Terminal(*terminal_id),
+                            Some(2), // exit status
+                            run_command,
+                            None,
+                            None,
+                        ))
+                        .with_context(err_context)?;
+                }
+            },
+            _ => Err::<(), _>(err).non_fatal(),
+        },
+    }
+    Ok(())
+}
+
+fn command_exit_callback(
+    senders: ThreadSenders,
+    tab_index: Option<usize>,
+) -> Box<dyn Fn(PaneId, Option<i32>, RunCommand) + Send> {
+    // the pty thread keeps track of restarts and of panes waiting for this one, and decides
+    // whether to hold or close the pane
+    Box::new(move |pane_id, exit_status, command| {
+        let _ = senders.send_to_pty(PtyInstruction::CommandExited(
+            pane_id,
+            exit_status,
+            command,
+            tab_index,
+        ));
+    })
+}
+
 fn send_command_not_found_to_screen(
     senders: ThreadSenders,
     terminal_id: u32,
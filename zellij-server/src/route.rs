@@ -7,7 +7,7 @@ use crate::{
     panes::PaneId,
     plugins::PluginInstruction,
     pty::{ClientTabIndexOrPaneId, PtyInstruction},
-    screen::ScreenInstruction,
+    screen::{PaneOperation, ScreenInstruction, TabOperation},
     ServerInstruction, SessionMetaData, SessionState,
 };
 use zellij_utils::{
@@ -15,7 +15,7 @@ use zellij_utils::{
     data::{Direction, Event, PluginCapabilities, ResizeStrategy},
     errors::prelude::*,
     input::{
-        actions::{Action, SearchDirection, SearchOption},
+        actions::{Action, ActionTarget, SearchDirection, SearchOption},
         command::TerminalAction,
         get_mode_info,
         layout::Layout,
@@ -45,7 +45,7 @@ pub(crate) fn route_action(
     // this is a bit of a hack around the unfortunate architecture we use with plugins
     // this will change as soon as we refactor
     match action {
-        Action::MouseHoldLeft(..) | Action::MouseHoldRight(..) => {},
+        Action::MouseHoldLeft(..) | Action::MouseHoldRight(..) | Action::MouseMotion(..) => {},
         _ => {
             senders
                 .send_to_plugin(PluginInstruction::Update(vec![(
@@ -505,6 +505,11 @@ pub(crate) fn route_action(
                 .send_to_screen(ScreenInstruction::CloseTab(client_id))
                 .with_context(err_context)?;
         },
+        Action::MoveTab(direction) => {
+            senders
+                .send_to_screen(ScreenInstruction::MoveTab(direction, client_id))
+                .with_context(err_context)?;
+        },
         Action::GoToTab(i) => {
             senders
                 .send_to_screen(ScreenInstruction::GoToTab(i, Some(client_id)))
@@ -591,6 +596,11 @@ pub(crate) fn route_action(
                 .send_to_screen(ScreenInstruction::MouseHoldMiddle(point, client_id))
                 .with_context(err_context)?;
         },
+        Action::MouseMotion(point) => {
+            senders
+                .send_to_screen(ScreenInstruction::MouseMotion(point, client_id))
+                .with_context(err_context)?;
+        },
         Action::Copy => {
             senders
                 .send_to_screen(ScreenInstruction::Copy(client_id))
@@ -804,6 +814,52 @@ pub(crate) fn route_action(
                 .send_to_screen(ScreenInstruction::RenameSession(name, client_id))
                 .with_context(err_context)?;
         },
+        Action::SignalPane(terminal_id, signal) => {
+            senders
+                .send_to_screen(ScreenInstruction::SignalPane(
+                    terminal_id,
+                    signal,
+                    client_id,
+                ))
+                .with_context(err_context)?;
+        },
+        Action::ListClients => {
+            senders
+                .send_to_server(ServerInstruction::ListClients(client_id))
+                .with_context(err_context)?;
+        },
+        Action::ListPanes(json) => {
+            senders
+                .send_to_screen(ScreenInstruction::ListPanes(json, client_id))
+                .with_context(err_context)?;
+        },
+        Action::ListTabs(json) => {
+            senders
+                .send_to_screen(ScreenInstruction::ListTabs(json, client_id))
+                .with_context(err_context)?;
+        },
+        Action::Subscribe { events, .. } => {
+            senders
+                .send_to_plugin(PluginInstruction::CliSubscribe {
+                    event_types: events.into_iter().collect(),
+                    cli_client_id: client_id,
+                })
+                .with_context(err_context)?;
+        },
+        Action::Targeted(target, action) => {
+            route_targeted_action(*action, target, client_id, &senders)
+                .with_context(err_context)?;
+        },
+        Action::SaveSnapshot(snapshot_name) => {
+            senders
+                .send_to_screen(ScreenInstruction::SaveSnapshot(snapshot_name, client_id))
+                .with_context(err_context)?;
+        },
+        Action::KickClient(client_id_to_kick) => {
+            senders
+                .send_to_server(ServerInstruction::KickClient(client_id_to_kick, client_id))
+                .with_context(err_context)?;
+        },
         Action::CliPipe {
             pipe_id,
             mut name,
@@ -859,6 +915,67 @@ pub(crate) fn route_action(
     Ok(should_break)
 }
 
+fn route_targeted_action(
+    action: Action,
+    target: ActionTarget,
+    client_id: ClientId,
+    senders: &ThreadSenders,
+) -> Result<()> {
+    let err_context = || format!("failed to route targeted action for client {client_id}");
+
+    let apply_to_pane = |operation| {
+        Some(ScreenInstruction::ApplyToPane(
+            operation,
+            target.clone(),
+            client_id,
+        ))
+    };
+    let apply_to_tab = |operation| {
+        Some(ScreenInstruction::ApplyToTab(
+            operation,
+            target.clone(),
+            client_id,
+        ))
+    };
+    let screen_instruction = match action {
+        Action::Write(bytes) => apply_to_pane(PaneOperation::Write(bytes)),
+        Action::WriteChars(chars) => apply_to_pane(PaneOperation::Write(chars.into_bytes())),
+        Action::ScrollUp => apply_to_pane(PaneOperation::ScrollUp),
+        Action::ScrollDown => apply_to_pane(PaneOperation::ScrollDown),
+        Action::PageScrollUp => apply_to_pane(PaneOperation::PageScrollUp),
+        Action::PageScrollDown => apply_to_pane(PaneOperation::PageScrollDown),
+        Action::HalfPageScrollUp => apply_to_pane(PaneOperation::HalfPageScrollUp),
+        Action::HalfPageScrollDown => apply_to_pane(PaneOperation::HalfPageScrollDown),
+        Action::ScrollToBottom => apply_to_pane(PaneOperation::ScrollToBottom),
+        Action::ScrollToTop => apply_to_pane(PaneOperation::ScrollToTop),
+        Action::ClearScreen => apply_to_pane(PaneOperation::Clear),
+        Action::DumpScreen(file, full) => apply_to_pane(PaneOperation::DumpScreen(file, full)),
+        Action::PaneNameInput(bytes) => apply_to_pane(PaneOperation::Rename(bytes)),
+        Action::UndoRenamePane => apply_to_pane(PaneOperation::UndoRename),
+        Action::CloseFocus => apply_to_pane(PaneOperation::Close),
+        Action::SignalPane(_, signal) => apply_to_pane(PaneOperation::Signal(signal)),
+        Action::Resize(resize, direction) => apply_to_pane(PaneOperation::Resize(
+            ResizeStrategy::new(resize, direction),
+        )),
+        Action::TabNameInput(bytes) => apply_to_tab(TabOperation::NameInput(bytes)),
+        Action::UndoRenameTab => apply_to_tab(TabOperation::UndoRename),
+        Action::ToggleActiveSyncTab => apply_to_tab(TabOperation::ToggleSyncPanes),
+        Action::CloseTab => apply_to_tab(TabOperation::Close),
+        _ => None,
+    };
+    match screen_instruction {
+        Some(screen_instruction) => senders
+            .send_to_screen(screen_instruction)
+            .with_context(err_context),
+        None => senders
+            .send_to_server(ServerInstruction::LogError(
+                vec!["This action cannot be applied to a specific pane or tab".to_owned()],
+                client_id,
+            ))
+            .with_context(err_context),
+    }
+}
+
 // this should only be used for one-off startup instructions
 macro_rules! send_to_screen_or_retry_queue {
     ($rlocked_sessions:expr, $message:expr, $instruction: expr, $retry_queue:expr) => {{
@@ -875,6 +992,38 @@ macro_rules! send_to_screen_or_retry_queue {
     }};
 }
 
+// a read-only client can watch the session, leave it and switch its own input mode - any other
+// action it sends is dropped, including scrolling since panes have a single viewport shared by all
+// clients
+fn action_for_read_only_client(action: Action) -> Option<Action> {
+    match action {
+        // a viewer leaving should never end the session
+        Action::Quit => Some(Action::Detach),
+        Action::Targeted(target, action) => action_for_read_only_client(*action)
+            .map(|action| Action::Targeted(target, Box::new(action))),
+        Action::Detach
+        | Action::NoOp
+        | Action::SwitchToMode(..)
+        | Action::ListClients
+        | Action::ListPanes(..)
+        | Action::ListTabs(..)
+        | Action::Subscribe { .. } => Some(action),
+        _ => None,
+    }
+}
+
+fn restricted_client_may_send(client_id: ClientId, instruction: &ClientToServerMsg) -> bool {
+    // remote and read-only clients can only attach to the running session and detach themselves,
+    // they cannot start or kill it
+    match instruction {
+        ClientToServerMsg::NewClient(..) | ClientToServerMsg::KillSession => false,
+        ClientToServerMsg::DetachSession(client_ids) => client_ids
+            .iter()
+            .all(|detached_client_id| *detached_client_id == client_id),
+        _ => true,
+    }
+}
+
 pub(crate) fn route_thread_main(
     session_data: Arc<RwLock<Option<SessionMetaData>>>,
     session_state: Arc<RwLock<SessionState>>,
@@ -897,9 +1046,49 @@ pub(crate) fn route_thread_main(
                 >|
                  -> Result<bool> {
                     let mut should_break = false;
+                    let (client_is_remote, sender_is_read_only) = {
+                        let session_state =
+                            session_state.read().to_anyhow().with_context(err_context)?;
+                        (
+                            session_state.client_is_remote(client_id),
+                            session_state.client_is_read_only(client_id),
+                        )
+                    };
+                    let client_is_restricted = client_is_remote || sender_is_read_only;
+                    if client_is_restricted && !restricted_client_may_send(client_id, &instruction)
+                    {
+                        log::warn!("Ignoring message from restricted client {}", client_id);
+                        return Ok(should_break);
+                    }
                     match instruction {
                         ClientToServerMsg::Action(action, maybe_pane_id, maybe_client_id) => {
-                            let client_id = maybe_client_id.unwrap_or(client_id);
+                            // remote and read-only clients cannot act on behalf of other clients
+                            let client_id = if client_is_restricted {
+                                client_id
+                            } else {
+                                maybe_client_id.unwrap_or(client_id)
+                            };
+                            let client_is_read_only = session_state
+                                .read()
+                                .to_anyhow()
+                                .with_context(err_context)?
+                                .client_is_read_only(client_id);
+                            let action = if client_is_read_only {
+                                match action_for_read_only_client(action) {
+                                    Some(action) => action,
+                                    None => {
+                                        // the client might be waiting for this action to be
+                                        // performed before accepting more input
+                                        let _ = os_input.send_to_client(
+                                            client_id,
+                                            ServerToClientMsg::UnblockInputThread,
+                                        );
+                                        return Ok(should_break);
+                                    },
+                                }
+                            } else {
+                                action
+                            };
                             if let Some(rlocked_sessions) = rlocked_sessions.as_ref() {
                                 if let Action::SwitchToMode(input_mode) = action {
                                     let send_res = os_input.send_to_client(
@@ -990,6 +1179,24 @@ pub(crate) fn route_thread_main(
                             )
                             .with_context(err_context)?;
                         },
+                        ClientToServerMsg::LocalEchoPredicted(ref cells) => {
+                            send_to_screen_or_retry_queue!(
+                                rlocked_sessions,
+                                ScreenInstruction::LocalEchoPredicted(cells.clone(), client_id),
+                                instruction,
+                                retry_queue
+                            )
+                            .with_context(err_context)?;
+                        },
+                        ClientToServerMsg::LocalEchoExpired => {
+                            send_to_screen_or_retry_queue!(
+                                rlocked_sessions,
+                                ScreenInstruction::LocalEchoExpired(client_id),
+                                instruction,
+                                retry_queue
+                            )
+                            .with_context(err_context)?;
+                        },
                         ClientToServerMsg::NewClient(
                             client_attributes,
                             cli_args,
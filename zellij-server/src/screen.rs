@@ -8,7 +8,8 @@ use std::str;
 use std::time::Duration;
 
 use zellij_utils::data::{
-    Direction, PaneManifest, PluginPermission, Resize, ResizeStrategy, SessionInfo,
+    Direction, PaneManifest, PaneProcessInfo, PaneSignal, PluginPermission, Resize, ResizeStrategy,
+    SessionInfo,
 };
 use zellij_utils::errors::prelude::*;
 use zellij_utils::input::command::RunCommand;
@@ -23,6 +24,8 @@ use zellij_utils::{
         SwapFloatingLayout, SwapTiledLayout, TiledPaneLayout,
     },
     position::Position,
+    regex::Regex,
+    shared::strip_ansi_codes,
 };
 
 use crate::background_jobs::BackgroundJob;
@@ -32,11 +35,11 @@ use crate::panes::terminal_character::AnsiCode;
 use crate::session_layout_metadata::{PaneLayoutMetadata, SessionLayoutMetadata};
 
 use crate::{
-    output::Output,
+    output::{Output, RenderedFrame},
     panes::sixel::SixelImageStore,
     panes::PaneId,
     plugins::{PluginInstruction, PluginRenderAsset},
-    pty::{ClientTabIndexOrPaneId, PtyInstruction, VteBytes},
+    pty::{ClientTabIndexOrPaneId, PaneListing, PtyInstruction, VteBytes},
     tab::Tab,
     thread_bus::Bus,
     ui::{
@@ -48,10 +51,13 @@ use crate::{
 use zellij_utils::{
     data::{Event, InputMode, ModeInfo, Palette, PaletteColor, PluginCapabilities, Style, TabInfo},
     errors::{ContextType, ScreenContext},
-    input::{get_mode_info, options::Options},
+    input::{actions::ActionTarget, get_mode_info, options::Options},
     ipc::{ClientAttributes, PixelDimensions, ServerToClientMsg},
 };
 
+// output lines longer than this are not matched against patterns panes are waiting for
+const MAX_UNTERMINATED_LINE_LENGTH: usize = 4096;
+
 /// Get the active tab and call a closure on it
 ///
 /// If no active tab can be found, an error is logged instead.
@@ -134,6 +140,36 @@ type InitialTitle = String;
 type ShouldFloat = bool;
 type HoldForCommand = Option<RunCommand>;
 
+/// Something that can be done to a pane without it being focused
+#[derive(Debug, Clone)]
+pub enum PaneOperation {
+    Write(Vec<u8>),
+    ScrollUp,
+    ScrollDown,
+    PageScrollUp,
+    PageScrollDown,
+    HalfPageScrollUp,
+    HalfPageScrollDown,
+    ScrollToBottom,
+    ScrollToTop,
+    Clear,
+    DumpScreen(String, bool), // String is the file path, bool is whether to dump the full scrollback
+    Rename(Vec<u8>),
+    UndoRename,
+    Close,
+    Signal(PaneSignal),
+    Resize(ResizeStrategy),
+}
+
+/// Something that can be done to a tab without it being focused
+#[derive(Debug, Clone)]
+pub enum TabOperation {
+    NameInput(Vec<u8>),
+    UndoRename,
+    ToggleSyncPanes,
+    Close,
+}
+
 /// Instructions that can be sent to the [`Screen`].
 #[derive(Debug, Clone)]
 pub enum ScreenInstruction {
@@ -199,6 +235,13 @@ pub enum ScreenInstruction {
         Option<usize>,
         Option<ClientId>,
     ), // Option<i32> is the exit status, Option<usize> is the tab_index
+    UpdatePaneRestarts(PaneId, usize, Option<i32>), // usize is the restart count, Option<i32> is
+    // the last exit status
+    UpdatePaneBlockedOn(PaneId, Option<String>), // String describes the pane this (held) pane is
+    // waiting for
+    RunHeldPane(PaneId),
+    WatchPaneOutput(u32, u32, String), // terminal id to watch, terminal id of the pane waiting
+    // for it, regex pattern
     UpdatePaneName(Vec<u8>, ClientId),
     UndoRenamePane(ClientId),
     NewTab(
@@ -232,6 +275,7 @@ pub enum ScreenInstruction {
         Option<ClientId>,
     ),
     ToggleTab(ClientId),
+    MoveTab(Direction, ClientId),
     UpdateTabName(Vec<u8>, ClientId),
     UndoRenameTab(ClientId),
     TerminalResize(Size),
@@ -250,6 +294,7 @@ pub enum ScreenInstruction {
     MouseHoldLeft(Position, ClientId),
     MouseHoldRight(Position, ClientId),
     MouseHoldMiddle(Position, ClientId),
+    MouseMotion(Position, ClientId),
     Copy(ClientId),
     AddClient(
         ClientId,
@@ -257,6 +302,10 @@ pub enum ScreenInstruction {
         Option<(u32, bool)>, // (pane_id, is_plugin) => pane_id to focus
     ),
     RemoveClient(ClientId),
+    EnableDiffRendering(ClientId),
+    ClientMissedRenders(ClientId),
+    LocalEchoPredicted(Vec<(usize, usize)>, ClientId), // (x, y) => cells the client drew over
+    LocalEchoExpired(ClientId),
     AddOverlay(Overlay, ClientId),
     RemoveOverlay(ClientId),
     ConfirmPrompt(ClientId),
@@ -272,6 +321,8 @@ pub enum ScreenInstruction {
     PreviousSwapLayout(ClientId),
     NextSwapLayout(ClientId),
     QueryTabNames(ClientId),
+    ListPanes(bool, ClientId), // bool is whether to list them as JSON
+    ListTabs(bool, ClientId),  // bool is whether to list them as JSON
     NewTiledPluginPane(RunPlugin, Option<String>, bool, ClientId), // Option<String> is
     // optional pane title, bool is skip cache
     NewFloatingPluginPane(RunPlugin, Option<String>, bool, ClientId), // Option<String> is an
@@ -320,7 +371,13 @@ pub enum ScreenInstruction {
         ClientTabIndexOrPaneId,
     ),
     DumpLayoutToHd,
+    SaveSnapshot(String, ClientId), // String is the name of the snapshot
     RenameSession(String, ClientId), // String -> new name
+    SignalPane(Option<u32>, PaneSignal, ClientId), // u32 is the terminal id, None is the
+    // focused pane
+    UpdatePaneProcessInfos(HashMap<u32, PaneProcessInfo>), // u32 is the terminal id
+    ApplyToPane(PaneOperation, ActionTarget, ClientId),
+    ApplyToTab(TabOperation, ActionTarget, ClientId),
 }
 
 impl From<&ScreenInstruction> for ScreenContext {
@@ -403,6 +460,10 @@ impl From<&ScreenInstruction> for ScreenContext {
             ScreenInstruction::SetSelectable(..) => ScreenContext::SetSelectable,
             ScreenInstruction::ClosePane(..) => ScreenContext::ClosePane,
             ScreenInstruction::HoldPane(..) => ScreenContext::HoldPane,
+            ScreenInstruction::UpdatePaneRestarts(..) => ScreenContext::UpdatePaneRestarts,
+            ScreenInstruction::UpdatePaneBlockedOn(..) => ScreenContext::UpdatePaneBlockedOn,
+            ScreenInstruction::RunHeldPane(..) => ScreenContext::RunHeldPane,
+            ScreenInstruction::WatchPaneOutput(..) => ScreenContext::WatchPaneOutput,
             ScreenInstruction::UpdatePaneName(..) => ScreenContext::UpdatePaneName,
             ScreenInstruction::UndoRenamePane(..) => ScreenContext::UndoRenamePane,
             ScreenInstruction::NewTab(..) => ScreenContext::NewTab,
@@ -441,10 +502,16 @@ impl From<&ScreenInstruction> for ScreenContext {
             ScreenInstruction::MouseHoldLeft(..) => ScreenContext::MouseHoldLeft,
             ScreenInstruction::MouseHoldRight(..) => ScreenContext::MouseHoldRight,
             ScreenInstruction::MouseHoldMiddle(..) => ScreenContext::MouseHoldMiddle,
+            ScreenInstruction::MouseMotion(..) => ScreenContext::MouseMotion,
             ScreenInstruction::Copy(..) => ScreenContext::Copy,
             ScreenInstruction::ToggleTab(..) => ScreenContext::ToggleTab,
+            ScreenInstruction::MoveTab(..) => ScreenContext::MoveTab,
             ScreenInstruction::AddClient(..) => ScreenContext::AddClient,
             ScreenInstruction::RemoveClient(..) => ScreenContext::RemoveClient,
+            ScreenInstruction::EnableDiffRendering(..) => ScreenContext::EnableDiffRendering,
+            ScreenInstruction::ClientMissedRenders(..) => ScreenContext::ClientMissedRenders,
+            ScreenInstruction::LocalEchoPredicted(..) => ScreenContext::LocalEchoPredicted,
+            ScreenInstruction::LocalEchoExpired(..) => ScreenContext::LocalEchoExpired,
             ScreenInstruction::AddOverlay(..) => ScreenContext::AddOverlay,
             ScreenInstruction::RemoveOverlay(..) => ScreenContext::RemoveOverlay,
             ScreenInstruction::ConfirmPrompt(..) => ScreenContext::ConfirmPrompt,
@@ -466,6 +533,8 @@ impl From<&ScreenInstruction> for ScreenContext {
             ScreenInstruction::PreviousSwapLayout(..) => ScreenContext::PreviousSwapLayout,
             ScreenInstruction::NextSwapLayout(..) => ScreenContext::NextSwapLayout,
             ScreenInstruction::QueryTabNames(..) => ScreenContext::QueryTabNames,
+            ScreenInstruction::ListPanes(..) => ScreenContext::ListPanes,
+            ScreenInstruction::ListTabs(..) => ScreenContext::ListTabs,
             ScreenInstruction::NewTiledPluginPane(..) => ScreenContext::NewTiledPluginPane,
             ScreenInstruction::NewFloatingPluginPane(..) => ScreenContext::NewFloatingPluginPane,
             ScreenInstruction::StartOrReloadPluginPane(..) => {
@@ -500,7 +569,12 @@ impl From<&ScreenInstruction> for ScreenContext {
             ScreenInstruction::ReplacePane(..) => ScreenContext::ReplacePane,
             ScreenInstruction::NewInPlacePluginPane(..) => ScreenContext::NewInPlacePluginPane,
             ScreenInstruction::DumpLayoutToHd => ScreenContext::DumpLayoutToHd,
+            ScreenInstruction::SaveSnapshot(..) => ScreenContext::SaveSnapshot,
             ScreenInstruction::RenameSession(..) => ScreenContext::RenameSession,
+            ScreenInstruction::SignalPane(..) => ScreenContext::SignalPane,
+            ScreenInstruction::UpdatePaneProcessInfos(..) => ScreenContext::UpdatePaneProcessInfos,
+            ScreenInstruction::ApplyToPane(..) => ScreenContext::ApplyToPane,
+            ScreenInstruction::ApplyToTab(..) => ScreenContext::ApplyToTab,
         }
     }
 }
@@ -549,6 +623,8 @@ pub(crate) struct Screen {
     pixel_dimensions: PixelDimensions,
     character_cell_size: Rc<RefCell<Option<SizeInPixels>>>,
     sixel_image_store: Rc<RefCell<SixelImageStore>>,
+    /// What was last sent to the clients that only want the cells that changed since
+    rendered_frames: Rc<RefCell<HashMap<ClientId, RenderedFrame>>>,
     /// The overlay that is drawn on top of [`Pane`]'s', [`Tab`]'s and the [`Screen`]
     overlay: OverlayWindow,
     terminal_emulator_colors: Rc<RefCell<Palette>>,
@@ -577,6 +653,10 @@ pub(crate) struct Screen {
     default_shell: Option<PathBuf>,
     styled_underlines: bool,
     arrow_fonts: bool,
+    workspace: Option<String>,
+    // terminal_id => (terminal ids of the panes waiting for its output and the patterns they are
+    // waiting for, its last unterminated line)
+    pane_output_watchers: HashMap<u32, (Vec<(u32, Regex)>, String)>,
 }
 
 impl Screen {
@@ -598,6 +678,7 @@ impl Screen {
         scrollback_lines_to_serialize: Option<usize>,
         styled_underlines: bool,
         arrow_fonts: bool,
+        workspace: Option<String>,
     ) -> Self {
         let session_name = mode_info.session_name.clone().unwrap_or_default();
         let session_info = SessionInfo::new(session_name.clone());
@@ -611,6 +692,7 @@ impl Screen {
             pixel_dimensions: Default::default(),
             character_cell_size: Rc::new(RefCell::new(None)),
             sixel_image_store: Rc::new(RefCell::new(SixelImageStore::default())),
+            rendered_frames: Rc::new(RefCell::new(HashMap::new())),
             style: client_attributes.style,
             connected_clients: Rc::new(RefCell::new(HashSet::new())),
             active_tab_indices: BTreeMap::new(),
@@ -635,10 +717,78 @@ impl Screen {
             scrollback_lines_to_serialize,
             styled_underlines,
             arrow_fonts,
+            workspace,
             resurrectable_sessions,
+            pane_output_watchers: HashMap::new(),
         }
     }
 
+    pub fn watch_pane_output(
+        &mut self,
+        terminal_id: u32,
+        dependent_terminal_id: u32,
+        pattern: String,
+    ) -> Result<()> {
+        let pattern = Regex::new(&pattern).with_context(|| {
+            format!("failed to watch output of terminal {terminal_id} for {dependent_terminal_id}")
+        })?;
+        let (patterns, _unterminated_line) = self
+            .pane_output_watchers
+            .entry(terminal_id)
+            .or_insert_with(|| (vec![], String::new()));
+        patterns.retain(|(waiting_terminal_id, _)| *waiting_terminal_id != dependent_terminal_id);
+        patterns.push((dependent_terminal_id, pattern));
+        Ok(())
+    }
+
+    fn match_pane_output(&mut self, terminal_id: u32, bytes: &[u8]) -> Result<()> {
+        let matched = match self.pane_output_watchers.get_mut(&terminal_id) {
+            Some((patterns, unterminated_line)) => {
+                unterminated_line.push_str(&strip_ansi_codes(bytes));
+                let lines: Vec<&str> = unterminated_line
+                    .split(|c| c == '\n' || c == '\r')
+                    .collect();
+                let mut matched = vec![];
+                patterns.retain(|(dependent_terminal_id, pattern)| {
+                    let is_match = lines.iter().any(|line| pattern.is_match(line));
+                    if is_match {
+                        matched.push(*dependent_terminal_id);
+                    }
+                    !is_match
+                });
+                // only the last line can continue in the next chunk of output
+                match unterminated_line.rfind(|c| c == '\n' || c == '\r') {
+                    Some(last_line_break) => {
+                        unterminated_line.drain(..=last_line_break);
+                    },
+                    None if unterminated_line.len() > MAX_UNTERMINATED_LINE_LENGTH => {
+                        unterminated_line.clear();
+                    },
+                    None => {},
+                }
+                matched
+            },
+            None => vec![],
+        };
+        if self
+            .pane_output_watchers
+            .get(&terminal_id)
+            .map_or(false, |(patterns, _)| patterns.is_empty())
+        {
+            self.pane_output_watchers.remove(&terminal_id);
+        }
+        for dependent_terminal_id in matched {
+            self.bus
+                .senders
+                .send_to_pty(PtyInstruction::PaneOutputMatched(
+                    terminal_id,
+                    dependent_terminal_id,
+                ))
+                .with_context(|| format!("failed to match output of terminal {terminal_id}"))?;
+        }
+        Ok(())
+    }
+
     /// Returns the index where a new [`Tab`] should be created in this [`Screen`].
     /// Currently, this is right after the last currently existing tab, or `0` if
     /// no tabs exist in this screen yet.
@@ -913,6 +1063,41 @@ impl Screen {
         Ok(())
     }
 
+    /// Swaps the position of the client's active [`Tab`] with its neighbour in the given direction,
+    /// tabs are not wrapped around the edges
+    pub fn move_active_tab(&mut self, direction: Direction, client_id: ClientId) -> Result<()> {
+        let err_context = || format!("failed to move active tab for client {client_id}");
+
+        let client_id = if self.get_active_tab(client_id).is_ok() {
+            client_id
+        } else if let Some(first_client_id) = self.get_first_client_id() {
+            first_client_id
+        } else {
+            return Ok(());
+        };
+        let active_tab_position = self
+            .get_active_tab(client_id)
+            .with_context(err_context)?
+            .position;
+        let new_position = match direction {
+            Direction::Left => active_tab_position.checked_sub(1),
+            Direction::Right => Some(active_tab_position + 1).filter(|p| *p < self.tabs.len()),
+            Direction::Up | Direction::Down => None,
+        };
+        if let Some(new_position) = new_position {
+            for tab in self.tabs.values_mut() {
+                if tab.position == new_position {
+                    tab.position = active_tab_position;
+                } else if tab.position == active_tab_position {
+                    tab.position = new_position;
+                }
+            }
+            self.log_and_report_session_state()
+                .with_context(err_context)?;
+        }
+        Ok(())
+    }
+
     pub fn go_to_tab(&mut self, tab_index: usize, client_id: ClientId) -> Result<()> {
         self.switch_active_tab(tab_index.saturating_sub(1), None, true, client_id)
     }
@@ -987,6 +1172,9 @@ impl Screen {
         let err_context = || format!("failed to resize to screen size: {new_screen_size:#?}");
 
         self.size = new_screen_size;
+        for rendered_frame in self.rendered_frames.borrow_mut().values_mut() {
+            rendered_frame.clear();
+        }
         for tab in self.tabs.values_mut() {
             tab.resize_whole_tab(new_screen_size)
                 .with_context(err_context)?;
@@ -1046,6 +1234,7 @@ impl Screen {
             self.character_cell_size.clone(),
             self.styled_underlines,
         );
+        output.diff_against_rendered_frames(self.rendered_frames.clone());
         let mut tabs_to_close = vec![];
         for (tab_index, tab) in &mut self.tabs {
             if tab.has_selectable_tiled_panes() {
@@ -1323,12 +1512,47 @@ impl Screen {
             self.tab_history.remove(&client_id);
         }
         self.connected_clients.borrow_mut().remove(&client_id);
+        self.rendered_frames.borrow_mut().remove(&client_id);
         self.log_and_report_session_state()
             .with_context(err_context)
     }
 
-    pub fn generate_and_report_tab_state(&mut self) -> Result<Vec<TabInfo>> {
-        let mut plugin_updates = vec![];
+    /// From now on, only send this client the cells that changed since its last render
+    pub fn enable_diff_rendering(&mut self, client_id: ClientId) {
+        self.rendered_frames
+            .borrow_mut()
+            .insert(client_id, RenderedFrame::default());
+    }
+
+    /// Render everything the client sees again, eg. because it missed some renders or drew over
+    /// parts of the screen itself
+    pub fn force_render_for_client(
+        &mut self,
+        client_id: ClientId,
+        forget_rendered_frame: bool,
+    ) -> Result<()> {
+        let err_context = || format!("failed to force render for client {client_id}");
+
+        if forget_rendered_frame {
+            if let Some(rendered_frame) = self.rendered_frames.borrow_mut().get_mut(&client_id) {
+                rendered_frame.clear();
+            }
+        }
+        if let Ok(active_tab) = self.get_active_tab_mut(client_id) {
+            active_tab.set_force_render();
+        }
+        self.render(None).with_context(err_context)
+    }
+
+    /// The client drew predictions of what will be echoed over these cells, so they have to be sent
+    /// again the next time they are rendered
+    pub fn forget_rendered_cells(&mut self, client_id: ClientId, cells: Vec<(usize, usize)>) {
+        if let Some(rendered_frame) = self.rendered_frames.borrow_mut().get_mut(&client_id) {
+            rendered_frame.forget_cells(cells.into_iter());
+        }
+    }
+
+    fn tab_infos(&self) -> Vec<TabInfo> {
         let mut tab_infos_for_screen_state = BTreeMap::new();
         for tab in self.tabs.values() {
             let all_focused_clients: Vec<ClientId> = self
@@ -1350,9 +1574,14 @@ impl Screen {
                 other_focused_clients: all_focused_clients,
                 active_swap_layout_name,
                 is_swap_layout_dirty,
+                is_requesting_attention: tab.is_requesting_attention(),
             };
             tab_infos_for_screen_state.insert(tab.position, tab_info_for_screen);
         }
+        tab_infos_for_screen_state.values().cloned().collect()
+    }
+    pub fn generate_and_report_tab_state(&mut self) -> Result<Vec<TabInfo>> {
+        let mut plugin_updates = vec![];
         for (client_id, active_tab_index) in self.active_tab_indices.iter() {
             let mut plugin_tab_updates = vec![];
             for tab in self.tabs.values() {
@@ -1380,16 +1609,19 @@ impl Screen {
                     other_focused_clients,
                     active_swap_layout_name,
                     is_swap_layout_dirty,
+                    is_requesting_attention: tab.is_requesting_attention(),
                 };
                 plugin_tab_updates.push(tab_info_for_plugins);
             }
+            // tabs are stored by their index, which is not their position once they were moved
+            plugin_tab_updates.sort_by_key(|t| t.position);
             plugin_updates.push((None, Some(*client_id), Event::TabUpdate(plugin_tab_updates)));
         }
         self.bus
             .senders
             .send_to_plugin(PluginInstruction::Update(plugin_updates))
             .context("failed to update tabs")?;
-        Ok(tab_infos_for_screen_state.values().cloned().collect())
+        Ok(self.tab_infos())
     }
     fn generate_and_report_pane_state(&mut self) -> Result<PaneManifest> {
         let mut pane_manifest = PaneManifest::default();
@@ -1418,6 +1650,7 @@ impl Screen {
             panes: pane_manifest,
             connected_clients: self.active_tab_indices.keys().len(),
             is_current_session: true,
+            workspace: self.workspace.clone(),
         };
         self.bus
             .senders
@@ -1431,6 +1664,10 @@ impl Screen {
             .senders
             .send_to_background_jobs(BackgroundJob::ReadAllSessionInfosOnMachine)
             .with_context(err_context)?;
+        self.bus
+            .senders
+            .send_to_background_jobs(BackgroundJob::SamplePaneProcesses)
+            .with_context(err_context)?;
         Ok(())
     }
     fn dump_layout_to_hd(&mut self) -> Result<()> {
@@ -1459,7 +1696,7 @@ impl Screen {
                     self.session_infos_on_machine.values().cloned().collect(),
                     self.resurrectable_sessions
                         .iter()
-                        .map(|(n, c)| (n.clone(), c.clone()))
+                        .map(|(n, c)| (n.clone(), *c))
                         .collect(),
                 ),
             )]))
@@ -1478,39 +1715,43 @@ impl Screen {
         };
 
         match client_id {
-            Some(client_id) => {
-                let s = str::from_utf8(&buf)
-                    .with_context(|| format!("failed to construct tab name from buf: {buf:?}"))
-                    .with_context(err_context)?;
-                match self.get_active_tab_mut(client_id) {
-                    Ok(active_tab) => {
-                        match s {
-                            "\0" => {
-                                active_tab.name = String::new();
-                            },
-                            "\u{007F}" | "\u{0008}" => {
-                                // delete and backspace keys
-                                active_tab.name.pop();
-                            },
-                            c => {
-                                // It only allows printable unicode
-                                if buf.iter().all(|u| matches!(u, 0x20..=0x7E | 0xA0..=0xFF)) {
-                                    active_tab.name.push_str(c);
-                                }
-                            },
-                        }
-                        self.log_and_report_session_state()
-                            .with_context(err_context)
-                    },
-                    Err(err) => {
-                        Err::<(), _>(err).with_context(err_context).non_fatal();
-                        Ok(())
-                    },
-                }
+            Some(client_id) => match self.get_active_tab(client_id) {
+                Ok(active_tab) => self
+                    .update_tab_name(active_tab.index, buf)
+                    .with_context(err_context),
+                Err(err) => {
+                    Err::<(), _>(err).with_context(err_context).non_fatal();
+                    Ok(())
+                },
             },
             None => Ok(()),
         }
     }
+    fn update_tab_name(&mut self, tab_index: usize, buf: Vec<u8>) -> Result<()> {
+        let err_context = || format!("failed to update name of tab {tab_index}");
+
+        let s = str::from_utf8(&buf)
+            .with_context(|| format!("failed to construct tab name from buf: {buf:?}"))
+            .with_context(err_context)?;
+        let tab = self.tabs.get_mut(&tab_index).with_context(err_context)?;
+        match s {
+            "\0" => {
+                tab.name = String::new();
+            },
+            "\u{007F}" | "\u{0008}" => {
+                // delete and backspace keys
+                tab.name.pop();
+            },
+            c => {
+                // It only allows printable unicode
+                if buf.iter().all(|u| matches!(u, 0x20..=0x7E | 0xA0..=0xFF)) {
+                    tab.name.push_str(c);
+                }
+            },
+        }
+        self.log_and_report_session_state()
+            .with_context(err_context)
+    }
     pub fn undo_active_rename_tab(&mut self, client_id: ClientId) -> Result<()> {
         let err_context = || format!("failed to undo active tab rename for client {}", client_id);
 
@@ -1521,14 +1762,8 @@ impl Screen {
         };
         match client_id {
             Some(client_id) => {
-                match self.get_active_tab_mut(client_id) {
-                    Ok(active_tab) => {
-                        if active_tab.name != active_tab.prev_name {
-                            active_tab.name = active_tab.prev_name.clone();
-                            self.log_and_report_session_state()
-                                .context("failed to undo renaming of active tab")?;
-                        }
-                    },
+                match self.get_active_tab(client_id) {
+                    Ok(active_tab) => self.undo_rename_tab(active_tab.index)?,
                     Err(err) => Err::<(), _>(err).with_context(err_context).non_fatal(),
                 };
                 Ok(())
@@ -1536,6 +1771,149 @@ impl Screen {
             None => Ok(()),
         }
     }
+    fn undo_rename_tab(&mut self, tab_index: usize) -> Result<()> {
+        if let Some(tab) = self.tabs.get_mut(&tab_index) {
+            if tab.name != tab.prev_name {
+                tab.name = tab.prev_name.clone();
+                self.log_and_report_session_state()
+                    .context("failed to undo renaming of tab")?;
+            }
+        }
+        Ok(())
+    }
+    /// Finds the tab (and the pane in it) a cli action was targeted at, the pane being the focused
+    /// one unless a specific pane was asked for
+    fn find_action_target(
+        &self,
+        target: &ActionTarget,
+        client_id: ClientId,
+    ) -> Result<(usize, Option<PaneId>)> {
+        let client_id = if self.get_active_tab(client_id).is_ok() {
+            Some(client_id)
+        } else {
+            self.get_first_client_id()
+        };
+        let tab = if let Some(tab_name) = &target.tab_name {
+            self.tabs
+                .values()
+                .find(|tab| &tab.name == tab_name)
+                .ok_or_else(|| anyhow!("There is no tab named \"{}\"", tab_name))?
+        } else if let Some(tab_position) = target.tab_position {
+            self.tabs
+                .values()
+                .find(|tab| tab.position == tab_position)
+                .ok_or_else(|| anyhow!("There is no tab at position {}", tab_position))?
+        } else if let Some(pane_id) = target.pane_id {
+            self.tabs
+                .values()
+                .find(|tab| tab.has_terminal_pid(pane_id))
+                .ok_or_else(|| anyhow!("There is no terminal pane with id {}", pane_id))?
+        } else {
+            client_id
+                .and_then(|client_id| self.get_active_tab(client_id).ok())
+                .ok_or_else(|| anyhow!("There is no active tab"))?
+        };
+        let pane_id = match target.pane_id {
+            Some(pane_id) if tab.has_terminal_pid(pane_id) => Some(PaneId::Terminal(pane_id)),
+            Some(pane_id) => {
+                return Err(anyhow!(
+                    "There is no terminal pane with id {} in tab \"{}\"",
+                    pane_id,
+                    tab.name
+                ));
+            },
+            None => tab.get_focused_pane_id_for_action(client_id),
+        };
+        Ok((tab.index, pane_id))
+    }
+    pub fn apply_to_pane(
+        &mut self,
+        operation: PaneOperation,
+        target: ActionTarget,
+        client_id: ClientId,
+    ) -> Result<()> {
+        let err_context = || format!("failed to apply operation to {target:?}");
+
+        let (tab_index, pane_id) = match self.find_action_target(&target, client_id) {
+            Ok((tab_index, Some(pane_id))) => (tab_index, pane_id),
+            Ok((_tab_index, None)) => {
+                return self
+                    .log_error_to_client("There is no pane to apply this action to", client_id);
+            },
+            Err(e) => return self.log_error_to_client(&e.to_string(), client_id),
+        };
+        match operation {
+            PaneOperation::Signal(signal) => match pane_id {
+                PaneId::Terminal(terminal_id) => self
+                    .bus
+                    .senders
+                    .send_to_pty(PtyInstruction::SignalPane(terminal_id, signal))
+                    .with_context(err_context)?,
+                PaneId::Plugin(_) => {
+                    return self.log_error_to_client("Plugin panes cannot be signaled", client_id);
+                },
+            },
+            PaneOperation::Resize(_)
+                if !self
+                    .tabs
+                    .get(&tab_index)
+                    .map(|tab| tab.has_non_suppressed_pane_with_pid(&pane_id))
+                    .unwrap_or(false) =>
+            {
+                return self.log_error_to_client(
+                    "Panes that are not displayed cannot be resized",
+                    client_id,
+                );
+            },
+            operation => {
+                self.tabs
+                    .get_mut(&tab_index)
+                    .with_context(err_context)?
+                    .apply_pane_operation(pane_id, operation, client_id)
+                    .with_context(err_context)?;
+                self.log_and_report_session_state()
+                    .with_context(err_context)?;
+                self.render(None).with_context(err_context)?;
+            },
+        }
+        self.unblock_input()
+    }
+    pub fn apply_to_tab(
+        &mut self,
+        operation: TabOperation,
+        target: ActionTarget,
+        client_id: ClientId,
+    ) -> Result<()> {
+        let err_context = || format!("failed to apply operation to {target:?}");
+
+        let tab_index = match self.find_action_target(&target, client_id) {
+            Ok((tab_index, _pane_id)) => tab_index,
+            Err(e) => return self.log_error_to_client(&e.to_string(), client_id),
+        };
+        match operation {
+            TabOperation::NameInput(buf) => self.update_tab_name(tab_index, buf),
+            TabOperation::UndoRename => self.undo_rename_tab(tab_index),
+            TabOperation::ToggleSyncPanes => {
+                if let Some(tab) = self.tabs.get_mut(&tab_index) {
+                    tab.toggle_sync_panes_is_active();
+                }
+                self.log_and_report_session_state()
+            },
+            TabOperation::Close => self.close_tab_at_index(tab_index),
+        }
+        .with_context(err_context)?;
+        self.render(None).with_context(err_context)?;
+        self.unblock_input()
+    }
+    fn log_error_to_client(&mut self, error_text: &str, client_id: ClientId) -> Result<()> {
+        self.bus
+            .senders
+            .send_to_server(ServerInstruction::LogError(
+                vec![error_text.to_owned()],
+                client_id,
+            ))
+            .context("failed to report error to client")
+    }
 
     pub fn change_mode(&mut self, mut mode_info: ModeInfo, client_id: ClientId) -> Result<()> {
         if mode_info.session_name.as_ref() != Some(&self.session_name) {
@@ -1984,8 +2362,11 @@ impl Screen {
         let active_tab_index =
             first_client_id.and_then(|client_id| self.active_tab_indices.get(&client_id));
 
-        for (tab_index, tab) in self.tabs.values().enumerate() {
-            let tab_is_focused = active_tab_index == Some(&tab_index);
+        // tabs can be moved, so their order is that of their positions rather than their indices
+        let mut tabs: Vec<&Tab> = self.tabs.values().collect();
+        tabs.sort_by_key(|tab| tab.position);
+        for tab in tabs {
+            let tab_is_focused = active_tab_index == Some(&tab.index);
             let hide_floating_panes = !tab.are_floating_panes_visible();
             let mut suppressed_panes = HashMap::new();
             for (triggering_pane_id, p) in tab.get_suppressed_panes() {
@@ -2108,6 +2489,7 @@ pub(crate) fn screen_thread_main(
         config_options.copy_on_select.unwrap_or(true),
     );
     let styled_underlines = config_options.styled_underlines.unwrap_or(true);
+    let workspace = config_options.workspace;
 
     let thread_senders = bus.senders.clone();
     let mut screen = Screen::new(
@@ -2134,6 +2516,7 @@ pub(crate) fn screen_thread_main(
         scrollback_lines_to_serialize,
         styled_underlines,
         arrow_fonts,
+        workspace,
     );
 
     let mut pending_tab_ids: HashSet<usize> = HashSet::new();
@@ -2153,14 +2536,23 @@ pub(crate) fn screen_thread_main(
 
         match event {
             ScreenInstruction::PtyBytes(pid, vte_bytes) => {
+                screen.match_pane_output(pid, &vte_bytes)?;
                 let all_tabs = screen.get_tabs_mut();
+                let mut started_requesting_attention = false;
                 for tab in all_tabs.values_mut() {
                     if tab.has_terminal_pid(pid) {
+                        let was_requesting_attention = tab.is_requesting_attention();
                         tab.handle_pty_bytes(pid, vte_bytes)
                             .context("failed to process pty bytes")?;
+                        started_requesting_attention =
+                            !was_requesting_attention && tab.is_requesting_attention();
                         break;
                     }
                 }
+                if started_requesting_attention {
+                    // so that the tab-bar can mark the tab
+                    screen.log_and_report_session_state()?;
+                }
             },
             ScreenInstruction::PluginBytes(mut plugin_render_assets) => {
                 for plugin_render_asset in plugin_render_assets.iter_mut() {
@@ -2731,6 +3123,37 @@ pub(crate) fn screen_thread_main(
                 screen.unblock_input()?;
                 screen.log_and_report_session_state()?;
             },
+            ScreenInstruction::UpdatePaneRestarts(pane_id, restart_count, last_exit_status) => {
+                for tab in screen.get_tabs_mut().values_mut() {
+                    if tab.has_pane_with_pid(&pane_id) {
+                        tab.update_pane_restarts(pane_id, restart_count, last_exit_status);
+                        break;
+                    }
+                }
+                screen.render(None)?;
+                screen.log_and_report_session_state()?;
+            },
+            ScreenInstruction::UpdatePaneBlockedOn(pane_id, blocked_on) => {
+                for tab in screen.get_tabs_mut().values_mut() {
+                    if tab.has_pane_with_pid(&pane_id) {
+                        tab.update_pane_blocked_on(pane_id, blocked_on);
+                        break;
+                    }
+                }
+                screen.render(None)?;
+            },
+            ScreenInstruction::RunHeldPane(pane_id) => {
+                for tab in screen.get_tabs_mut().values_mut() {
+                    if tab.has_pane_with_pid(&pane_id) {
+                        tab.run_held_pane(pane_id)?;
+                        break;
+                    }
+                }
+                screen.render(None)?;
+            },
+            ScreenInstruction::WatchPaneOutput(terminal_id, dependent_terminal_id, pattern) => {
+                screen.watch_pane_output(terminal_id, dependent_terminal_id, pattern)?;
+            },
             ScreenInstruction::UpdatePaneName(c, client_id) => {
                 active_tab_and_connected_client_id!(
                     screen,
@@ -3015,6 +3438,11 @@ pub(crate) fn screen_thread_main(
                     .handle_mouse_hold_middle(&point, client_id), ?);
                 screen.render(None)?;
             },
+            ScreenInstruction::MouseMotion(point, client_id) => {
+                // panes that react to the mouse moving over them render themselves
+                active_tab!(screen, client_id, |tab: &mut Tab| tab
+                    .handle_mouse_motion(&point, client_id), ?);
+            },
             ScreenInstruction::Copy(client_id) => {
                 active_tab!(screen, client_id, |tab: &mut Tab| tab
                     .copy_selection(client_id), ?);
@@ -3028,6 +3456,11 @@ pub(crate) fn screen_thread_main(
                 screen.unblock_input()?;
                 screen.render(None)?;
             },
+            ScreenInstruction::MoveTab(direction, client_id) => {
+                screen.move_active_tab(direction, client_id)?;
+                screen.unblock_input()?;
+                screen.render(None)?;
+            },
             ScreenInstruction::AddClient(client_id, tab_position_to_focus, pane_id_to_focus) => {
                 screen.add_client(client_id)?;
                 let pane_id = pane_id_to_focus.map(|(pane_id, is_plugin)| {
@@ -3050,6 +3483,18 @@ pub(crate) fn screen_thread_main(
                 screen.log_and_report_session_state()?;
                 screen.render(None)?;
             },
+            ScreenInstruction::EnableDiffRendering(client_id) => {
+                screen.enable_diff_rendering(client_id);
+            },
+            ScreenInstruction::ClientMissedRenders(client_id) => {
+                screen.force_render_for_client(client_id, true)?;
+            },
+            ScreenInstruction::LocalEchoPredicted(cells, client_id) => {
+                screen.forget_rendered_cells(client_id, cells);
+            },
+            ScreenInstruction::LocalEchoExpired(client_id) => {
+                screen.force_render_for_client(client_id, false)?;
+            },
             ScreenInstruction::AddOverlay(overlay, _client_id) => {
                 screen.get_active_overlays_mut().pop();
                 screen.get_active_overlays_mut().push(overlay);
@@ -3177,9 +3622,10 @@ pub(crate) fn screen_thread_main(
                 screen.unblock_input()?;
             },
             ScreenInstruction::QueryTabNames(client_id) => {
-                let tab_names = screen
-                    .get_tabs_mut()
-                    .values()
+                let mut tabs: Vec<&Tab> = screen.get_tabs_mut().values().collect();
+                tabs.sort_by_key(|tab| tab.position);
+                let tab_names = tabs
+                    .iter()
                     .map(|tab| tab.name.clone())
                     .collect::<Vec<String>>();
                 screen
@@ -3187,6 +3633,43 @@ pub(crate) fn screen_thread_main(
                     .senders
                     .send_to_server(ServerInstruction::Log(tab_names, client_id))?;
             },
+            ScreenInstruction::ListPanes(json, client_id) => {
+                let mut pane_listings = vec![];
+                for tab in screen.tabs.values() {
+                    for pane in tab.pane_infos() {
+                        pane_listings.push(PaneListing {
+                            tab_position: tab.position,
+                            tab_name: tab.name.clone(),
+                            pane,
+                            cwd: None, // filled in by the pty thread
+                        });
+                    }
+                }
+                pane_listings.sort_by_key(|p| p.tab_position);
+                screen.bus.senders.send_to_pty(PtyInstruction::ListPanes(
+                    pane_listings,
+                    json,
+                    client_id,
+                ))?;
+            },
+            ScreenInstruction::ListTabs(json, client_id) => {
+                let tab_infos = screen.tab_infos();
+                let lines = if json {
+                    vec![serde_json::to_string(&tab_infos).context("failed to serialize tabs")?]
+                } else {
+                    tab_infos
+                        .iter()
+                        .map(|tab_info| {
+                            let active = if tab_info.active { "active" } else { "" };
+                            format!("{}\t{}\t{}", tab_info.position, tab_info.name, active)
+                        })
+                        .collect()
+                };
+                screen
+                    .bus
+                    .senders
+                    .send_to_server(ServerInstruction::Log(lines, client_id))?;
+            },
             ScreenInstruction::NewTiledPluginPane(
                 run_plugin,
                 pane_title,
@@ -3641,6 +4124,76 @@ pub(crate) fn screen_thread_main(
                     screen.dump_layout_to_hd()?;
                 }
             },
+            ScreenInstruction::SaveSnapshot(snapshot_name, client_id) => {
+                // snapshots are saved on request, even if the session is not otherwise serialized
+                let session_layout_metadata =
+                    screen.get_layout_metadata(screen.default_shell.clone());
+                screen
+                    .bus
+                    .senders
+                    .send_to_plugin(PluginInstruction::SaveSnapshot(
+                        session_layout_metadata,
+                        snapshot_name,
+                        client_id,
+                    ))
+                    .context("failed to save snapshot")?;
+            },
+            ScreenInstruction::SignalPane(terminal_id, signal, client_id) => {
+                let mut terminal_id = terminal_id;
+                match terminal_id {
+                    Some(id) => {
+                        if !screen.tabs.values().any(|tab| tab.has_terminal_pid(id)) {
+                            terminal_id = None;
+                        }
+                    },
+                    None => {
+                        active_tab_and_connected_client_id!(
+                            screen,
+                            client_id,
+                            |tab: &mut Tab, client_id: ClientId| {
+                                terminal_id = tab.get_active_terminal_id(client_id);
+                            }
+                        );
+                    },
+                }
+                match terminal_id {
+                    Some(terminal_id) => {
+                        screen
+                            .bus
+                            .senders
+                            .send_to_pty(PtyInstruction::SignalPane(terminal_id, signal))
+                            .with_context(|| format!("failed to signal terminal {terminal_id}"))?;
+                    },
+                    None => {
+                        log::error!("Could not find a terminal pane to send {} to", signal);
+                    },
+                }
+                screen.unblock_input()?;
+            },
+            ScreenInstruction::ApplyToPane(operation, target, client_id) => {
+                screen.apply_to_pane(operation, target, client_id)?;
+            },
+            ScreenInstruction::ApplyToTab(operation, target, client_id) => {
+                screen.apply_to_tab(operation, target, client_id)?;
+            },
+            ScreenInstruction::UpdatePaneProcessInfos(mut process_infos) => {
+                let mut process_infos_changed = false;
+                let mut needs_render = false;
+                for tab in screen.tabs.values_mut() {
+                    let (tab_process_infos_changed, tab_needs_render) =
+                        tab.update_pane_process_infos(&mut process_infos);
+                    process_infos_changed |= tab_process_infos_changed;
+                    needs_render |= tab_needs_render;
+                }
+                // samples are taken every few seconds, most of them only change the resource usage
+                // which plugins can see but which isn't displayed
+                if process_infos_changed {
+                    screen.log_and_report_session_state()?;
+                }
+                if needs_render {
+                    screen.render(None)?;
+                }
+            },
             ScreenInstruction::RenameSession(name, client_id) => {
                 if screen.session_infos_on_machine.contains_key(&name) {
                     let error_text = "A session by this name already exists.";
@@ -128,10 +128,22 @@ impl SessionLayoutMetadata {
                         let args: Vec<String> = command_line.map(|c| c.to_owned()).collect();
                         if Self::is_default_shell(self.default_shell.as_ref(), &command_name, &args)
                         {
-                            pane_layout_metadata.run = None;
+                            // a shell pane keeps the env it was given
+                            if !matches!(pane_layout_metadata.run, Some(Run::Shell(_))) {
+                                pane_layout_metadata.run = None;
+                            }
                         } else {
                             let mut run_command = RunCommand::new(PathBuf::from(command_name));
                             run_command.args = args;
+                            if let Some(Run::Command(invoked_with)) = &pane_layout_metadata.run {
+                                // the running process does not tell us how it should be restarted or which
+                                // env it was given
+                                run_command.restart_policy = invoked_with.restart_policy;
+                                run_command.max_restarts = invoked_with.max_restarts;
+                                run_command.restart_backoff_ms = invoked_with.restart_backoff_ms;
+                                run_command.env = invoked_with.env.clone();
+                                run_command.env_file = invoked_with.env_file.clone();
+                            }
                             pane_layout_metadata.run = Some(Run::Command(run_command));
                         }
                     }
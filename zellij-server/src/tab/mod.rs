@@ -11,7 +11,8 @@ use std::env::temp_dir;
 use std::path::PathBuf;
 use uuid::Uuid;
 use zellij_utils::data::{
-    Direction, PaneInfo, PermissionStatus, PermissionType, PluginPermission, ResizeStrategy,
+    Direction, PaneInfo, PaneProcessInfo, PermissionStatus, PermissionType, PluginPermission,
+    ResizeStrategy,
 };
 use zellij_utils::errors::prelude::*;
 use zellij_utils::input::command::RunCommand;
@@ -20,7 +21,7 @@ use zellij_utils::{position::Position, serde};
 
 use crate::background_jobs::BackgroundJob;
 use crate::pty_writer::PtyWriteInstruction;
-use crate::screen::CopyOptions;
+use crate::screen::{CopyOptions, PaneOperation};
 use crate::ui::{loading_indication::LoadingIndication, pane_boundaries_frame::FrameParams};
 use layout_applier::LayoutApplier;
 use swap_layouts::SwapLayouts;
@@ -173,6 +174,9 @@ pub(crate) struct Tab {
     focus_pane_id: Option<PaneId>,
     copy_on_select: bool,
     last_mouse_hold_position: Option<Position>,
+    // a plugin pane that was clicked and keeps receiving mouse holds and the release even if it
+    // is not focused, so that plugins can be dragged in (eg. the tab-bar)
+    plugin_pane_held_with_mouse: Option<PaneId>,
     terminal_emulator_colors: Rc<RefCell<Palette>>,
     terminal_emulator_color_codes: Rc<RefCell<HashMap<usize, String>>>,
     pids_waiting_resize: HashSet<u32>, // u32 is the terminal_id
@@ -277,6 +281,7 @@ pub trait Pane {
     fn start_selection(&mut self, _start: &Position, _client_id: ClientId) {}
     fn update_selection(&mut self, _position: &Position, _client_id: ClientId) {}
     fn end_selection(&mut self, _end: &Position, _client_id: ClientId) {}
+    fn mouse_motion(&mut self, _position: &Position, _client_id: ClientId) {}
     fn reset_selection(&mut self) {}
     fn get_selected_text(&self) -> Option<String> {
         None
@@ -391,9 +396,14 @@ pub trait Pane {
     fn borderless(&self) -> bool;
     fn set_exclude_from_sync(&mut self, exclude_from_sync: bool);
     fn exclude_from_sync(&self) -> bool;
+    // whether a bell rang in this pane and was not yet rendered to the user
+    fn has_pending_bell(&self) -> bool {
+        false
+    }
 
     // TODO: this should probably be merged with the mouse_right_click
     fn handle_right_click(&mut self, _to: &Position, _client_id: ClientId) {}
+    fn handle_middle_click(&mut self, _to: &Position, _client_id: ClientId) {}
     fn mouse_left_click(&self, _position: &Position, _is_held: bool) -> Option<String> {
         None
     }
@@ -474,6 +484,29 @@ pub trait Pane {
     fn exit_status(&self) -> Option<i32> {
         None
     }
+    fn set_restarts(&mut self, _restart_count: usize, _last_exit_status: Option<i32>) {
+        // No-op by default, only command panes can be restarted
+    }
+    fn restarts(&self) -> Option<(usize, Option<i32>)> {
+        // (restart count, last exit status)
+        None
+    }
+    fn set_blocked_on(&mut self, _blocked_on: Option<String>) {
+        // No-op by default, only command panes can wait for other panes
+    }
+    /// Returns true if the pane displays any of the process info that changed, and so needs to
+    /// be rendered again
+    fn set_process_info(&mut self, _process_info: Option<PaneProcessInfo>) -> bool {
+        // No-op by default, only terminal panes run processes
+        false
+    }
+    fn process_info(&self) -> Option<PaneProcessInfo> {
+        None
+    }
+    fn run_held_command(&mut self) -> Option<RunCommand> {
+        // un-holds the pane and returns the command it should run, if it is held
+        None
+    }
     fn rename(&mut self, _buf: Vec<u8>) {}
     fn serialize(&self, _scrollback_lines_to_serialize: Option<usize>) -> Option<String> {
         None
@@ -616,6 +649,7 @@ impl Tab {
             focus_pane_id: None,
             copy_on_select: copy_options.copy_on_select,
             last_mouse_hold_position: None,
+            plugin_pane_held_with_mouse: None,
             terminal_emulator_colors,
             terminal_emulator_color_codes,
             pids_waiting_resize: HashSet::new(),
@@ -1449,6 +1483,12 @@ impl Tab {
             self.get_active_pane_mut(client_id)
         }
     }
+    fn get_tiled_or_floating_pane_mut(&mut self, pane_id: PaneId) -> Option<&mut Box<dyn Pane>> {
+        match self.tiled_panes.get_pane_mut(pane_id) {
+            Some(pane) => Some(pane),
+            None => self.floating_panes.get_pane_mut(pane_id),
+        }
+    }
     pub fn get_active_pane_id(&self, client_id: ClientId) -> Option<PaneId> {
         if self.floating_panes.panes_are_visible() {
             self.floating_panes.get_active_pane_id(client_id)
@@ -1456,13 +1496,28 @@ impl Tab {
             self.tiled_panes.get_active_pane_id(client_id)
         }
     }
-    fn get_active_terminal_id(&self, client_id: ClientId) -> Option<u32> {
+    pub fn get_active_terminal_id(&self, client_id: ClientId) -> Option<u32> {
         if let Some(PaneId::Terminal(pid)) = self.get_active_pane_id(client_id) {
             Some(pid)
         } else {
             None
         }
     }
+    /// The pane an action applies to when none was specified: the one focused by the given client
+    /// or, if it has never been to this tab, the one focused by anyone else
+    pub fn get_focused_pane_id_for_action(&self, client_id: Option<ClientId>) -> Option<PaneId> {
+        client_id
+            .and_then(|client_id| self.get_active_pane_id(client_id))
+            .or_else(|| {
+                if self.floating_panes.panes_are_visible() {
+                    self.floating_panes.first_active_floating_pane_id()
+                } else {
+                    None
+                }
+            })
+            .or_else(|| self.tiled_panes.first_active_pane_id())
+            .or_else(|| self.tiled_panes.first_selectable_pane_id())
+    }
     pub fn has_terminal_pid(&self, pid: u32) -> bool {
         self.tiled_panes.panes_contain(&PaneId::Terminal(pid))
             || self.floating_panes.panes_contain(&PaneId::Terminal(pid))
@@ -1813,6 +1868,14 @@ impl Tab {
     pub fn is_fullscreen_active(&self) -> bool {
         self.tiled_panes.fullscreen_is_active()
     }
+    /// A bell rang in one of this tab's panes while no client was looking at it
+    pub fn is_requesting_attention(&self) -> bool {
+        self.connected_clients.borrow().is_empty()
+            && self
+                .get_tiled_panes()
+                .chain(self.get_floating_panes())
+                .any(|(_, p)| p.has_pending_bell())
+    }
     pub fn are_floating_panes_visible(&self) -> bool {
         self.floating_panes.panes_are_visible()
     }
@@ -2067,19 +2130,30 @@ impl Tab {
         Ok(())
     }
     pub fn resize(&mut self, client_id: ClientId, strategy: ResizeStrategy) -> Result<()> {
+        let active_pane_id = if self.floating_panes.panes_are_visible() {
+            self.floating_panes.active_pane_id(client_id)
+        } else {
+            self.tiled_panes.get_active_pane_id(client_id)
+        };
+        match active_pane_id {
+            Some(active_pane_id) => self.resize_pane_with_id(strategy, active_pane_id),
+            None => Ok(()),
+        }
+    }
+    pub fn resize_pane_with_id(&mut self, strategy: ResizeStrategy, pane_id: PaneId) -> Result<()> {
         let err_context = || format!("unable to resize pane");
         self.swap_layouts.set_is_floating_damaged();
         self.swap_layouts.set_is_tiled_damaged();
-        if self.floating_panes.panes_are_visible() {
+        if self.floating_panes.panes_contain(&pane_id) {
             let successfully_resized = self
                 .floating_panes
-                .resize_active_pane(client_id, &mut self.os_api, &strategy)
+                .resize_pane_with_id(pane_id, &mut self.os_api, &strategy)
                 .with_context(err_context)?;
             if successfully_resized {
                 self.set_force_render(); // we force render here to make sure the panes under the floating pane render and don't leave "garbage" in case of a decrease
             }
         } else {
-            match self.tiled_panes.resize_active_pane(client_id, &strategy) {
+            match self.tiled_panes.resize_pane_with_id(pane_id, &strategy) {
                 Ok(_) => {},
                 Err(err) => match err.downcast_ref::<ZellijError>() {
                     Some(ZellijError::CantResizeFixedPanes { pane_ids }) => {
@@ -2617,6 +2691,101 @@ impl Tab {
             ))
             .with_context(err_context)
     }
+    pub fn apply_pane_operation(
+        &mut self,
+        pane_id: PaneId,
+        operation: PaneOperation,
+        client_id: ClientId,
+    ) -> Result<()> {
+        let err_context = || format!("failed to apply operation to pane {pane_id:?}");
+
+        match operation {
+            PaneOperation::Write(bytes) => {
+                self.write_to_pane_id(bytes, pane_id, None)
+                    .with_context(err_context)?;
+                return Ok(());
+            },
+            PaneOperation::Rename(bytes) => {
+                return self.rename_pane(bytes, pane_id).with_context(err_context);
+            },
+            PaneOperation::Close => {
+                self.close_pane(pane_id, false, None);
+                return self
+                    .senders
+                    .send_to_pty(PtyInstruction::ClosePane(pane_id))
+                    .with_context(err_context);
+            },
+            PaneOperation::Resize(strategy) => {
+                return self
+                    .resize_pane_with_id(strategy, pane_id)
+                    .with_context(err_context);
+            },
+            _ => {},
+        }
+        let pane = self
+            .floating_panes
+            .get_pane_mut(pane_id)
+            .or_else(|| self.tiled_panes.get_pane_mut(pane_id))
+            .or_else(|| {
+                self.suppressed_panes
+                    .get_mut(&pane_id)
+                    .map(|s_p| &mut s_p.1)
+            })
+            .with_context(err_context)?;
+        match &operation {
+            PaneOperation::ScrollUp => pane.scroll_up(1, client_id),
+            PaneOperation::ScrollDown => pane.scroll_down(1, client_id),
+            PaneOperation::PageScrollUp => {
+                // prevent overflow when row == 0
+                let scroll_rows = pane.rows().max(1) - 1;
+                pane.scroll_up(scroll_rows, client_id);
+            },
+            PaneOperation::PageScrollDown => {
+                let scroll_rows = pane.get_content_rows();
+                pane.scroll_down(scroll_rows, client_id);
+            },
+            PaneOperation::HalfPageScrollUp => {
+                let scroll_rows = (pane.rows().max(1) - 1) / 2;
+                pane.scroll_up(scroll_rows, client_id);
+            },
+            PaneOperation::HalfPageScrollDown => {
+                let scroll_rows = (pane.rows().max(1) - 1) / 2;
+                pane.scroll_down(scroll_rows, client_id);
+            },
+            PaneOperation::ScrollToBottom => pane.clear_scroll(),
+            PaneOperation::ScrollToTop => {
+                pane.clear_scroll();
+                if let Some(size) = pane.get_line_number() {
+                    pane.scroll_up(size, client_id);
+                }
+            },
+            PaneOperation::Clear => pane.clear_screen(),
+            PaneOperation::UndoRename => pane.load_pane_name(),
+            PaneOperation::DumpScreen(file, full) => {
+                let dump = pane.dump_screen(client_id, *full);
+                self.os_api
+                    .write_to_file(dump, Some(file.to_owned()))
+                    .with_context(err_context)?;
+                return Ok(());
+            },
+            _ => {},
+        }
+        let scrolled_down = matches!(
+            operation,
+            PaneOperation::ScrollDown
+                | PaneOperation::PageScrollDown
+                | PaneOperation::HalfPageScrollDown
+                | PaneOperation::ScrollToBottom
+        );
+        if scrolled_down && !pane.is_scrolled() {
+            if let PaneId::Terminal(raw_fd) = pane_id {
+                self.process_pending_vte_events(raw_fd)
+                    .with_context(err_context)?;
+            }
+        }
+        Ok(())
+    }
+
     pub fn scroll_active_terminal_up(&mut self, client_id: ClientId) {
         if let Some(active_pane) = self.get_active_pane_or_floating_pane_mut(client_id) {
             active_pane.scroll_up(1, client_id);
@@ -2861,6 +3030,7 @@ impl Tab {
             )
         };
 
+        self.plugin_pane_held_with_mouse = None;
         self.focus_pane_at(position, client_id)
             .with_context(err_context)?;
 
@@ -2887,8 +3057,9 @@ impl Tab {
                 }
             } else {
                 pane.start_selection(&relative_position, client_id);
-                if let PaneId::Terminal(_) = pane.pid() {
-                    self.selecting_with_mouse = true;
+                match pane.pid() {
+                    PaneId::Terminal(_) => self.selecting_with_mouse = true,
+                    plugin_pane_id => self.plugin_pane_held_with_mouse = Some(plugin_pane_id),
                 }
             }
         };
@@ -2942,6 +3113,8 @@ impl Tab {
                     self.write_to_active_terminal(mouse_event.into_bytes(), client_id)
                         .with_context(err_context)?;
                 }
+            } else {
+                pane.handle_middle_click(&relative_position, client_id);
             }
         };
         Ok(())
@@ -3057,6 +3230,14 @@ impl Tab {
             return Ok(());
         }
 
+        if let Some(held_pane_id) = self.plugin_pane_held_with_mouse.take() {
+            if let Some(held_pane) = self.get_tiled_or_floating_pane_mut(held_pane_id) {
+                let relative_position = held_pane.relative_position(position);
+                held_pane.end_selection(&relative_position, client_id);
+                return Ok(());
+            }
+        }
+
         // read these here to avoid use of borrowed `*self`, since we are holding active_pane
         let selecting = self.selecting_with_mouse;
         let copy_on_release = self.copy_on_select;
@@ -3137,6 +3318,16 @@ impl Tab {
                                      // return;
         }
 
+        if let Some(held_pane_id) = self.plugin_pane_held_with_mouse {
+            if let Some(held_pane) = self.get_tiled_or_floating_pane_mut(held_pane_id) {
+                if !is_repeated {
+                    let relative_position = held_pane.relative_position(position_on_screen);
+                    held_pane.update_selection(&relative_position, client_id);
+                }
+                return Ok(false); // the plugin will render itself if it needs to
+            }
+        }
+
         let selecting = self.selecting_with_mouse;
         let active_pane = self.get_active_pane_or_floating_pane_mut(client_id);
 
@@ -3168,6 +3359,25 @@ impl Tab {
         Ok(false) // we shouldn't even get here, but might as well not needlessly render if we do
     }
 
+    pub fn handle_mouse_motion(
+        &mut self,
+        position_on_screen: &Position,
+        client_id: ClientId,
+    ) -> Result<()> {
+        let err_context = || {
+            format!("failed to handle mouse motion at position {position_on_screen:?} for client {client_id}")
+        };
+
+        let search_selectable = false;
+        if let Some(pane) = self
+            .get_pane_at(position_on_screen, search_selectable)
+            .with_context(err_context)?
+        {
+            let relative_position = pane.relative_position(position_on_screen);
+            pane.mouse_motion(&relative_position, client_id);
+        }
+        Ok(())
+    }
     pub fn handle_mouse_hold_right(
         &mut self,
         position_on_screen: &Position,
@@ -3501,6 +3711,93 @@ impl Tab {
             pane.add_red_pane_frame_color_override(error_text);
         }
     }
+    pub fn update_pane_restarts(
+        &mut self,
+        pane_id: PaneId,
+        restart_count: usize,
+        last_exit_status: Option<i32>,
+    ) {
+        if let Some(pane) = self
+            .tiled_panes
+            .get_pane_mut(pane_id)
+            .or_else(|| self.floating_panes.get_pane_mut(pane_id))
+            .or_else(|| {
+                self.suppressed_panes
+                    .values_mut()
+                    .find(|s_p| s_p.1.pid() == pane_id)
+                    .map(|s_p| &mut s_p.1)
+            })
+        {
+            pane.set_restarts(restart_count, last_exit_status);
+        }
+    }
+    pub fn update_pane_process_infos(
+        &mut self,
+        process_infos: &mut HashMap<u32, PaneProcessInfo>,
+    ) -> (bool, bool) {
+        // returns whether the process info of any of this tab's panes changed, and whether any of
+        // them needs to be rendered again because of it
+        let mut process_infos_changed = false;
+        let mut needs_render = false;
+        let terminal_ids: Vec<u32> = process_infos.keys().copied().collect();
+        for terminal_id in terminal_ids {
+            let pane_id = PaneId::Terminal(terminal_id);
+            if let Some(pane) = self
+                .tiled_panes
+                .get_pane_mut(pane_id)
+                .or_else(|| self.floating_panes.get_pane_mut(pane_id))
+                .or_else(|| {
+                    self.suppressed_panes
+                        .values_mut()
+                        .find(|s_p| s_p.1.pid() == pane_id)
+                        .map(|s_p| &mut s_p.1)
+                })
+            {
+                let process_info = process_infos.remove(&terminal_id);
+                if pane.process_info() != process_info {
+                    needs_render |= pane.set_process_info(process_info);
+                    process_infos_changed = true;
+                }
+            }
+        }
+        (process_infos_changed, needs_render)
+    }
+    pub fn update_pane_blocked_on(&mut self, pane_id: PaneId, blocked_on: Option<String>) {
+        if let Some(pane) = self
+            .tiled_panes
+            .get_pane_mut(pane_id)
+            .or_else(|| self.floating_panes.get_pane_mut(pane_id))
+            .or_else(|| {
+                self.suppressed_panes
+                    .values_mut()
+                    .find(|s_p| s_p.1.pid() == pane_id)
+                    .map(|s_p| &mut s_p.1)
+            })
+        {
+            pane.set_blocked_on(blocked_on);
+        }
+    }
+    pub fn run_held_pane(&mut self, pane_id: PaneId) -> Result<()> {
+        let err_context = || format!("failed to run held pane {:?}", pane_id);
+        let run_command = self
+            .tiled_panes
+            .get_pane_mut(pane_id)
+            .or_else(|| self.floating_panes.get_pane_mut(pane_id))
+            .or_else(|| {
+                self.suppressed_panes
+                    .values_mut()
+                    .find(|s_p| s_p.1.pid() == pane_id)
+                    .map(|s_p| &mut s_p.1)
+            })
+            .and_then(|pane| pane.run_held_command());
+        if let (PaneId::Terminal(terminal_id), Some(run_command)) = (pane_id, run_command) {
+            self.pids_waiting_resize.insert(terminal_id);
+            self.senders
+                .send_to_pty(PtyInstruction::ReRunCommandInPane(pane_id, run_command))
+                .with_context(err_context)?;
+        }
+        Ok(())
+    }
     pub fn clear_pane_frame_color_override(&mut self, pane_id: PaneId) {
         if let Some(pane) = self
             .tiled_panes
@@ -3742,6 +4039,11 @@ pub fn pane_info_for_pane(pane_id: &PaneId, pane: &Box<dyn Pane>) -> PaneInfo {
     pane_info.exited = pane.exited();
     pane_info.exit_status = pane.exit_status();
     pane_info.is_held = pane.is_held();
+    if let Some((restart_count, last_exit_status)) = pane.restarts() {
+        pane_info.restart_count = restart_count;
+        pane_info.last_exit_status = last_exit_status;
+    }
+    pane_info.process_info = pane.process_info();
 
     match pane_id {
         PaneId::Terminal(terminal_id) => {
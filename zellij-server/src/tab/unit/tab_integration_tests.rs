@@ -8,7 +8,7 @@ use crate::{
     panes::PaneId,
     plugins::PluginInstruction,
     thread_bus::ThreadSenders,
-    ClientId,
+    ClientId, ServerInstruction,
 };
 use std::path::PathBuf;
 use std::sync::Mutex;
@@ -37,7 +37,7 @@ use std::os::unix::io::RawFd;
 use std::rc::Rc;
 
 use zellij_utils::{
-    data::{InputMode, ModeInfo, Palette, Style},
+    data::{Event, InputMode, ModeInfo, Palette, Style},
     input::command::{RunCommand, TerminalAction},
     interprocess::local_socket::LocalSocketStream,
     ipc::{ClientToServerMsg, ServerToClientMsg},
@@ -103,6 +103,7 @@ impl ServerOsApi for FakeInputOutput {
         &mut self,
         _client_id: ClientId,
         _stream: LocalSocketStream,
+        _to_server: SenderWithContext<ServerInstruction>,
     ) -> Result<IpcReceiverWithContext<ClientToServerMsg>> {
         unimplemented!()
     }
@@ -276,6 +277,32 @@ fn create_new_tab_with_swap_layouts(
         HashMap<(RunPluginLocation, PluginUserConfiguration), Vec<u32>>,
     )>,
     draw_pane_frames: bool,
+) -> Tab {
+    let (mock_plugin_sender, _mock_plugin_receiver): ChannelWithContext<PluginInstruction> =
+        channels::unbounded();
+    create_new_tab_with_swap_layouts_and_mock_plugin_sender(
+        size,
+        default_mode,
+        swap_layouts,
+        base_layout_and_ids,
+        draw_pane_frames,
+        SenderWithContext::new(mock_plugin_sender),
+    )
+}
+
+fn create_new_tab_with_swap_layouts_and_mock_plugin_sender(
+    size: Size,
+    default_mode: ModeInfo,
+    swap_layouts: (Vec<SwapTiledLayout>, Vec<SwapFloatingLayout>),
+    base_layout_and_ids: Option<(
+        TiledPaneLayout,
+        Vec<FloatingPaneLayout>,
+        Vec<(u32, Option<RunCommand>)>,
+        Vec<(u32, Option<RunCommand>)>,
+        HashMap<(RunPluginLocation, PluginUserConfiguration), Vec<u32>>,
+    )>,
+    draw_pane_frames: bool,
+    mock_plugin_sender: SenderWithContext<PluginInstruction>,
 ) -> Tab {
     set_session_name("test".into());
     let index = 0;
@@ -283,9 +310,7 @@ fn create_new_tab_with_swap_layouts(
     let name = String::new();
     let os_api = Box::new(FakeInputOutput::default());
     let mut senders = ThreadSenders::default().silently_fail_on_send();
-    let (mock_plugin_sender, _mock_plugin_receiver): ChannelWithContext<PluginInstruction> =
-        channels::unbounded();
-    senders.replace_to_plugin(SenderWithContext::new(mock_plugin_sender));
+    senders.replace_to_plugin(mock_plugin_sender);
     let max_panes = None;
     let mode_info = default_mode;
     let style = Style::default();
@@ -6990,3 +7015,95 @@ fn can_define_expanded_pane_in_stack() {
     );
     assert_snapshot!(snapshot);
 }
+
+#[test]
+fn mouse_motion_over_ui_component_is_reported_to_plugin() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 1;
+    let mut output = Output::default();
+    let base_layout = r#"
+        layout {
+            pane size=1 borderless=true {
+                plugin location="zellij:tab-bar"
+            }
+            pane
+        }
+    "#;
+    let (base_layout, base_floating_layout) =
+        Layout::from_kdl(base_layout, "file_name.kdl".into(), None, None)
+            .unwrap()
+            .template
+            .unwrap();
+    let mut new_plugin_ids = HashMap::new();
+    new_plugin_ids.insert(
+        (
+            RunPluginLocation::Zellij(PluginTag::new("tab-bar")),
+            Default::default(),
+        ),
+        vec![1],
+    );
+    let (mock_plugin_sender, mock_plugin_receiver): ChannelWithContext<PluginInstruction> =
+        channels::unbounded();
+    let mut tab = create_new_tab_with_swap_layouts_and_mock_plugin_sender(
+        size,
+        ModeInfo::default(),
+        (vec![], vec![]),
+        Some((
+            base_layout,
+            base_floating_layout,
+            vec![(1, None)],
+            vec![],
+            new_plugin_ids,
+        )),
+        true,
+        SenderWithContext::new(mock_plugin_sender),
+    );
+    // the text "hi" with the id "b"
+    let _ = tab.handle_plugin_bytes(1, 1, "\u{1b}Pztext;@98@104,105\u{1b}\\".as_bytes().to_vec());
+    tab.render(&mut output).unwrap();
+
+    // no mouse button is held in either of these
+    tab.handle_mouse_motion(&Position::new(0, 1), client_id)
+        .unwrap();
+    // still over the same component
+    tab.handle_mouse_motion(&Position::new(0, 0), client_id)
+        .unwrap();
+    tab.handle_mouse_motion(&Position::new(0, 5), client_id)
+        .unwrap();
+    // back onto the component after leaving it
+    tab.handle_mouse_motion(&Position::new(0, 0), client_id)
+        .unwrap();
+    tab.handle_mouse_motion(&Position::new(5, 1), client_id)
+        .unwrap();
+
+    let hovered_components: Vec<Event> = mock_plugin_receiver
+        .try_iter()
+        .filter_map(|(instruction, _)| match instruction {
+            PluginInstruction::Update(updates) => Some(updates),
+            _ => None,
+        })
+        .flatten()
+        .filter_map(|(_, _, event)| match event {
+            Event::ComponentHovered { .. } => Some(event),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        hovered_components,
+        vec![
+            Event::ComponentHovered {
+                id: "b".to_owned(),
+                row: 0,
+                col: 1,
+            },
+            Event::ComponentHovered {
+                id: "b".to_owned(),
+                row: 0,
+                col: 0,
+            }
+        ]
+    );
+}
@@ -5,10 +5,11 @@ use crate::{
     os_input_output::{AsyncReader, Pid, ServerOsApi},
     panes::PaneId,
     thread_bus::ThreadSenders,
-    ClientId,
+    ClientId, ServerInstruction,
 };
 use std::path::PathBuf;
-use zellij_utils::data::{Direction, Resize, ResizeStrategy};
+use zellij_utils::channels::SenderWithContext;
+use zellij_utils::data::{Direction, PaneProcessInfo, Resize, ResizeStrategy};
 use zellij_utils::errors::prelude::*;
 use zellij_utils::input::layout::{SplitDirection, SplitSize, TiledPaneLayout};
 use zellij_utils::ipc::IpcReceiverWithContext;
@@ -77,6 +78,7 @@ impl ServerOsApi for FakeInputOutput {
         &mut self,
         _client_id: ClientId,
         _stream: LocalSocketStream,
+        _to_server: SenderWithContext<ServerInstruction>,
     ) -> Result<IpcReceiverWithContext<ClientToServerMsg>> {
         unimplemented!()
     }
@@ -14424,3 +14426,58 @@ fn correctly_resize_frameless_panes_on_pane_close() {
     let content_size = (pane.get_content_columns(), pane.get_content_rows());
     assert_eq!(content_size, (cols, rows));
 }
+
+#[test]
+fn update_pane_process_infos() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    let process_info = PaneProcessInfo {
+        foreground_process: Some("vim".to_owned()),
+        cpu_usage: 3,
+        memory_usage: 4096,
+        child_commands: vec!["vim Cargo.toml".to_owned()],
+        git_branch: Some("main".to_owned()),
+    };
+    let mut process_infos = HashMap::new();
+    process_infos.insert(1, process_info.clone());
+    process_infos.insert(100, process_info.clone()); // not in this tab
+    assert_eq!(
+        tab.update_pane_process_infos(&mut process_infos),
+        (true, false),
+        "process info of an existing pane updated, it is not displayed by default"
+    );
+    assert_eq!(
+        process_infos.keys().collect::<Vec<_>>(),
+        vec![&100],
+        "process infos of panes in this tab consumed"
+    );
+    let pane_infos = tab.pane_infos();
+    assert_eq!(
+        pane_infos[0].process_info,
+        Some(process_info.clone()),
+        "process info exposed in the pane info"
+    );
+    let mut process_infos = HashMap::new();
+    process_infos.insert(1, process_info.clone());
+    assert_eq!(
+        tab.update_pane_process_infos(&mut process_infos),
+        (false, false),
+        "identical sample does not count as a change"
+    );
+    let mut process_infos = HashMap::new();
+    process_infos.insert(
+        1,
+        PaneProcessInfo {
+            cpu_usage: 5,
+            ..process_info
+        },
+    );
+    assert_eq!(
+        tab.update_pane_process_infos(&mut process_infos),
+        (true, false),
+        "a change in resource usage is reported but not rendered"
+    );
+}
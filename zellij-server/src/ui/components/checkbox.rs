@@ -0,0 +1,34 @@
+use super::{stringify_text, Coordinates, Text};
+use crate::panes::terminal_character::{AnsiCode, RESET_STYLES};
+use zellij_utils::data::Style;
+
+pub fn checkbox(
+    label: Text,
+    checked: bool,
+    style: &Style,
+    component_coordinates: Option<Coordinates>,
+) -> Vec<u8> {
+    let mut label_style = RESET_STYLES.bold(Some(AnsiCode::On));
+    if label.selected {
+        label_style = label_style.background(Some(style.colors.bg.into()));
+    }
+    let box_style = RESET_STYLES
+        .foreground(Some(style.colors.green.into()))
+        .bold(Some(AnsiCode::On));
+    let check = if checked { "x" } else { " " };
+    let label_coordinates = component_coordinates.as_ref().map(|c| Coordinates {
+        width: c.width.map(|w| w.saturating_sub(4)), // "[x] "
+        ..c.clone()
+    });
+    let (text, _text_width) = stringify_text(&label, None, &label_coordinates, style, label_style);
+    let stringified = format!(
+        "{}[{}]{} {}{}{}",
+        box_style, check, RESET_STYLES, label_style, text, RESET_STYLES
+    );
+    match component_coordinates {
+        Some(component_coordinates) => format!("{}{}", component_coordinates, stringified)
+            .as_bytes()
+            .to_vec(),
+        None => stringified.as_bytes().to_vec(),
+    }
+}
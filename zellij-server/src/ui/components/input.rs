@@ -0,0 +1,57 @@
+use super::{is_too_wide, Coordinates, Text};
+use crate::panes::terminal_character::{AnsiCode, RESET_STYLES};
+use unicode_width::UnicodeWidthChar;
+
+pub fn input(
+    content: Text,
+    cursor_col: Option<usize>,
+    is_placeholder: bool,
+    component_coordinates: Option<Coordinates>,
+) -> Vec<u8> {
+    let text_style = if is_placeholder {
+        RESET_STYLES.dim(Some(AnsiCode::On))
+    } else {
+        RESET_STYLES
+    };
+    let cursor_style = text_style.reverse(Some(AnsiCode::On));
+    let mut stringified = String::new();
+    let mut text_width = 0;
+    let mut cursor_drawn = false;
+    for character in content.text.chars() {
+        let character_width = character.width().unwrap_or(0);
+        if is_too_wide(character_width, text_width, &component_coordinates) {
+            break;
+        }
+        if cursor_col == Some(text_width) {
+            stringified.push_str(&format!("{}{}{}", cursor_style, character, text_style));
+            cursor_drawn = true;
+        } else {
+            stringified.push(character);
+        }
+        text_width += character_width;
+    }
+    if cursor_col.is_some() && !cursor_drawn && !is_too_wide(1, text_width, &component_coordinates)
+    {
+        stringified.push_str(&format!("{} {}", cursor_style, text_style));
+        text_width += 1;
+    }
+    // pad the input to its full width so that what was previously typed into it is erased
+    let max_width = component_coordinates
+        .as_ref()
+        .and_then(|c| c.width)
+        .unwrap_or(text_width);
+    for _ in text_width..max_width {
+        stringified.push(' ');
+    }
+    match component_coordinates {
+        Some(component_coordinates) => format!(
+            "{}{}{}{}",
+            component_coordinates, text_style, stringified, RESET_STYLES
+        )
+        .as_bytes()
+        .to_vec(),
+        None => format!("{}{}{}", text_style, stringified, RESET_STYLES)
+            .as_bytes()
+            .to_vec(),
+    }
+}
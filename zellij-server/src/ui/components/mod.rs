@@ -1,17 +1,25 @@
+mod checkbox;
 mod component_coordinates;
+mod input;
 mod nested_list;
+mod progress_bar;
 mod ribbon;
 mod table;
+mod tabs;
 mod text;
 
 use crate::panes::grid::Grid;
 use zellij_utils::errors::prelude::*;
 use zellij_utils::{data::Style, lazy_static::lazy_static, regex::Regex, vte};
 
+use checkbox::checkbox;
 use component_coordinates::{is_too_high, is_too_wide, Coordinates};
+use input::input;
 use nested_list::{nested_list, parse_nested_list_items};
+use progress_bar::progress_bar;
 use ribbon::{emphasis_variants_for_ribbon, emphasis_variants_for_selected_ribbon, ribbon};
 use table::table;
+use tabs::tabs;
 use text::{parse_text, parse_text_params, stringify_text, text, Text};
 
 macro_rules! parse_next_param {
@@ -78,12 +86,28 @@ impl<'a> UiComponentParser<'a> {
                 let _ = params_iter.next(); // we just peeked, let's consume the coords now
             }
         }
+        // components without coordinates are rendered at the cursor
+        let component_origin = component_coordinates
+            .as_ref()
+            .map(|c| ComponentOrigin {
+                x: c.x,
+                y: c.y,
+                rows_start_at_x: true,
+            })
+            .unwrap_or_else(|| {
+                let (x, y) = self.grid.cursor_position();
+                ComponentOrigin {
+                    x,
+                    y,
+                    rows_start_at_x: false,
+                }
+            });
 
         if component_name == &"table" {
             let columns = parse_next_param!(params_iter.next(), usize, "table", "columns");
             let rows = parse_next_param!(params_iter.next(), usize, "table", "rows");
             let stringified_params = parse_text_params(params_iter);
-            let encoded_table = table(
+            let (encoded_table, regions) = table(
                 columns,
                 rows,
                 stringified_params,
@@ -92,6 +116,7 @@ impl<'a> UiComponentParser<'a> {
                 component_coordinates,
             );
             parse_vte_bytes!(self, encoded_table);
+            self.add_regions(regions, component_origin);
             Ok(())
         } else if component_name == &"ribbon" {
             let stringified_params = parse_text_params(params_iter)
@@ -108,22 +133,89 @@ impl<'a> UiComponentParser<'a> {
             Ok(())
         } else if component_name == &"nested_list" {
             let nested_list_items = parse_nested_list_items(params_iter);
-            let encoded_nested_list =
+            let (encoded_nested_list, regions) =
                 nested_list(nested_list_items, &self.style, component_coordinates);
             parse_vte_bytes!(self, encoded_nested_list);
+            self.add_regions(regions, component_origin);
             Ok(())
         } else if component_name == &"text" {
             let stringified_params = parse_text_params(params_iter)
                 .into_iter()
                 .next()
                 .with_context(|| format!("text must have, well, text..."))?;
-            let encoded_text = text(stringified_params, &self.style, component_coordinates);
+            let id = stringified_params.id.clone();
+            let (encoded_text, text_width) =
+                text(stringified_params, &self.style, component_coordinates);
             parse_vte_bytes!(self, encoded_text);
+            if let Some(id) = id {
+                let region = UiComponentRegion::new(id, 0, 0, text_width, 1);
+                self.add_regions(vec![region], component_origin);
+            }
+            Ok(())
+        } else if component_name == &"input" {
+            // an empty cursor column means the input is not focused
+            let cursor_col = params_iter
+                .next()
+                .and_then(|stringified_param| stringified_param.parse::<usize>().ok());
+            let is_placeholder = parse_next_param!(params_iter.next(), u8, "input", "placeholder");
+            let stringified_params = parse_text_params(params_iter)
+                .into_iter()
+                .next()
+                .with_context(|| format!("an input must have text"))?;
+            let encoded_input = input(
+                stringified_params,
+                cursor_col,
+                is_placeholder == 1,
+                component_coordinates,
+            );
+            parse_vte_bytes!(self, encoded_input);
+            Ok(())
+        } else if component_name == &"tabs" {
+            let stringified_params = parse_text_params(params_iter);
+            let encoded_tabs = tabs(stringified_params, &self.style, component_coordinates);
+            parse_vte_bytes!(self, encoded_tabs);
+            Ok(())
+        } else if component_name == &"checkbox" {
+            let checked = parse_next_param!(params_iter.next(), u8, "checkbox", "checked");
+            let stringified_params = parse_text_params(params_iter)
+                .into_iter()
+                .next()
+                .with_context(|| format!("a checkbox must have a label"))?;
+            let encoded_checkbox = checkbox(
+                stringified_params,
+                checked == 1,
+                &self.style,
+                component_coordinates,
+            );
+            parse_vte_bytes!(self, encoded_checkbox);
+            Ok(())
+        } else if component_name == &"progress_bar" {
+            let permille = parse_next_param!(params_iter.next(), usize, "progress_bar", "progress");
+            let stringified_params = parse_text_params(params_iter)
+                .into_iter()
+                .next()
+                .with_context(|| format!("a progress bar must have a label"))?;
+            let encoded_progress_bar = progress_bar(
+                stringified_params,
+                permille,
+                &self.style,
+                component_coordinates,
+            );
+            parse_vte_bytes!(self, encoded_progress_bar);
             Ok(())
         } else {
             Err(anyhow!("Unknown component: {}", component_name))
         }
     }
+    fn add_regions(&mut self, regions: Vec<UiComponentRegion>, component_origin: ComponentOrigin) {
+        for mut region in regions {
+            if region.y == 0 || component_origin.rows_start_at_x {
+                region.x += component_origin.x;
+            }
+            region.y += component_origin.y;
+            self.grid.add_ui_component_region(region);
+        }
+    }
     fn parse_coordinates(&self, coordinates: &str) -> Result<Option<Coordinates>> {
         lazy_static! {
             static ref RE: Regex = Regex::new(r"(\d*)/(\d*)/(\d*)/(\d*)").unwrap();
@@ -155,6 +247,60 @@ impl<'a> UiComponentParser<'a> {
     }
 }
 
+#[derive(Debug, Clone, Copy)]
+struct ComponentOrigin {
+    x: usize,
+    y: usize,
+    // without coordinates, rows after the first one start at the beginning of the line
+    rows_start_at_x: bool,
+}
+
+/// The area of a UI component rendered with an id, clicks on it are reported to the plugin
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UiComponentRegion {
+    pub id: String,
+    pub x: usize,
+    pub y: usize,
+    pub width: usize,
+    pub height: usize,
+}
+
+impl UiComponentRegion {
+    pub fn new(id: String, x: usize, y: usize, width: usize, height: usize) -> Self {
+        UiComponentRegion {
+            id,
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+    /// The id of the component along with the row and col relative to it, if these coordinates
+    /// are inside it
+    pub fn relative_position(&self, x: usize, y: usize) -> Option<(String, usize, usize)> {
+        let is_inside =
+            x >= self.x && x < self.x + self.width && y >= self.y && y < self.y + self.height;
+        if is_inside {
+            Some((self.id.clone(), y - self.y, x - self.x))
+        } else {
+            None
+        }
+    }
+}
+
+fn parse_id(stringified: &mut String) -> Option<String> {
+    if !stringified.starts_with('@') {
+        return None;
+    }
+    let id_end = stringified[1..].find('@')? + 1;
+    let id_bytes: Vec<u8> = stringified[1..id_end]
+        .split(',')
+        .filter_map(|s| s.parse::<u8>().ok())
+        .collect();
+    stringified.drain(0..=id_end);
+    Some(String::from_utf8_lossy(&id_bytes).to_string())
+}
+
 fn parse_selected(stringified: &mut String) -> bool {
     let mut selected = false;
     if stringified.chars().next() == Some('x') {
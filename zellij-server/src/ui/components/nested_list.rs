@@ -1,5 +1,6 @@
 use super::{
-    is_too_high, parse_indices, parse_selected, parse_text, stringify_text, Coordinates, Text,
+    is_too_high, parse_id, parse_indices, parse_selected, parse_text, stringify_text, Coordinates,
+    Text, UiComponentRegion,
 };
 use crate::panes::terminal_character::{AnsiCode, RESET_STYLES};
 use zellij_utils::data::Style;
@@ -12,12 +13,14 @@ pub struct NestedListItem {
     pub indentation_level: usize,
 }
 
+// returns the encoded list and the regions of its items that have an id, relative to the list
 pub fn nested_list(
     mut contents: Vec<NestedListItem>,
     style: &Style,
     coordinates: Option<Coordinates>,
-) -> Vec<u8> {
+) -> (Vec<u8>, Vec<UiComponentRegion>) {
     let mut stringified = String::new();
+    let mut regions = vec![];
     let max_width = coordinates
         .as_ref()
         .and_then(|c| c.width)
@@ -30,6 +33,15 @@ pub fn nested_list(
         if line_item.text.selected {
             reset_styles_for_item.background = None;
         };
+        if let Some(id) = &line_item.text.id {
+            regions.push(UiComponentRegion::new(
+                id.clone(),
+                0,
+                line_index,
+                max_width,
+                1,
+            ));
+        }
         let padding = line_item.indentation_level * 2 + 1;
         let bulletin = if line_item.indentation_level % 2 == 0 {
             "> "
@@ -73,7 +85,7 @@ pub fn nested_list(
             ));
         }
     }
-    stringified.as_bytes().to_vec()
+    (stringified.as_bytes().to_vec(), regions)
 }
 
 pub fn parse_nested_list_items<'a>(
@@ -82,6 +94,7 @@ pub fn parse_nested_list_items<'a>(
     params_iter
         .flat_map(|mut stringified| {
             let indentation_level = parse_indentation_level(&mut stringified);
+            let id = parse_id(&mut stringified);
             let selected = parse_selected(&mut stringified);
             let indices = parse_indices(&mut stringified);
             let text = parse_text(&mut stringified).map_err(|e| e.to_string())?;
@@ -89,6 +102,7 @@ pub fn parse_nested_list_items<'a>(
                 text,
                 selected,
                 indices,
+                id,
             };
             Ok::<NestedListItem, String>(NestedListItem {
                 text,
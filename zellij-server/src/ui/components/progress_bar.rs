@@ -0,0 +1,53 @@
+use super::{stringify_text, Coordinates, Text};
+use crate::panes::terminal_character::{AnsiCode, RESET_STYLES};
+use zellij_utils::data::Style;
+
+const DEFAULT_BAR_WIDTH: usize = 20;
+const PERCENTAGE_WIDTH: usize = 5; // " 100%"
+
+pub fn progress_bar(
+    label: Text,
+    permille: usize,
+    style: &Style,
+    component_coordinates: Option<Coordinates>,
+) -> Vec<u8> {
+    let permille = std::cmp::min(permille, 1000);
+    let label_style = RESET_STYLES.bold(Some(AnsiCode::On));
+    let (label_text, label_width) = if label.text.is_empty() {
+        (String::new(), 0)
+    } else {
+        let label_coordinates = component_coordinates.as_ref().map(|c| Coordinates {
+            width: c.width.map(|w| w.saturating_sub(PERCENTAGE_WIDTH + 2)),
+            ..c.clone()
+        });
+        let (text, text_width) =
+            stringify_text(&label, None, &label_coordinates, style, label_style);
+        (format!("{} ", text), text_width + 1)
+    };
+    let bar_width = component_coordinates
+        .as_ref()
+        .and_then(|c| c.width)
+        .map(|w| w.saturating_sub(label_width + PERCENTAGE_WIDTH))
+        .unwrap_or(DEFAULT_BAR_WIDTH);
+    let filled_width = bar_width * permille / 1000;
+    let filled_style = RESET_STYLES.foreground(Some(style.colors.green.into()));
+    let empty_style = RESET_STYLES.dim(Some(AnsiCode::On));
+    let stringified = format!(
+        "{}{}{}{}{}{}{}{:>4}%{}",
+        label_style,
+        label_text,
+        filled_style,
+        "█".repeat(filled_width),
+        empty_style,
+        "░".repeat(bar_width - filled_width),
+        label_style,
+        permille / 10,
+        RESET_STYLES
+    );
+    match component_coordinates {
+        Some(component_coordinates) => format!("{}{}", component_coordinates, stringified)
+            .as_bytes()
+            .to_vec(),
+        None => stringified.as_bytes().to_vec(),
+    }
+}
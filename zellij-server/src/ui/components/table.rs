@@ -1,4 +1,4 @@
-use super::{is_too_high, is_too_wide, stringify_text, Coordinates, Text};
+use super::{is_too_high, is_too_wide, stringify_text, Coordinates, Text, UiComponentRegion};
 use crate::panes::terminal_character::{AnsiCode, RESET_STYLES};
 use std::collections::BTreeMap;
 use zellij_utils::{
@@ -13,9 +13,10 @@ pub fn table(
     title_color: Option<PaletteColor>,
     style: &Style,
     coordinates: Option<Coordinates>,
-) -> Vec<u8> {
+) -> (Vec<u8>, Vec<UiComponentRegion>) {
     let mut stringified = String::new();
-    // we first arrange the data by columns so that we can pad them by the widest one
+    let mut regions = vec![]; // of the cells with an id, relative to the table
+                              // we first arrange the data by columns so that we can pad them by the widest one
     let stringified_columns = stringify_table_columns(contents, columns);
     let stringified_rows = stringify_table_rows(stringified_columns, &coordinates);
     let title_styles = RESET_STYLES
@@ -27,6 +28,7 @@ pub fn table(
         if is_too_high(row_index + 1, &coordinates) {
             break;
         }
+        let mut cell_x = 0;
         for cell in row {
             let mut reset_styles_for_item = RESET_STYLES;
             let mut text_style = if is_title_row {
@@ -40,7 +42,17 @@ pub fn table(
             }
             // here we intentionally don't pass our coordinates even if we have them, because
             // these cells have already been padded and truncated
-            let (text, _text_width) = stringify_text(&cell, None, &None, style, text_style);
+            let (text, text_width) = stringify_text(&cell, None, &None, style, text_style);
+            if let Some(id) = &cell.id {
+                regions.push(UiComponentRegion::new(
+                    id.clone(),
+                    cell_x,
+                    row_index,
+                    text_width,
+                    1,
+                ));
+            }
+            cell_x += text_width + 1;
             stringified.push_str(&format!("{}{}{} ", text_style, text, reset_styles_for_item));
         }
         let next_row_instruction = coordinates
@@ -49,13 +61,14 @@ pub fn table(
             .unwrap_or_else(|| format!("\n\r"));
         stringified.push_str(&next_row_instruction);
     }
-    if let Some(coordinates) = coordinates {
+    let encoded_table = if let Some(coordinates) = coordinates {
         format!("{}{}", coordinates, stringified)
             .as_bytes()
             .to_vec()
     } else {
         stringified.as_bytes().to_vec()
-    }
+    };
+    (encoded_table, regions)
 }
 
 fn stringify_table_columns(contents: Vec<Text>, columns: usize) -> BTreeMap<usize, Vec<Text>> {
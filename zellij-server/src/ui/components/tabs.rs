@@ -0,0 +1,41 @@
+use super::{stringify_text, Coordinates, Text};
+use crate::panes::terminal_character::{AnsiCode, RESET_STYLES};
+use unicode_width::UnicodeWidthStr;
+use zellij_utils::data::Style;
+
+pub fn tabs(
+    contents: Vec<Text>,
+    style: &Style,
+    component_coordinates: Option<Coordinates>,
+) -> Vec<u8> {
+    let colors = style.colors;
+    let mut stringified = component_coordinates
+        .as_ref()
+        .map(|c| c.to_string())
+        .unwrap_or_default();
+    let mut tabs_width = 0;
+    for tab in contents {
+        let background = if tab.selected {
+            colors.green
+        } else {
+            colors.fg
+        };
+        let tab_style = RESET_STYLES
+            .foreground(Some(colors.black.into()))
+            .background(Some(background.into()))
+            .bold(Some(AnsiCode::On));
+        // the tab's padding is included in its width
+        let coordinates_for_tab = component_coordinates.as_ref().map(|c| Coordinates {
+            width: c.width.map(|w| w.saturating_sub(tabs_width + 2)),
+            ..c.clone()
+        });
+        let (text, text_width) = stringify_text(&tab, None, &coordinates_for_tab, style, tab_style);
+        if text_width < tab.text.width() {
+            // the plugin only sends the tabs that fit, this one was cut
+            break;
+        }
+        stringified.push_str(&format!("{} {} {}", tab_style, text, RESET_STYLES));
+        tabs_width += text_width + 2;
+    }
+    stringified.as_bytes().to_vec()
+}
@@ -1,5 +1,5 @@
 use super::{
-    emphasis_variants_for_ribbon, emphasis_variants_for_selected_ribbon, is_too_wide,
+    emphasis_variants_for_ribbon, emphasis_variants_for_selected_ribbon, is_too_wide, parse_id,
     parse_indices, parse_selected, Coordinates,
 };
 use crate::panes::terminal_character::{AnsiCode, CharacterStyles, RESET_STYLES};
@@ -11,19 +11,25 @@ use zellij_utils::{
 use unicode_width::UnicodeWidthChar;
 use zellij_utils::errors::prelude::*;
 
-pub fn text(content: Text, style: &Style, component_coordinates: Option<Coordinates>) -> Vec<u8> {
+// returns the encoded text and its width
+pub fn text(
+    content: Text,
+    style: &Style,
+    component_coordinates: Option<Coordinates>,
+) -> (Vec<u8>, usize) {
     let mut text_style = RESET_STYLES.bold(Some(AnsiCode::On));
     if content.selected {
         text_style = text_style.background(Some(style.colors.bg.into()));
     }
-    let (text, _text_width) =
+    let (text, text_width) =
         stringify_text(&content, None, &component_coordinates, style, text_style);
-    match component_coordinates {
+    let encoded_text = match component_coordinates {
         Some(component_coordinates) => format!("{}{}{}", component_coordinates, text_style, text)
             .as_bytes()
             .to_vec(),
         None => format!("{}{}", text_style, text).as_bytes().to_vec(),
-    }
+    };
+    (encoded_text, text_width)
 }
 
 pub fn stringify_text(
@@ -82,6 +88,7 @@ pub fn emphasis_variants(style: &Style) -> [PaletteColor; 4] {
 pub fn parse_text_params<'a>(params_iter: impl Iterator<Item = &'a mut String>) -> Vec<Text> {
     params_iter
         .flat_map(|mut stringified| {
+            let id = parse_id(&mut stringified);
             let selected = parse_selected(&mut stringified);
             let indices = parse_indices(&mut stringified);
             let text = parse_text(&mut stringified).map_err(|e| e.to_string())?;
@@ -89,6 +96,7 @@ pub fn parse_text_params<'a>(params_iter: impl Iterator<Item = &'a mut String>)
                 text,
                 selected,
                 indices,
+                id,
             })
         })
         .collect::<Vec<Text>>()
@@ -99,6 +107,7 @@ pub struct Text {
     pub text: String,
     pub selected: bool,
     pub indices: Vec<Vec<usize>>,
+    pub id: Option<String>, // clicks on text with an id are reported to the plugin
 }
 
 impl Text {
@@ -153,6 +162,10 @@ impl Text {
 }
 
 pub fn parse_text(stringified: &mut String) -> Result<String> {
+    if stringified.is_empty() {
+        // eg. an empty input or a progress bar without a label
+        return Ok(String::new());
+    }
     let mut utf8 = vec![];
     for stringified_character in stringified.split(',') {
         utf8.push(
@@ -93,6 +93,8 @@ pub struct PaneFrame {
     pub other_focused_clients: Vec<ClientId>,
     exit_status: Option<ExitStatus>,
     is_first_run: bool,
+    restarts: Option<(usize, Option<i32>)>, // (restart count, last exit status)
+    blocked_on: Option<String>,             // the pane a held pane is waiting for
     pane_is_stacked_over: bool,
     pane_is_stacked_under: bool,
     should_draw_pane_frames: bool,
@@ -117,6 +119,8 @@ impl PaneFrame {
             other_cursors_exist_in_session: frame_params.other_cursors_exist_in_session,
             exit_status: None,
             is_first_run: false,
+            restarts: None,
+            blocked_on: None,
             pane_is_stacked_over: frame_params.pane_is_stacked_over,
             pane_is_stacked_under: frame_params.pane_is_stacked_under,
             should_draw_pane_frames: frame_params.should_draw_pane_frames,
@@ -128,6 +132,12 @@ impl PaneFrame {
             None => Some(ExitStatus::Exited),
         };
     }
+    pub fn add_blocked_on(&mut self, blocked_on: String) {
+        self.blocked_on = Some(blocked_on);
+    }
+    pub fn add_restarts(&mut self, restart_count: usize, last_exit_status: Option<i32>) {
+        self.restarts = Some((restart_count, last_exit_status));
+    }
     pub fn indicate_first_run(&mut self) {
         self.is_first_run = true;
     }
@@ -744,6 +754,14 @@ impl PaneFrame {
                             x,
                             y,
                         ));
+                    } else if self.restarts.is_some() {
+                        let x = self.geom.x;
+                        let y = self.geom.y + row;
+                        character_chunks.push(CharacterChunk::new(
+                            self.render_restarts_undertitle(),
+                            x,
+                            y,
+                        ));
                     } else {
                         let mut bottom_row = vec![];
                         for col in 0..self.geom.cols {
@@ -827,9 +845,90 @@ impl PaneFrame {
                     left_bracket.len() + exited_text.len() + right_bracket.len(),
                 )
             },
+            None => match &self.blocked_on {
+                Some(blocked_on) => {
+                    let mut first_part = vec![];
+                    let left_bracket = " [ ";
+                    let waiting_text = "WAITING FOR: ";
+                    let right_bracket = " ] ";
+                    first_part.append(&mut foreground_color(left_bracket, self.color));
+                    first_part.append(&mut foreground_color(waiting_text, self.color));
+                    first_part.append(&mut foreground_color(
+                        blocked_on,
+                        Some(self.style.colors.orange),
+                    ));
+                    first_part.append(&mut foreground_color(right_bracket, self.color));
+                    (
+                        first_part,
+                        left_bracket.len()
+                            + waiting_text.len()
+                            + blocked_on.width()
+                            + right_bracket.len(),
+                    )
+                },
+                None => (foreground_color(boundary_type::HORIZONTAL, self.color), 1),
+            },
+        }
+    }
+    fn restarts_title_part_full(&self) -> (Vec<TerminalCharacter>, usize) {
+        // (title part, length)
+        match self.restarts {
+            Some((restart_count, last_exit_status)) => {
+                let mut part = vec![];
+                let left_bracket = " [ ";
+                let restarts_text = format!("RESTARTS: {}", restart_count);
+                let last_exit_text = ", LAST EXIT CODE: ";
+                let (exit_code_text, exit_code_color) = match last_exit_status {
+                    Some(0) => ("0".to_owned(), self.style.colors.green),
+                    Some(exit_code) => (format!("{}", exit_code), self.style.colors.red),
+                    None => ("-".to_owned(), self.style.colors.red),
+                };
+                let right_bracket = " ] ";
+                part.append(&mut foreground_color(left_bracket, self.color));
+                part.append(&mut foreground_color(
+                    &restarts_text,
+                    Some(self.style.colors.orange),
+                ));
+                part.append(&mut foreground_color(last_exit_text, self.color));
+                part.append(&mut foreground_color(
+                    &exit_code_text,
+                    Some(exit_code_color),
+                ));
+                part.append(&mut foreground_color(right_bracket, self.color));
+                (
+                    part,
+                    left_bracket.len()
+                        + restarts_text.chars().count()
+                        + last_exit_text.len()
+                        + exit_code_text.chars().count()
+                        + right_bracket.len(),
+                )
+            },
             None => (foreground_color(boundary_type::HORIZONTAL, self.color), 1),
         }
     }
+    fn render_restarts_undertitle(&self) -> Vec<TerminalCharacter> {
+        let max_undertitle_length = self.geom.cols.saturating_sub(2); // 2 for the left and right corners
+        let (mut restarts_part, restarts_part_len) = self.restarts_title_part_full();
+        if restarts_part_len <= max_undertitle_length {
+            let mut left_boundary =
+                foreground_color(self.get_corner(boundary_type::BOTTOM_LEFT), self.color);
+            let mut right_boundary =
+                foreground_color(self.get_corner(boundary_type::BOTTOM_RIGHT), self.color);
+            let mut padding = String::new();
+            for _ in restarts_part_len..max_undertitle_length {
+                padding.push_str(boundary_type::HORIZONTAL);
+            }
+            let mut ret = vec![];
+            ret.append(&mut left_boundary);
+            ret.append(&mut restarts_part);
+            ret.append(&mut foreground_color(&padding, self.color));
+            ret.append(&mut right_boundary);
+            ret
+        } else {
+            self.empty_undertitle(max_undertitle_length)
+        }
+    }
     fn second_held_title_part_full(&self) -> (Vec<TerminalCharacter>, usize) {
         // (title part, length)
         let mut second_part = vec![];
@@ -137,6 +137,13 @@ impl<'a> PaneContentsAndUi<'a> {
                 })?;
             if let Some(colors) = client_id_to_colors(*fake_cursor_client_id, self.style.colors) {
                 if let Some(vte_output) = self.pane.render_fake_cursor(colors.0, colors.1) {
+                    if let Some((cursor_x, cursor_y)) = self.pane.cursor_coordinates() {
+                        self.output.add_cell_drawn_by_vte_instruction(
+                            client_id,
+                            self.pane.get_content_x() + cursor_x,
+                            self.pane.get_content_y() + cursor_y,
+                        );
+                    }
                     self.output.add_post_vte_instruction_to_client(
                         client_id,
                         &format!(
@@ -40,6 +40,7 @@ fn get_cwd() {
         client_senders: Arc::default(),
         terminal_id_to_raw_fd: Arc::default(),
         cached_resizes: Arc::default(),
+        process_sampler: Arc::default(),
     };
 
     let pid = nix::unistd::getpid();
@@ -49,3 +50,58 @@ fn get_cwd() {
         pid
     );
 }
+
+#[test]
+fn get_pane_process_infos() {
+    let test_terminal = TestTerminal::new();
+    let test_termios =
+        termios::tcgetattr(test_terminal.slave()).expect("Could not configure the termios");
+
+    let server = ServerOsInputOutput {
+        orig_termios: Arc::new(Mutex::new(test_termios)),
+        client_senders: Arc::default(),
+        terminal_id_to_raw_fd: Arc::default(),
+        cached_resizes: Arc::default(),
+        process_sampler: Arc::default(),
+    };
+
+    let pid = nix::unistd::getpid();
+    let process_infos = server.get_pane_process_infos(vec![(1, pid)], true);
+    let process_info = process_infos
+        .get(&1)
+        .expect("Sample the process tree of the current process");
+    assert!(
+        process_info.foreground_process.is_some(),
+        "Terminal without a pty falls back to its own process as the foreground process"
+    );
+    assert!(
+        process_info.memory_usage > 0,
+        "Memory usage is sampled for PID {}",
+        pid
+    );
+}
+
+#[test]
+fn git_branch_of_folder_in_repository() {
+    let repository = tempfile::tempdir().expect("Could not create a temporary folder");
+    let git_dir = repository.path().join(".git");
+    std::fs::create_dir(&git_dir).unwrap();
+    std::fs::write(git_dir.join("HEAD"), "ref: refs/heads/main\n").unwrap();
+    let nested_folder = repository.path().join("src").join("unit");
+    std::fs::create_dir_all(&nested_folder).unwrap();
+    assert_eq!(
+        git_branch(&nested_folder),
+        Some("main".to_owned()),
+        "Branch found from a nested folder"
+    );
+    std::fs::write(
+        git_dir.join("HEAD"),
+        "4b950f9a1d3c9e2b7f0e8d6c5a4b3c2d1e0f9a8b\n",
+    )
+    .unwrap();
+    assert_eq!(
+        git_branch(repository.path()),
+        Some("4b950f9".to_owned()),
+        "Short commit hash of a detached HEAD"
+    );
+}
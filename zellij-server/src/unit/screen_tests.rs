@@ -10,9 +10,9 @@ use crate::{
 use insta::assert_snapshot;
 use std::path::PathBuf;
 use zellij_utils::cli::CliAction;
-use zellij_utils::data::{Event, Resize, Style};
+use zellij_utils::data::{Event, PaneSignal, Resize, Style};
 use zellij_utils::errors::{prelude::*, ErrorContext};
-use zellij_utils::input::actions::Action;
+use zellij_utils::input::actions::{Action, ActionTarget};
 use zellij_utils::input::command::{RunCommand, TerminalAction};
 use zellij_utils::input::layout::{
     FloatingPaneLayout, Layout, Run, RunPlugin, RunPluginLocation, SplitDirection, TiledPaneLayout,
@@ -35,6 +35,7 @@ use zellij_utils::{
     data::{Direction, InputMode, ModeInfo, Palette, PluginCapabilities},
     interprocess::local_socket::LocalSocketStream,
     ipc::{ClientAttributes, ClientToServerMsg, ServerToClientMsg},
+    session_serialization::serialize_session_layout,
 };
 
 use crate::panes::grid::Grid;
@@ -107,6 +108,15 @@ fn send_cli_action_to_server(
     session_metadata: &SessionMetaData,
     cli_action: CliAction,
     client_id: ClientId,
+) {
+    send_targeted_cli_action_to_server(session_metadata, cli_action, None, client_id)
+}
+
+fn send_targeted_cli_action_to_server(
+    session_metadata: &SessionMetaData,
+    cli_action: CliAction,
+    target: Option<ActionTarget>,
+    client_id: ClientId,
 ) {
     let get_current_dir = || PathBuf::from(".");
     let actions = Action::actions_from_cli(cli_action, Box::new(get_current_dir), None).unwrap();
@@ -116,6 +126,10 @@ fn send_cli_action_to_server(
     let default_shell = None;
     let default_layout = Box::new(Layout::default());
     for action in actions {
+        let action = match &target {
+            Some(target) => Action::Targeted(target.clone(), Box::new(action)),
+            None => action,
+        };
         route_action(
             action,
             client_id,
@@ -191,6 +205,7 @@ impl ServerOsApi for FakeInputOutput {
         &mut self,
         _client_id: ClientId,
         _stream: LocalSocketStream,
+        _to_server: SenderWithContext<ServerInstruction>,
     ) -> Result<IpcReceiverWithContext<ClientToServerMsg>> {
         unimplemented!()
     }
@@ -249,6 +264,7 @@ fn create_new_screen(size: Size) -> Screen {
     let debug = false;
     let styled_underlines = true;
     let arrow_fonts = true;
+    let workspace = None;
     let screen = Screen::new(
         bus,
         &client_attributes,
@@ -266,6 +282,7 @@ fn create_new_screen(size: Size) -> Screen {
         scrollback_lines_to_serialize,
         styled_underlines,
         arrow_fonts,
+        workspace,
     );
     screen
 }
@@ -613,6 +630,78 @@ pub fn switch_to_next_tab() {
     );
 }
 
+#[test]
+pub fn move_active_tab() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut screen = create_new_screen(size);
+
+    new_tab(&mut screen, 1, 1);
+    new_tab(&mut screen, 2, 2);
+    new_tab(&mut screen, 3, 3);
+    screen.move_active_tab(Direction::Left, 1).expect("TEST");
+    screen.move_active_tab(Direction::Left, 1).expect("TEST");
+    screen.move_active_tab(Direction::Left, 1).expect("TEST");
+
+    let positions_by_index = |screen: &mut Screen| -> Vec<(usize, usize)> {
+        screen
+            .get_tabs_mut()
+            .values()
+            .map(|tab| (tab.index, tab.position))
+            .collect()
+    };
+    assert_eq!(
+        screen.get_active_tab(1).unwrap().position,
+        0,
+        "Active tab moved to the first position and no further"
+    );
+    assert_eq!(
+        positions_by_index(&mut screen),
+        vec![(1, 1), (2, 2), (3, 0)],
+        "Other tabs moved to the right"
+    );
+
+    screen.move_active_tab(Direction::Right, 1).expect("TEST");
+    assert_eq!(
+        positions_by_index(&mut screen),
+        vec![(1, 0), (2, 2), (3, 1)],
+        "Active tab moved back to the right"
+    );
+}
+
+#[test]
+pub fn move_active_tab_and_dump_layout() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut screen = create_new_screen(size);
+
+    new_tab(&mut screen, 1, 1);
+    new_tab(&mut screen, 2, 2);
+    new_tab(&mut screen, 3, 3);
+    screen.move_active_tab(Direction::Left, 1).expect("TEST");
+
+    let (kdl_layout, _pane_contents) =
+        serialize_session_layout(screen.get_layout_metadata(None).into()).expect("TEST");
+    let tab_lines: Vec<&str> = kdl_layout
+        .lines()
+        .map(|line| line.trim())
+        .filter(|line| line.starts_with("tab name="))
+        .collect();
+    assert_eq!(
+        tab_lines,
+        vec![
+            "tab name=\"Tab #2\" hide_floating_panes=true {",
+            "tab name=\"Tab #4\" focus=true hide_floating_panes=true {",
+            "tab name=\"Tab #3\" hide_floating_panes=true {",
+        ],
+        "Tabs dumped in the order they were moved to, the moved tab still focused"
+    );
+}
+
 #[test]
 pub fn switch_to_tab_name() {
     let size = Size {
@@ -1043,6 +1132,54 @@ fn attach_after_first_tab_closed() {
     screen.add_client(1).expect("TEST");
 }
 
+#[test]
+fn panes_waiting_for_the_output_of_the_same_pane_are_matched_separately() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut screen = create_new_screen(size);
+    let (to_pty, pty_receiver): ChannelWithContext<PtyInstruction> = channels::unbounded();
+    screen.bus.senders.to_pty = Some(SenderWithContext::new(to_pty));
+    let matched_panes = || -> Vec<(u32, u32)> {
+        pty_receiver
+            .try_iter()
+            .filter_map(|(instruction, _err_ctx)| match instruction {
+                PtyInstruction::PaneOutputMatched(terminal_id, dependent_terminal_id) => {
+                    Some((terminal_id, dependent_terminal_id))
+                },
+                _ => None,
+            })
+            .collect()
+    };
+
+    screen
+        .watch_pane_output(1, 2, "listening on port \\d+".to_owned())
+        .expect("TEST");
+    screen
+        .watch_pane_output(1, 3, "migrations done".to_owned())
+        .expect("TEST");
+    screen
+        .match_pane_output(1, b"starting\r\nlistening on port 8080\r\n")
+        .expect("TEST");
+    assert_eq!(
+        matched_panes(),
+        vec![(1, 2)],
+        "only the first pattern matched"
+    );
+    screen
+        .match_pane_output(1, b"listening on port 8080\r\nmigrations ")
+        .expect("TEST");
+    assert_eq!(
+        matched_panes(),
+        vec![],
+        "a matched pane is not matched again"
+    );
+    screen.match_pane_output(1, b"done\r\n").expect("TEST");
+    assert_eq!(matched_panes(), vec![(1, 3)], "the second pattern matched");
+    assert!(screen.pane_output_watchers.is_empty());
+}
+
 // Following are tests for sending CLI actions
 // these tests are only partially relevant to Screen
 // and are included here for two reasons:
@@ -1892,6 +2029,9 @@ pub fn send_cli_new_pane_action_with_default_parameters() {
         name: None,
         close_on_exit: false,
         start_suspended: false,
+        restart: None,
+        max_restarts: None,
+        restart_backoff: None,
         configuration: None,
         skip_plugin_cache: false,
     };
@@ -1931,6 +2071,9 @@ pub fn send_cli_new_pane_action_with_split_direction() {
         name: None,
         close_on_exit: false,
         start_suspended: false,
+        restart: None,
+        max_restarts: None,
+        restart_backoff: None,
         configuration: None,
         skip_plugin_cache: false,
     };
@@ -1970,6 +2113,9 @@ pub fn send_cli_new_pane_action_with_command_and_cwd() {
         name: None,
         close_on_exit: false,
         start_suspended: false,
+        restart: None,
+        max_restarts: None,
+        restart_backoff: None,
         configuration: None,
         skip_plugin_cache: false,
     };
@@ -1979,6 +2125,167 @@ pub fn send_cli_new_pane_action_with_command_and_cwd() {
     assert_snapshot!(format!("{:?}", *received_pty_instructions.lock().unwrap()));
 }
 
+#[test]
+pub fn send_cli_signal_pane_action() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 10; // fake client id should not appear in the screen's state
+    let mut mock_screen = MockScreen::new(size);
+    let pty_receiver = mock_screen.pty_receiver.take().unwrap();
+    let session_metadata = mock_screen.clone_session_metadata();
+    let mut initial_layout = TiledPaneLayout::default();
+    initial_layout.children_split_direction = SplitDirection::Vertical;
+    initial_layout.children = vec![TiledPaneLayout::default(), TiledPaneLayout::default()];
+    let screen_thread = mock_screen.run(Some(initial_layout), vec![]);
+    let received_pty_instructions = Arc::new(Mutex::new(vec![]));
+    let pty_thread = log_actions_in_thread!(
+        received_pty_instructions,
+        PtyInstruction::Exit,
+        pty_receiver
+    );
+    let signal_focused_pane = CliAction::SignalPane {
+        signal: PaneSignal::Stop,
+    };
+    let signal_pane_by_id = CliAction::SignalPane {
+        signal: PaneSignal::Terminate,
+    };
+    let signal_non_existent_pane = CliAction::SignalPane {
+        signal: PaneSignal::Kill,
+    };
+    send_cli_action_to_server(&session_metadata, signal_focused_pane, client_id);
+    send_targeted_cli_action_to_server(
+        &session_metadata,
+        signal_pane_by_id,
+        ActionTarget::new(Some(1), None, None),
+        client_id,
+    );
+    send_targeted_cli_action_to_server(
+        &session_metadata,
+        signal_non_existent_pane,
+        ActionTarget::new(Some(100), None, None),
+        client_id,
+    );
+    std::thread::sleep(std::time::Duration::from_millis(100)); // give time for actions to be
+    mock_screen.teardown(vec![pty_thread, screen_thread]);
+    let signal_instructions: Vec<String> = received_pty_instructions
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|instruction| matches!(instruction, PtyInstruction::SignalPane(..)))
+        .map(|instruction| format!("{:?}", instruction))
+        .collect();
+    assert_eq!(
+        signal_instructions,
+        vec!["SignalPane(0, Stop)", "SignalPane(1, Terminate)"],
+        "signals sent to the focused pane and to the pane with the given id"
+    );
+}
+
+#[test]
+pub fn send_cli_write_chars_action_to_pane_by_id() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 10; // fake client id should not appear in the screen's state
+    let mut mock_screen = MockScreen::new(size);
+    let pty_writer_receiver = mock_screen.pty_writer_receiver.take().unwrap();
+    let session_metadata = mock_screen.clone_session_metadata();
+    let mut initial_layout = TiledPaneLayout::default();
+    initial_layout.children_split_direction = SplitDirection::Vertical;
+    initial_layout.children = vec![TiledPaneLayout::default(), TiledPaneLayout::default()];
+    let screen_thread = mock_screen.run(Some(initial_layout), vec![]);
+    let received_pty_instructions = Arc::new(Mutex::new(vec![]));
+    let pty_writer_thread = log_actions_in_thread!(
+        received_pty_instructions,
+        PtyWriteInstruction::Exit,
+        pty_writer_receiver
+    );
+    let cli_action = CliAction::WriteChars {
+        chars: "input from the cli".into(),
+    };
+    send_targeted_cli_action_to_server(
+        &session_metadata,
+        cli_action,
+        ActionTarget::new(Some(1), None, None),
+        client_id,
+    );
+    std::thread::sleep(std::time::Duration::from_millis(100)); // give time for actions to be
+    mock_screen.teardown(vec![pty_writer_thread, screen_thread]);
+    let write_instructions: Vec<String> = received_pty_instructions
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|instruction| matches!(instruction, PtyWriteInstruction::Write(..)))
+        .map(|instruction| format!("{:?}", instruction))
+        .collect();
+    assert_eq!(
+        write_instructions,
+        vec![format!(
+            "Write({:?}, 1)",
+            "input from the cli".as_bytes().to_vec()
+        )],
+        "chars written to the pane with the given id rather than the focused one"
+    );
+}
+
+#[test]
+pub fn send_cli_resize_action_to_pane_by_id() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 10; // fake client id should not appear in the screen's state
+    let mut mock_screen = MockScreen::new(size);
+    let pty_writer_receiver = mock_screen.pty_writer_receiver.take().unwrap();
+    let session_metadata = mock_screen.clone_session_metadata();
+    let mut initial_layout = TiledPaneLayout::default();
+    initial_layout.children_split_direction = SplitDirection::Vertical;
+    initial_layout.children = vec![TiledPaneLayout::default(), TiledPaneLayout::default()];
+    let screen_thread = mock_screen.run(Some(initial_layout), vec![]);
+    let received_pty_instructions = Arc::new(Mutex::new(vec![]));
+    let pty_writer_thread = log_actions_in_thread!(
+        received_pty_instructions,
+        PtyWriteInstruction::Exit,
+        pty_writer_receiver
+    );
+    std::thread::sleep(std::time::Duration::from_millis(100)); // give time for the layout to be applied
+    received_pty_instructions.lock().unwrap().clear();
+    // the first pane is focused
+    let cli_action = CliAction::Resize {
+        resize: Resize::Increase,
+        direction: None,
+    };
+    send_targeted_cli_action_to_server(
+        &session_metadata,
+        cli_action,
+        ActionTarget::new(Some(1), None, None),
+        client_id,
+    );
+    std::thread::sleep(std::time::Duration::from_millis(100)); // give time for actions to be
+    mock_screen.teardown(vec![pty_writer_thread, screen_thread]);
+    let resized_columns: Vec<(u32, u16)> = received_pty_instructions
+        .lock()
+        .unwrap()
+        .iter()
+        .filter_map(|instruction| match instruction {
+            PtyWriteInstruction::ResizePty(terminal_id, columns, ..) => {
+                Some((*terminal_id, *columns))
+            },
+            _ => None,
+        })
+        .collect();
+    assert!(
+        resized_columns
+            .iter()
+            .any(|(terminal_id, columns)| *terminal_id == 1 && *columns > 60),
+        "pane with the given id grew rather than the focused one: {:?}",
+        resized_columns
+    );
+}
+
 #[test]
 pub fn send_cli_edit_action_with_default_parameters() {
     let size = Size {
@@ -2498,6 +2805,59 @@ pub fn send_cli_rename_tab() {
     assert_snapshot!(format!("{:#?}", plugin_rename_tab_instruction))
 }
 
+#[test]
+pub fn send_cli_rename_tab_by_position() {
+    let size = Size { cols: 80, rows: 10 };
+    let client_id = 10; // fake client id should not appear in the screen's state
+    let mut mock_screen = MockScreen::new(size);
+    mock_screen.new_tab(TiledPaneLayout::default());
+    let session_metadata = mock_screen.clone_session_metadata();
+    let screen_thread = mock_screen.run(Some(TiledPaneLayout::default()), vec![]);
+    let received_plugin_instructions = Arc::new(Mutex::new(vec![]));
+    let plugin_receiver = mock_screen.plugin_receiver.take().unwrap();
+    let plugin_thread = log_actions_in_thread!(
+        received_plugin_instructions,
+        PluginInstruction::Exit,
+        plugin_receiver
+    );
+    let rename_tab = CliAction::RenameTab {
+        name: "new-tab-name".into(),
+    };
+    send_targeted_cli_action_to_server(
+        &session_metadata,
+        rename_tab,
+        ActionTarget::new(None, None, Some(0)),
+        client_id,
+    );
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    mock_screen.teardown(vec![plugin_thread, screen_thread]);
+    let last_tab_update = received_plugin_instructions
+        .lock()
+        .unwrap()
+        .iter()
+        .filter_map(|instruction| match instruction {
+            PluginInstruction::Update(updates) => updates.iter().find_map(|u| match u {
+                (_, _, Event::TabUpdate(tab_infos)) => Some(tab_infos.clone()),
+                _ => None,
+            }),
+            _ => None,
+        })
+        .last()
+        .unwrap();
+    let tab_names: Vec<(String, bool)> = last_tab_update
+        .iter()
+        .map(|tab| (tab.name.clone(), tab.active))
+        .collect();
+    assert_eq!(
+        tab_names,
+        vec![
+            ("new-tab-name".to_owned(), false),
+            ("Tab #2".to_owned(), true)
+        ],
+        "first tab renamed without changing the active tab"
+    );
+}
+
 #[test]
 pub fn send_cli_undo_rename_tab() {
     let size = Size { cols: 80, rows: 10 };
@@ -2579,6 +2939,123 @@ pub fn send_cli_query_tab_names_action() {
     assert_snapshot!(format!("{:#?}", log_tab_names_instruction));
 }
 
+#[test]
+pub fn send_cli_list_tabs_action() {
+    let size = Size { cols: 80, rows: 10 };
+    let client_id = 10; // fake client id should not appear in the screen's state
+    let mut mock_screen = MockScreen::new(size);
+    mock_screen.new_tab(TiledPaneLayout::default());
+    let session_metadata = mock_screen.clone_session_metadata();
+    let screen_thread = mock_screen.run(Some(TiledPaneLayout::default()), vec![]);
+    let received_server_instructions = Arc::new(Mutex::new(vec![]));
+    let server_receiver = mock_screen.server_receiver.take().unwrap();
+    let server_thread = log_actions_in_thread!(
+        received_server_instructions,
+        ServerInstruction::KillSession,
+        server_receiver
+    );
+    let list_tabs = CliAction::ListTabs { json: true };
+    send_cli_action_to_server(&session_metadata, list_tabs, client_id);
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    mock_screen.teardown(vec![server_thread, screen_thread]);
+    let log_tabs_instruction = received_server_instructions
+        .lock()
+        .unwrap()
+        .iter()
+        .find(|instruction| match instruction {
+            ServerInstruction::Log(..) => true,
+            _ => false,
+        })
+        .cloned();
+    assert_snapshot!(format!("{:#?}", log_tabs_instruction));
+}
+
+#[test]
+pub fn send_cli_save_snapshot_action() {
+    let size = Size { cols: 80, rows: 10 };
+    let client_id = 10; // fake client id should not appear in the screen's state
+    let mut mock_screen = MockScreen::new(size);
+    mock_screen.new_tab(TiledPaneLayout::default());
+    let session_metadata = mock_screen.clone_session_metadata();
+    let screen_thread = mock_screen.run(Some(TiledPaneLayout::default()), vec![]);
+    let received_plugin_instructions = Arc::new(Mutex::new(vec![]));
+    let plugin_receiver = mock_screen.plugin_receiver.take().unwrap();
+    let plugin_thread = log_actions_in_thread!(
+        received_plugin_instructions,
+        PluginInstruction::Exit,
+        plugin_receiver
+    );
+    let save_snapshot = CliAction::SaveSnapshot {
+        name: "nightly".into(),
+    };
+    send_cli_action_to_server(&session_metadata, save_snapshot, client_id);
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    mock_screen.teardown(vec![plugin_thread, screen_thread]);
+    let save_snapshot_instruction = received_plugin_instructions
+        .lock()
+        .unwrap()
+        .iter()
+        .find_map(|instruction| match instruction {
+            PluginInstruction::SaveSnapshot(session_layout_metadata, snapshot_name, client_id) => {
+                Some((
+                    session_layout_metadata.all_terminal_ids().len(),
+                    snapshot_name.clone(),
+                    *client_id,
+                ))
+            },
+            _ => None,
+        });
+    assert_eq!(
+        save_snapshot_instruction,
+        Some((2, "nightly".to_owned(), client_id)),
+        "layout with the panes of both tabs sent to be saved as a snapshot"
+    );
+    let invalid_snapshot_name = CliAction::SaveSnapshot {
+        name: "my/snapshot".into(),
+    };
+    assert!(
+        Action::actions_from_cli(invalid_snapshot_name, Box::new(|| PathBuf::from(".")), None)
+            .is_err(),
+        "snapshot names cannot contain a path separator"
+    );
+}
+
+#[test]
+pub fn send_cli_list_panes_action() {
+    let size = Size { cols: 80, rows: 10 };
+    let client_id = 10; // fake client id should not appear in the screen's state
+    let mut mock_screen = MockScreen::new(size);
+    let pty_receiver = mock_screen.pty_receiver.take().unwrap();
+    let session_metadata = mock_screen.clone_session_metadata();
+    let screen_thread = mock_screen.run(Some(TiledPaneLayout::default()), vec![]);
+    let received_pty_instructions = Arc::new(Mutex::new(vec![]));
+    let pty_thread = log_actions_in_thread!(
+        received_pty_instructions,
+        PtyInstruction::Exit,
+        pty_receiver
+    );
+    let list_panes = CliAction::ListPanes { json: false };
+    send_cli_action_to_server(&session_metadata, list_panes, client_id);
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    mock_screen.teardown(vec![pty_thread, screen_thread]);
+    let pane_listings =
+        received_pty_instructions.lock().unwrap().iter().find_map(
+            |instruction| match instruction {
+                PtyInstruction::ListPanes(pane_listings, json, _client_id) => {
+                    Some((pane_listings.clone(), *json))
+                },
+                _ => None,
+            },
+        );
+    let (pane_listings, json) = pane_listings.expect("panes were not listed");
+    assert!(!json);
+    assert_eq!(pane_listings.len(), 1);
+    assert_eq!(
+        pane_listings[0].to_string(),
+        "terminal_0\tTab #1\tPane #1\t-\t-\tfocused"
+    );
+}
+
 #[test]
 pub fn send_cli_launch_or_focus_plugin_action() {
     let size = Size {
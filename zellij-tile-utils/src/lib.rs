@@ -1,3 +1,5 @@
+pub mod tab_line;
+
 #[macro_export]
 macro_rules! rgb {
     ($a:expr) => {
@@ -0,0 +1,313 @@
+//! The tab line shared by the tab-bar and compact-bar plugins: clicking, dragging and closing its
+//! tabs, and searching through the ones that did not fit in it
+use unicode_width::UnicodeWidthStr;
+use zellij_tile::prelude::*;
+
+use crate::style;
+
+#[derive(Debug, Default)]
+pub struct LinePart {
+    pub part: String,
+    pub len: usize,
+    pub tab_index: Option<usize>,
+    pub is_overflow_marker: bool, // one of the "+3" markers standing for the tabs that did not fit
+}
+
+pub fn get_clicked_line_part(tab_line: &[LinePart], mouse_click_col: usize) -> Option<&LinePart> {
+    let mut len = 0;
+    for tab_line_part in tab_line {
+        if mouse_click_col >= len && mouse_click_col < len + tab_line_part.len {
+            return Some(tab_line_part);
+        }
+        len += tab_line_part.len;
+    }
+    None
+}
+
+/// What the user did with the mouse and keyboard in the tab line, besides focusing tabs
+#[derive(Debug, Default)]
+pub struct TabLineInteractions {
+    dragged_tab_position: Option<usize>,
+    hidden_tabs_search: Option<HiddenTabsSearch>,
+}
+
+impl TabLineInteractions {
+    /// `tab_to_focus` is the tab the plugin would focus for this click, returns true if the tab
+    /// line should be rendered again
+    pub fn handle_left_click(
+        &mut self,
+        tab_line: &[LinePart],
+        tabs: &[TabInfo],
+        col: usize,
+        tab_to_focus: Option<usize>,
+    ) -> bool {
+        let clicked_line_part = get_clicked_line_part(tab_line, col);
+        if self.hidden_tabs_search.is_some() {
+            if let Some(position) = clicked_line_part.and_then(|l| l.tab_index) {
+                self.close_hidden_tabs_search();
+                switch_tab_to(position as u32 + 1);
+            }
+            return true;
+        }
+        if clicked_line_part.map(|l| l.is_overflow_marker) == Some(true) {
+            self.open_hidden_tabs_search(tab_line, tabs);
+            return true;
+        }
+        if let Some(tab_to_focus) = tab_to_focus {
+            switch_tab_to(tab_to_focus as u32);
+            self.dragged_tab_position = None;
+        } else {
+            // only the focused tab can be dragged, once a tab is focused the mouse events go to
+            // the tab line of its own tab
+            self.dragged_tab_position = clicked_line_part.and_then(|l| l.tab_index);
+        }
+        false
+    }
+    pub fn handle_hold(&mut self, tab_line: &[LinePart], col: usize) {
+        if let Some(dragged_tab_position) = self.dragged_tab_position {
+            if let Some(position_under_mouse) = clicked_tab_position(tab_line, col) {
+                drag_tab(dragged_tab_position, position_under_mouse);
+                self.dragged_tab_position = Some(position_under_mouse);
+            }
+        }
+    }
+    pub fn handle_release(&mut self) {
+        self.dragged_tab_position = None;
+    }
+    /// `active_tab_position` starts from 0
+    pub fn handle_middle_click(
+        &mut self,
+        tab_line: &[LinePart],
+        col: usize,
+        active_tab_position: usize,
+    ) {
+        if let Some(clicked_tab_position) = clicked_tab_position(tab_line, col) {
+            close_tab(clicked_tab_position, active_tab_position);
+        }
+    }
+    /// Returns true if the tab line should be rendered again
+    pub fn handle_key(&mut self, key: Key, tabs: &[TabInfo]) -> bool {
+        let action = match self.hidden_tabs_search.as_mut() {
+            Some(hidden_tabs_search) => hidden_tabs_search.handle_key(key, tabs),
+            None => return false,
+        };
+        match action {
+            HiddenTabsAction::SwitchTo(position) => {
+                self.close_hidden_tabs_search();
+                switch_tab_to(position as u32 + 1);
+            },
+            HiddenTabsAction::Close => self.close_hidden_tabs_search(),
+            HiddenTabsAction::None => {},
+        }
+        true
+    }
+    /// Renders the hidden tabs search in place of the tab line, if it is open
+    pub fn render_hidden_tabs_search(
+        &self,
+        tabs: &[TabInfo],
+        cols: usize,
+        palette: Palette,
+        render_tab: impl Fn(&TabInfo) -> LinePart,
+    ) -> Option<Vec<LinePart>> {
+        self.hidden_tabs_search
+            .as_ref()
+            .map(|hidden_tabs_search| hidden_tabs_search.render(tabs, cols, palette, render_tab))
+    }
+    fn open_hidden_tabs_search(&mut self, tab_line: &[LinePart], tabs: &[TabInfo]) {
+        let visible_tab_positions: Vec<usize> = tab_line
+            .iter()
+            .filter(|l| !l.is_overflow_marker)
+            .filter_map(|l| l.tab_index)
+            .collect();
+        let hidden_tab_positions = tabs
+            .iter()
+            .map(|t| t.position)
+            .filter(|p| !visible_tab_positions.contains(p))
+            .collect();
+        self.hidden_tabs_search = Some(HiddenTabsSearch::new(hidden_tab_positions));
+        // we need to be focused to receive the keys typed in the search
+        set_selectable(true);
+        focus_plugin_pane(get_plugin_ids().plugin_id, false);
+    }
+    fn close_hidden_tabs_search(&mut self) {
+        self.hidden_tabs_search = None;
+        // this moves the focus back to the pane that was focused before
+        set_selectable(false);
+    }
+}
+
+fn clicked_tab_position(tab_line: &[LinePart], col: usize) -> Option<usize> {
+    get_clicked_line_part(tab_line, col)
+        .filter(|l| !l.is_overflow_marker)
+        .and_then(|l| l.tab_index)
+}
+
+// move_tab moves the focused tab, which is the only one that can be dragged
+fn drag_tab(from_position: usize, to_position: usize) {
+    let direction = if to_position > from_position {
+        Direction::Right
+    } else {
+        Direction::Left
+    };
+    for _ in 0..from_position.abs_diff(to_position) {
+        move_tab(direction);
+    }
+}
+
+// there is no command to close a tab that is not focused, so it is focused first
+fn close_tab(tab_position: usize, active_tab_position: usize) {
+    if tab_position == active_tab_position {
+        close_focused_tab();
+        return;
+    }
+    switch_tab_to(tab_position as u32 + 1);
+    close_focused_tab();
+    let active_tab_position_after_close = if active_tab_position > tab_position {
+        active_tab_position - 1
+    } else {
+        active_tab_position
+    };
+    switch_tab_to(active_tab_position_after_close as u32 + 1);
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum HiddenTabsAction {
+    None,
+    SwitchTo(usize), // tab position
+    Close,
+}
+
+/// A search through the tabs that did not fit in the tab line, opened by clicking one of the
+/// "+3" markers and rendered in place of the tab line
+#[derive(Debug, Default)]
+struct HiddenTabsSearch {
+    hidden_tab_positions: Vec<usize>,
+    query: String,
+    selected: usize, // index in the matching tabs
+}
+
+impl HiddenTabsSearch {
+    fn new(hidden_tab_positions: Vec<usize>) -> Self {
+        HiddenTabsSearch {
+            hidden_tab_positions,
+            ..Default::default()
+        }
+    }
+    fn matching_tabs<'a>(&self, tabs: &'a [TabInfo]) -> Vec<&'a TabInfo> {
+        let query = self.query.to_lowercase();
+        tabs.iter()
+            .filter(|t| self.hidden_tab_positions.contains(&t.position))
+            .filter(|t| t.name.to_lowercase().contains(&query))
+            .collect()
+    }
+    fn handle_key(&mut self, key: Key, tabs: &[TabInfo]) -> HiddenTabsAction {
+        let match_count = self.matching_tabs(tabs).len();
+        match key {
+            Key::Char('\n') => {
+                return match self.matching_tabs(tabs).get(self.selected) {
+                    Some(tab) => HiddenTabsAction::SwitchTo(tab.position),
+                    None => HiddenTabsAction::None,
+                };
+            },
+            Key::Esc | Key::Ctrl('c') => return HiddenTabsAction::Close,
+            Key::Left | Key::Up | Key::BackTab => {
+                self.selected = if self.selected == 0 {
+                    match_count.saturating_sub(1)
+                } else {
+                    self.selected - 1
+                };
+            },
+            Key::Right | Key::Down | Key::Char('\t') => {
+                self.selected = if self.selected + 1 >= match_count {
+                    0
+                } else {
+                    self.selected + 1
+                };
+            },
+            Key::Char(character) => {
+                self.query.push(character);
+                self.selected = 0;
+            },
+            Key::Backspace => {
+                self.query.pop();
+                self.selected = 0;
+            },
+            _ => {},
+        }
+        HiddenTabsAction::None
+    }
+    fn render(
+        &self,
+        tabs: &[TabInfo],
+        cols: usize,
+        palette: Palette,
+        render_tab: impl Fn(&TabInfo) -> LinePart,
+    ) -> Vec<LinePart> {
+        let text_color = match palette.theme_hue {
+            ThemeHue::Dark => palette.white,
+            ThemeHue::Light => palette.black,
+        };
+        let bg_color = match palette.theme_hue {
+            ThemeHue::Dark => palette.black,
+            ThemeHue::Light => palette.white,
+        };
+        let prompt = format!(" Hidden tabs: {}_ ", self.query);
+        let prompt_len = prompt.width();
+        let mut line = vec![LinePart {
+            part: style!(text_color, bg_color)
+                .bold()
+                .paint(prompt)
+                .to_string(),
+            len: prompt_len,
+            tab_index: None,
+            is_overflow_marker: false,
+        }];
+        let matching_tabs: Vec<LinePart> = self
+            .matching_tabs(tabs)
+            .iter()
+            .enumerate()
+            .map(|(index, tab)| {
+                // the selected tab is styled like the active one
+                let tab = TabInfo {
+                    active: index == self.selected,
+                    other_focused_clients: vec![],
+                    ..(*tab).clone()
+                };
+                render_tab(&tab)
+            })
+            .collect();
+        if matching_tabs.is_empty() {
+            let no_matches = " No matching tabs ";
+            line.push(LinePart {
+                part: style!(palette.orange, bg_color)
+                    .paint(no_matches)
+                    .to_string(),
+                len: no_matches.width(),
+                tab_index: None,
+                is_overflow_marker: false,
+            });
+            return line;
+        }
+        // skip tabs from the start until the selected one fits
+        let available_cols = cols.saturating_sub(prompt_len);
+        let mut first_index = 0;
+        while first_index < self.selected
+            && matching_tabs[first_index..=self.selected]
+                .iter()
+                .map(|t| t.len)
+                .sum::<usize>()
+                > available_cols
+        {
+            first_index += 1;
+        }
+        let mut len = 0;
+        for tab in matching_tabs.into_iter().skip(first_index) {
+            if len + tab.len > available_cols {
+                break;
+            }
+            len += tab.len;
+            line.push(tab);
+        }
+        line
+    }
+}
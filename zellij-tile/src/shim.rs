@@ -6,8 +6,11 @@ use std::{
 };
 use zellij_utils::data::*;
 use zellij_utils::errors::prelude::*;
+use zellij_utils::input::actions::Action;
 pub use zellij_utils::plugin_api;
-use zellij_utils::plugin_api::plugin_command::ProtobufPluginCommand;
+use zellij_utils::plugin_api::plugin_command::{
+    ProtobufPluginCommand, ProtobufResurrectableSessionLayout,
+};
 use zellij_utils::plugin_api::plugin_ids::{ProtobufPluginIds, ProtobufZellijVersion};
 
 pub use super::ui_components::*;
@@ -349,6 +352,30 @@ pub fn move_focus_or_tab(direction: Direction) {
     unsafe { host_run_plugin_command() };
 }
 
+/// Move the focused tab one position to the left or right
+pub fn move_tab(direction: Direction) {
+    let plugin_command = PluginCommand::MoveTab(direction);
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
+/// Run any action as if it was bound to a key and the key was pressed in this plugin's pane,
+/// actions that cannot be sent to the host (see [`Action::can_be_run_by_plugins`]) are skipped
+pub fn run_action(action: Action) {
+    if !action.can_be_run_by_plugins() {
+        eprintln!(
+            "Skipping {:?}: this action cannot be run by plugins",
+            action
+        );
+        return;
+    }
+    let plugin_command = PluginCommand::RunAction(action);
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
 /// Detach the user from the active session
 pub fn detach() {
     let plugin_command = PluginCommand::Detach;
@@ -664,6 +691,29 @@ pub fn switch_session_with_focus(
         name: Some(name.to_owned()),
         tab_position,
         pane_id,
+        ..Default::default()
+    });
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
+/// Switch to a session with the given name, create one if no name is given or if it does not
+/// exist. A newly created session is started with the given layout (the name of a built-in
+/// layout or of one in the layout dir), in the given cwd and with the given environment
+/// variables set.
+pub fn switch_session_with_layout(
+    name: Option<&str>,
+    layout: Option<&str>,
+    cwd: Option<PathBuf>,
+    env: BTreeMap<String, String>,
+) {
+    let plugin_command = PluginCommand::SwitchSession(ConnectToSession {
+        name: name.map(|n| n.to_string()),
+        layout: layout.map(|l| l.to_string()),
+        cwd,
+        env,
+        ..Default::default()
     });
     let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
     object_to_stdout(&protobuf_plugin_command.encode_to_vec());
@@ -686,6 +736,22 @@ pub fn delete_all_dead_sessions() {
     unsafe { host_run_plugin_command() };
 }
 
+/// Returns the serialized layout a resurrectable session (or snapshot, by its
+/// `<session_name>@<snapshot_name>` id) would be resurrected with, eg. to preview it
+///
+/// Requires the `ReadApplicationState` permission, returns `None` without it
+pub fn get_resurrectable_session_layout(name: &str) -> Option<String> {
+    let plugin_command = PluginCommand::GetResurrectableSessionLayout(name.to_owned());
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+    // nothing is sent back if the command is denied
+    let bytes = bytes_from_stdin().ok()?;
+    ProtobufResurrectableSessionLayout::decode(bytes.as_slice())
+        .ok()?
+        .serialized_layout
+}
+
 /// Rename the current session
 pub fn rename_session(name: &str) {
     let plugin_command = PluginCommand::RenameSession(name.to_owned());
@@ -726,6 +792,14 @@ pub fn pipe_message_to_plugin(message_to_plugin: MessageToPlugin) {
     unsafe { host_run_plugin_command() };
 }
 
+/// Send a signal (eg. SIGTERM or SIGSTOP) to the processes running in a terminal pane
+pub fn signal_terminal_pane(terminal_pane_id: u32, signal: PaneSignal) {
+    let plugin_command = PluginCommand::SignalTerminalPane(terminal_pane_id, signal);
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
 // Utility Functions
 
 #[allow(unused)]
@@ -0,0 +1,90 @@
+use super::{ComponentArea, Text};
+use unicode_width::UnicodeWidthStr;
+use zellij_utils::data::Key;
+
+const CHECKBOX_WIDTH: usize = 4; // "[x] "
+
+/// A labeled checkbox
+///
+/// Feed it keys with [`Checkbox::handle_key`] and clicks with [`Checkbox::handle_click`], then
+/// render it with [`Checkbox::render`]
+#[derive(Debug, Default, Clone)]
+pub struct Checkbox {
+    label: String,
+    checked: bool,
+    focused: bool,
+    area: Option<ComponentArea>,
+}
+
+impl Checkbox {
+    pub fn new<S: ToString>(label: S) -> Self {
+        Checkbox {
+            label: label.to_string(),
+            ..Default::default()
+        }
+    }
+    pub fn checked(mut self) -> Self {
+        self.checked = true;
+        self
+    }
+    pub fn is_checked(&self) -> bool {
+        self.checked
+    }
+    pub fn set_checked(&mut self, checked: bool) {
+        self.checked = checked;
+    }
+    pub fn toggle(&mut self) {
+        self.checked = !self.checked;
+    }
+    /// A focused checkbox is highlighted and toggled with the space key
+    pub fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+    }
+    pub fn is_focused(&self) -> bool {
+        self.focused
+    }
+    /// Returns true if the key toggled the checkbox
+    pub fn handle_key(&mut self, key: &Key) -> bool {
+        match key {
+            Key::Char(' ') if self.focused => {
+                self.toggle();
+                true
+            },
+            _ => false,
+        }
+    }
+    /// Returns true if the click toggled the checkbox
+    pub fn handle_click(&mut self, line: isize, col: usize) -> bool {
+        match self.area {
+            Some(area) if area.contains(line, col) => {
+                self.toggle();
+                true
+            },
+            _ => false,
+        }
+    }
+    /// The area the checkbox was last rendered in
+    pub fn area(&self) -> Option<ComponentArea> {
+        self.area
+    }
+    pub fn render(&mut self, x: usize, y: usize) {
+        print!("{}", self.serialize(x, y));
+    }
+    /// The instructions printed by [`Checkbox::render`]
+    pub fn serialize(&mut self, x: usize, y: usize) -> String {
+        let width = CHECKBOX_WIDTH + self.label.width();
+        self.area = Some(ComponentArea::new(x, y, width, 1));
+        let mut label = Text::new(&self.label);
+        if self.focused {
+            label = label.selected();
+        }
+        format!(
+            "\u{1b}Pzcheckbox;{}/{}/{}/;{};{}\u{1b}\\",
+            x,
+            y,
+            width,
+            if self.checked { 1 } else { 0 },
+            label.serialize()
+        )
+    }
+}
@@ -0,0 +1,35 @@
+/// Where a stateful component was last rendered, used to tell whether a mouse event (eg.
+/// [`Mouse::LeftClick`](crate::prelude::Mouse::LeftClick)) happened over it
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ComponentArea {
+    pub x: usize,
+    pub y: usize,
+    pub width: usize,
+    pub height: usize,
+}
+
+impl ComponentArea {
+    pub fn new(x: usize, y: usize, width: usize, height: usize) -> Self {
+        ComponentArea {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+    pub fn contains(&self, line: isize, col: usize) -> bool {
+        line >= 0
+            && (line as usize) >= self.y
+            && (line as usize) < self.y + self.height
+            && col >= self.x
+            && col < self.x + self.width
+    }
+    /// The position of this line/col relative to the top left of the area, if it is inside it
+    pub fn relative_position(&self, line: isize, col: usize) -> Option<(usize, usize)> {
+        if self.contains(line, col) {
+            Some((line as usize - self.y, col - self.x))
+        } else {
+            None
+        }
+    }
+}
@@ -1,12 +1,24 @@
+mod checkbox;
+mod component_area;
 mod nested_list;
+mod progress_bar;
 mod ribbon;
+mod selectable_list;
 mod table;
+mod tabs;
 mod text;
+mod text_input;
 
 pub use zellij_utils::plugin_api;
 pub use zellij_utils::prost::{self, *};
 
+pub use checkbox::*;
+pub use component_area::*;
 pub use nested_list::*;
+pub use progress_bar::*;
 pub use ribbon::*;
+pub use selectable_list::*;
 pub use table::*;
+pub use tabs::*;
 pub use text::*;
+pub use text_input::*;
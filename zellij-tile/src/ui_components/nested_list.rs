@@ -25,6 +25,12 @@ impl NestedListItem {
         self.content = self.content.selected();
         self
     }
+    /// Clicking on an item rendered with an id sends an
+    /// [`Event::ComponentClicked`](crate::prelude::Event::ComponentClicked) with it to the plugin
+    pub fn id<S: ToString>(mut self, id: S) -> Self {
+        self.content = self.content.id(id);
+        self
+    }
     pub fn color_indices(mut self, index_level: usize, indices: Vec<usize>) -> Self {
         self.content = self.content.color_indices(index_level, indices);
         self
@@ -59,6 +65,19 @@ pub fn print_nested_list_with_coordinates(
     width: Option<usize>,
     height: Option<usize>,
 ) {
+    print!(
+        "{}",
+        serialize_nested_list_with_coordinates(items, x, y, width, height)
+    )
+}
+
+pub(crate) fn serialize_nested_list_with_coordinates(
+    items: Vec<NestedListItem>,
+    x: usize,
+    y: usize,
+    width: Option<usize>,
+    height: Option<usize>,
+) -> String {
     let width = width.map(|w| w.to_string()).unwrap_or_default();
     let height = height.map(|h| h.to_string()).unwrap_or_default();
     let items = items
@@ -66,7 +85,7 @@ pub fn print_nested_list_with_coordinates(
         .map(|i| i.serialize())
         .collect::<Vec<_>>()
         .join(";");
-    print!(
+    format!(
         "\u{1b}Pznested_list;{}/{}/{}/{};{}\u{1b}\\",
         x, y, width, height, items
     )
@@ -0,0 +1,63 @@
+use super::{ComponentArea, Text};
+
+/// A horizontal bar showing how much of something is done
+#[derive(Debug, Default, Clone)]
+pub struct ProgressBar {
+    label: String,
+    done: usize,
+    total: usize,
+    area: Option<ComponentArea>,
+}
+
+impl ProgressBar {
+    pub fn new<S: ToString>(label: S) -> Self {
+        ProgressBar {
+            label: label.to_string(),
+            ..Default::default()
+        }
+    }
+    pub fn set_label<S: ToString>(&mut self, label: S) {
+        self.label = label.to_string();
+    }
+    /// `done` is capped at `total`, a `total` of 0 is shown as an empty bar
+    pub fn set_progress(&mut self, done: usize, total: usize) {
+        self.done = std::cmp::min(done, total);
+        self.total = total;
+    }
+    /// Between 0 and 100
+    pub fn percent(&self) -> usize {
+        if self.total == 0 {
+            0
+        } else {
+            self.done * 100 / self.total
+        }
+    }
+    pub fn is_done(&self) -> bool {
+        self.total > 0 && self.done == self.total
+    }
+    /// The area the progress bar was last rendered in
+    pub fn area(&self) -> Option<ComponentArea> {
+        self.area
+    }
+    /// Render the label followed by the bar and the percentage, all fitting in this width
+    pub fn render(&mut self, x: usize, y: usize, width: usize) {
+        print!("{}", self.serialize(x, y, width));
+    }
+    /// The instructions printed by [`ProgressBar::render`]
+    pub fn serialize(&mut self, x: usize, y: usize, width: usize) -> String {
+        self.area = Some(ComponentArea::new(x, y, width, 1));
+        let permille = if self.total == 0 {
+            0
+        } else {
+            self.done * 1000 / self.total
+        };
+        format!(
+            "\u{1b}Pzprogress_bar;{}/{}/{}/;{};{}\u{1b}\\",
+            x,
+            y,
+            width,
+            permille,
+            Text::new(&self.label).serialize()
+        )
+    }
+}
@@ -0,0 +1,203 @@
+use super::{serialize_nested_list_with_coordinates, ComponentArea, NestedListItem};
+use zellij_utils::data::Key;
+
+const DEFAULT_PAGE_SIZE: usize = 10;
+
+/// A scrollable list with one selected item that can be filtered by the text its items contain
+///
+/// Feed it keys with [`SelectableList::handle_key`] and clicks with
+/// [`SelectableList::handle_click`], then render it with [`SelectableList::render`]
+#[derive(Debug, Default, Clone)]
+pub struct SelectableList {
+    items: Vec<String>,
+    filter: String,
+    matches: Vec<(usize, Vec<usize>)>, // (item index, indices of the characters matching the filter)
+    selected: usize,                   // in matches
+    scroll_offset: usize,              // the first visible match
+    scrolled_away_from_selection: bool,
+    area: Option<ComponentArea>,
+}
+
+impl SelectableList {
+    pub fn new<S: ToString>(items: Vec<S>) -> Self {
+        let mut list = SelectableList::default();
+        list.set_items(items);
+        list
+    }
+    /// Replace the items, keeping the current filter
+    pub fn set_items<S: ToString>(&mut self, items: Vec<S>) {
+        self.items = items.iter().map(|i| i.to_string()).collect();
+        self.apply_filter();
+    }
+    pub fn items(&self) -> &[String] {
+        &self.items
+    }
+    /// Only show items containing this text (ignoring case), an empty filter shows all of them
+    pub fn set_filter<S: AsRef<str>>(&mut self, filter: S) {
+        self.filter = filter.as_ref().to_owned();
+        self.selected = 0;
+        self.scroll_offset = 0;
+        self.scrolled_away_from_selection = false;
+        self.apply_filter();
+    }
+    pub fn filter(&self) -> &str {
+        &self.filter
+    }
+    /// The number of items matching the filter
+    pub fn len(&self) -> usize {
+        self.matches.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.matches.is_empty()
+    }
+    /// The index of the selected item in the list given to [`SelectableList::set_items`]
+    pub fn selected_index(&self) -> Option<usize> {
+        self.matches.get(self.selected).map(|(index, _)| *index)
+    }
+    pub fn selected_item(&self) -> Option<&str> {
+        self.selected_index()
+            .and_then(|index| self.items.get(index))
+            .map(|item| item.as_str())
+    }
+    /// Select the item with this index in the list given to [`SelectableList::set_items`], if it
+    /// matches the filter
+    pub fn select(&mut self, item_index: usize) {
+        if let Some(position) = self
+            .matches
+            .iter()
+            .position(|(index, _)| *index == item_index)
+        {
+            self.selected = position;
+            self.scrolled_away_from_selection = false;
+        }
+    }
+    pub fn select_next(&mut self) {
+        if !self.matches.is_empty() {
+            self.selected = (self.selected + 1) % self.matches.len();
+            self.scrolled_away_from_selection = false;
+        }
+    }
+    pub fn select_previous(&mut self) {
+        if !self.matches.is_empty() {
+            self.selected = self
+                .selected
+                .checked_sub(1)
+                .unwrap_or(self.matches.len() - 1);
+            self.scrolled_away_from_selection = false;
+        }
+    }
+    /// Returns true if the key moved the selection
+    pub fn handle_key(&mut self, key: &Key) -> bool {
+        let page_size = self
+            .area
+            .map(|area| area.height)
+            .unwrap_or(DEFAULT_PAGE_SIZE)
+            .max(1);
+        match key {
+            Key::Down | Key::Ctrl('n') => {
+                self.select_next();
+                true
+            },
+            Key::Up | Key::Ctrl('p') => {
+                self.select_previous();
+                true
+            },
+            Key::PageDown => {
+                self.selected = std::cmp::min(
+                    self.selected + page_size,
+                    self.matches.len().saturating_sub(1),
+                );
+                self.scrolled_away_from_selection = false;
+                true
+            },
+            Key::PageUp => {
+                self.selected = self.selected.saturating_sub(page_size);
+                self.scrolled_away_from_selection = false;
+                true
+            },
+            _ => false,
+        }
+    }
+    /// Selects the clicked item, returning its index in the list given to
+    /// [`SelectableList::set_items`]
+    pub fn handle_click(&mut self, line: isize, col: usize) -> Option<usize> {
+        let (relative_line, _relative_col) = self.area?.relative_position(line, col)?;
+        let position = self.scroll_offset + relative_line;
+        if position < self.matches.len() {
+            self.selected = position;
+            self.selected_index()
+        } else {
+            None
+        }
+    }
+    /// Scroll the visible items without changing the selection (eg. on
+    /// [`Mouse::ScrollUp`](crate::prelude::Mouse::ScrollUp))
+    pub fn scroll_up(&mut self, lines: usize) {
+        self.scrolled_away_from_selection = true;
+        self.scroll_offset = self.scroll_offset.saturating_sub(lines);
+    }
+    pub fn scroll_down(&mut self, lines: usize) {
+        self.scrolled_away_from_selection = true;
+        let height = self.area.map(|area| area.height).unwrap_or(0);
+        let max_scroll_offset = self.matches.len().saturating_sub(height);
+        self.scroll_offset = std::cmp::min(self.scroll_offset + lines, max_scroll_offset);
+    }
+    /// The area the list was last rendered in
+    pub fn area(&self) -> Option<ComponentArea> {
+        self.area
+    }
+    /// Render the items matching the filter, scrolling so that the selected one is visible unless
+    /// the list was scrolled away from it since it was last selected
+    pub fn render(&mut self, x: usize, y: usize, width: usize, height: usize) {
+        print!("{}", self.serialize(x, y, width, height));
+    }
+    /// The instructions printed by [`SelectableList::render`]
+    pub fn serialize(&mut self, x: usize, y: usize, width: usize, height: usize) -> String {
+        if !self.scrolled_away_from_selection && height > 0 {
+            if self.selected < self.scroll_offset {
+                self.scroll_offset = self.selected;
+            } else if self.selected >= self.scroll_offset + height {
+                self.scroll_offset = self.selected + 1 - height;
+            }
+        }
+        self.area = Some(ComponentArea::new(x, y, width, height));
+        let items = self
+            .matches
+            .iter()
+            .enumerate()
+            .skip(self.scroll_offset)
+            .take(height)
+            .map(|(position, (index, matching_indices))| {
+                let mut item = NestedListItem::new(&self.items[*index]);
+                if !matching_indices.is_empty() {
+                    item = item.color_indices(0, matching_indices.clone());
+                }
+                if position == self.selected {
+                    item = item.selected();
+                }
+                item
+            })
+            .collect();
+        serialize_nested_list_with_coordinates(items, x, y, Some(width), Some(height))
+    }
+    fn apply_filter(&mut self) {
+        let filter: Vec<char> = self.filter.to_lowercase().chars().collect();
+        self.matches = self
+            .items
+            .iter()
+            .enumerate()
+            .filter_map(|(index, item)| {
+                if filter.is_empty() {
+                    return Some((index, vec![]));
+                }
+                let item: Vec<char> = item.to_lowercase().chars().collect();
+                item.windows(filter.len())
+                    .position(|window| window == filter.as_slice())
+                    .map(|start| (index, (start..start + filter.len()).collect()))
+            })
+            .collect();
+        self.selected = std::cmp::min(self.selected, self.matches.len().saturating_sub(1));
+        self.scroll_offset =
+            std::cmp::min(self.scroll_offset, self.matches.len().saturating_sub(1));
+    }
+}
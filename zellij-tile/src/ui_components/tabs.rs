@@ -0,0 +1,106 @@
+use super::{ComponentArea, Text};
+use unicode_width::UnicodeWidthStr;
+use zellij_utils::data::Key;
+
+/// A strip of tabs, one of which is selected
+///
+/// Feed it keys with [`Tabs::handle_key`] and clicks with [`Tabs::handle_click`], then render it
+/// with [`Tabs::render`]
+#[derive(Debug, Default, Clone)]
+pub struct Tabs {
+    names: Vec<String>,
+    selected: usize,
+    rendered_tabs: Vec<(usize, usize)>, // (x, width) of each tab that fit in the last render
+    area: Option<ComponentArea>,
+}
+
+impl Tabs {
+    pub fn new<S: ToString>(names: Vec<S>) -> Self {
+        Tabs {
+            names: names.iter().map(|n| n.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+    pub fn select(&mut self, index: usize) {
+        if index < self.names.len() {
+            self.selected = index;
+        }
+    }
+    pub fn select_next(&mut self) {
+        if !self.names.is_empty() {
+            self.selected = (self.selected + 1) % self.names.len();
+        }
+    }
+    pub fn select_previous(&mut self) {
+        if !self.names.is_empty() {
+            self.selected = self.selected.checked_sub(1).unwrap_or(self.names.len() - 1);
+        }
+    }
+    /// Tab and Shift-Tab move the selection, returns true if it moved
+    pub fn handle_key(&mut self, key: &Key) -> bool {
+        match key {
+            Key::Char('\t') => {
+                self.select_next();
+                true
+            },
+            Key::BackTab => {
+                self.select_previous();
+                true
+            },
+            _ => false,
+        }
+    }
+    /// Selects the clicked tab, returning its index
+    pub fn handle_click(&mut self, line: isize, col: usize) -> Option<usize> {
+        let (_relative_line, relative_col) = self.area?.relative_position(line, col)?;
+        let x = self.area?.x + relative_col;
+        let index = self
+            .rendered_tabs
+            .iter()
+            .position(|(tab_x, tab_width)| x >= *tab_x && x < tab_x + tab_width)?;
+        self.selected = index;
+        Some(index)
+    }
+    /// The area the tabs were last rendered in
+    pub fn area(&self) -> Option<ComponentArea> {
+        self.area
+    }
+    /// Render the tabs from left to right, the ones that do not fit in the width are not shown
+    pub fn render(&mut self, x: usize, y: usize, width: usize) {
+        print!("{}", self.serialize(x, y, width));
+    }
+    /// The instructions printed by [`Tabs::render`]
+    pub fn serialize(&mut self, x: usize, y: usize, width: usize) -> String {
+        self.area = Some(ComponentArea::new(x, y, width, 1));
+        self.rendered_tabs.clear();
+        let mut tab_x = x;
+        let mut serialized_tabs = vec![];
+        for (index, name) in self.names.iter().enumerate() {
+            let tab_width = name.width() + 2; // the tab name is padded by a space on each side
+            if tab_x + tab_width > x + width {
+                break;
+            }
+            self.rendered_tabs.push((tab_x, tab_width));
+            tab_x += tab_width;
+            let text = Text::new(name);
+            if index == self.selected {
+                serialized_tabs.push(text.selected().serialize());
+            } else {
+                serialized_tabs.push(text.serialize());
+            }
+        }
+        format!(
+            "\u{1b}Pztabs;{}/{}/{}/;{}\u{1b}\\",
+            x,
+            y,
+            width,
+            serialized_tabs.join(";")
+        )
+    }
+}
@@ -6,6 +6,7 @@ pub struct Text {
     text: String,
     selected: bool,
     indices: Vec<Vec<usize>>,
+    id: Option<String>,
 }
 
 impl Text {
@@ -17,8 +18,15 @@ impl Text {
             text: content.to_string(),
             selected: false,
             indices: vec![],
+            id: None,
         }
     }
+    /// Clicking on text rendered with an id sends an
+    /// [`Event::ComponentClicked`](crate::prelude::Event::ComponentClicked) with it to the plugin
+    pub fn id<S: ToString>(mut self, id: S) -> Self {
+        self.id = Some(id.to_string());
+        self
+    }
     pub fn selected(mut self) -> Self {
         self.selected = true;
         self
@@ -75,10 +83,23 @@ impl Text {
                     .join(",")
             ));
         }
+        let id = self
+            .id
+            .as_ref()
+            .map(|id| {
+                let id = id
+                    .as_bytes()
+                    .iter()
+                    .map(|b| b.to_string())
+                    .collect::<Vec<_>>()
+                    .join(",");
+                format!("@{}@", id)
+            })
+            .unwrap_or_default();
         if self.selected {
-            format!("x{}{}", indices, text)
+            format!("{}x{}{}", id, indices, text)
         } else {
-            format!("{}{}", indices, text)
+            format!("{}{}{}", id, indices, text)
         }
     }
 }
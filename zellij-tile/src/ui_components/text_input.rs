@@ -0,0 +1,242 @@
+use super::{ComponentArea, Text};
+use unicode_width::UnicodeWidthChar;
+use zellij_utils::data::{CharOrArrow, Direction, Key};
+
+/// A single line text input, keeping track of its text and cursor
+///
+/// Feed it keys with [`TextInput::handle_key`] and clicks with [`TextInput::handle_click`], then
+/// render it with [`TextInput::render`]
+#[derive(Debug, Default, Clone)]
+pub struct TextInput {
+    text: String,
+    cursor: usize, // in characters
+    placeholder: String,
+    unfocused: bool,
+    scroll_offset: usize, // the first visible character
+    area: Option<ComponentArea>,
+}
+
+impl TextInput {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn with_text<S: ToString>(mut self, text: S) -> Self {
+        self.set_text(text);
+        self
+    }
+    /// Shown when the input is empty
+    pub fn with_placeholder<S: ToString>(mut self, placeholder: S) -> Self {
+        self.placeholder = placeholder.to_string();
+        self
+    }
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+    /// Replace the text, placing the cursor at its end
+    pub fn set_text<S: ToString>(&mut self, text: S) {
+        self.text = text.to_string();
+        self.cursor = self.text.chars().count();
+    }
+    pub fn clear(&mut self) {
+        self.text.clear();
+        self.cursor = 0;
+        self.scroll_offset = 0;
+    }
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+    /// The cursor position in characters
+    pub fn cursor_position(&self) -> usize {
+        self.cursor
+    }
+    /// An unfocused input does not show its cursor
+    pub fn set_focused(&mut self, focused: bool) {
+        self.unfocused = !focused;
+    }
+    pub fn is_focused(&self) -> bool {
+        !self.unfocused
+    }
+    /// Returns true if the key was used to edit the text or move the cursor
+    pub fn handle_key(&mut self, key: &Key) -> bool {
+        match key {
+            Key::Char('\n') | Key::Char('\t') => false,
+            Key::Char(character) => {
+                self.text.insert(self.byte_index(self.cursor), *character);
+                self.cursor += 1;
+                true
+            },
+            Key::Backspace => {
+                if self.cursor > 0 {
+                    self.cursor -= 1;
+                    self.text.remove(self.byte_index(self.cursor));
+                }
+                true
+            },
+            Key::Delete => {
+                if self.cursor < self.char_count() {
+                    self.text.remove(self.byte_index(self.cursor));
+                }
+                true
+            },
+            Key::Left => {
+                self.cursor = self.cursor.saturating_sub(1);
+                true
+            },
+            Key::Right => {
+                self.cursor = std::cmp::min(self.cursor + 1, self.char_count());
+                true
+            },
+            Key::Home | Key::Ctrl('a') => {
+                self.cursor = 0;
+                true
+            },
+            Key::End | Key::Ctrl('e') => {
+                self.cursor = self.char_count();
+                true
+            },
+            Key::Ctrl('u') => {
+                self.text.replace_range(..self.byte_index(self.cursor), "");
+                self.cursor = 0;
+                true
+            },
+            Key::Ctrl('k') => {
+                self.text.truncate(self.byte_index(self.cursor));
+                true
+            },
+            Key::Ctrl('w') => {
+                let word_start = self.previous_word_start();
+                self.text.replace_range(
+                    self.byte_index(word_start)..self.byte_index(self.cursor),
+                    "",
+                );
+                self.cursor = word_start;
+                true
+            },
+            Key::Alt(CharOrArrow::Char('b'))
+            | Key::Alt(CharOrArrow::Direction(Direction::Left)) => {
+                self.cursor = self.previous_word_start();
+                true
+            },
+            Key::Alt(CharOrArrow::Char('f'))
+            | Key::Alt(CharOrArrow::Direction(Direction::Right)) => {
+                self.cursor = self.next_word_end();
+                true
+            },
+            _ => false,
+        }
+    }
+    /// Moves the cursor to the clicked character, returns true if the click was on the input
+    pub fn handle_click(&mut self, line: isize, col: usize) -> bool {
+        match self.area.and_then(|area| area.relative_position(line, col)) {
+            Some((_line, relative_col)) => {
+                let mut width = 0;
+                let mut cursor = self.scroll_offset;
+                for character in self.text.chars().skip(self.scroll_offset) {
+                    width += character.width().unwrap_or(0);
+                    if width > relative_col {
+                        break;
+                    }
+                    cursor += 1;
+                }
+                self.cursor = cursor;
+                true
+            },
+            None => false,
+        }
+    }
+    /// The area the input was last rendered in
+    pub fn area(&self) -> Option<ComponentArea> {
+        self.area
+    }
+    /// Render the input, scrolling its text horizontally if needed so that the cursor is always
+    /// visible
+    pub fn render(&mut self, x: usize, y: usize, width: usize) {
+        print!("{}", self.serialize(x, y, width));
+    }
+    /// The instructions printed by [`TextInput::render`]
+    pub fn serialize(&mut self, x: usize, y: usize, width: usize) -> String {
+        self.area = Some(ComponentArea::new(x, y, width, 1));
+        self.scroll_to_cursor(width);
+        let mut visible_text = String::new();
+        let mut visible_width = 0;
+        let mut cursor_col = 0;
+        for (index, character) in self.text.chars().enumerate().skip(self.scroll_offset) {
+            let character_width = character.width().unwrap_or(0);
+            if visible_width + character_width > width {
+                break;
+            }
+            if index < self.cursor {
+                cursor_col += character_width;
+            }
+            visible_width += character_width;
+            visible_text.push(character);
+        }
+        let show_placeholder = self.text.is_empty() && !self.placeholder.is_empty();
+        if show_placeholder {
+            visible_text = self.placeholder.clone();
+        }
+        let cursor_col = if self.unfocused {
+            String::new()
+        } else {
+            cursor_col.to_string()
+        };
+        format!(
+            "\u{1b}Pzinput;{}/{}/{}/;{};{};{}\u{1b}\\",
+            x,
+            y,
+            width,
+            cursor_col,
+            if show_placeholder { 1 } else { 0 },
+            Text::new(visible_text).serialize()
+        )
+    }
+    fn scroll_to_cursor(&mut self, width: usize) {
+        if self.cursor < self.scroll_offset {
+            self.scroll_offset = self.cursor;
+        }
+        let width_until_cursor = |scroll_offset: usize| -> usize {
+            self.text
+                .chars()
+                .skip(scroll_offset)
+                .take(self.cursor.saturating_sub(scroll_offset))
+                .map(|c| c.width().unwrap_or(0))
+                .sum::<usize>()
+                + 1 // the cursor itself
+        };
+        while self.scroll_offset < self.cursor && width_until_cursor(self.scroll_offset) > width {
+            self.scroll_offset += 1;
+        }
+    }
+    fn char_count(&self) -> usize {
+        self.text.chars().count()
+    }
+    fn byte_index(&self, char_index: usize) -> usize {
+        self.text
+            .char_indices()
+            .nth(char_index)
+            .map(|(byte_index, _)| byte_index)
+            .unwrap_or(self.text.len())
+    }
+    fn previous_word_start(&self) -> usize {
+        let characters: Vec<char> = self.text.chars().collect();
+        let mut position = self.cursor;
+        while position > 0 && characters[position - 1].is_whitespace() {
+            position -= 1;
+        }
+        while position > 0 && !characters[position - 1].is_whitespace() {
+            position -= 1;
+        }
+        position
+    }
+    fn next_word_end(&self) -> usize {
+        let characters: Vec<char> = self.text.chars().collect();
+        let mut position = self.cursor;
+        while position < characters.len() && characters[position].is_whitespace() {
+            position += 1;
+        }
+        while position < characters.len() && !characters[position].is_whitespace() {
+            position += 1;
+        }
+        position
+    }
+}
@@ -5,7 +5,7 @@ pub struct Action {
     pub name: i32,
     #[prost(
         oneof = "action::OptionalPayload",
-        tags = "2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32, 33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43, 44, 45, 46, 47"
+        tags = "2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32, 33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43, 44, 45, 46, 47, 48, 49"
     )]
     pub optional_payload: ::core::option::Option<action::OptionalPayload>,
 }
@@ -106,10 +106,22 @@ pub mod action {
         LaunchPluginPayload(super::LaunchOrFocusPluginPayload),
         #[prost(message, tag = "47")]
         MessagePayload(super::CliPipePayload),
+        #[prost(message, tag = "48")]
+        SignalPanePayload(super::SignalPanePayload),
+        #[prost(enumeration = "super::super::resize::ResizeDirection", tag = "49")]
+        MoveTabPayload(i32),
     }
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SignalPanePayload {
+    #[prost(uint32, optional, tag = "1")]
+    pub pane_id: ::core::option::Option<u32>,
+    #[prost(string, tag = "2")]
+    pub signal: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct CliPipePayload {
     #[prost(string, optional, tag = "1")]
     pub name: ::core::option::Option<::prost::alloc::string::String>,
@@ -269,6 +281,16 @@ pub struct RunCommandAction {
     pub hold_on_close: bool,
     #[prost(bool, tag = "7")]
     pub hold_on_start: bool,
+    #[prost(string, optional, tag = "8")]
+    pub restart_policy: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(uint32, optional, tag = "9")]
+    pub max_restarts: ::core::option::Option<u32>,
+    #[prost(uint64, optional, tag = "10")]
+    pub restart_backoff_ms: ::core::option::Option<u64>,
+    #[prost(message, repeated, tag = "11")]
+    pub env: ::prost::alloc::vec::Vec<NameAndValue>,
+    #[prost(string, optional, tag = "12")]
+    pub env_file: ::core::option::Option<::prost::alloc::string::String>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -425,6 +447,8 @@ pub enum ActionName {
     RenameSession = 80,
     LaunchPlugin = 81,
     CliPipe = 82,
+    SignalPane = 83,
+    MoveTab = 84,
 }
 impl ActionName {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -516,6 +540,8 @@ impl ActionName {
             ActionName::RenameSession => "RenameSession",
             ActionName::LaunchPlugin => "LaunchPlugin",
             ActionName::CliPipe => "CliPipe",
+            ActionName::SignalPane => "SignalPane",
+            ActionName::MoveTab => "MoveTab",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -604,6 +630,8 @@ impl ActionName {
             "RenameSession" => Some(Self::RenameSession),
             "LaunchPlugin" => Some(Self::LaunchPlugin),
             "CliPipe" => Some(Self::CliPipe),
+            "SignalPane" => Some(Self::SignalPane),
+            "MoveTab" => Some(Self::MoveTab),
             _ => None,
         }
     }
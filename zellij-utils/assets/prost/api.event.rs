@@ -11,7 +11,7 @@ pub struct Event {
     pub name: i32,
     #[prost(
         oneof = "event::Payload",
-        tags = "2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15"
+        tags = "2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18"
     )]
     pub payload: ::core::option::Option<event::Payload>,
 }
@@ -48,6 +48,12 @@ pub mod event {
         RunCommandResultPayload(super::RunCommandResultPayload),
         #[prost(message, tag = "15")]
         WebRequestResultPayload(super::WebRequestResultPayload),
+        #[prost(message, tag = "16")]
+        ComponentEventPayload(super::ComponentEventPayload),
+        #[prost(message, tag = "17")]
+        AvailableLayoutInfoPayload(super::AvailableLayoutInfoPayload),
+        #[prost(message, tag = "18")]
+        AvailablePluginAliasesPayload(super::AvailablePluginAliasesPayload),
     }
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -84,6 +90,38 @@ pub struct WebRequestResultPayload {
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AvailableLayoutInfoPayload {
+    #[prost(message, repeated, tag = "1")]
+    pub available_layouts: ::prost::alloc::vec::Vec<LayoutInfo>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AvailablePluginAliasesPayload {
+    #[prost(string, repeated, tag = "1")]
+    pub aliases: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct LayoutInfo {
+    #[prost(string, tag = "1")]
+    pub name: ::prost::alloc::string::String,
+    #[prost(bool, tag = "2")]
+    pub is_builtin: bool,
+    #[prost(string, tag = "3")]
+    pub stringified_layout: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ComponentEventPayload {
+    #[prost(string, tag = "1")]
+    pub id: ::prost::alloc::string::String,
+    #[prost(uint32, tag = "2")]
+    pub row: u32,
+    #[prost(uint32, tag = "3")]
+    pub col: u32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct ContextItem {
     #[prost(string, tag = "1")]
     pub name: ::prost::alloc::string::String,
@@ -172,6 +210,8 @@ pub struct SessionManifest {
     pub connected_clients: u32,
     #[prost(bool, tag = "5")]
     pub is_current_session: bool,
+    #[prost(string, optional, tag = "6")]
+    pub workspace: ::core::option::Option<::prost::alloc::string::String>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -228,6 +268,26 @@ pub struct PaneInfo {
     pub plugin_url: ::core::option::Option<::prost::alloc::string::String>,
     #[prost(bool, tag = "22")]
     pub is_selectable: bool,
+    #[prost(uint32, tag = "23")]
+    pub restart_count: u32,
+    #[prost(int32, optional, tag = "24")]
+    pub last_exit_status: ::core::option::Option<i32>,
+    #[prost(message, optional, tag = "25")]
+    pub process_info: ::core::option::Option<PaneProcessInfo>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PaneProcessInfo {
+    #[prost(string, optional, tag = "1")]
+    pub foreground_process: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(uint32, tag = "2")]
+    pub cpu_usage: u32,
+    #[prost(uint64, tag = "3")]
+    pub memory_usage: u64,
+    #[prost(string, repeated, tag = "4")]
+    pub child_commands: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    #[prost(string, optional, tag = "5")]
+    pub git_branch: ::core::option::Option<::prost::alloc::string::String>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -252,6 +312,8 @@ pub struct TabInfo {
     pub active_swap_layout_name: ::core::option::Option<::prost::alloc::string::String>,
     #[prost(bool, tag = "10")]
     pub is_swap_layout_dirty: bool,
+    #[prost(bool, tag = "11")]
+    pub is_requesting_attention: bool,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -320,6 +382,14 @@ pub enum EventType {
     SessionUpdate = 16,
     RunCommandResult = 17,
     WebRequestResult = 18,
+    /// / A UI component rendered with an id was clicked
+    ComponentClicked = 19,
+    /// / The mouse moved over a UI component rendered with an id
+    ComponentHovered = 20,
+    /// / The layouts new sessions can be started with
+    AvailableLayoutInfo = 21,
+    /// / The plugin aliases configured in the `plugins` block of the config
+    AvailablePluginAliases = 22,
 }
 impl EventType {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -347,6 +417,10 @@ impl EventType {
             EventType::SessionUpdate => "SessionUpdate",
             EventType::RunCommandResult => "RunCommandResult",
             EventType::WebRequestResult => "WebRequestResult",
+            EventType::ComponentClicked => "ComponentClicked",
+            EventType::ComponentHovered => "ComponentHovered",
+            EventType::AvailableLayoutInfo => "AvailableLayoutInfo",
+            EventType::AvailablePluginAliases => "AvailablePluginAliases",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -371,6 +445,10 @@ impl EventType {
             "SessionUpdate" => Some(Self::SessionUpdate),
             "RunCommandResult" => Some(Self::RunCommandResult),
             "WebRequestResult" => Some(Self::WebRequestResult),
+            "ComponentClicked" => Some(Self::ComponentClicked),
+            "ComponentHovered" => Some(Self::ComponentHovered),
+            "AvailableLayoutInfo" => Some(Self::AvailableLayoutInfo),
+            "AvailablePluginAliases" => Some(Self::AvailablePluginAliases),
             _ => None,
         }
     }
@@ -413,6 +491,7 @@ pub enum MouseEventName {
     MouseRightClick = 3,
     MouseHold = 4,
     MouseRelease = 5,
+    MouseMiddleClick = 6,
 }
 impl MouseEventName {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -427,6 +506,7 @@ impl MouseEventName {
             MouseEventName::MouseRightClick => "MouseRightClick",
             MouseEventName::MouseHold => "MouseHold",
             MouseEventName::MouseRelease => "MouseRelease",
+            MouseEventName::MouseMiddleClick => "MouseMiddleClick",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -438,6 +518,7 @@ impl MouseEventName {
             "MouseRightClick" => Some(Self::MouseRightClick),
             "MouseHold" => Some(Self::MouseHold),
             "MouseRelease" => Some(Self::MouseRelease),
+            "MouseMiddleClick" => Some(Self::MouseMiddleClick),
             _ => None,
         }
     }
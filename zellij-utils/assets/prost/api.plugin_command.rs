@@ -5,7 +5,7 @@ pub struct PluginCommand {
     pub name: i32,
     #[prost(
         oneof = "plugin_command::Payload",
-        tags = "2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32, 33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43, 44, 45, 46, 47, 48, 49, 50"
+        tags = "2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32, 33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43, 44, 45, 46, 47, 48, 49, 50, 51, 52, 53, 54"
     )]
     pub payload: ::core::option::Option<plugin_command::Payload>,
 }
@@ -112,10 +112,32 @@ pub mod plugin_command {
         CliPipeOutputPayload(super::CliPipeOutputPayload),
         #[prost(message, tag = "50")]
         MessageToPluginPayload(super::MessageToPluginPayload),
+        #[prost(message, tag = "51")]
+        SignalTerminalPanePayload(super::SignalTerminalPanePayload),
+        #[prost(message, tag = "52")]
+        MoveTabPayload(super::MovePayload),
+        #[prost(message, tag = "53")]
+        RunActionPayload(super::super::action::Action),
+        #[prost(string, tag = "54")]
+        GetResurrectableSessionLayoutPayload(::prost::alloc::string::String),
     }
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ResurrectableSessionLayout {
+    #[prost(string, optional, tag = "1")]
+    pub serialized_layout: ::core::option::Option<::prost::alloc::string::String>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SignalTerminalPanePayload {
+    #[prost(uint32, tag = "1")]
+    pub pane_id: u32,
+    #[prost(string, tag = "2")]
+    pub signal: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct CliPipeOutputPayload {
     #[prost(string, tag = "1")]
     pub pipe_name: ::prost::alloc::string::String,
@@ -171,6 +193,12 @@ pub struct SwitchSessionPayload {
     pub pane_id: ::core::option::Option<u32>,
     #[prost(bool, optional, tag = "4")]
     pub pane_id_is_plugin: ::core::option::Option<bool>,
+    #[prost(string, optional, tag = "5")]
+    pub layout: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag = "6")]
+    pub cwd: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(message, repeated, tag = "7")]
+    pub env_variables: ::prost::alloc::vec::Vec<EnvVariable>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -376,6 +404,10 @@ pub enum CommandName {
     BlockCliPipeInput = 77,
     CliPipeOutput = 78,
     MessageToPlugin = 79,
+    SignalTerminalPane = 80,
+    MoveTab = 81,
+    RunAction = 82,
+    GetResurrectableSessionLayout = 83,
 }
 impl CommandName {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -464,6 +496,10 @@ impl CommandName {
             CommandName::BlockCliPipeInput => "BlockCliPipeInput",
             CommandName::CliPipeOutput => "CliPipeOutput",
             CommandName::MessageToPlugin => "MessageToPlugin",
+            CommandName::SignalTerminalPane => "SignalTerminalPane",
+            CommandName::MoveTab => "MoveTab",
+            CommandName::RunAction => "RunAction",
+            CommandName::GetResurrectableSessionLayout => "GetResurrectableSessionLayout",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -549,6 +585,10 @@ impl CommandName {
             "BlockCliPipeInput" => Some(Self::BlockCliPipeInput),
             "CliPipeOutput" => Some(Self::CliPipeOutput),
             "MessageToPlugin" => Some(Self::MessageToPlugin),
+            "SignalTerminalPane" => Some(Self::SignalTerminalPane),
+            "MoveTab" => Some(Self::MoveTab),
+            "RunAction" => Some(Self::RunAction),
+            "GetResurrectableSessionLayout" => Some(Self::GetResurrectableSessionLayout),
             _ => None,
         }
     }
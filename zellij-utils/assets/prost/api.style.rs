@@ -7,6 +7,10 @@ pub struct Style {
     pub rounded_corners: bool,
     #[prost(bool, tag = "3")]
     pub hide_session_name: bool,
+    #[prost(bool, tag = "4")]
+    pub show_foreground_process: bool,
+    #[prost(bool, tag = "5")]
+    pub show_git_branch: bool,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
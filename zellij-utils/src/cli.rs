@@ -1,10 +1,10 @@
-use crate::data::{Direction, InputMode, Resize};
+use crate::data::{Direction, EventType, InputMode, PaneSignal, Resize};
 use crate::setup::Setup;
 use crate::{
     consts::{ZELLIJ_CONFIG_DIR_ENV, ZELLIJ_CONFIG_FILE_ENV},
-    input::{layout::PluginUserConfiguration, options::CliOptions},
+    input::{command::RestartPolicy, layout::PluginUserConfiguration, options::CliOptions},
 };
-use clap::{Parser, Subcommand};
+use clap::{ArgEnum, Parser, Subcommand};
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 use url::Url;
@@ -106,6 +106,10 @@ pub enum Sessions {
         /// Print just the session name
         #[clap(short, long, value_parser, takes_value(false), default_value("false"))]
         short: bool,
+
+        /// Print the sessions as a JSON array (useful for scripting)
+        #[clap(long, value_parser, takes_value(false), default_value("false"))]
+        json: bool,
     },
 
     /// Attach to a session
@@ -130,6 +134,30 @@ pub enum Sessions {
         /// If resurrecting a dead session, immediately run all its commands on startup
         #[clap(short, long, value_parser, takes_value(false), default_value("false"))]
         force_run_commands: bool,
+
+        /// Attach as a read-only viewer: the session is rendered but only detaching and switching
+        /// modes are allowed
+        #[clap(long, value_parser, takes_value(false), default_value("false"))]
+        read_only: bool,
+
+        /// Attach to a session on another machine, through the address it accepts remote clients
+        /// on over TLS (its `remote_attach_address` option)
+        #[clap(long, value_parser, conflicts_with_all(&["session-name", "create", "index"]))]
+        remote: Option<String>,
+
+        /// The token to authenticate with when attaching to a remote session
+        #[clap(
+            long,
+            value_parser,
+            env = "ZELLIJ_REMOTE_ATTACH_TOKEN",
+            hide_env_values = true
+        )]
+        token: Option<String>,
+
+        /// The PEM encoded certificate of the remote session (or of the authority that issued
+        /// it) to trust when attaching to a remote session
+        #[clap(long, value_parser, env = "ZELLIJ_REMOTE_ATTACH_CA_CERT")]
+        ca_cert: Option<PathBuf>,
     },
 
     /// Kill a specific session
@@ -172,8 +200,21 @@ pub enum Sessions {
 
     /// Send actions to a specific session
     #[clap(visible_alias = "ac")]
-    #[clap(subcommand)]
-    Action(CliAction),
+    Action {
+        /// Apply the action to the terminal pane with this id (eg. $ZELLIJ_PANE_ID) instead of the
+        /// focused pane, without focusing it
+        #[clap(long, value_parser, global(true))]
+        pane_id: Option<u32>,
+        /// Apply the action to the tab with this name (or to its focused pane) instead of the
+        /// focused tab, without switching to it
+        #[clap(long, value_parser, global(true), conflicts_with("tab-index"))]
+        tab_name: Option<String>,
+        /// Apply the action to the tab at this position, as shown by `list-tabs` (starting at 0)
+        #[clap(long, value_parser, global(true))]
+        tab_index: Option<usize>,
+        #[clap(subcommand)]
+        action: CliAction,
+    },
     /// Run a command in a new pane
     #[clap(visible_alias = "r")]
     Run {
@@ -216,6 +257,18 @@ pub enum Sessions {
         /// Start the command suspended, only running after you first presses ENTER
         #[clap(short, long, value_parser, default_value("false"), takes_value(false))]
         start_suspended: bool,
+
+        /// Re-run the command when it exits (never, on-failure or always)
+        #[clap(long, value_parser)]
+        restart: Option<RestartPolicy>,
+
+        /// The maximum number of times to restart the command (restarts indefinitely if not set)
+        #[clap(long, value_parser, requires("restart"))]
+        max_restarts: Option<usize>,
+
+        /// Milliseconds to wait before the first restart, doubled after every consecutive restart
+        #[clap(long, value_parser, requires("restart"))]
+        restart_backoff: Option<u64>,
     },
     /// Load a plugin
     #[clap(visible_alias = "r")]
@@ -326,6 +379,149 @@ tail -f /tmp/my-live-logfile | zellij pipe --name logs --plugin https://example.
         #[clap(short('c'), long, value_parser, display_order(4))]
         plugin_configuration: Option<PluginUserConfiguration>,
     },
+    /// Stream session events (eg. tab, pane and session changes) to STDOUT until interrupted
+    #[clap(after_help(
+        "Events are printed one per line, as they happen. The latest state of each
+subscribed event is printed as soon as the subscription starts.
+
+* Follow tab and pane changes as newline-delimited JSON:
+
+zellij subscribe --events TabUpdate,PaneUpdate
+
+* Re-run a script whenever the session list changes:
+
+zellij subscribe --events SessionUpdate --format plain | while read -r _; do ./refresh.sh; done
+"
+    ))]
+    Subscribe {
+        /// The events to subscribe to, separated by commas (eg. TabUpdate,PaneUpdate,SessionUpdate)
+        #[clap(short, long, value_parser, use_value_delimiter(true), required(true))]
+        events: Vec<EventType>,
+        /// How to print each event [json|plain]
+        #[clap(short, long, arg_enum, value_parser, default_value("json"))]
+        format: EventStreamFormat,
+    },
+    /// Manage sessions and their snapshots
+    Session {
+        #[clap(subcommand)]
+        command: SessionSubcommand,
+    },
+    /// Attach to or list workspaces: named groups of sessions sharing a default layout, cwd, env
+    /// and keybinds
+    #[clap(after_help(
+        "Workspaces are defined in a workspaces.kdl file next to config.kdl, eg.:
+
+workspace \"zellij\" {
+    default_session \"zellij-main\" // defaults to the name of the workspace
+    layout \"compact\"
+    cwd \"/home/me/code/zellij\"
+    env {
+        RUST_BACKTRACE 1
+    }
+    keybinds {
+        normal {
+            bind \"Alt b\" { Run \"cargo\" \"build\"; }
+        }
+    }
+}
+
+Other sessions can be created in a workspace with `zellij attach -c <name> options --workspace <workspace>`
+"
+    ))]
+    Workspace {
+        #[clap(subcommand)]
+        command: WorkspaceCommand,
+    },
+}
+
+#[derive(Debug, Subcommand, Clone, Serialize, Deserialize)]
+pub enum WorkspaceCommand {
+    /// Attach to the default session of the workspace, creating it in the workspace if it does
+    /// not exist
+    #[clap(visible_alias = "a")]
+    Attach {
+        /// Name of the workspace
+        #[clap(value_parser)]
+        name: String,
+    },
+    /// List the defined workspaces along with their sessions
+    #[clap(visible_alias = "ls")]
+    List {
+        /// Do not add colors and formatting to the list (useful for parsing)
+        #[clap(short, long, value_parser, takes_value(false), default_value("false"))]
+        no_formatting: bool,
+    },
+}
+
+#[derive(Debug, Subcommand, Clone, Serialize, Deserialize)]
+pub enum SessionSubcommand {
+    /// Save, list, restore or delete snapshots of sessions, any of which can be resurrected as a
+    /// new session
+    #[clap(after_help(
+        "Snapshots are referred to as <session>@<snapshot>. Besides the ones saved by name,
+a bounded history of automatic snapshots (see the `auto_snapshot_history` option) is kept for
+every session. All of them are also listed in the session-manager's resurrectable sessions.
+
+* Save a snapshot of the current session and restore it later:
+
+zellij session snapshot save nightly
+zellij session snapshot restore my-session@nightly --name my-session-again
+"
+    ))]
+    Snapshot {
+        #[clap(subcommand)]
+        command: SnapshotCommand,
+    },
+}
+
+#[derive(Debug, Subcommand, Clone, Serialize, Deserialize)]
+pub enum SnapshotCommand {
+    /// Save a snapshot of the current session (or of the one given with --session) under this
+    /// name, replacing any previous snapshot by the same name
+    Save {
+        #[clap(value_parser)]
+        name: String,
+    },
+    /// List the saved snapshots, newest first
+    #[clap(visible_alias = "ls")]
+    List {
+        /// Only list the snapshots of this session
+        #[clap(value_parser)]
+        session_name: Option<String>,
+
+        /// Do not add colors and formatting to the list (useful for parsing)
+        #[clap(short, long, value_parser, takes_value(false), default_value("false"))]
+        no_formatting: bool,
+    },
+    /// Resurrect a snapshot as a new session and attach to it
+    Restore {
+        /// The snapshot to restore, as listed (<session>@<snapshot>)
+        #[clap(value_parser)]
+        snapshot: String,
+
+        /// Name of the new session (defaults to the name of the snapshot)
+        #[clap(short, long, value_parser)]
+        name: Option<String>,
+
+        /// Immediately run all the commands of the snapshot on startup
+        #[clap(short, long, value_parser, takes_value(false), default_value("false"))]
+        force_run_commands: bool,
+    },
+    /// Delete a snapshot
+    Delete {
+        /// The snapshot to delete, as listed (<session>@<snapshot>)
+        #[clap(value_parser)]
+        snapshot: String,
+    },
+}
+
+/// The way events streamed by `zellij subscribe` are printed
+#[derive(ArgEnum, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EventStreamFormat {
+    /// The full event serialized as a single line of JSON
+    Json,
+    /// Only the name of the event (eg. TabUpdate)
+    Plain,
 }
 
 #[derive(Debug, Subcommand, Clone, Serialize, Deserialize)]
@@ -456,6 +652,15 @@ pub enum CliAction {
             requires("command")
         )]
         start_suspended: bool,
+        /// Re-run the command when it exits (never, on-failure or always)
+        #[clap(long, value_parser, requires("command"))]
+        restart: Option<RestartPolicy>,
+        /// The maximum number of times to restart the command (restarts indefinitely if not set)
+        #[clap(long, value_parser, requires("restart"))]
+        max_restarts: Option<usize>,
+        /// Milliseconds to wait before the first restart, doubled after every consecutive restart
+        #[clap(long, value_parser, requires("restart"))]
+        restart_backoff: Option<u64>,
         #[clap(long, value_parser)]
         configuration: Option<PluginUserConfiguration>,
         #[clap(short, long, value_parser)]
@@ -519,6 +724,10 @@ pub enum CliAction {
     GoToTab {
         index: u32,
     },
+    /// Move the current tab one position to the left or right [left|right]
+    MoveTab {
+        direction: Direction,
+    },
     /// Go to tab with name [name]
     GoToTabName {
         name: String,
@@ -661,4 +870,42 @@ tail -f /tmp/my-live-logfile | zellij action pipe --name logs --plugin https://e
         #[clap(short('t'), long, value_parser, display_order(10))]
         plugin_title: Option<String>,
     },
+    /// Send a signal to the processes running in a terminal pane
+    /// [SIGINT|SIGTERM|SIGKILL|SIGHUP|SIGSTOP|SIGCONT|<signal number>]
+    /// (use --pane-id to signal a pane other than the focused one)
+    SignalPane {
+        signal: PaneSignal,
+    },
+    /// List the clients attached to this session, marking read-only viewers
+    ListClients,
+    /// List all panes in this session along with their tab, title, command and cwd
+    ListPanes {
+        /// Print the panes as a JSON array (useful for scripting)
+        #[clap(long, value_parser, takes_value(false), default_value("false"))]
+        json: bool,
+    },
+    /// List all tabs in this session
+    ListTabs {
+        /// Print the tabs as a JSON array (useful for scripting)
+        #[clap(long, value_parser, takes_value(false), default_value("false"))]
+        json: bool,
+    },
+    /// Detach a read-only viewer from this session
+    KickClient {
+        /// The id of the client to detach, as shown in `list-clients`
+        client_id: u16,
+    },
+    /// Stream session events (eg. tab, pane and session changes) to STDOUT until interrupted
+    Subscribe {
+        /// The events to subscribe to, separated by commas (eg. TabUpdate,PaneUpdate,SessionUpdate)
+        #[clap(short, long, value_parser, use_value_delimiter(true), required(true))]
+        events: Vec<EventType>,
+        /// How to print each event [json|plain]
+        #[clap(short, long, arg_enum, value_parser, default_value("json"))]
+        format: EventStreamFormat,
+    },
+    /// Save a named snapshot of the session, which can later be resurrected as a new session
+    SaveSnapshot {
+        name: String,
+    },
 }
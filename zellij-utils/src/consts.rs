@@ -18,6 +18,12 @@ pub static DEBUG_MODE: OnceCell<bool> = OnceCell::new();
 pub const SYSTEM_DEFAULT_CONFIG_DIR: &str = "/etc/zellij";
 pub const SYSTEM_DEFAULT_DATA_DIR_PREFIX: &str = system_default_data_dir();
 
+pub const SESSION_LAYOUT_FILE_NAME: &str = "session-layout.kdl";
+pub const SNAPSHOT_ID_SEPARATOR: char = '@';
+/// Automatic snapshots are named after the time they were taken, with this prefix
+pub const AUTO_SNAPSHOT_PREFIX: &str = "auto-";
+pub const DEFAULT_AUTO_SNAPSHOT_HISTORY: usize = 5;
+
 pub static ZELLIJ_DEFAULT_THEMES: Dir = include_dir!("$CARGO_MANIFEST_DIR/assets/themes");
 
 pub fn session_info_cache_file_name(session_name: &str) -> PathBuf {
@@ -25,13 +31,99 @@ pub fn session_info_cache_file_name(session_name: &str) -> PathBuf {
 }
 
 pub fn session_layout_cache_file_name(session_name: &str) -> PathBuf {
-    session_info_folder_for_session(session_name).join("session-layout.kdl")
+    session_info_folder_for_session(session_name).join(SESSION_LAYOUT_FILE_NAME)
+}
+
+/// Holds the name of the workspace the session belongs to, unlike the session metadata it is kept
+/// after the session exits so that resurrectable sessions are still grouped by workspace
+pub fn session_workspace_file_name(session_name: &str) -> PathBuf {
+    session_info_folder_for_session(session_name).join("workspace")
+}
+
+/// Holds the unix time (in seconds) at which a session with no connected clients will be killed
+/// (see the `idle_session_timeout` option), it only exists while the session has no clients
+pub fn session_idle_deadline_file_name(session_name: &str) -> PathBuf {
+    session_info_folder_for_session(session_name).join("idle-deadline")
 }
 
 pub fn session_info_folder_for_session(session_name: &str) -> PathBuf {
     ZELLIJ_SESSION_INFO_CACHE_DIR.join(session_name)
 }
 
+pub fn session_snapshots_folder_for_session(session_name: &str) -> PathBuf {
+    session_info_folder_for_session(session_name).join("snapshots")
+}
+
+pub fn session_snapshot_folder(session_name: &str, snapshot_name: &str) -> PathBuf {
+    session_snapshots_folder_for_session(session_name).join(snapshot_name)
+}
+
+pub fn session_snapshot_layout_file_name(session_name: &str, snapshot_name: &str) -> PathBuf {
+    session_snapshot_folder(session_name, snapshot_name).join(SESSION_LAYOUT_FILE_NAME)
+}
+
+/// Snapshots are referred to as `<session_name>@<snapshot_name>`, which is also the name of the
+/// session they are resurrected as by default
+pub fn session_snapshot_id(session_name: &str, snapshot_name: &str) -> String {
+    format!("{}{}{}", session_name, SNAPSHOT_ID_SEPARATOR, snapshot_name)
+}
+
+pub fn split_session_snapshot_id(snapshot_id: &str) -> Option<(&str, &str)> {
+    snapshot_id
+        .rsplit_once(SNAPSHOT_ID_SEPARATOR)
+        .filter(|(session_name, snapshot_name)| {
+            validate_snapshot_path_component(session_name).is_ok()
+                && validate_snapshot_path_component(snapshot_name).is_ok()
+        })
+}
+
+/// The layout file a dead session or a snapshot (by its `<session_name>@<snapshot_name>` id) would
+/// be resurrected from, names that could point outside of the session info folder have none
+pub fn resurrectable_session_layout_file_name(name: &str) -> Option<PathBuf> {
+    let dead_session_layout_file_name = validate_snapshot_path_component(name)
+        .ok()
+        .map(|_| session_layout_cache_file_name(name))
+        .filter(|layout_file_name| layout_file_name.exists());
+    dead_session_layout_file_name.or_else(|| {
+        split_session_snapshot_id(name).map(|(session_name, snapshot_name)| {
+            session_snapshot_layout_file_name(session_name, snapshot_name)
+        })
+    })
+}
+
+/// Session and snapshot names are joined into the paths of snapshot folders, so names that could
+/// point anywhere else are rejected
+pub fn validate_snapshot_path_component(name: &str) -> Result<(), String> {
+    if name.is_empty() || name.contains(['/', '\\', '\0']) || name.contains("..") {
+        Err(format!(
+            "Invalid snapshot name: {:?}, names cannot be empty or contain '/', '\\', '..' or NUL",
+            name
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// Removes the folder of a snapshot, after making sure it is inside the snapshots folder of its
+/// session
+pub fn remove_session_snapshot_folder(
+    session_name: &str,
+    snapshot_name: &str,
+) -> std::io::Result<()> {
+    let invalid_input = |e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e);
+    validate_snapshot_path_component(session_name).map_err(invalid_input)?;
+    validate_snapshot_path_component(snapshot_name).map_err(invalid_input)?;
+    let snapshots_folder = session_snapshots_folder_for_session(session_name).canonicalize()?;
+    let snapshot_folder = session_snapshot_folder(session_name, snapshot_name).canonicalize()?;
+    if snapshot_folder == snapshots_folder || !snapshot_folder.starts_with(&snapshots_folder) {
+        return Err(invalid_input(format!(
+            "Snapshot folder {:?} is outside of {:?}",
+            snapshot_folder, snapshots_folder
+        )));
+    }
+    std::fs::remove_dir_all(snapshot_folder)
+}
+
 const fn system_default_data_dir() -> &'static str {
     if let Some(data_dir) = std::option_env!("PREFIX") {
         data_dir
@@ -110,6 +202,7 @@ mod not_wasm {
             add_plugin!(assets, "tab-bar.wasm");
             add_plugin!(assets, "strider.wasm");
             add_plugin!(assets, "session-manager.wasm");
+            add_plugin!(assets, "command-palette.wasm");
             assets
         };
     }
@@ -148,3 +241,45 @@ mod unix_only {
         };
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snapshot_names_pointing_outside_of_the_snapshots_folder_are_rejected() {
+        for name in ["", "..", "../..", "a/b", "a\\b", "a\0b"] {
+            assert!(
+                validate_snapshot_path_component(name).is_err(),
+                "{:?} is rejected",
+                name
+            );
+        }
+        assert!(validate_snapshot_path_component("my-snapshot").is_ok());
+        let e = remove_session_snapshot_folder("my-session", "..").unwrap_err();
+        assert_eq!(e.kind(), std::io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn snapshot_ids_pointing_outside_of_the_snapshots_folder_are_rejected() {
+        assert_eq!(split_session_snapshot_id("a@../../.."), None);
+        assert_eq!(split_session_snapshot_id("../..@snapshot"), None);
+        assert_eq!(
+            split_session_snapshot_id("my-session@my-snapshot"),
+            Some(("my-session", "my-snapshot"))
+        );
+    }
+
+    #[test]
+    fn resurrectable_session_layouts_pointing_outside_of_the_session_info_folder_are_rejected() {
+        assert_eq!(resurrectable_session_layout_file_name(".."), None);
+        assert_eq!(resurrectable_session_layout_file_name("a@../../.."), None);
+        assert_eq!(
+            resurrectable_session_layout_file_name("my-session@my-snapshot"),
+            Some(session_snapshot_layout_file_name(
+                "my-session",
+                "my-snapshot"
+            ))
+        );
+    }
+}
@@ -449,12 +449,13 @@ impl fmt::Display for ResizeStrategy {
 // left click) and the `ScrollUp` and `ScrollDown` events could probably be
 // merged into a single `Scroll(isize)` event.
 pub enum Mouse {
-    ScrollUp(usize),          // number of lines
-    ScrollDown(usize),        // number of lines
-    LeftClick(isize, usize),  // line and column
-    RightClick(isize, usize), // line and column
-    Hold(isize, usize),       // line and column
-    Release(isize, usize),    // line and column
+    ScrollUp(usize),           // number of lines
+    ScrollDown(usize),         // number of lines
+    LeftClick(isize, usize),   // line and column
+    RightClick(isize, usize),  // line and column
+    MiddleClick(isize, usize), // line and column
+    Hold(isize, usize),        // line and column
+    Release(isize, usize),     // line and column
 }
 
 /// These events can be subscribed to with subscribe method exported by `zellij-tile`.
@@ -508,9 +509,26 @@ pub enum Event {
         Vec<u8>,
         BTreeMap<String, String>,
     ), // status,
-       // headers,
-       // body,
-       // context
+    // headers,
+    // body,
+    // context
+    /// A UI component rendered with an id was clicked, row and col are relative to the component
+    ComponentClicked {
+        id: String,
+        row: usize,
+        col: usize,
+    },
+    /// The mouse moved onto a UI component rendered with an id, row and col are relative to the
+    /// component (only sent with the `mouse_hover_events` option)
+    ComponentHovered {
+        id: String,
+        row: usize,
+        col: usize,
+    },
+    /// The layouts new sessions can be started with
+    AvailableLayoutInfo(Vec<LayoutInfo>),
+    /// The plugin aliases configured in the `plugins` block of the config
+    AvailablePluginAliases(Vec<String>),
 }
 
 #[derive(
@@ -728,6 +746,8 @@ pub struct Style {
     pub colors: Palette,
     pub rounded_corners: bool,
     pub hide_session_name: bool,
+    pub show_foreground_process: bool,
+    pub show_git_branch: bool,
 }
 
 // FIXME: Poor devs hashtable since HashTable can't derive `Default`...
@@ -765,6 +785,16 @@ pub struct SessionInfo {
     pub panes: PaneManifest,
     pub connected_clients: usize,
     pub is_current_session: bool,
+    /// The workspace the session belongs to, if any
+    pub workspace: Option<String>,
+}
+
+/// A layout new sessions can be started with, either built-in or found in the layout directory
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct LayoutInfo {
+    pub name: String,
+    pub is_builtin: bool,
+    pub stringified_layout: String,
 }
 
 use std::hash::{Hash, Hasher};
@@ -814,6 +844,8 @@ pub struct TabInfo {
     pub active_swap_layout_name: Option<String>,
     /// Whether the user manually changed the layout, moving out of the swap layout scheme
     pub is_swap_layout_dirty: bool,
+    /// Whether a pane in this tab rang the bell since the tab was last focused
+    pub is_requesting_attention: bool,
 }
 
 /// The `PaneManifest` contains a dictionary of panes, indexed by the tab position (0 indexed).
@@ -879,6 +911,30 @@ pub struct PaneInfo {
     /// Unselectable panes are often used for UI elements that do not have direct user interaction
     /// (eg. the default `status-bar` or `tab-bar`).
     pub is_selectable: bool,
+    /// The number of times this command pane was restarted by its restart policy
+    pub restart_count: usize,
+    /// The exit status of the last run of the command if it was restarted by its restart policy
+    pub last_exit_status: Option<i32>,
+    /// The last sample of the processes running inside this pane, only relevant to terminal panes
+    pub process_info: Option<PaneProcessInfo>,
+}
+
+/// A periodic sample of the process tree running inside a terminal pane
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub struct PaneProcessInfo {
+    /// The name of the process in the foreground of the pane's terminal (eg. `vim` when the user
+    /// is editing a file, or the shell itself when it is waiting for input)
+    pub foreground_process: Option<String>,
+    /// The CPU usage of all the pane's processes since the previous sample, in percent of one
+    /// core (so this can exceed 100 on multi-core machines)
+    pub cpu_usage: u32,
+    /// The resident memory of all the pane's processes, in bytes
+    pub memory_usage: u64,
+    /// The command lines of all the processes spawned by the pane's shell or command
+    pub child_commands: Vec<String>,
+    /// The current branch (or short commit hash if detached) of the git repository the pane's
+    /// shell or command is in, if any
+    pub git_branch: Option<String>,
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
@@ -1066,6 +1122,10 @@ pub struct ConnectToSession {
     pub name: Option<String>,
     pub tab_position: Option<usize>,
     pub pane_id: Option<(u32, bool)>, // (id, is_plugin)
+    // the following only apply if a new session is created
+    pub layout: Option<String>, // the name of a built-in layout or of one in the layout dir
+    pub cwd: Option<PathBuf>,
+    pub env: BTreeMap<String, String>,
 }
 
 #[derive(Debug, Default, Clone)]
@@ -1100,6 +1160,54 @@ pub enum HttpVerb {
     Delete,
 }
 
+/// A signal that can be sent to the processes running in a terminal pane
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub enum PaneSignal {
+    Interrupt,  // SIGINT
+    Terminate,  // SIGTERM
+    Kill,       // SIGKILL
+    Hangup,     // SIGHUP
+    Stop,       // SIGSTOP
+    Continue,   // SIGCONT
+    Other(i32), // raw signal number
+}
+
+impl FromStr for PaneSignal {
+    type Err = String;
+    fn from_str(signal: &str) -> Result<Self, Self::Err> {
+        if let Ok(signal_number) = signal.parse::<i32>() {
+            return Ok(PaneSignal::Other(signal_number));
+        }
+        let signal_name = signal.to_uppercase();
+        match signal_name.strip_prefix("SIG").unwrap_or(&signal_name) {
+            "INT" => Ok(PaneSignal::Interrupt),
+            "TERM" => Ok(PaneSignal::Terminate),
+            "KILL" => Ok(PaneSignal::Kill),
+            "HUP" => Ok(PaneSignal::Hangup),
+            "STOP" => Ok(PaneSignal::Stop),
+            "CONT" => Ok(PaneSignal::Continue),
+            _ => Err(format!(
+                "Unknown signal: {}, expected one of SIGINT, SIGTERM, SIGKILL, SIGHUP, SIGSTOP, SIGCONT or a signal number",
+                signal
+            )),
+        }
+    }
+}
+
+impl fmt::Display for PaneSignal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PaneSignal::Interrupt => write!(f, "SIGINT"),
+            PaneSignal::Terminate => write!(f, "SIGTERM"),
+            PaneSignal::Kill => write!(f, "SIGKILL"),
+            PaneSignal::Hangup => write!(f, "SIGHUP"),
+            PaneSignal::Stop => write!(f, "SIGSTOP"),
+            PaneSignal::Continue => write!(f, "SIGCONT"),
+            PaneSignal::Other(signal_number) => write!(f, "{}", signal_number),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum PipeSource {
     Cli(String), // String is the pipe_id of the CLI pipe (used for blocking/unblocking)
@@ -1228,4 +1336,8 @@ pub enum PluginCommand {
     BlockCliPipeInput(String),     // String => pipe name
     CliPipeOutput(String, String), // String => pipe name, String => output
     MessageToPlugin(MessageToPlugin),
+    SignalTerminalPane(u32, PaneSignal), // terminal pane id, signal
+    MoveTab(Direction),
+    RunAction(Action),
+    GetResurrectableSessionLayout(String), // String -> session name or snapshot id
 }
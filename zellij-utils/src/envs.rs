@@ -65,3 +65,60 @@ impl EnvironmentVariables {
         }
     }
 }
+
+/// Parses the contents of a dotenv style file (`KEY=value` lines, optionally prefixed with
+/// `export`), ignoring empty lines and comments
+pub fn parse_env_file(contents: &str) -> BTreeMap<String, String> {
+    let mut env = BTreeMap::new();
+    for line in contents.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let line = line.strip_prefix("export ").unwrap_or(line);
+        if let Some((key, value)) = line.split_once('=') {
+            let value = value.trim();
+            let value = if value.len() >= 2
+                && ((value.starts_with('"') && value.ends_with('"'))
+                    || (value.starts_with('\'') && value.ends_with('\'')))
+            {
+                &value[1..value.len() - 1]
+            } else {
+                value
+            };
+            env.insert(key.trim().to_owned(), value.to_owned());
+        }
+    }
+    env
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn env_file_with_quotes_comments_exports_and_blank_lines_is_parsed() {
+        let contents = r#"
+# a comment
+export PORT=8080
+
+NAME="my app"
+  GREETING='hi = there'
+EMPTY=
+MISMATCHED="quote'
+not a variable
+"#;
+        let env = parse_env_file(contents);
+        let expected: BTreeMap<String, String> = [
+            ("PORT", "8080"),
+            ("NAME", "my app"),
+            ("GREETING", "hi = there"),
+            ("EMPTY", ""),
+            ("MISMATCHED", "\"quote'"),
+        ]
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+        assert_eq!(env, expected);
+    }
+}
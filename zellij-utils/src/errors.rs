@@ -280,6 +280,10 @@ pub enum ScreenContext {
     SetFixedWidth,
     ClosePane,
     HoldPane,
+    UpdatePaneRestarts,
+    UpdatePaneBlockedOn,
+    RunHeldPane,
+    WatchPaneOutput,
     UpdatePaneName,
     UndoRenamePane,
     NewTab,
@@ -307,8 +311,10 @@ pub enum ScreenContext {
     MouseHoldLeft,
     MouseHoldRight,
     MouseHoldMiddle,
+    MouseMotion,
     Copy,
     ToggleTab,
+    MoveTab,
     AddClient,
     RemoveClient,
     AddOverlay,
@@ -349,6 +355,17 @@ pub enum ScreenContext {
     NewInPlacePluginPane,
     DumpLayoutToHd,
     RenameSession,
+    SignalPane,
+    UpdatePaneProcessInfos,
+    ApplyToPane,
+    ApplyToTab,
+    ListPanes,
+    ListTabs,
+    SaveSnapshot,
+    EnableDiffRendering,
+    ClientMissedRenders,
+    LocalEchoPredicted,
+    LocalEchoExpired,
 }
 
 /// Stack call representations corresponding to the different types of [`PtyInstruction`]s.
@@ -364,11 +381,18 @@ pub enum PtyContext {
     ClosePane,
     CloseTab,
     ReRunCommandInPane,
+    CommandExited,
+    RestartCommandInPane,
+    PaneOutputMatched,
+    SignalPane,
+    SamplePaneProcesses,
     DropToShellInPane,
     SpawnInPlaceTerminal,
     DumpLayout,
     LogLayoutToHd,
+    SaveSnapshot,
     FillPluginCwd,
+    ListPanes,
     Exit,
 }
 
@@ -398,6 +422,9 @@ pub enum PluginContext {
     CachePluginEvents,
     MessageFromPlugin,
     UnblockCliPipes,
+    CliSubscribe,
+    UpdateAvailableLayouts,
+    SaveSnapshot,
 }
 
 /// Stack call representations corresponding to the different types of [`ClientInstruction`]s.
@@ -420,6 +447,8 @@ pub enum ClientContext {
     SetSynchronisedOutput,
     UnblockCliPipeInput,
     CliPipeOutput,
+    SubscribedEvent,
+    PredictLocalEcho,
 }
 
 /// Stack call representations corresponding to the different types of [`ServerInstruction`]s.
@@ -442,6 +471,10 @@ pub enum ServerContext {
     UnblockCliPipeInput,
     CliPipeOutput,
     AssociatePipeWithClient,
+    ListClients,
+    KickClient,
+    SubscribedEvent,
+    ClientMissedRenders,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
@@ -459,8 +492,10 @@ pub enum BackgroundJobContext {
     AnimatePluginLoading,
     StopPluginLoadingAnimation,
     ReadAllSessionInfosOnMachine,
+    SamplePaneProcesses,
     ReportSessionInfo,
     ReportLayoutInfo,
+    SaveSnapshot,
     RunCommand,
     WebRequest,
     Exit,
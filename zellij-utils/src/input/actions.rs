@@ -5,9 +5,10 @@ use super::layout::{
     FloatingPaneLayout, Layout, RunPlugin, RunPluginLocation, SwapFloatingLayout, SwapTiledLayout,
     TiledPaneLayout,
 };
-use crate::cli::CliAction;
+use crate::cli::{CliAction, EventStreamFormat};
+use crate::consts::SNAPSHOT_ID_SEPARATOR;
 use crate::data::InputMode;
-use crate::data::{Direction, Resize};
+use crate::data::{ClientId, Direction, EventType, PaneSignal, Resize};
 use crate::home::{find_default_config_dir, get_layout_dir};
 use crate::input::config::{Config, ConfigError, KdlError};
 use crate::input::options::OnForceClose;
@@ -202,6 +203,9 @@ pub enum Action {
     GoToTab(u32),
     GoToTabName(String, bool),
     ToggleTab,
+    /// Move the current tab one position to the left or right, tabs at either end stay where they
+    /// are
+    MoveTab(Direction),
     TabNameInput(Vec<u8>),
     UndoRenameTab,
     /// Run specified command in new pane.
@@ -221,6 +225,8 @@ pub enum Action {
     MouseHoldLeft(Position),
     MouseHoldRight(Position),
     MouseHoldMiddle(Position),
+    /// The mouse moved without any of its buttons held
+    MouseMotion(Position),
     Copy,
     /// Confirm a prompt
     Confirm,
@@ -272,6 +278,49 @@ pub enum Action {
         cwd: Option<PathBuf>,
         pane_title: Option<String>,
     },
+    SignalPane(Option<u32>, PaneSignal), // u32 is the terminal pane id, None is the focused pane
+    /// List the clients attached to the session
+    ListClients,
+    KickClient(ClientId),
+    /// List the panes of the session, bool is whether to list them as JSON
+    ListPanes(bool),
+    /// List the tabs of the session, bool is whether to list them as JSON
+    ListTabs(bool),
+    /// Stream the given events to the (cli) client until it disconnects
+    Subscribe {
+        events: Vec<EventType>,
+        format: EventStreamFormat,
+    },
+    /// Apply an action to a specific pane or tab rather than the focused one
+    Targeted(ActionTarget, Box<Action>),
+    /// Save a named snapshot of the session
+    SaveSnapshot(String),
+}
+
+/// The pane or tab a cli action should be applied to instead of the focused one
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ActionTarget {
+    pub pane_id: Option<u32>, // terminal pane id
+    pub tab_name: Option<String>,
+    pub tab_position: Option<usize>,
+}
+
+impl ActionTarget {
+    pub fn new(
+        pane_id: Option<u32>,
+        tab_name: Option<String>,
+        tab_position: Option<usize>,
+    ) -> Option<Self> {
+        if pane_id.is_none() && tab_name.is_none() && tab_position.is_none() {
+            None
+        } else {
+            Some(ActionTarget {
+                pane_id,
+                tab_name,
+                tab_position,
+            })
+        }
+    }
 }
 
 impl Action {
@@ -328,6 +377,9 @@ impl Action {
                 name,
                 close_on_exit,
                 start_suspended,
+                restart,
+                max_restarts,
+                restart_backoff,
                 configuration,
                 skip_plugin_cache,
             } => {
@@ -383,6 +435,10 @@ impl Action {
                         direction,
                         hold_on_close,
                         hold_on_start,
+                        restart_policy: restart.unwrap_or_default(),
+                        max_restarts,
+                        restart_backoff_ms: restart_backoff,
+                        ..Default::default()
                     };
                     if floating {
                         Ok(vec![Action::NewFloatingPane(
@@ -450,6 +506,7 @@ impl Action {
             CliAction::GoToPreviousTab => Ok(vec![Action::GoToPreviousTab]),
             CliAction::CloseTab => Ok(vec![Action::CloseTab]),
             CliAction::GoToTab { index } => Ok(vec![Action::GoToTab(index)]),
+            CliAction::MoveTab { direction } => Ok(vec![Action::MoveTab(direction)]),
             CliAction::GoToTabName { name, create } => Ok(vec![Action::GoToTabName(name, create)]),
             CliAction::RenameTab { name } => Ok(vec![
                 Action::TabNameInput(vec![0]),
@@ -598,6 +655,29 @@ impl Action {
                 )])
             },
             CliAction::RenameSession { name } => Ok(vec![Action::RenameSession(name)]),
+            CliAction::SignalPane { signal } => Ok(vec![Action::SignalPane(None, signal)]),
+            CliAction::ListClients => Ok(vec![Action::ListClients]),
+            CliAction::KickClient { client_id } => Ok(vec![Action::KickClient(client_id)]),
+            CliAction::ListPanes { json } => Ok(vec![Action::ListPanes(json)]),
+            CliAction::ListTabs { json } => Ok(vec![Action::ListTabs(json)]),
+            CliAction::Subscribe { events, format } => {
+                Ok(vec![Action::Subscribe { events, format }])
+            },
+            CliAction::SaveSnapshot { name } => {
+                if name.is_empty()
+                    || name == "."
+                    || name == ".."
+                    || name.contains('/')
+                    || name.contains(SNAPSHOT_ID_SEPARATOR)
+                {
+                    Err(format!(
+                        "Invalid snapshot name: \"{}\", snapshot names cannot be empty or contain '/' or '{}'",
+                        name, SNAPSHOT_ID_SEPARATOR
+                    ))
+                } else {
+                    Ok(vec![Action::SaveSnapshot(name)])
+                }
+            },
             CliAction::Pipe {
                 name,
                 payload,
@@ -1,7 +1,11 @@
 //! Trigger a command
 use crate::data::Direction;
+use crate::envs::parse_env_file;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Duration;
 
 #[derive(Debug, Clone)]
 pub enum TerminalAction {
@@ -35,6 +39,17 @@ pub struct RunCommand {
     pub hold_on_close: bool,
     #[serde(default)]
     pub hold_on_start: bool,
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
+    #[serde(default)]
+    pub env_file: Option<PathBuf>, // relative paths are relative to the cwd
+    #[serde(default)]
+    pub restart_policy: RestartPolicy,
+    #[serde(default)]
+    pub max_restarts: Option<usize>, // None means the command will be restarted indefinitely
+    #[serde(default)]
+    pub restart_backoff_ms: Option<u64>, // the delay before the first restart, doubled on each
+                                         // subsequent one
 }
 
 impl std::fmt::Display for RunCommand {
@@ -68,6 +83,16 @@ pub struct RunCommandAction {
     pub hold_on_close: bool,
     #[serde(default)]
     pub hold_on_start: bool,
+    #[serde(default)]
+    pub restart_policy: RestartPolicy,
+    #[serde(default)]
+    pub max_restarts: Option<usize>,
+    #[serde(default)]
+    pub restart_backoff_ms: Option<u64>,
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
+    #[serde(default)]
+    pub env_file: Option<PathBuf>,
 }
 
 impl From<RunCommandAction> for RunCommand {
@@ -78,6 +103,11 @@ impl From<RunCommandAction> for RunCommand {
             cwd: action.cwd,
             hold_on_close: action.hold_on_close,
             hold_on_start: action.hold_on_start,
+            restart_policy: action.restart_policy,
+            max_restarts: action.max_restarts,
+            restart_backoff_ms: action.restart_backoff_ms,
+            env: action.env,
+            env_file: action.env_file,
         }
     }
 }
@@ -91,6 +121,11 @@ impl From<RunCommand> for RunCommandAction {
             direction: None,
             hold_on_close: run_command.hold_on_close,
             hold_on_start: run_command.hold_on_start,
+            restart_policy: run_command.restart_policy,
+            max_restarts: run_command.max_restarts,
+            restart_backoff_ms: run_command.restart_backoff_ms,
+            env: run_command.env,
+            env_file: run_command.env_file,
         }
     }
 }
@@ -106,4 +141,93 @@ impl RunCommand {
         self.cwd = Some(cwd);
         self
     }
+    pub fn env_vars(&self) -> BTreeMap<String, String> {
+        // variables set explicitly take precedence over those in the env file
+        let mut env_vars = match &self.env_file {
+            Some(env_file) => {
+                let env_file = match &self.cwd {
+                    Some(cwd) => cwd.join(env_file),
+                    None => env_file.clone(),
+                };
+                match std::fs::read_to_string(&env_file) {
+                    Ok(contents) => parse_env_file(&contents),
+                    Err(e) => {
+                        log::error!("Failed to read env file {}: {}", env_file.display(), e);
+                        BTreeMap::new()
+                    },
+                }
+            },
+            None => BTreeMap::new(),
+        };
+        env_vars.extend(self.env.clone());
+        env_vars
+    }
+    pub fn should_restart(&self, exit_status: Option<i32>, restart_count: usize) -> bool {
+        let has_retries_left = self
+            .max_restarts
+            .map(|max_restarts| restart_count < max_restarts)
+            .unwrap_or(true);
+        has_retries_left && self.restart_policy.applies_to(exit_status)
+    }
+    pub fn restart_delay(&self, restart_count: usize) -> Duration {
+        // exponential backoff, capped so that a crash-looping command is still retried
+        // every once in a while
+        let backoff_ms = self
+            .restart_backoff_ms
+            .unwrap_or(DEFAULT_RESTART_BACKOFF_MS);
+        let multiplier = 2_u64.saturating_pow(restart_count.min(u32::MAX as usize) as u32);
+        Duration::from_millis(
+            backoff_ms
+                .saturating_mul(multiplier)
+                .min(MAX_RESTART_BACKOFF_MS),
+        )
+    }
+}
+
+const DEFAULT_RESTART_BACKOFF_MS: u64 = 1000;
+const MAX_RESTART_BACKOFF_MS: u64 = 60000;
+
+/// Whether a command pane should re-run its command once it exits
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub enum RestartPolicy {
+    #[default]
+    Never,
+    OnFailure,
+    Always,
+}
+
+impl RestartPolicy {
+    pub fn applies_to(&self, exit_status: Option<i32>) -> bool {
+        match self {
+            RestartPolicy::Never => false,
+            RestartPolicy::Always => true,
+            // a command killed by a signal has no exit status, we consider this a failure
+            RestartPolicy::OnFailure => exit_status != Some(0),
+        }
+    }
+}
+
+impl FromStr for RestartPolicy {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "never" => Ok(RestartPolicy::Never),
+            "on-failure" => Ok(RestartPolicy::OnFailure),
+            "always" => Ok(RestartPolicy::Always),
+            _ => Err(format!(
+                "Failed to parse restart policy: {}, expected one of: never, on-failure, always",
+                s
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for RestartPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RestartPolicy::Never => write!(f, "never"),
+            RestartPolicy::OnFailure => write!(f, "on-failure"),
+            RestartPolicy::Always => write!(f, "always"),
+        }
+    }
 }
@@ -647,6 +647,8 @@ mod config_test {
                 pane_frames {
                     rounded_corners true
                     hide_session_name true
+                    show_foreground_process true
+                    show_git_branch true
                 }
             }
         "#;
@@ -655,6 +657,8 @@ mod config_test {
             pane_frames: FrameConfig {
                 rounded_corners: true,
                 hide_session_name: true,
+                show_foreground_process: true,
+                show_git_branch: true,
             },
         };
         assert_eq!(config.ui, expected_ui_config, "Ui config defined in config");
@@ -9,10 +9,10 @@
 //  If plugins should be able to depend on the layout system
 //  then [`zellij-utils`] could be a proper place.
 use crate::{
-    data::Direction,
+    data::{Direction, LayoutInfo},
     home::find_default_config_dir,
     input::{
-        command::RunCommand,
+        command::{RestartPolicy, RunCommand},
         config::{Config, ConfigError},
     },
     pane_size::{Constraint, Dimension, PaneGeom},
@@ -76,6 +76,18 @@ pub enum Run {
     Command(RunCommand),
     EditFile(PathBuf, Option<usize>, Option<PathBuf>), // TODO: merge this with TerminalAction::OpenFile
     Cwd(PathBuf),
+    Shell(RunShell),
+}
+
+/// The default shell of a pane that was given env variables without a command
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct RunShell {
+    #[serde(default)]
+    pub cwd: Option<PathBuf>,
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
+    #[serde(default)]
+    pub env_file: Option<PathBuf>, // relative paths are relative to the cwd
 }
 
 impl Run {
@@ -93,6 +105,11 @@ impl Run {
                 if merged.args.is_empty() && !base_run_command.args.is_empty() {
                     merged.args = base_run_command.args.clone();
                 }
+                Run::inherit_env_for_run_command(
+                    &mut merged,
+                    &base_run_command.env,
+                    &base_run_command.env_file,
+                );
                 Some(Run::Command(merged))
             },
             (Some(Run::Command(base_run_command)), Some(Run::Cwd(other_cwd))) => {
@@ -100,6 +117,15 @@ impl Run {
                 merged.cwd = Some(other_cwd.clone());
                 Some(Run::Command(merged))
             },
+            (Some(Run::Command(base_run_command)), Some(Run::Shell(other_shell))) => {
+                let mut merged = base_run_command.clone();
+                if other_shell.cwd.is_some() {
+                    merged.cwd = other_shell.cwd.clone();
+                }
+                let mut merged = Run::Command(merged);
+                merged.add_env(other_shell.env.clone(), other_shell.env_file.clone());
+                Some(merged)
+            },
             (Some(Run::Cwd(base_cwd)), Some(Run::Command(other_command))) => {
                 let mut merged = other_command.clone();
                 if merged.cwd.is_none() {
@@ -107,6 +133,40 @@ impl Run {
                 }
                 Some(Run::Command(merged))
             },
+            (Some(Run::Shell(base_shell)), Some(Run::Command(other_command))) => {
+                let mut merged = other_command.clone();
+                if merged.cwd.is_none() {
+                    merged.cwd = base_shell.cwd.clone();
+                }
+                Run::inherit_env_for_run_command(
+                    &mut merged,
+                    &base_shell.env,
+                    &base_shell.env_file,
+                );
+                Some(Run::Command(merged))
+            },
+            (Some(Run::Shell(base_shell)), Some(Run::Cwd(other_cwd))) => {
+                let mut merged = base_shell.clone();
+                merged.cwd = Some(other_cwd.clone());
+                Some(Run::Shell(merged))
+            },
+            (Some(Run::Cwd(base_cwd)), Some(Run::Shell(other_shell))) => {
+                let mut merged = other_shell.clone();
+                if merged.cwd.is_none() {
+                    merged.cwd = Some(base_cwd.clone());
+                }
+                Some(Run::Shell(merged))
+            },
+            (Some(Run::Shell(base_shell)), Some(Run::Shell(other_shell))) => {
+                let mut merged = Run::Shell(other_shell.clone());
+                if other_shell.cwd.is_none() {
+                    if let Some(base_cwd) = &base_shell.cwd {
+                        merged.add_cwd(base_cwd);
+                    }
+                }
+                merged.inherit_env(&base_shell.env, &base_shell.env_file);
+                Some(merged)
+            },
             (
                 Some(Run::Command(base_run_command)),
                 Some(Run::EditFile(file_to_edit, line_number, edit_cwd)),
@@ -122,7 +182,10 @@ impl Run {
                     edit_cwd.clone(),
                 )),
             },
-            (Some(Run::Cwd(cwd)), Some(Run::EditFile(file_to_edit, line_number, edit_cwd))) => {
+            (
+                Some(Run::Cwd(cwd)) | Some(Run::Shell(RunShell { cwd: Some(cwd), .. })),
+                Some(Run::EditFile(file_to_edit, line_number, edit_cwd)),
+            ) => {
                 let cwd = edit_cwd.clone().unwrap_or(cwd.clone());
                 Some(Run::EditFile(
                     cwd.join(&file_to_edit),
@@ -160,6 +223,14 @@ impl Run {
             Run::Cwd(path) => {
                 *path = cwd.join(&path);
             },
+            Run::Shell(run_shell) => match run_shell.cwd.as_mut() {
+                Some(shell_cwd) => {
+                    *shell_cwd = cwd.join(&shell_cwd);
+                },
+                None => {
+                    run_shell.cwd = Some(cwd.clone());
+                },
+            },
             _ => {}, // plugins aren't yet supported
         }
     }
@@ -192,18 +263,112 @@ impl Run {
             }
         }
     }
+    pub fn add_env(&mut self, env: BTreeMap<String, String>, env_file: Option<PathBuf>) {
+        // overrides the env of a Run::Command or of a shell, keeping variables that were not
+        // overridden
+        match self {
+            Run::Command(run_command) => {
+                run_command.env.extend(env);
+                if env_file.is_some() {
+                    run_command.env_file = env_file;
+                }
+            },
+            Run::Shell(run_shell) => {
+                run_shell.env.extend(env);
+                if env_file.is_some() {
+                    run_shell.env_file = env_file;
+                }
+            },
+            Run::Cwd(cwd) if !env.is_empty() || env_file.is_some() => {
+                *self = Run::Shell(RunShell {
+                    cwd: Some(cwd.clone()),
+                    env,
+                    env_file,
+                });
+            },
+            _ => {}, // plugins and edited files do not run with an env of their own
+        }
+    }
+    pub fn inherit_env(&mut self, env: &BTreeMap<String, String>, env_file: &Option<PathBuf>) {
+        // the env of a Run::Command or of a shell takes precedence over the inherited one
+        match self {
+            Run::Command(run_command) => {
+                Run::inherit_env_for_run_command(run_command, env, env_file);
+            },
+            Run::Shell(run_shell) => {
+                for (key, value) in env {
+                    run_shell
+                        .env
+                        .entry(key.clone())
+                        .or_insert_with(|| value.clone());
+                }
+                if run_shell.env_file.is_none() {
+                    run_shell.env_file = env_file.clone();
+                }
+            },
+            Run::Cwd(cwd) if !env.is_empty() || env_file.is_some() => {
+                *self = Run::Shell(RunShell {
+                    cwd: Some(cwd.clone()),
+                    env: env.clone(),
+                    env_file: env_file.clone(),
+                });
+            },
+            _ => {}, // plugins and edited files do not run with an env of their own
+        }
+    }
+    fn inherit_env_for_run_command(
+        run_command: &mut RunCommand,
+        env: &BTreeMap<String, String>,
+        env_file: &Option<PathBuf>,
+    ) {
+        for (key, value) in env {
+            run_command
+                .env
+                .entry(key.clone())
+                .or_insert_with(|| value.clone());
+        }
+        if run_command.env_file.is_none() {
+            run_command.env_file = env_file.clone();
+        }
+    }
+    pub fn add_restart_policy(
+        &mut self,
+        restart_policy: Option<RestartPolicy>,
+        max_restarts: Option<usize>,
+        restart_backoff_ms: Option<u64>,
+    ) {
+        // overrides the restart policy of a Run::Command if they are Some
+        if let Run::Command(run_command) = self {
+            if let Some(restart_policy) = restart_policy {
+                run_command.restart_policy = restart_policy;
+            }
+            if max_restarts.is_some() {
+                run_command.max_restarts = max_restarts;
+            }
+            if restart_backoff_ms.is_some() {
+                run_command.restart_backoff_ms = restart_backoff_ms;
+            }
+        }
+    }
     pub fn is_same_category(first: &Option<Run>, second: &Option<Run>) -> bool {
         match (first, second) {
             (Some(Run::Plugin(..)), Some(Run::Plugin(..))) => true,
             (Some(Run::Command(..)), Some(Run::Command(..))) => true,
             (Some(Run::EditFile(..)), Some(Run::EditFile(..))) => true,
-            (Some(Run::Cwd(..)), Some(Run::Cwd(..))) => true,
+            (
+                Some(Run::Cwd(..)) | Some(Run::Shell(..)),
+                Some(Run::Cwd(..)) | Some(Run::Shell(..)),
+            ) => true,
             _ => false,
         }
     }
     pub fn is_terminal(run: &Option<Run>) -> bool {
         match run {
-            Some(Run::Command(..)) | Some(Run::EditFile(..)) | Some(Run::Cwd(..)) | None => true,
+            Some(Run::Command(..))
+            | Some(Run::EditFile(..))
+            | Some(Run::Cwd(..))
+            | Some(Run::Shell(..))
+            | None => true,
             _ => false,
         }
     }
@@ -213,6 +378,7 @@ impl Run {
             Run::Command(run_command) => run_command.cwd.clone(),
             Run::EditFile(_file, _line_num, cwd) => cwd.clone(),
             Run::Cwd(cwd) => Some(cwd.clone()),
+            Run::Shell(run_shell) => run_shell.cwd.clone(),
         }
     }
 }
@@ -482,6 +648,7 @@ pub struct FloatingPaneLayout {
     pub focus: Option<bool>,
     pub already_running: bool,
     pub pane_initial_contents: Option<String>,
+    pub depends_on: Option<PaneDependency>,
 }
 
 impl FloatingPaneLayout {
@@ -498,6 +665,22 @@ impl FloatingPaneLayout {
             run.add_start_suspended(start_suspended);
         }
     }
+    pub fn add_env_to_layout(
+        &mut self,
+        env: &BTreeMap<String, String>,
+        env_file: &Option<PathBuf>,
+    ) {
+        match self.run.as_mut() {
+            Some(run) => run.inherit_env(env, env_file),
+            None => {
+                self.run = Some(Run::Shell(RunShell {
+                    cwd: None,
+                    env: env.clone(),
+                    env_file: env_file.clone(),
+                }));
+            },
+        }
+    }
 }
 
 impl From<&TiledPaneLayout> for FloatingPaneLayout {
@@ -506,11 +689,54 @@ impl From<&TiledPaneLayout> for FloatingPaneLayout {
             name: pane_layout.name.clone(),
             run: pane_layout.run.clone(),
             focus: pane_layout.focus,
+            depends_on: pane_layout.depends_on.clone(),
             ..Default::default()
         }
     }
 }
 
+/// A command pane that depends on another (named) command pane in the same layout will only be
+/// started once that pane is ready
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct PaneDependency {
+    pub pane_name: String,
+    pub ready_when: ReadyCondition,
+}
+
+impl fmt::Display for PaneDependency {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.ready_when {
+            ReadyCondition::Started => write!(f, "{} (started)", self.pane_name),
+            ReadyCondition::ExitedSuccessfully => write!(f, "{} (exit code 0)", self.pane_name),
+            ReadyCondition::OutputMatches(pattern) => {
+                write!(f, "{} (output: {})", self.pane_name, pattern)
+            },
+        }
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub enum ReadyCondition {
+    #[default]
+    Started,
+    ExitedSuccessfully,
+    OutputMatches(String), // regex
+}
+
+impl FromStr for ReadyCondition {
+    type Err = Box<dyn std::error::Error>;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "started" => Ok(ReadyCondition::Started),
+            "success" => Ok(ReadyCondition::ExitedSuccessfully),
+            _ => Err("ready_when must be either started or success (use ready_pattern to wait for output)".into()),
+        }
+    }
+}
+
+// (run instruction, pane name, pane dependency)
+pub type RunInstructionAndDependency = (Option<Run>, Option<String>, Option<PaneDependency>);
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Default)]
 pub struct TiledPaneLayout {
     pub children_split_direction: SplitDirection,
@@ -527,6 +753,7 @@ pub struct TiledPaneLayout {
     pub run_instructions_to_ignore: Vec<Option<Run>>,
     pub hide_floating_panes: bool, // only relevant if this is the base layout
     pub pane_initial_contents: Option<String>,
+    pub depends_on: Option<PaneDependency>,
 }
 
 impl TiledPaneLayout {
@@ -638,21 +865,27 @@ impl TiledPaneLayout {
         Ok(layouts)
     }
     pub fn extract_run_instructions(&self) -> Vec<Option<Run>> {
+        self.extract_run_instructions_and_dependencies()
+            .into_iter()
+            .map(|(run_instruction, _pane_name, _pane_dependency)| run_instruction)
+            .collect()
+    }
+    pub fn extract_run_instructions_and_dependencies(&self) -> Vec<RunInstructionAndDependency> {
         // the order of these run instructions is significant and needs to be the same
         // as the order of the "flattened" layout panes received from eg. position_panes_in_space
         let mut run_instructions = vec![];
         if self.children.is_empty() {
-            run_instructions.push(self.run.clone());
+            run_instructions.push((self.run.clone(), self.name.clone(), self.depends_on.clone()));
         }
         for child in &self.children {
-            let mut child_run_instructions = child.extract_run_instructions();
+            let mut child_run_instructions = child.extract_run_instructions_and_dependencies();
             run_instructions.append(&mut child_run_instructions);
         }
         let mut successfully_ignored = 0;
         for instruction_to_ignore in &self.run_instructions_to_ignore {
             if let Some(position) = run_instructions
                 .iter()
-                .position(|i| i == instruction_to_ignore)
+                .position(|(i, _, _)| i == instruction_to_ignore)
             {
                 run_instructions.remove(position);
                 successfully_ignored += 1;
@@ -668,7 +901,7 @@ impl TiledPaneLayout {
                 .len()
                 .saturating_sub(successfully_ignored)
             {
-                if let Some(position) = run_instructions.iter().position(|i| i == &None) {
+                if let Some(position) = run_instructions.iter().position(|(i, _, _)| i.is_none()) {
                     run_instructions.remove(position);
                 }
             }
@@ -694,6 +927,28 @@ impl TiledPaneLayout {
             child.add_cwd_to_layout(cwd);
         }
     }
+    pub fn add_env_to_layout(
+        &mut self,
+        env: &BTreeMap<String, String>,
+        env_file: &Option<PathBuf>,
+    ) {
+        // panes with children do not run anything themselves
+        if self.children.is_empty() {
+            match self.run.as_mut() {
+                Some(run) => run.inherit_env(env, env_file),
+                None => {
+                    self.run = Some(Run::Shell(RunShell {
+                        cwd: None,
+                        env: env.clone(),
+                        env_file: env_file.clone(),
+                    }));
+                },
+            }
+        }
+        for child in self.children.iter_mut() {
+            child.add_env_to_layout(env, env_file);
+        }
+    }
     pub fn deepest_depth(&self) -> usize {
         let mut deepest_child_depth = 0;
         for child in self.children.iter() {
@@ -957,6 +1212,60 @@ impl Layout {
             )),
         }
     }
+    /// Whether this names a layout in the layout dir or a built-in one, rather than being a path
+    /// that [`Layout::from_path_or_default`] would load from anywhere
+    pub fn is_layout_name(layout_name: &str) -> bool {
+        let layout_path = Path::new(layout_name);
+        let mut components = layout_path.components();
+        layout_path.extension().is_none()
+            && matches!(
+                (components.next(), components.next()),
+                (Some(std::path::Component::Normal(_)), None)
+            )
+    }
+    /// The layouts that can be loaded by name: the ones in the layout dir and the built-in ones
+    /// they don't shadow
+    pub fn list_available_layouts(layout_dir: Option<&Path>) -> Vec<LayoutInfo> {
+        let mut available_layouts: Vec<LayoutInfo> = layout_dir
+            .and_then(|layout_dir| std::fs::read_dir(layout_dir).ok())
+            .map(|entries| {
+                entries
+                    .filter_map(|entry| {
+                        let path = entry.ok()?.path();
+                        let file_name = path.file_name()?.to_str()?;
+                        // swap layouts are loaded along with the layout they belong to
+                        if !file_name.ends_with(".kdl") || file_name.ends_with(".swap.kdl") {
+                            return None;
+                        }
+                        Some(LayoutInfo {
+                            name: file_name.trim_end_matches(".kdl").to_owned(),
+                            is_builtin: false,
+                            stringified_layout: std::fs::read_to_string(&path).ok()?,
+                        })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        for builtin_layout_name in ["default", "compact", "strider", "disable-status-bar"] {
+            if available_layouts
+                .iter()
+                .any(|l| l.name == builtin_layout_name)
+            {
+                continue;
+            }
+            if let Ok((_, stringified_layout, _)) =
+                Layout::stringified_from_default_assets(Path::new(builtin_layout_name))
+            {
+                available_layouts.push(LayoutInfo {
+                    name: builtin_layout_name.to_owned(),
+                    is_builtin: true,
+                    stringified_layout,
+                });
+            }
+        }
+        available_layouts.sort_by(|a, b| a.name.cmp(&b.name));
+        available_layouts
+    }
     pub fn stringified_default_from_assets() -> Result<String, ConfigError> {
         Ok(String::from_utf8(setup::DEFAULT_LAYOUT.to_vec())?)
     }
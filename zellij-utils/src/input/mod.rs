@@ -7,6 +7,7 @@ pub mod options;
 pub mod permission;
 pub mod plugins;
 pub mod theme;
+pub mod workspaces;
 
 // Can't use this in wasm due to dependency on the `termwiz` crate.
 #[cfg(not(target_family = "wasm"))]
@@ -73,6 +73,11 @@ pub struct Options {
     pub mouse_mode: Option<bool>,
     #[clap(long, value_parser)]
     #[serde(default)]
+    /// Report the mouse moving over UI components to plugins (true or false), this has the
+    /// terminal send every movement of the mouse even when no button is held
+    pub mouse_hover_events: Option<bool>,
+    #[clap(long, value_parser)]
+    #[serde(default)]
     /// Set display of the pane frames (true or false)
     pub pane_frames: Option<bool>,
     #[clap(long, value_parser)]
@@ -151,6 +156,70 @@ pub struct Options {
     /// The interval at which to serialize sessions for resurrection (in seconds)
     #[clap(long, value_parser)]
     pub serialization_interval: Option<u64>,
+
+    /// An address (eg. 0.0.0.0:8082) on which to accept remote clients over TLS, remote clients
+    /// must authenticate with `remote_attach_token`. A bare port listens on 127.0.0.1. Requires
+    /// `remote_attach_cert` and `remote_attach_key` to be set as well
+    #[clap(long, value_parser)]
+    #[serde(default)]
+    pub remote_attach_address: Option<String>,
+
+    /// The token remote clients must present in order to attach to this session
+    #[clap(long, value_parser)]
+    #[serde(default)]
+    pub remote_attach_token: Option<String>,
+
+    /// The PEM encoded certificate (chain) presented to remote clients
+    #[clap(long, value_parser)]
+    #[serde(default)]
+    pub remote_attach_cert: Option<PathBuf>,
+
+    /// The PEM encoded private key of `remote_attach_cert`
+    #[clap(long, value_parser)]
+    #[serde(default)]
+    pub remote_attach_key: Option<PathBuf>,
+
+    /// The number of automatic snapshots to keep for each session, one is taken whenever the
+    /// serialized layout of the session changes (0 disables automatic snapshots)
+    #[clap(long, value_parser)]
+    #[serde(default)]
+    pub auto_snapshot_history: Option<usize>,
+
+    /// The workspace (defined in workspaces.kdl next to the configuration file) the session
+    /// belongs to, its layout, cwd, env and keybinds are applied when the session is created
+    #[clap(long, value_parser)]
+    #[serde(default)]
+    pub workspace: Option<String>,
+
+    /// Kill the session after it has had no connected clients for this many hours (0 never kills
+    /// it)
+    #[clap(long, value_parser)]
+    #[serde(default)]
+    pub idle_session_timeout: Option<u64>,
+
+    /// Delete resurrectable sessions that exited more than this many hours ago (0 keeps them
+    /// forever)
+    #[clap(long, value_parser)]
+    #[serde(default)]
+    pub resurrectable_session_ttl: Option<u64>,
+
+    /// The maximum number of resurrectable sessions to keep, the ones that exited longest ago are
+    /// deleted first
+    #[clap(long, value_parser)]
+    #[serde(default)]
+    pub max_resurrectable_sessions: Option<usize>,
+
+    /// Only send the cells that changed since the last render to this client, rather than whole
+    /// lines, default is false
+    #[clap(long, value_parser)]
+    #[serde(default)]
+    pub diff_rendering: Option<bool>,
+
+    /// Show characters typed into the focused terminal pane before the server echoes them back,
+    /// for slow or high latency connections (implies `diff_rendering`), default is false
+    #[clap(long, value_parser)]
+    #[serde(default)]
+    pub local_echo_prediction: Option<bool>,
 }
 
 #[derive(ArgEnum, Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
@@ -191,6 +260,7 @@ impl Options {
     // TODO: Maybe a good candidate for a macro?
     pub fn merge(&self, other: Options) -> Options {
         let mouse_mode = other.mouse_mode.or(self.mouse_mode);
+        let mouse_hover_events = other.mouse_hover_events.or(self.mouse_hover_events);
         let pane_frames = other.pane_frames.or(self.pane_frames);
         let auto_layout = other.auto_layout.or(self.auto_layout);
         let mirror_session = other.mirror_session.or(self.mirror_session);
@@ -223,6 +293,29 @@ impl Options {
             .or(self.scrollback_lines_to_serialize);
         let styled_underlines = other.styled_underlines.or(self.styled_underlines);
         let serialization_interval = other.serialization_interval.or(self.serialization_interval);
+        let remote_attach_address = other
+            .remote_attach_address
+            .or_else(|| self.remote_attach_address.clone());
+        let remote_attach_token = other
+            .remote_attach_token
+            .or_else(|| self.remote_attach_token.clone());
+        let remote_attach_cert = other
+            .remote_attach_cert
+            .or_else(|| self.remote_attach_cert.clone());
+        let remote_attach_key = other
+            .remote_attach_key
+            .or_else(|| self.remote_attach_key.clone());
+        let auto_snapshot_history = other.auto_snapshot_history.or(self.auto_snapshot_history);
+        let workspace = other.workspace.or_else(|| self.workspace.clone());
+        let idle_session_timeout = other.idle_session_timeout.or(self.idle_session_timeout);
+        let resurrectable_session_ttl = other
+            .resurrectable_session_ttl
+            .or(self.resurrectable_session_ttl);
+        let max_resurrectable_sessions = other
+            .max_resurrectable_sessions
+            .or(self.max_resurrectable_sessions);
+        let diff_rendering = other.diff_rendering.or(self.diff_rendering);
+        let local_echo_prediction = other.local_echo_prediction.or(self.local_echo_prediction);
 
         Options {
             simplified_ui,
@@ -234,6 +327,7 @@ impl Options {
             layout_dir,
             theme_dir,
             mouse_mode,
+            mouse_hover_events,
             pane_frames,
             mirror_session,
             on_force_close,
@@ -250,6 +344,17 @@ impl Options {
             scrollback_lines_to_serialize,
             styled_underlines,
             serialization_interval,
+            remote_attach_address,
+            remote_attach_token,
+            remote_attach_cert,
+            remote_attach_key,
+            auto_snapshot_history,
+            workspace,
+            idle_session_timeout,
+            resurrectable_session_ttl,
+            max_resurrectable_sessions,
+            diff_rendering,
+            local_echo_prediction,
         }
     }
 
@@ -270,6 +375,7 @@ impl Options {
 
         let simplified_ui = merge_bool(other.simplified_ui, self.simplified_ui);
         let mouse_mode = merge_bool(other.mouse_mode, self.mouse_mode);
+        let mouse_hover_events = merge_bool(other.mouse_hover_events, self.mouse_hover_events);
         let pane_frames = merge_bool(other.pane_frames, self.pane_frames);
         let auto_layout = merge_bool(other.auto_layout, self.auto_layout);
         let mirror_session = merge_bool(other.mirror_session, self.mirror_session);
@@ -302,6 +408,30 @@ impl Options {
             .or_else(|| self.scrollback_lines_to_serialize.clone());
         let styled_underlines = other.styled_underlines.or(self.styled_underlines);
         let serialization_interval = other.serialization_interval.or(self.serialization_interval);
+        let remote_attach_address = other
+            .remote_attach_address
+            .or_else(|| self.remote_attach_address.clone());
+        let remote_attach_token = other
+            .remote_attach_token
+            .or_else(|| self.remote_attach_token.clone());
+        let remote_attach_cert = other
+            .remote_attach_cert
+            .or_else(|| self.remote_attach_cert.clone());
+        let remote_attach_key = other
+            .remote_attach_key
+            .or_else(|| self.remote_attach_key.clone());
+        let auto_snapshot_history = other.auto_snapshot_history.or(self.auto_snapshot_history);
+        let workspace = other.workspace.or_else(|| self.workspace.clone());
+        let idle_session_timeout = other.idle_session_timeout.or(self.idle_session_timeout);
+        let resurrectable_session_ttl = other
+            .resurrectable_session_ttl
+            .or(self.resurrectable_session_ttl);
+        let max_resurrectable_sessions = other
+            .max_resurrectable_sessions
+            .or(self.max_resurrectable_sessions);
+        let diff_rendering = merge_bool(other.diff_rendering, self.diff_rendering);
+        let local_echo_prediction =
+            merge_bool(other.local_echo_prediction, self.local_echo_prediction);
 
         Options {
             simplified_ui,
@@ -313,6 +443,7 @@ impl Options {
             layout_dir,
             theme_dir,
             mouse_mode,
+            mouse_hover_events,
             pane_frames,
             mirror_session,
             on_force_close,
@@ -329,6 +460,17 @@ impl Options {
             scrollback_lines_to_serialize,
             styled_underlines,
             serialization_interval,
+            remote_attach_address,
+            remote_attach_token,
+            remote_attach_cert,
+            remote_attach_key,
+            auto_snapshot_history,
+            workspace,
+            idle_session_timeout,
+            resurrectable_session_ttl,
+            max_resurrectable_sessions,
+            diff_rendering,
+            local_echo_prediction,
         }
     }
 
@@ -377,6 +519,7 @@ impl From<CliOptions> for Options {
             layout_dir: opts.layout_dir,
             theme_dir: opts.theme_dir,
             mouse_mode: opts.mouse_mode,
+            mouse_hover_events: opts.mouse_hover_events,
             pane_frames: opts.pane_frames,
             mirror_session: opts.mirror_session,
             on_force_close: opts.on_force_close,
@@ -393,6 +536,17 @@ impl From<CliOptions> for Options {
             scrollback_lines_to_serialize: opts.scrollback_lines_to_serialize,
             styled_underlines: opts.styled_underlines,
             serialization_interval: opts.serialization_interval,
+            remote_attach_address: opts.remote_attach_address,
+            remote_attach_token: opts.remote_attach_token,
+            remote_attach_cert: opts.remote_attach_cert,
+            remote_attach_key: opts.remote_attach_key,
+            auto_snapshot_history: opts.auto_snapshot_history,
+            workspace: opts.workspace,
+            idle_session_timeout: opts.idle_session_timeout,
+            resurrectable_session_ttl: opts.resurrectable_session_ttl,
+            max_resurrectable_sessions: opts.max_resurrectable_sessions,
+            diff_rendering: opts.diff_rendering,
+            local_echo_prediction: opts.local_echo_prediction,
             ..Default::default()
         }
     }
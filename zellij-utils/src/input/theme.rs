@@ -26,6 +26,8 @@ impl UiConfig {
 pub struct FrameConfig {
     pub rounded_corners: bool,
     pub hide_session_name: bool,
+    pub show_foreground_process: bool,
+    pub show_git_branch: bool,
 }
 
 impl FrameConfig {
@@ -33,6 +35,8 @@ impl FrameConfig {
         let mut merged = self.clone();
         merged.rounded_corners = other.rounded_corners;
         merged.hide_session_name = other.hide_session_name;
+        merged.show_foreground_process = other.show_foreground_process;
+        merged.show_git_branch = other.show_git_branch;
         merged
     }
 }
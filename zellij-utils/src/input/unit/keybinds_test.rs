@@ -542,3 +542,16 @@ fn error_received_on_unknown_key_instruction() {
     let config_error = Config::from_kdl(config_contents, None).unwrap_err();
     assert_snapshot!(format!("{:?}", config_error));
 }
+
+#[test]
+fn error_received_on_negative_max_restarts_in_run_action() {
+    let config_contents = r#"
+        keybinds {
+            normal {
+                bind "z" { Run "ls" { max_restarts -1; }; }
+            }
+        }
+    "#;
+    let config_error = Config::from_kdl(config_contents, None).unwrap_err();
+    assert_snapshot!(format!("{:?}", config_error));
+}
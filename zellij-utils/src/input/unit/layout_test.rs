@@ -524,6 +524,190 @@ fn layout_with_command_panes_and_start_suspended() {
     assert_snapshot!(format!("{:#?}", layout));
 }
 
+#[test]
+fn layout_with_command_panes_and_restart_policy() {
+    let kdl_layout = r#"
+        layout {
+            pane command="npm" {
+                args "run" "dev"
+                restart "on-failure"
+                max_restarts 5
+                restart_backoff 500
+            }
+            pane command="cargo" restart="always"
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, "layout_file_name".into(), None, None).unwrap();
+    assert_snapshot!(format!("{:#?}", layout));
+}
+
+#[test]
+fn cannot_define_restart_policy_without_command() {
+    let kdl_layout = r#"
+        layout {
+            pane restart="always"
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, "layout_file_name".into(), None, None);
+    assert!(layout.is_err(), "error provided");
+}
+
+#[test]
+fn cannot_define_unknown_restart_policy() {
+    let kdl_layout = r#"
+        layout {
+            pane command="htop" restart="sometimes"
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, "layout_file_name".into(), None, None);
+    assert!(layout.is_err(), "error provided");
+}
+
+#[test]
+fn layout_with_command_panes_and_dependencies() {
+    let kdl_layout = r#"
+        layout {
+            pane name="db" command="postgres"
+            pane name="migrations" command="migrate" depends_on="db"
+            pane command="api" {
+                depends_on "migrations"
+                ready_when "success"
+            }
+            floating_panes {
+                pane command="tail" depends_on="db" ready_pattern="ready to accept connections"
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, "layout_file_name".into(), None, None).unwrap();
+    assert_snapshot!(format!("{:#?}", layout));
+}
+
+#[test]
+fn cannot_define_dependency_without_command() {
+    let kdl_layout = r#"
+        layout {
+            pane name="db" command="postgres"
+            pane depends_on="db"
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, "layout_file_name".into(), None, None);
+    assert!(layout.is_err(), "error provided");
+}
+
+#[test]
+fn cannot_define_ready_condition_without_dependency() {
+    let kdl_layout = r#"
+        layout {
+            pane command="api" ready_when="success"
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, "layout_file_name".into(), None, None);
+    assert!(layout.is_err(), "error provided");
+}
+
+#[test]
+fn cannot_define_invalid_ready_pattern() {
+    let kdl_layout = r#"
+        layout {
+            pane name="db" command="postgres"
+            pane command="api" depends_on="db" ready_pattern="(unclosed"
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, "layout_file_name".into(), None, None);
+    assert!(layout.is_err(), "error provided");
+}
+
+#[test]
+fn layout_with_command_panes_and_env() {
+    let kdl_layout = r#"
+        layout {
+            pane command="api" {
+                env_file ".env"
+                env {
+                    PORT 8080
+                    LOG_LEVEL "debug"
+                }
+            }
+            floating_panes {
+                pane command="worker" env_file="/etc/worker.env"
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, "layout_file_name".into(), None, None).unwrap();
+    assert_snapshot!(format!("{:#?}", layout));
+}
+
+#[test]
+fn layout_with_tab_env_inherited_by_command_panes() {
+    let kdl_layout = r#"
+        layout {
+            tab env_file=".env" {
+                env {
+                    LOG_LEVEL "info"
+                    REGION "eu"
+                }
+                pane command="api" {
+                    env {
+                        LOG_LEVEL "debug"
+                    }
+                }
+                pane
+                floating_panes {
+                    pane command="worker"
+                }
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, "layout_file_name".into(), None, None).unwrap();
+    assert_snapshot!(format!("{:#?}", layout));
+}
+
+#[test]
+fn layout_with_shell_panes_and_env() {
+    let kdl_layout = r#"
+        layout {
+            pane {
+                env {
+                    PORT 8080
+                }
+            }
+            pane cwd="/tmp" env_file=".env"
+            floating_panes {
+                pane {
+                    env {
+                        PORT 8081
+                    }
+                }
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, "layout_file_name".into(), None, None).unwrap();
+    assert_snapshot!(format!("{:#?}", layout));
+}
+
+#[test]
+fn cannot_define_env_for_plugin_or_edit_panes() {
+    let plugin_pane_layout = r#"
+        layout {
+            pane {
+                plugin location="zellij:tab-bar"
+                env {
+                    PORT 8080
+                }
+            }
+        }
+    "#;
+    let edit_pane_layout = r#"
+        layout {
+            pane edit="my_file" env_file=".env"
+        }
+    "#;
+    let layout = Layout::from_kdl(plugin_pane_layout, "layout_file_name".into(), None, None);
+    assert!(layout.is_err(), "error provided for plugin pane");
+    let layout = Layout::from_kdl(edit_pane_layout, "layout_file_name".into(), None, None);
+    assert!(layout.is_err(), "error provided for edit pane");
+}
+
 #[test]
 fn layout_with_plugin_panes() {
     let kdl_layout = r#"
@@ -1390,6 +1574,26 @@ fn close_on_exit_overrides_close_on_exit_in_template() {
     assert_snapshot!(format!("{:#?}", layout));
 }
 
+#[test]
+fn restart_policy_overrides_restart_policy_in_template() {
+    let kdl_layout = r#"
+        layout {
+            pane_template name="watcher" {
+                command "cargo"
+                restart "always"
+                restart_backoff 2000
+            }
+            watcher
+            watcher {
+                restart "on-failure"
+                max_restarts 3
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, "layout_file_name".into(), None, None).unwrap();
+    assert_snapshot!(format!("{:#?}", layout));
+}
+
 #[test]
 fn args_added_to_args_in_template() {
     let kdl_layout = r#"
@@ -2208,3 +2412,43 @@ fn env_var_missing() {
     let layout = Layout::from_kdl(kdl_layout, "layout_file_name".into(), None, None);
     assert!(layout.is_err(), "invalid env var lookup should fail");
 }
+
+#[test]
+fn list_available_layouts_from_layout_dir_and_builtins() {
+    let layout_dir = tempfile::tempdir().unwrap();
+    std::fs::write(layout_dir.path().join("my-layout.kdl"), "layout { pane; }").unwrap();
+    std::fs::write(layout_dir.path().join("my-layout.swap.kdl"), "").unwrap();
+    std::fs::write(
+        layout_dir.path().join("compact.kdl"),
+        "layout { pane; pane; }",
+    )
+    .unwrap();
+    std::fs::write(layout_dir.path().join("notes.txt"), "").unwrap();
+    let available_layouts = Layout::list_available_layouts(Some(layout_dir.path()));
+    let names_and_origins: Vec<(&str, bool)> = available_layouts
+        .iter()
+        .map(|l| (l.name.as_str(), l.is_builtin))
+        .collect();
+    assert_eq!(
+        names_and_origins,
+        vec![
+            ("compact", false),
+            ("default", true),
+            ("disable-status-bar", true),
+            ("my-layout", false),
+            ("strider", true),
+        ],
+        "layouts in the layout dir are listed and shadow the built-in ones"
+    );
+    assert_eq!(available_layouts[3].stringified_layout, "layout { pane; }");
+}
+
+#[test]
+fn only_plain_names_are_layout_names() {
+    for layout_name in ["default", "my-layout", "compact"] {
+        assert!(Layout::is_layout_name(layout_name), "{:?}", layout_name);
+    }
+    for path in ["/tmp/x.kdl", "x.kdl", "../x", "dir/x", "..", ".", ""] {
+        assert!(!Layout::is_layout_name(path), "{:?}", path);
+    }
+}
@@ -0,0 +1,115 @@
+use super::super::config::Config;
+use super::super::workspaces::*;
+use crate::data::{CharOrArrow, InputMode, Key};
+use crate::envs::EnvironmentVariables;
+use crate::input::actions::Action;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+fn rust_backtrace_env() -> EnvironmentVariables {
+    EnvironmentVariables::from_data(HashMap::from([(
+        "RUST_BACKTRACE".to_owned(),
+        "1".to_owned(),
+    )]))
+}
+
+fn base_config() -> Config {
+    Config::from_default_assets().unwrap()
+}
+
+#[test]
+fn workspaces_from_kdl() {
+    let kdl_workspaces = r#"
+        workspace "zellij" {
+            default_session "zellij-main"
+            layout "compact"
+            cwd "/tmp/zellij"
+            env {
+                RUST_BACKTRACE 1
+            }
+            keybinds {
+                normal {
+                    bind "Alt b" { NewTab; }
+                }
+            }
+        }
+        workspace "notes"
+    "#;
+    let config = base_config();
+    let workspaces = Workspaces::from_kdl(kdl_workspaces, &config).unwrap();
+    let zellij = workspaces.get("zellij").unwrap();
+    assert_eq!(zellij.default_session_name(), "zellij-main");
+    assert_eq!(zellij.layout, Some(PathBuf::from("compact")));
+    assert_eq!(zellij.cwd, Some(PathBuf::from("/tmp/zellij")));
+    assert_eq!(zellij.env, rust_backtrace_env());
+    let keybinds = zellij.keybinds.as_ref().unwrap();
+    assert_eq!(
+        keybinds.get_actions_for_key_in_mode(&InputMode::Normal, &Key::Alt(CharOrArrow::Char('b'))),
+        Some(&vec![Action::NewTab(None, vec![], None, None, None)]),
+        "workspace keybinds are added to the ones of the base config"
+    );
+    assert_eq!(
+        keybinds.get_actions_for_key_in_mode(&InputMode::Normal, &Key::Ctrl('q')),
+        config
+            .keybinds
+            .get_actions_for_key_in_mode(&InputMode::Normal, &Key::Ctrl('q')),
+        "base config keybinds are kept"
+    );
+    let notes = workspaces.get("notes").unwrap();
+    assert_eq!(notes.default_session_name(), "notes");
+    assert_eq!(notes.keybinds, None);
+}
+
+#[test]
+fn workspace_settings_are_applied_to_config() {
+    let kdl_workspaces = r#"
+        workspace "zellij" {
+            layout "compact"
+            cwd "/tmp/zellij"
+            env {
+                RUST_BACKTRACE 1
+            }
+        }
+    "#;
+    let mut config = base_config();
+    let expected_env = config.env.merge(rust_backtrace_env());
+    let workspaces = Workspaces::from_kdl(kdl_workspaces, &config).unwrap();
+    workspaces
+        .get("zellij")
+        .unwrap()
+        .apply_to_config(&mut config);
+    assert_eq!(config.options.workspace, Some("zellij".to_owned()));
+    assert_eq!(
+        config.options.default_layout,
+        Some(PathBuf::from("compact"))
+    );
+    assert_eq!(
+        config.options.default_cwd,
+        Some(PathBuf::from("/tmp/zellij"))
+    );
+    assert_eq!(config.env, expected_env);
+}
+
+#[test]
+fn duplicate_workspaces_are_err() {
+    let kdl_workspaces = r#"
+        workspace "zellij"
+        workspace "zellij"
+    "#;
+    assert!(Workspaces::from_kdl(kdl_workspaces, &base_config()).is_err());
+}
+
+#[test]
+fn unknown_nodes_are_err() {
+    let kdl_workspaces = r#"
+        session "zellij"
+    "#;
+    assert!(Workspaces::from_kdl(kdl_workspaces, &base_config()).is_err());
+}
+
+#[test]
+fn missing_workspaces_file_means_no_workspaces() {
+    let config_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("src/input/unit/fixtures");
+    let workspaces = Workspaces::from_config_dir(Some(&config_dir), &base_config()).unwrap();
+    assert!(workspaces.workspaces.is_empty());
+}
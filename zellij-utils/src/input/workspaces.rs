@@ -0,0 +1,102 @@
+//! Workspaces are named groups of sessions that share a default layout, working directory,
+//! environment variables and keybindings. They are defined in a `workspaces.kdl` file next to
+//! `config.kdl`, eg.
+//!
+//! ```kdl
+//! workspace "zellij" {
+//!     default_session "zellij-main"
+//!     layout "compact"
+//!     cwd "/home/me/code/zellij"
+//!     env {
+//!         RUST_BACKTRACE 1
+//!     }
+//!     keybinds {
+//!         normal {
+//!             bind "Alt b" { Run "cargo" "build"; }
+//!         }
+//!     }
+//! }
+//! ```
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use super::config::{Config, ConfigError};
+use super::keybinds::Keybinds;
+use crate::envs::EnvironmentVariables;
+
+pub const WORKSPACES_FILE_NAME: &str = "workspaces.kdl";
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Workspace {
+    pub name: String,
+    /// The session `zellij workspace attach` attaches to, defaults to the name of the workspace
+    pub default_session: Option<String>,
+    pub layout: Option<PathBuf>,
+    pub cwd: Option<PathBuf>,
+    pub env: EnvironmentVariables,
+    /// The keybindings of the base configuration with the overrides of this workspace applied
+    pub keybinds: Option<Keybinds>,
+}
+
+impl Workspace {
+    pub fn default_session_name(&self) -> String {
+        self.default_session
+            .clone()
+            .unwrap_or_else(|| self.name.clone())
+    }
+    /// The settings of the workspace take precedence over the ones in the configuration, but
+    /// not over ones given explicitly on the command line (which are merged on top later)
+    pub fn apply_to_config(&self, config: &mut Config) {
+        config.env = config.env.merge(self.env.clone());
+        if let Some(keybinds) = &self.keybinds {
+            config.keybinds = keybinds.clone();
+        }
+        if self.layout.is_some() {
+            config.options.default_layout = self.layout.clone();
+        }
+        if self.cwd.is_some() {
+            config.options.default_cwd = self.cwd.clone();
+        }
+        config.options.workspace = Some(self.name.clone());
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Workspaces {
+    pub workspaces: BTreeMap<String, Workspace>,
+}
+
+impl Workspaces {
+    pub fn get(&self, workspace_name: &str) -> Option<&Workspace> {
+        self.workspaces.get(workspace_name)
+    }
+    /// A missing workspaces file is not an error, it just means no workspaces are defined
+    pub fn from_config_dir(
+        config_dir: Option<&Path>,
+        base_config: &Config,
+    ) -> Result<Self, ConfigError> {
+        match config_dir.map(|c| c.join(WORKSPACES_FILE_NAME)) {
+            Some(path) if path.exists() => Workspaces::from_path(&path, base_config),
+            _ => Ok(Workspaces::default()),
+        }
+    }
+    pub fn from_path(path: &Path, base_config: &Config) -> Result<Self, ConfigError> {
+        let mut kdl_workspaces = String::new();
+        File::open(path)
+            .and_then(|mut file| file.read_to_string(&mut kdl_workspaces))
+            .map_err(|e| ConfigError::IoPath(e, path.to_path_buf()))?;
+        match Workspaces::from_kdl(&kdl_workspaces, base_config) {
+            Err(ConfigError::KdlError(kdl_error)) => Err(ConfigError::KdlError(
+                kdl_error.add_src(path.to_string_lossy().to_string(), kdl_workspaces),
+            )),
+            result => result,
+        }
+    }
+}
+
+// The unit test location.
+#[cfg(test)]
+#[path = "./unit/workspaces_test.rs"]
+mod workspaces_test;
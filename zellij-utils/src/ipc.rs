@@ -1,7 +1,8 @@
 //! IPC stuff for starting to split things into a client and server model.
 use crate::{
     cli::CliArgs,
-    data::{ClientId, ConnectToSession, InputMode, Style},
+    consts::VERSION,
+    data::{ClientId, ConnectToSession, Event, InputMode, Style},
     errors::{get_current_ctx, prelude::*, ErrorContext},
     input::keybinds::Keybinds,
     input::{actions::Action, layout::Layout, options::Options, plugins::PluginsConfig},
@@ -9,13 +10,30 @@ use crate::{
 };
 use interprocess::local_socket::LocalSocketStream;
 use log::warn;
-use nix::unistd::dup;
+use nix::{
+    errno::Errno,
+    poll::{poll, PollFd, PollFlags},
+    unistd::dup,
+};
+use rustls::{
+    ClientConfig, ClientConnection, Connection, RootCertStore, ServerConfig, ServerConnection,
+    ServerName, StreamOwned,
+};
 use serde::{Deserialize, Serialize};
 use std::{
     fmt::{Display, Error, Formatter},
-    io::{self, Write},
+    fs::File,
+    io::{self, BufReader, Read, Write},
     marker::PhantomData,
-    os::unix::io::{AsRawFd, FromRawFd},
+    mem::ManuallyDrop,
+    net::{IpAddr, Ipv4Addr, SocketAddr, TcpStream, ToSocketAddrs},
+    os::unix::{
+        io::{AsRawFd, FromRawFd, IntoRawFd},
+        net::UnixStream,
+    },
+    path::Path,
+    sync::Arc,
+    time::Duration,
 };
 
 type SessionId = u64;
@@ -42,6 +60,11 @@ pub struct ClientAttributes {
     pub size: Size,
     pub style: Style,
     pub keybinds: Keybinds,
+    /// A read-only client can watch the session but not interact with it
+    pub read_only: bool,
+    /// Only send the cells that changed since the last render to this client
+    #[serde(default)]
+    pub diff_rendering: bool,
 }
 
 #[derive(Default, Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
@@ -89,6 +112,8 @@ pub enum ClientToServerMsg {
     KillSession,
     ConnStatus,
     ListClients,
+    LocalEchoPredicted(Vec<(usize, usize)>), // (x, y) => cells the client drew predictions over
+    LocalEchoExpired,
 }
 
 // Types of messages sent from the server to the client
@@ -105,6 +130,7 @@ pub enum ServerToClientMsg {
     SwitchSession(ConnectToSession),
     UnblockCliPipeInput(String),   // String -> pipe name
     CliPipeOutput(String, String), // String -> pipe name, String -> Output
+    SubscribedEvent(Event),
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -114,6 +140,7 @@ pub enum ExitReason {
     ForceDetached,
     CannotAttach,
     Disconnect,
+    IncompatibleVersion(Option<ProtocolInfo>), // the server's protocol, if it could be negotiated
     Error(String),
 }
 
@@ -154,11 +181,196 @@ There are a few things you can try now:
     "
                 )
             },
+            Self::IncompatibleVersion(server_protocol) => {
+                let server_version = match server_protocol {
+                    Some(server_protocol) => format!(
+                        "Zellij {} (protocol version {})",
+                        server_protocol.zellij_version, server_protocol.version
+                    ),
+                    None => "an older version of Zellij".to_string(),
+                };
+                write!(
+                    f,
+                    "This session is running {}, which cannot talk to this client (Zellij {}, protocol version {}). Please attach with a matching version of Zellij.",
+                    server_version, VERSION, IPC_PROTOCOL_VERSION
+                )
+            },
             Self::Error(e) => write!(f, "Error occurred in server:\n{}", e),
         }
     }
 }
 
+/// The version of the protocol clients and servers speak, bump this whenever a change to the
+/// messages above would prevent clients and servers of different versions from understanding each
+/// other (eg. renaming or removing a variant, or adding a field without a default).
+///
+/// The messages of each version are pinned by the fixtures in
+/// `src/test-fixtures/ipc/v<IPC_PROTOCOL_VERSION>`: the tests fail whenever the messages are no
+/// longer encoded exactly as recorded there. Changes that older peers can still read (eg. a new
+/// variant they will never be sent) only need the fixtures of the current version extended, all
+/// others need this version bumped and fixtures recorded for the new version - the fixtures of
+/// previous versions are kept as a record of what their peers send.
+pub const IPC_PROTOCOL_VERSION: u32 = 1;
+
+/// Optional protocol features, announced during the handshake so that the other side can tell
+/// whether they are supported without bumping [`IPC_PROTOCOL_VERSION`]
+pub const IPC_CAPABILITY_READ_ONLY_CLIENTS: &str = "read_only_clients";
+pub const IPC_CAPABILITY_REMOTE_ATTACH: &str = "remote_attach";
+pub const IPC_CAPABILITY_LOCAL_ECHO: &str = "local_echo";
+
+/// Exchanged by clients and servers as soon as they connect, before any other message
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ProtocolInfo {
+    pub version: u32,
+    pub zellij_version: String,
+    #[serde(default)]
+    pub capabilities: Vec<String>,
+}
+
+impl ProtocolInfo {
+    pub fn current() -> Self {
+        ProtocolInfo {
+            version: IPC_PROTOCOL_VERSION,
+            zellij_version: VERSION.to_string(),
+            capabilities: vec![
+                IPC_CAPABILITY_READ_ONLY_CLIENTS.to_string(),
+                IPC_CAPABILITY_REMOTE_ATTACH.to_string(),
+                IPC_CAPABILITY_LOCAL_ECHO.to_string(),
+            ],
+        }
+    }
+    pub fn is_compatible_with(&self, other: &ProtocolInfo) -> bool {
+        self.version == other.version
+    }
+    pub fn supports(&self, capability: &str) -> bool {
+        self.capabilities.iter().any(|c| c == capability)
+    }
+}
+
+/// The first bytes of every handshake frame, see [`ProtocolInfo::to_handshake_frame`]
+const HANDSHAKE_MAGIC: &[u8; 4] = b"ZJHS";
+
+/// How long either side of a new connection waits for the other's handshake frame before giving up
+/// on it, so that a hung peer cannot block eg. `zellij ls`
+pub const IPC_HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(2);
+
+impl ProtocolInfo {
+    /// Encodes this [`ProtocolInfo`] as a handshake frame. Unlike the messages exchanged after it,
+    /// the layout of this frame must never change, so that any two versions of Zellij can tell
+    /// whether they are able to talk to each other:
+    ///
+    /// ```text
+    /// | bytes | content                                                                   |
+    /// |-------|---------------------------------------------------------------------------|
+    /// | 4     | the magic bytes "ZJHS"                                                    |
+    /// | 4     | the protocol version, a big endian u32                                    |
+    /// | 2     | the length of the body, a big endian u16                                  |
+    /// | n     | the body, UTF-8 text: the Zellij version followed by each capability, all |
+    /// |       | separated by newlines                                                     |
+    /// ```
+    pub fn to_handshake_frame(&self) -> Vec<u8> {
+        let body = std::iter::once(&self.zellij_version)
+            .chain(self.capabilities.iter())
+            .map(|line| line.as_str())
+            .collect::<Vec<_>>()
+            .join("\n");
+        let body = body.as_bytes();
+        // the body is a handful of short lines, it is nowhere near the limit
+        let body_len = u16::try_from(body.len()).unwrap_or(u16::MAX);
+        let mut frame = Vec::with_capacity(10 + body_len as usize);
+        frame.extend_from_slice(HANDSHAKE_MAGIC);
+        frame.extend_from_slice(&self.version.to_be_bytes());
+        frame.extend_from_slice(&body_len.to_be_bytes());
+        frame.extend_from_slice(&body[..body_len as usize]);
+        frame
+    }
+
+    /// Reads a handshake frame, as described in [`ProtocolInfo::to_handshake_frame`]
+    pub fn from_handshake_frame(reader: &mut impl Read) -> Result<Self> {
+        let mut header = [0; 10];
+        reader.read_exact(&mut header)?;
+        if &header[..4] != HANDSHAKE_MAGIC {
+            return Err(anyhow!(
+                "the peer did not start the connection with a handshake"
+            ));
+        }
+        let version = u32::from_be_bytes([header[4], header[5], header[6], header[7]]);
+        let body_len = u16::from_be_bytes([header[8], header[9]]);
+        let mut body = vec![0; body_len as usize];
+        reader.read_exact(&mut body)?;
+        let body = String::from_utf8(body).context("the handshake body is not valid UTF-8")?;
+        let mut lines = body.split('\n');
+        let zellij_version = lines.next().unwrap_or_default().to_owned();
+        let capabilities = lines
+            .filter(|capability| !capability.is_empty())
+            .map(|capability| capability.to_owned())
+            .collect();
+        Ok(ProtocolInfo {
+            version,
+            zellij_version,
+            capabilities,
+        })
+    }
+}
+
+/// Sets the read timeout of the socket behind `stream`, or clears it when `timeout` is `None`
+pub fn set_read_timeout(stream: &LocalSocketStream, timeout: Option<Duration>) -> io::Result<()> {
+    // we only borrow the socket, so the wrapper must not close it when it is dropped
+    let socket = ManuallyDrop::new(unsafe { UnixStream::from_raw_fd(stream.as_raw_fd()) });
+    socket.set_read_timeout(timeout)
+}
+
+fn send_protocol_info(stream: &mut LocalSocketStream, protocol_info: &ProtocolInfo) -> Result<()> {
+    stream
+        .write_all(&protocol_info.to_handshake_frame())
+        .and_then(|_| stream.flush())
+        .map_err(|e| anyhow!("failed to send protocol info: {}", e))
+}
+
+fn recv_protocol_info(stream: &mut LocalSocketStream) -> Result<ProtocolInfo> {
+    set_read_timeout(stream, Some(IPC_HANDSHAKE_TIMEOUT))?;
+    let protocol_info = ProtocolInfo::from_handshake_frame(stream)
+        .map_err(|e| anyhow!("failed to read protocol info: {}", e))?;
+    set_read_timeout(stream, None)?;
+    Ok(protocol_info)
+}
+
+/// Introduces a newly connected client to its server, returning the server's [`ProtocolInfo`].
+/// It is up to the caller to decide whether it is compatible.
+pub fn handshake_with_server(stream: &mut LocalSocketStream) -> Result<ProtocolInfo> {
+    send_protocol_info(stream, &ProtocolInfo::current())
+        .and_then(|_| recv_protocol_info(stream))
+        .context("failed to negotiate a protocol with the server, it might be running an incompatible version of Zellij")
+}
+
+/// Introduces the server to a newly connected client, returning the client's [`ProtocolInfo`].
+/// It is up to the caller to decide whether it is compatible.
+pub fn handshake_with_client(stream: &mut LocalSocketStream) -> Result<ProtocolInfo> {
+    recv_protocol_info(stream)
+        .and_then(|client_protocol_info| {
+            send_protocol_info(stream, &ProtocolInfo::current())?;
+            Ok(client_protocol_info)
+        })
+        .context("failed to negotiate a protocol with the client")
+}
+
+// every message is framed as a msgpack array of the message itself (with its field names, so that
+// fields can be added with a default) and the error context, an opaque blob which a peer that
+// cannot read it ignores
+fn write_message<T: Serialize>(
+    writer: &mut impl Write,
+    msg: &T,
+    err_ctx: &[u8],
+) -> std::result::Result<(), rmp_serde::encode::Error> {
+    rmp_serde::encode::write_named(writer, &(msg, err_ctx))
+}
+
+fn read_message<T: for<'de> Deserialize<'de>>(
+    reader: &mut impl Read,
+) -> std::result::Result<(T, Vec<u8>), rmp_serde::decode::Error> {
+    rmp_serde::decode::from_read(reader)
+}
+
 /// Sends messages on a stream socket, along with an [`ErrorContext`].
 pub struct IpcSenderWithContext<T: Serialize> {
     sender: io::BufWriter<LocalSocketStream>,
@@ -176,8 +388,10 @@ impl<T: Serialize> IpcSenderWithContext<T> {
 
     /// Sends an event, along with the current [`ErrorContext`], on this [`IpcSenderWithContext`]'s socket.
     pub fn send(&mut self, msg: T) -> Result<()> {
-        let err_ctx = get_current_ctx();
-        if rmp_serde::encode::write(&mut self.sender, &(msg, err_ctx)).is_err() {
+        // the error context is sent as an opaque blob so that a peer which doesn't know some of
+        // our threads can still read the message itself
+        let err_ctx = rmp_serde::to_vec_named(&get_current_ctx()).unwrap_or_default();
+        if write_message(&mut self.sender, &msg, &err_ctx).is_err() {
             Err(anyhow!("failed to send message to client"))
         } else {
             // TODO: unwrapping here can cause issues when the server disconnects which we don't mind
@@ -219,8 +433,11 @@ where
 
     /// Receives an event, along with the current [`ErrorContext`], on this [`IpcReceiverWithContext`]'s socket.
     pub fn recv(&mut self) -> Option<(T, ErrorContext)> {
-        match rmp_serde::decode::from_read(&mut self.receiver) {
-            Ok(msg) => Some(msg),
+        match read_message::<T>(&mut self.receiver) {
+            Ok((msg, err_ctx)) => {
+                let err_ctx = rmp_serde::from_slice(&err_ctx).unwrap_or_default();
+                Some((msg, err_ctx))
+            },
             Err(e) => {
                 warn!("Error in IpcReceiver.recv(): {:?}", e);
                 None
@@ -236,3 +453,664 @@ where
         IpcSenderWithContext::new(socket)
     }
 }
+
+/// The reply a server sends to a remote client once it has presented a valid token
+const REMOTE_ATTACH_ACCEPTED: &[u8] = b"OK\n";
+/// The longest token line a server is willing to read from a remote client
+const MAX_REMOTE_ATTACH_TOKEN_LEN: usize = 1024;
+/// How long a remote client has to present its token (or a server has to accept it)
+const REMOTE_ATTACH_HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(5);
+
+fn read_handshake_line(stream: &mut impl Read) -> io::Result<Vec<u8>> {
+    // we read byte by byte so as not to consume anything that comes after the handshake
+    let mut line = vec![];
+    let mut byte = [0; 1];
+    loop {
+        match stream.read(&mut byte)? {
+            0 => return Err(io::Error::from(io::ErrorKind::UnexpectedEof)),
+            _ if byte[0] == b'\n' => return Ok(line),
+            _ if line.len() >= MAX_REMOTE_ATTACH_TOKEN_LEN => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "remote attach handshake line is too long",
+                ))
+            },
+            _ => line.push(byte[0]),
+        }
+    }
+}
+
+fn tokens_match(presented: &[u8], expected: &[u8]) -> bool {
+    // compare in constant time so that neither the token nor its length can be guessed byte by
+    // byte - every byte of the expected token is compared even when the lengths differ
+    let mut difference = presented.len() ^ expected.len();
+    for (i, expected_byte) in expected.iter().enumerate() {
+        let presented_byte = presented.get(i).copied().unwrap_or(!expected_byte);
+        difference |= std::hint::black_box((presented_byte ^ expected_byte) as usize);
+    }
+    difference == 0
+}
+
+fn load_certificates(path: &Path) -> Result<Vec<rustls::Certificate>> {
+    let err_context = || format!("failed to load certificates from {}", path.display());
+
+    let file = File::open(path).with_context(err_context)?;
+    let certificates =
+        rustls_pemfile::certs(&mut BufReader::new(file)).with_context(err_context)?;
+    if certificates.is_empty() {
+        return Err(anyhow!("no PEM encoded certificates found")).with_context(err_context);
+    }
+    Ok(certificates.into_iter().map(rustls::Certificate).collect())
+}
+
+fn load_private_key(path: &Path) -> Result<rustls::PrivateKey> {
+    let err_context = || format!("failed to load private key from {}", path.display());
+
+    let mut reader = BufReader::new(File::open(path).with_context(err_context)?);
+    loop {
+        match rustls_pemfile::read_one(&mut reader).with_context(err_context)? {
+            Some(
+                rustls_pemfile::Item::PKCS8Key(key)
+                | rustls_pemfile::Item::RSAKey(key)
+                | rustls_pemfile::Item::ECKey(key),
+            ) => return Ok(rustls::PrivateKey(key)),
+            Some(_) => continue,
+            None => {
+                return Err(anyhow!("no PEM encoded private key found")).with_context(err_context)
+            },
+        }
+    }
+}
+
+/// Builds the TLS configuration a server accepts remote clients with, presenting the PEM encoded
+/// certificate chain in `cert` signed by the private key in `key`.
+pub fn remote_attach_server_config(cert: &Path, key: &Path) -> Result<Arc<ServerConfig>> {
+    let config = ServerConfig::builder()
+        .with_safe_defaults()
+        .with_no_client_auth()
+        .with_single_cert(load_certificates(cert)?, load_private_key(key)?)
+        .context("failed to configure TLS for remote clients")?;
+    Ok(Arc::new(config))
+}
+
+/// Builds the TLS configuration a remote client connects with, only trusting servers presenting
+/// one of the PEM encoded certificates in `ca_cert` (or a certificate issued by one of them).
+pub fn remote_attach_client_config(ca_cert: &Path) -> Result<Arc<ClientConfig>> {
+    let err_context = || format!("failed to trust certificates in {}", ca_cert.display());
+
+    let mut root_certificates = RootCertStore::empty();
+    for certificate in load_certificates(ca_cert)? {
+        root_certificates
+            .add(&certificate)
+            .with_context(err_context)?;
+    }
+    let config = ClientConfig::builder()
+        .with_safe_defaults()
+        .with_root_certificates(root_certificates)
+        .with_no_client_auth();
+    Ok(Arc::new(config))
+}
+
+fn remote_server_name(address: &str) -> Result<ServerName> {
+    let host = address
+        .rsplit_once(':')
+        .map(|(host, _port)| host)
+        .unwrap_or(address);
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    match host.parse::<IpAddr>() {
+        Ok(ip_address) => Ok(ServerName::IpAddress(ip_address)),
+        Err(_) => ServerName::try_from(host).map_err(|_| anyhow!("invalid server name {host:?}")),
+    }
+}
+
+fn relay_tls(
+    mut connection: Connection,
+    mut socket: TcpStream,
+    mut local: UnixStream,
+) -> io::Result<()> {
+    let mut buffer = [0; 16 * 1024];
+    loop {
+        // hand over what was already decrypted (eg. during the handshake) before waiting for more
+        loop {
+            match connection.reader().read(&mut buffer) {
+                Ok(0) => return Ok(()),
+                Ok(read) => local.write_all(&buffer[..read])?,
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => break,
+                Err(e) => return Err(e),
+            }
+        }
+        while connection.wants_write() {
+            connection.write_tls(&mut socket)?;
+        }
+        let mut fds = [
+            PollFd::new(socket.as_raw_fd(), PollFlags::POLLIN),
+            PollFd::new(local.as_raw_fd(), PollFlags::POLLIN),
+        ];
+        match poll(&mut fds, -1) {
+            Ok(_) => {},
+            Err(Errno::EINTR) => continue,
+            Err(e) => return Err(e.into()),
+        }
+        let is_ready = |fd: &PollFd| fd.revents().map_or(false, |events| !events.is_empty());
+        if is_ready(&fds[0]) {
+            if connection.read_tls(&mut socket)? == 0 {
+                return Ok(());
+            }
+            connection
+                .process_new_packets()
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        }
+        if is_ready(&fds[1]) {
+            let read = local.read(&mut buffer)?;
+            if read == 0 {
+                connection.send_close_notify();
+                while connection.wants_write() {
+                    connection.write_tls(&mut socket)?;
+                }
+                return Ok(());
+            }
+            connection.writer().write_all(&buffer[..read])?;
+        }
+    }
+}
+
+fn into_local_socket_stream(
+    connection: Connection,
+    socket: TcpStream,
+) -> Result<LocalSocketStream> {
+    // the ipc channels only ever read and write to the underlying file descriptor, so we give them
+    // one end of a unix socket and relay it to and from the encrypted connection in a thread
+    socket.set_read_timeout(None)?;
+    let (local, relayed) = UnixStream::pair()?;
+    std::thread::Builder::new()
+        .name("remote_attach_relay".to_string())
+        .spawn(move || {
+            if let Err(e) = relay_tls(connection, socket, relayed) {
+                warn!("Remote attach connection closed: {}", e);
+            }
+        })?;
+    Ok(unsafe { LocalSocketStream::from_raw_fd(local.into_raw_fd()) })
+}
+
+/// Authenticates a remote client that connected over TCP, returning the connection as a
+/// [LocalSocketStream](interprocess::local_socket::LocalSocketStream) if it completed a TLS
+/// handshake with `tls_config` and then presented the expected token.
+pub fn accept_remote_client(
+    socket: TcpStream,
+    tls_config: Arc<ServerConfig>,
+    token: &str,
+) -> Result<LocalSocketStream> {
+    let err_context = || "failed to authenticate remote client".to_string();
+
+    socket
+        .set_read_timeout(Some(REMOTE_ATTACH_HANDSHAKE_TIMEOUT))
+        .with_context(err_context)?;
+    let connection = ServerConnection::new(tls_config).with_context(err_context)?;
+    let mut stream = StreamOwned::new(connection, socket);
+    // the token is only read once the TLS handshake that this read drives has completed
+    let presented_token = read_handshake_line(&mut stream).with_context(err_context)?;
+    if !tokens_match(&presented_token, token.as_bytes()) {
+        return Err(anyhow!("remote client presented an invalid token")).with_context(err_context);
+    }
+    stream
+        .write_all(REMOTE_ATTACH_ACCEPTED)
+        .and_then(|_| stream.flush())
+        .with_context(err_context)?;
+    let StreamOwned { conn, sock } = stream;
+    into_local_socket_stream(conn.into(), sock).with_context(err_context)
+}
+
+/// Connects to a server listening for remote clients on `address` over TLS with `tls_config` and
+/// authenticates with `token`, returning the connection as a
+/// [LocalSocketStream](interprocess::local_socket::LocalSocketStream).
+pub fn connect_to_remote_server(
+    address: &str,
+    tls_config: Arc<ClientConfig>,
+    token: &str,
+) -> Result<LocalSocketStream> {
+    let err_context = || format!("failed to attach to remote session at {address}");
+
+    let server_name = remote_server_name(address).with_context(err_context)?;
+    let socket = TcpStream::connect(address).with_context(err_context)?;
+    socket
+        .set_read_timeout(Some(REMOTE_ATTACH_HANDSHAKE_TIMEOUT))
+        .with_context(err_context)?;
+    let connection = ClientConnection::new(tls_config, server_name).with_context(err_context)?;
+    let mut stream = StreamOwned::new(connection, socket);
+    // writing the token drives the TLS handshake first, so it is never sent if the server could
+    // not be verified
+    stream
+        .write_all(format!("{}\n", token).as_bytes())
+        .and_then(|_| stream.flush())
+        .with_context(err_context)?;
+    let mut reply = read_handshake_line(&mut stream)
+        .map_err(|_| anyhow!("the server refused the token"))
+        .with_context(err_context)?;
+    reply.push(b'\n');
+    if reply != REMOTE_ATTACH_ACCEPTED {
+        return Err(anyhow!("unexpected reply from server")).with_context(err_context);
+    }
+    let StreamOwned { conn, sock } = stream;
+    into_local_socket_stream(conn.into(), sock).with_context(err_context)
+}
+
+/// Resolves the address a server accepts remote clients on, a bare port is bound on localhost.
+pub fn remote_attach_listen_address(address: &str) -> Result<SocketAddr> {
+    let err_context = || format!("failed to accept remote clients on {address}");
+
+    if let Ok(port) = address.parse::<u16>() {
+        return Ok(SocketAddr::from((Ipv4Addr::LOCALHOST, port)));
+    }
+    address
+        .to_socket_addrs()
+        .with_context(err_context)?
+        .next()
+        .ok_or_else(|| anyhow!("the address does not resolve"))
+        .with_context(err_context)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::net::TcpListener;
+    use std::path::PathBuf;
+    use std::time::Instant;
+
+    fn local_socket_pair() -> (LocalSocketStream, LocalSocketStream) {
+        let (client, server) = UnixStream::pair().unwrap();
+        unsafe {
+            (
+                LocalSocketStream::from_raw_fd(client.into_raw_fd()),
+                LocalSocketStream::from_raw_fd(server.into_raw_fd()),
+            )
+        }
+    }
+
+    #[test]
+    fn client_and_server_exchange_protocol_info_before_messages() {
+        let (mut client, mut server) = local_socket_pair();
+        let server = std::thread::spawn(move || {
+            let client_protocol = handshake_with_client(&mut server).unwrap();
+            let mut receiver: IpcReceiverWithContext<ClientToServerMsg> =
+                IpcReceiverWithContext::new(server);
+            let (msg, _err_ctx) = receiver.recv().unwrap();
+            assert!(matches!(msg, ClientToServerMsg::ConnStatus));
+            client_protocol
+        });
+        let server_protocol = handshake_with_server(&mut client).unwrap();
+        let mut sender: IpcSenderWithContext<ClientToServerMsg> = IpcSenderWithContext::new(client);
+        sender.send(ClientToServerMsg::ConnStatus).unwrap();
+        let client_protocol = server.join().unwrap();
+        assert_eq!(server_protocol, ProtocolInfo::current());
+        assert_eq!(client_protocol, ProtocolInfo::current());
+        assert!(server_protocol.supports(IPC_CAPABILITY_READ_ONLY_CLIENTS));
+    }
+
+    // one of each message a client sends, so that their encoding is pinned by the fixtures
+    fn client_to_server_messages() -> Vec<ClientToServerMsg> {
+        let messages = vec![
+            ClientToServerMsg::DetachSession(vec![1, 2]),
+            ClientToServerMsg::TerminalPixelDimensions(PixelDimensions {
+                text_area_size: Some(SizeInPixels {
+                    height: 600,
+                    width: 800,
+                }),
+                character_cell_size: None,
+            }),
+            ClientToServerMsg::BackgroundColor("#000000".to_owned()),
+            ClientToServerMsg::ForegroundColor("#ffffff".to_owned()),
+            ClientToServerMsg::ColorRegisters(vec![(0, "#ff0000".to_owned())]),
+            ClientToServerMsg::TerminalResize(Size { rows: 24, cols: 80 }),
+            ClientToServerMsg::NewClient(
+                ClientAttributes::default(),
+                Box::new(CliArgs::default()),
+                Box::new(Options::default()),
+                Box::new(Layout::default()),
+                None,
+            ),
+            ClientToServerMsg::AttachClient(
+                ClientAttributes::default(),
+                Options::default(),
+                Some(1),
+                Some((2, false)),
+            ),
+            ClientToServerMsg::Action(Action::Write(b"hi".to_vec()), Some(1), Some(2)),
+            ClientToServerMsg::ClientExited,
+            ClientToServerMsg::KillSession,
+            ClientToServerMsg::ConnStatus,
+            ClientToServerMsg::ListClients,
+            ClientToServerMsg::LocalEchoPredicted(vec![(4, 2)]),
+            ClientToServerMsg::LocalEchoExpired,
+        ];
+        // fails to compile when a message is added, so that it is added above too
+        let covered = |msg: &ClientToServerMsg| match msg {
+            ClientToServerMsg::DetachSession(..)
+            | ClientToServerMsg::TerminalPixelDimensions(..)
+            | ClientToServerMsg::BackgroundColor(..)
+            | ClientToServerMsg::ForegroundColor(..)
+            | ClientToServerMsg::ColorRegisters(..)
+            | ClientToServerMsg::TerminalResize(..)
+            | ClientToServerMsg::NewClient(..)
+            | ClientToServerMsg::AttachClient(..)
+            | ClientToServerMsg::Action(..)
+            | ClientToServerMsg::ClientExited
+            | ClientToServerMsg::KillSession
+            | ClientToServerMsg::ConnStatus
+            | ClientToServerMsg::ListClients
+            | ClientToServerMsg::LocalEchoPredicted(..)
+            | ClientToServerMsg::LocalEchoExpired => true,
+        };
+        assert!(messages.iter().all(covered));
+        messages
+    }
+
+    // one of each message a server sends, so that their encoding is pinned by the fixtures
+    fn server_to_client_messages() -> Vec<ServerToClientMsg> {
+        let messages = vec![
+            ServerToClientMsg::Render("\u{1b}[Hhi".to_owned()),
+            ServerToClientMsg::UnblockInputThread,
+            ServerToClientMsg::Exit(ExitReason::Normal),
+            ServerToClientMsg::Exit(ExitReason::NormalDetached),
+            ServerToClientMsg::Exit(ExitReason::ForceDetached),
+            ServerToClientMsg::Exit(ExitReason::CannotAttach),
+            ServerToClientMsg::Exit(ExitReason::Disconnect),
+            ServerToClientMsg::Exit(ExitReason::IncompatibleVersion(Some(ProtocolInfo {
+                version: 1,
+                zellij_version: "0.40.0".to_owned(),
+                capabilities: vec![IPC_CAPABILITY_READ_ONLY_CLIENTS.to_owned()],
+            }))),
+            ServerToClientMsg::Exit(ExitReason::Error("oops".to_owned())),
+            ServerToClientMsg::SwitchToMode(InputMode::Locked),
+            ServerToClientMsg::Connected,
+            ServerToClientMsg::ActiveClients(vec![1, 2]),
+            ServerToClientMsg::Log(vec!["a line".to_owned()]),
+            ServerToClientMsg::LogError(vec!["an error".to_owned()]),
+            ServerToClientMsg::SwitchSession(ConnectToSession {
+                name: Some("other-session".to_owned()),
+                tab_position: Some(1),
+                ..Default::default()
+            }),
+            ServerToClientMsg::UnblockCliPipeInput("pipe".to_owned()),
+            ServerToClientMsg::CliPipeOutput("pipe".to_owned(), "output".to_owned()),
+            ServerToClientMsg::SubscribedEvent(Event::Timer(0.5)),
+        ];
+        // fails to compile when a message or exit reason is added, so that it is added above too
+        let covered = |msg: &ServerToClientMsg| match msg {
+            ServerToClientMsg::Exit(exit_reason) => match exit_reason {
+                ExitReason::Normal
+                | ExitReason::NormalDetached
+                | ExitReason::ForceDetached
+                | ExitReason::CannotAttach
+                | ExitReason::Disconnect
+                | ExitReason::IncompatibleVersion(..)
+                | ExitReason::Error(..) => true,
+            },
+            ServerToClientMsg::Render(..)
+            | ServerToClientMsg::UnblockInputThread
+            | ServerToClientMsg::SwitchToMode(..)
+            | ServerToClientMsg::Connected
+            | ServerToClientMsg::ActiveClients(..)
+            | ServerToClientMsg::Log(..)
+            | ServerToClientMsg::LogError(..)
+            | ServerToClientMsg::SwitchSession(..)
+            | ServerToClientMsg::UnblockCliPipeInput(..)
+            | ServerToClientMsg::CliPipeOutput(..)
+            | ServerToClientMsg::SubscribedEvent(..) => true,
+        };
+        assert!(messages.iter().all(covered));
+        messages
+    }
+
+    fn encode_messages<T: Serialize>(messages: &[T]) -> Vec<u8> {
+        let mut encoded = vec![];
+        for msg in messages {
+            write_message(&mut encoded, msg, &[]).unwrap();
+        }
+        encoded
+    }
+
+    // the fixtures of a new protocol version are recorded the first time its tests run, after which
+    // they are never overwritten
+    fn assert_matches_protocol_fixture<T>(fixture_name: &str, messages: &[T])
+    where
+        T: for<'de> Deserialize<'de> + Serialize,
+    {
+        let fixture_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("src/test-fixtures/ipc")
+            .join(format!("v{}", IPC_PROTOCOL_VERSION))
+            .join(fixture_name);
+        let encoded = encode_messages(messages);
+        let fixture = match std::fs::read(&fixture_path) {
+            Ok(fixture) => fixture,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                std::fs::create_dir_all(fixture_path.parent().unwrap()).unwrap();
+                std::fs::write(&fixture_path, &encoded).unwrap();
+                panic!(
+                    "recorded {} for protocol version {}, commit it and run the tests again",
+                    fixture_path.display(),
+                    IPC_PROTOCOL_VERSION
+                );
+            },
+            Err(e) => panic!("failed to read {}: {}", fixture_path.display(), e),
+        };
+        // every recorded message can still be read...
+        let mut reader = fixture.as_slice();
+        let mut decoded = vec![];
+        while !reader.is_empty() {
+            let (msg, err_ctx) = read_message::<T>(&mut reader).unwrap_or_else(|e| {
+                panic!(
+                    "message {} of {} can no longer be read: {}",
+                    decoded.len(),
+                    fixture_path.display(),
+                    e
+                )
+            });
+            assert!(err_ctx.is_empty());
+            decoded.push(msg);
+        }
+        assert_eq!(decoded.len(), messages.len());
+        assert_eq!(
+            encode_messages(&decoded),
+            fixture,
+            "reading {} loses information",
+            fixture_path.display()
+        );
+        // ...and is still sent exactly as recorded
+        assert!(
+            encoded == fixture,
+            "the messages are no longer encoded as recorded in {}, either keep them compatible or \
+             bump IPC_PROTOCOL_VERSION (see its documentation)",
+            fixture_path.display()
+        );
+    }
+
+    #[test]
+    fn client_messages_are_encoded_as_recorded_for_this_protocol_version() {
+        assert_matches_protocol_fixture("client-to-server.msgpack", &client_to_server_messages());
+    }
+
+    #[test]
+    fn server_messages_are_encoded_as_recorded_for_this_protocol_version() {
+        assert_matches_protocol_fixture("server-to-client.msgpack", &server_to_client_messages());
+    }
+
+    #[test]
+    fn protocols_with_different_versions_are_incompatible() {
+        let mut other_protocol = ProtocolInfo::current();
+        other_protocol.version += 1;
+        other_protocol
+            .capabilities
+            .push("some_new_capability".to_string());
+        assert!(!ProtocolInfo::current().is_compatible_with(&other_protocol));
+        other_protocol.version = IPC_PROTOCOL_VERSION;
+        assert!(ProtocolInfo::current().is_compatible_with(&other_protocol));
+    }
+
+    #[test]
+    fn handshake_frame_has_a_fixed_layout() {
+        let protocol_info = ProtocolInfo {
+            version: 1,
+            zellij_version: "0.1.0".to_string(),
+            capabilities: vec!["a".to_string(), "b".to_string()],
+        };
+        let frame = protocol_info.to_handshake_frame();
+        assert_eq!(frame, b"ZJHS\x00\x00\x00\x01\x00\x090.1.0\na\nb".to_vec());
+        let decoded = ProtocolInfo::from_handshake_frame(&mut frame.as_slice()).unwrap();
+        assert_eq!(decoded, protocol_info);
+    }
+
+    #[test]
+    fn handshake_fails_when_the_server_does_not_speak_it() {
+        let (mut client, server) = local_socket_pair();
+        let server = std::thread::spawn(move || {
+            // a server that predates the handshake starts with a regular message
+            let mut sender: IpcSenderWithContext<ServerToClientMsg> =
+                IpcSenderWithContext::new(server);
+            sender.send(ServerToClientMsg::Connected).unwrap();
+            sender
+        });
+        assert!(handshake_with_server(&mut client).is_err());
+        drop(server.join().unwrap());
+    }
+
+    #[test]
+    fn handshake_times_out_when_the_server_does_not_answer() {
+        let (mut client, _server) = local_socket_pair();
+        let started_at = Instant::now();
+        assert!(handshake_with_server(&mut client).is_err());
+        assert!(started_at.elapsed() >= IPC_HANDSHAKE_TIMEOUT);
+    }
+
+    fn test_fixture(name: &str) -> PathBuf {
+        PathBuf::from(format!(
+            "{}/src/test-fixtures/{}",
+            env!("CARGO_MANIFEST_DIR"),
+            name
+        ))
+    }
+
+    fn remote_attach_test_configs(ca_cert: &str) -> (Arc<ServerConfig>, Arc<ClientConfig>) {
+        let server_config = remote_attach_server_config(
+            &test_fixture("remote-attach-cert.pem"),
+            &test_fixture("remote-attach-key.pem"),
+        )
+        .unwrap();
+        let client_config = remote_attach_client_config(&test_fixture(ca_cert)).unwrap();
+        (server_config, client_config)
+    }
+
+    #[test]
+    fn remote_client_with_valid_token_can_exchange_messages() {
+        let (server_config, client_config) = remote_attach_test_configs("remote-attach-cert.pem");
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        let server = std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let stream = accept_remote_client(stream, server_config, "secret").unwrap();
+            let mut receiver: IpcReceiverWithContext<ClientToServerMsg> =
+                IpcReceiverWithContext::new(stream);
+            let (msg, _err_ctx) = receiver.recv().unwrap();
+            assert!(matches!(msg, ClientToServerMsg::ConnStatus));
+            let mut sender: IpcSenderWithContext<ServerToClientMsg> = receiver.get_sender();
+            sender.send(ServerToClientMsg::Connected).unwrap();
+        });
+        let stream = connect_to_remote_server(&address, client_config, "secret").unwrap();
+        let mut sender: IpcSenderWithContext<ClientToServerMsg> = IpcSenderWithContext::new(stream);
+        sender.send(ClientToServerMsg::ConnStatus).unwrap();
+        let mut receiver: IpcReceiverWithContext<ServerToClientMsg> = sender.get_receiver();
+        let (msg, _err_ctx) = receiver.recv().unwrap();
+        assert!(matches!(msg, ServerToClientMsg::Connected));
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn remote_client_with_invalid_token_is_refused() {
+        let (server_config, client_config) = remote_attach_test_configs("remote-attach-cert.pem");
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        let server = std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            assert!(accept_remote_client(stream, server_config, "secret").is_err());
+        });
+        assert!(connect_to_remote_server(&address, client_config, "not-the-secret").is_err());
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn remote_client_does_not_send_its_token_to_an_untrusted_server() {
+        let (server_config, client_config) =
+            remote_attach_test_configs("remote-attach-untrusted-cert.pem");
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        let server = std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            accept_remote_client(stream, server_config, "secret")
+                .unwrap_err()
+                .to_string()
+        });
+        assert!(connect_to_remote_server(&address, client_config, "secret").is_err());
+        // the client aborted during the TLS handshake, before the server could read a token
+        let server_error = server.join().unwrap();
+        assert_eq!(server_error, "failed to authenticate remote client");
+    }
+
+    #[test]
+    fn remote_client_without_tls_is_refused() {
+        let (server_config, _client_config) = remote_attach_test_configs("remote-attach-cert.pem");
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let server = std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            assert!(accept_remote_client(stream, server_config, "secret").is_err());
+        });
+        let mut stream = TcpStream::connect(address).unwrap();
+        stream.write_all(b"secret\n").unwrap();
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn tokens_only_match_when_identical() {
+        assert!(tokens_match(b"secret", b"secret"));
+        assert!(!tokens_match(b"secrets", b"secret"));
+        assert!(!tokens_match(b"secre", b"secret"));
+        assert!(!tokens_match(b"", b"secret"));
+        assert!(!tokens_match(b"Secret", b"secret"));
+    }
+
+    #[test]
+    fn remote_clients_are_accepted_on_any_address() {
+        assert_eq!(
+            remote_attach_listen_address("8082").unwrap(),
+            "127.0.0.1:8082".parse().unwrap()
+        );
+        assert_eq!(
+            remote_attach_listen_address("[::1]:8082").unwrap(),
+            "[::1]:8082".parse().unwrap()
+        );
+        assert_eq!(
+            remote_attach_listen_address("0.0.0.0:8082").unwrap(),
+            "0.0.0.0:8082".parse().unwrap()
+        );
+        assert_eq!(
+            remote_attach_listen_address("192.168.1.2:8082").unwrap(),
+            "192.168.1.2:8082".parse().unwrap()
+        );
+    }
+
+    #[test]
+    fn remote_server_names_are_taken_from_the_address() {
+        assert_eq!(
+            remote_server_name("127.0.0.1:8082").unwrap(),
+            ServerName::IpAddress("127.0.0.1".parse().unwrap())
+        );
+        assert_eq!(
+            remote_server_name("[::1]:8082").unwrap(),
+            ServerName::IpAddress("::1".parse().unwrap())
+        );
+        assert_eq!(
+            remote_server_name("example.com:8082").unwrap(),
+            ServerName::try_from("example.com").unwrap()
+        );
+    }
+}
@@ -1,12 +1,13 @@
 use crate::input::{
-    command::RunCommand,
+    command::{RestartPolicy, RunCommand},
     config::ConfigError,
     layout::{
-        FloatingPaneLayout, Layout, LayoutConstraint, PercentOrFixed, PluginUserConfiguration, Run,
-        RunPlugin, RunPluginLocation, SplitDirection, SplitSize, SwapFloatingLayout,
-        SwapTiledLayout, TiledPaneLayout,
+        FloatingPaneLayout, Layout, LayoutConstraint, PaneDependency, PercentOrFixed,
+        PluginUserConfiguration, ReadyCondition, Run, RunPlugin, RunPluginLocation, RunShell,
+        SplitDirection, SplitSize, SwapFloatingLayout, SwapTiledLayout, TiledPaneLayout,
     },
 };
+use regex::Regex;
 
 use kdl::*;
 
@@ -14,8 +15,8 @@ use std::collections::{BTreeMap, HashMap, HashSet};
 use std::str::FromStr;
 
 use crate::{
-    kdl_child_with_name, kdl_children_nodes, kdl_first_entry_as_bool, kdl_first_entry_as_i64,
-    kdl_first_entry_as_string, kdl_get_bool_property_or_child_value,
+    kdl_child_with_name, kdl_children_nodes, kdl_children_nodes_or_error, kdl_first_entry_as_bool,
+    kdl_first_entry_as_i64, kdl_first_entry_as_string, kdl_get_bool_property_or_child_value,
     kdl_get_bool_property_or_child_value_with_error, kdl_get_child,
     kdl_get_int_property_or_child_value, kdl_get_property_or_child,
     kdl_get_string_property_or_child_value, kdl_get_string_property_or_child_value_with_error,
@@ -33,6 +34,12 @@ pub enum PaneOrFloatingPane {
     Either(TiledPaneLayout),
 }
 
+// (restart policy, max restarts, restart backoff in ms)
+type RestartAttributes = (Option<RestartPolicy>, Option<usize>, Option<u64>);
+
+// (env variables, env file)
+type EnvAttributes = (Option<BTreeMap<String, String>>, Option<PathBuf>);
+
 pub struct KdlLayoutParser<'a> {
     global_cwd: Option<PathBuf>,
     raw_layout: &'a str,
@@ -72,6 +79,13 @@ impl<'a> KdlLayoutParser<'a> {
             || word == "args"
             || word == "close_on_exit"
             || word == "start_suspended"
+            || word == "restart"
+            || word == "max_restarts"
+            || word == "restart_backoff"
+            || word == "depends_on"
+            || word == "ready_when"
+            || word == "ready_pattern"
+            || word == "env_file"
             || word == "borderless"
             || word == "focus"
             || word == "name"
@@ -95,6 +109,14 @@ impl<'a> KdlLayoutParser<'a> {
             || property_name == "args"
             || property_name == "close_on_exit"
             || property_name == "start_suspended"
+            || property_name == "restart"
+            || property_name == "max_restarts"
+            || property_name == "restart_backoff"
+            || property_name == "depends_on"
+            || property_name == "ready_when"
+            || property_name == "ready_pattern"
+            || property_name == "env"
+            || property_name == "env_file"
             || property_name == "split_direction"
             || property_name == "pane"
             || property_name == "children"
@@ -114,6 +136,14 @@ impl<'a> KdlLayoutParser<'a> {
             || property_name == "args"
             || property_name == "close_on_exit"
             || property_name == "start_suspended"
+            || property_name == "restart"
+            || property_name == "max_restarts"
+            || property_name == "restart_backoff"
+            || property_name == "depends_on"
+            || property_name == "ready_when"
+            || property_name == "ready_pattern"
+            || property_name == "env"
+            || property_name == "env_file"
             || property_name == "x"
             || property_name == "y"
             || property_name == "width"
@@ -125,6 +155,7 @@ impl<'a> KdlLayoutParser<'a> {
             || property_name == "name"
             || property_name == "split_direction"
             || property_name == "cwd"
+            || property_name == "env_file"
             || property_name == "floating_panes"
             || property_name == "children"
             || property_name == "max_panes"
@@ -414,6 +445,92 @@ impl<'a> KdlLayoutParser<'a> {
             None => Ok(None),
         }
     }
+    fn parse_restart_policy(&self, kdl_node: &KdlNode) -> Result<RestartAttributes, ConfigError> {
+        let restart_policy =
+            match kdl_get_string_property_or_child_value_with_error!(kdl_node, "restart") {
+                Some(restart_policy) => Some(
+                    RestartPolicy::from_str(restart_policy)
+                        .map_err(|e| kdl_parsing_error!(e, kdl_node))?,
+                ),
+                None => None,
+            };
+        let max_restarts = kdl_get_int_property_or_child_value!(kdl_node, "max_restarts");
+        let restart_backoff = kdl_get_int_property_or_child_value!(kdl_node, "restart_backoff");
+        if max_restarts.map(|m| m < 0).unwrap_or(false)
+            || restart_backoff.map(|r| r < 0).unwrap_or(false)
+        {
+            return Err(kdl_parsing_error!(
+                format!("max_restarts and restart_backoff cannot be negative"),
+                kdl_node
+            ));
+        }
+        Ok((
+            restart_policy,
+            max_restarts.map(|m| m as usize),
+            restart_backoff.map(|r| r as u64),
+        ))
+    }
+    fn parse_env(&self, kdl_node: &KdlNode) -> Result<EnvAttributes, ConfigError> {
+        let env = match kdl_get_child!(kdl_node, "env") {
+            Some(env_node) => {
+                let mut env = BTreeMap::new();
+                for env_var in kdl_children_nodes_or_error!(env_node, "empty env block") {
+                    let env_var_name = kdl_name!(env_var);
+                    let env_var_value = kdl_first_entry_as_string!(env_var)
+                        .map(|s| s.to_string())
+                        .or_else(|| kdl_first_entry_as_i64!(env_var).map(|i| i.to_string()))
+                        .ok_or(kdl_parsing_error!(
+                            format!("Failed to parse env var: {:?}", env_var_name),
+                            env_var
+                        ))?;
+                    env.insert(env_var_name.to_owned(), env_var_value);
+                }
+                Some(env)
+            },
+            None => None,
+        };
+        let env_file = self.parse_path(kdl_node, "env_file")?;
+        Ok((env, env_file))
+    }
+    fn parse_pane_dependency(
+        &self,
+        kdl_node: &KdlNode,
+    ) -> Result<Option<PaneDependency>, ConfigError> {
+        let depends_on = kdl_get_string_property_or_child_value_with_error!(kdl_node, "depends_on");
+        let ready_when = kdl_get_string_property_or_child_value_with_error!(kdl_node, "ready_when");
+        let ready_pattern =
+            kdl_get_string_property_or_child_value_with_error!(kdl_node, "ready_pattern");
+        let ready_when = match (ready_when, ready_pattern) {
+            (Some(_), Some(_)) => {
+                return Err(kdl_parsing_error!(
+                    format!("ready_when and ready_pattern cannot be specified together"),
+                    kdl_node
+                ));
+            },
+            (Some(ready_when), None) => Some(
+                ReadyCondition::from_str(ready_when)
+                    .map_err(|e| kdl_parsing_error!(e.to_string(), kdl_node))?,
+            ),
+            (None, Some(ready_pattern)) => {
+                Regex::new(ready_pattern).map_err(|e| {
+                    kdl_parsing_error!(format!("Invalid ready_pattern: {}", e), kdl_node)
+                })?;
+                Some(ReadyCondition::OutputMatches(ready_pattern.to_owned()))
+            },
+            (None, None) => None,
+        };
+        match (depends_on, ready_when) {
+            (Some(pane_name), ready_when) => Ok(Some(PaneDependency {
+                pane_name: pane_name.to_owned(),
+                ready_when: ready_when.unwrap_or_default(),
+            })),
+            (None, Some(_)) => Err(kdl_parsing_error!(
+                format!("ready_when and ready_pattern can only be specified along with depends_on"),
+                kdl_node
+            )),
+            (None, None) => Ok(None),
+        }
+    }
     fn parse_pane_command(
         &self,
         pane_node: &KdlNode,
@@ -427,18 +544,28 @@ impl<'a> KdlLayoutParser<'a> {
             kdl_get_bool_property_or_child_value_with_error!(pane_node, "close_on_exit");
         let start_suspended =
             kdl_get_bool_property_or_child_value_with_error!(pane_node, "start_suspended");
+        let (restart_policy, max_restarts, restart_backoff_ms) =
+            self.parse_restart_policy(pane_node)?;
+        let (env, env_file) = self.parse_env(pane_node)?;
         if !is_template {
             self.assert_no_bare_attributes_in_pane_node(
                 &command,
                 &args,
                 &close_on_exit,
                 &start_suspended,
+                &restart_policy,
                 pane_node,
             )?;
         }
         let hold_on_close = close_on_exit.map(|c| !c).unwrap_or(true);
         let hold_on_start = start_suspended.map(|c| c).unwrap_or(false);
+        let has_env = env.is_some() || env_file.is_some();
         match (command, edit, cwd) {
+            (None, None, cwd) if has_env => Ok(Some(Run::Shell(RunShell {
+                cwd,
+                env: env.unwrap_or_default(),
+                env_file,
+            }))),
             (None, None, Some(cwd)) => Ok(Some(Run::Cwd(cwd))),
             (Some(command), None, cwd) => Ok(Some(Run::Command(RunCommand {
                 command,
@@ -446,6 +573,11 @@ impl<'a> KdlLayoutParser<'a> {
                 cwd,
                 hold_on_close,
                 hold_on_start,
+                env: env.unwrap_or_default(),
+                env_file,
+                restart_policy: restart_policy.unwrap_or_default(),
+                max_restarts,
+                restart_backoff_ms,
             }))),
             (None, Some(edit), Some(cwd)) => {
                 Ok(Some(Run::EditFile(cwd.join(edit), None, Some(cwd))))
@@ -467,7 +599,7 @@ impl<'a> KdlLayoutParser<'a> {
         if let Some(plugin_block) = kdl_get_child!(kdl_node, "plugin") {
             let has_non_cwd_run_prop = run
                 .map(|r| match r {
-                    Run::Cwd(_) => false,
+                    Run::Cwd(_) | Run::Shell(_) => false,
                     _ => true,
                 })
                 .unwrap_or(false);
@@ -480,6 +612,8 @@ impl<'a> KdlLayoutParser<'a> {
             }
             run = self.parse_plugin_block(plugin_block)?;
         }
+        let (env, env_file) = self.parse_env(kdl_node)?;
+        self.assert_env_has_command_or_shell(&run, &env, &env_file, kdl_node)?;
         Ok(run)
     }
     fn parse_command_plugin_or_edit_block_for_template(
@@ -490,7 +624,7 @@ impl<'a> KdlLayoutParser<'a> {
         if let Some(plugin_block) = kdl_get_child!(kdl_node, "plugin") {
             let has_non_cwd_run_prop = run
                 .map(|r| match r {
-                    Run::Cwd(_) => false,
+                    Run::Cwd(_) | Run::Shell(_) => false,
                     _ => true,
                 })
                 .unwrap_or(false);
@@ -503,6 +637,8 @@ impl<'a> KdlLayoutParser<'a> {
             }
             run = self.parse_plugin_block(plugin_block)?;
         }
+        let (env, env_file) = self.parse_env(kdl_node)?;
+        self.assert_env_has_command_or_shell(&run, &env, &env_file, kdl_node)?;
         Ok(run)
     }
     fn parse_pane_node(
@@ -525,6 +661,8 @@ impl<'a> KdlLayoutParser<'a> {
             kdl_get_string_property_or_child_value_with_error!(kdl_node, "contents_file");
         let split_size = self.parse_split_size(kdl_node)?;
         let run = self.parse_command_plugin_or_edit_block(kdl_node)?;
+        let depends_on = self.parse_pane_dependency(kdl_node)?;
+        self.assert_pane_dependency_has_command(&run, &depends_on, kdl_node)?;
         let children_split_direction = self.parse_split_direction(kdl_node)?;
         let (external_children_index, children) = match kdl_children_nodes!(kdl_node) {
             Some(children) => {
@@ -571,6 +709,7 @@ impl<'a> KdlLayoutParser<'a> {
             children_are_stacked,
             is_expanded_in_stack,
             pane_initial_contents,
+            depends_on,
             ..Default::default()
         })
     }
@@ -584,6 +723,8 @@ impl<'a> KdlLayoutParser<'a> {
         let x = self.parse_percent_or_fixed(kdl_node, "x", true)?;
         let y = self.parse_percent_or_fixed(kdl_node, "y", true)?;
         let run = self.parse_command_plugin_or_edit_block(kdl_node)?;
+        let depends_on = self.parse_pane_dependency(kdl_node)?;
+        self.assert_pane_dependency_has_command(&run, &depends_on, kdl_node)?;
         let focus = kdl_get_bool_property_or_child_value_with_error!(kdl_node, "focus");
         let name = kdl_get_string_property_or_child_value_with_error!(kdl_node, "name")
             .map(|name| name.to_string());
@@ -604,6 +745,7 @@ impl<'a> KdlLayoutParser<'a> {
             run,
             focus,
             pane_initial_contents,
+            depends_on,
             ..Default::default()
         })
     }
@@ -696,6 +838,10 @@ impl<'a> KdlLayoutParser<'a> {
                     kdl_get_bool_property_or_child_value_with_error!(kdl_node, "close_on_exit");
                 let start_suspended =
                     kdl_get_bool_property_or_child_value_with_error!(kdl_node, "start_suspended");
+                let (restart_policy, max_restarts, restart_backoff_ms) =
+                    self.parse_restart_policy(kdl_node)?;
+                let depends_on = self.parse_pane_dependency(kdl_node)?;
+                let (env, env_file) = self.parse_env(kdl_node)?;
                 let split_size = self.parse_split_size(kdl_node)?;
                 let run = self.parse_command_plugin_or_edit_block_for_template(kdl_node)?;
                 let exclude_from_sync =
@@ -712,6 +858,7 @@ impl<'a> KdlLayoutParser<'a> {
                     &args,
                     &close_on_exit,
                     &start_suspended,
+                    &restart_policy,
                     kdl_node,
                 )?;
                 self.insert_children_to_pane_template(
@@ -726,7 +873,20 @@ impl<'a> KdlLayoutParser<'a> {
                     pane_template_run_command.add_args(args);
                     pane_template_run_command.add_close_on_exit(close_on_exit);
                     pane_template_run_command.add_start_suspended(start_suspended);
+                    pane_template_run_command.add_restart_policy(
+                        restart_policy,
+                        max_restarts,
+                        restart_backoff_ms,
+                    );
+                    pane_template_run_command
+                        .add_env(env.clone().unwrap_or_default(), env_file.clone());
                 };
+                self.assert_env_has_command_or_shell(
+                    &pane_template.run,
+                    &env,
+                    &env_file,
+                    kdl_node,
+                )?;
                 if let Some(borderless) = borderless {
                     pane_template.borderless = borderless;
                 }
@@ -736,6 +896,14 @@ impl<'a> KdlLayoutParser<'a> {
                 if let Some(name) = name {
                     pane_template.name = Some(name);
                 }
+                if let Some(depends_on) = depends_on {
+                    pane_template.depends_on = Some(depends_on);
+                }
+                self.assert_pane_dependency_has_command(
+                    &pane_template.run,
+                    &pane_template.depends_on,
+                    kdl_node,
+                )?;
                 if let Some(exclude_from_sync) = exclude_from_sync {
                     pane_template.exclude_from_sync = Some(exclude_from_sync);
                 }
@@ -802,6 +970,10 @@ impl<'a> KdlLayoutParser<'a> {
                     kdl_get_bool_property_or_child_value_with_error!(kdl_node, "close_on_exit");
                 let start_suspended =
                     kdl_get_bool_property_or_child_value_with_error!(kdl_node, "start_suspended");
+                let (restart_policy, max_restarts, restart_backoff_ms) =
+                    self.parse_restart_policy(kdl_node)?;
+                let depends_on = self.parse_pane_dependency(kdl_node)?;
+                let (env, env_file) = self.parse_env(kdl_node)?;
                 let run = self.parse_command_plugin_or_edit_block_for_template(kdl_node)?;
                 self.assert_no_bare_attributes_in_pane_node_with_template(
                     &run,
@@ -809,6 +981,7 @@ impl<'a> KdlLayoutParser<'a> {
                     &args,
                     &close_on_exit,
                     &start_suspended,
+                    &restart_policy,
                     kdl_node,
                 )?;
                 pane_template.run = Run::merge(&pane_template.run, &run);
@@ -818,13 +991,34 @@ impl<'a> KdlLayoutParser<'a> {
                     pane_template_run_command.add_args(args);
                     pane_template_run_command.add_close_on_exit(close_on_exit);
                     pane_template_run_command.add_start_suspended(start_suspended);
+                    pane_template_run_command.add_restart_policy(
+                        restart_policy,
+                        max_restarts,
+                        restart_backoff_ms,
+                    );
+                    pane_template_run_command
+                        .add_env(env.clone().unwrap_or_default(), env_file.clone());
                 };
+                self.assert_env_has_command_or_shell(
+                    &pane_template.run,
+                    &env,
+                    &env_file,
+                    kdl_node,
+                )?;
                 if let Some(focus) = focus {
                     pane_template.focus = Some(focus);
                 }
                 if let Some(name) = name {
                     pane_template.name = Some(name);
                 }
+                if let Some(depends_on) = depends_on {
+                    pane_template.depends_on = Some(depends_on);
+                }
+                self.assert_pane_dependency_has_command(
+                    &pane_template.run,
+                    &pane_template.depends_on,
+                    kdl_node,
+                )?;
                 let height = self.parse_percent_or_fixed(kdl_node, "height", false)?;
                 let width = self.parse_percent_or_fixed(kdl_node, "width", false)?;
                 let x = self.parse_percent_or_fixed(kdl_node, "x", true)?;
@@ -853,6 +1047,10 @@ impl<'a> KdlLayoutParser<'a> {
                     kdl_get_bool_property_or_child_value_with_error!(kdl_node, "close_on_exit");
                 let start_suspended =
                     kdl_get_bool_property_or_child_value_with_error!(kdl_node, "start_suspended");
+                let (restart_policy, max_restarts, restart_backoff_ms) =
+                    self.parse_restart_policy(kdl_node)?;
+                let depends_on = self.parse_pane_dependency(kdl_node)?;
+                let (env, env_file) = self.parse_env(kdl_node)?;
                 let run = self.parse_command_plugin_or_edit_block_for_template(kdl_node)?;
                 self.assert_no_bare_attributes_in_pane_node_with_template(
                     &run,
@@ -860,6 +1058,7 @@ impl<'a> KdlLayoutParser<'a> {
                     &args,
                     &close_on_exit,
                     &start_suspended,
+                    &restart_policy,
                     kdl_node,
                 )?;
                 pane_template.run = Run::merge(&pane_template.run, &run);
@@ -869,13 +1068,34 @@ impl<'a> KdlLayoutParser<'a> {
                     pane_template_run_command.add_args(args);
                     pane_template_run_command.add_close_on_exit(close_on_exit);
                     pane_template_run_command.add_start_suspended(start_suspended);
+                    pane_template_run_command.add_restart_policy(
+                        restart_policy,
+                        max_restarts,
+                        restart_backoff_ms,
+                    );
+                    pane_template_run_command
+                        .add_env(env.clone().unwrap_or_default(), env_file.clone());
                 };
+                self.assert_env_has_command_or_shell(
+                    &pane_template.run,
+                    &env,
+                    &env_file,
+                    kdl_node,
+                )?;
                 if let Some(focus) = focus {
                     pane_template.focus = Some(focus);
                 }
                 if let Some(name) = name {
                     pane_template.name = Some(name);
                 }
+                if let Some(depends_on) = depends_on {
+                    pane_template.depends_on = Some(depends_on);
+                }
+                self.assert_pane_dependency_has_command(
+                    &pane_template.run,
+                    &pane_template.depends_on,
+                    kdl_node,
+                )?;
                 let height = self.parse_percent_or_fixed(kdl_node, "height", false)?;
                 let width = self.parse_percent_or_fixed(kdl_node, "width", false)?;
                 let x = self.parse_percent_or_fixed(kdl_node, "x", true)?;
@@ -1162,8 +1382,26 @@ impl<'a> KdlLayoutParser<'a> {
         if let Some(cwd_prefix) = &self.cwd_prefix(tab_cwd.as_ref())? {
             pane_layout.add_cwd_to_layout(&cwd_prefix);
         }
+        self.add_tab_env_to_panes(kdl_node, &mut pane_layout, &mut child_floating_panes)?;
         Ok((is_focused, tab_name, pane_layout, child_floating_panes))
     }
+    fn add_tab_env_to_panes(
+        &self,
+        kdl_node: &KdlNode,
+        tab_layout: &mut TiledPaneLayout,
+        floating_panes: &mut [FloatingPaneLayout],
+    ) -> Result<(), ConfigError> {
+        // panes that define their own env variables or env file take precedence
+        let (env, env_file) = self.parse_env(kdl_node)?;
+        let env = env.unwrap_or_default();
+        if !env.is_empty() || env_file.is_some() {
+            tab_layout.add_env_to_layout(&env, &env_file);
+            for floating_pane in floating_panes.iter_mut() {
+                floating_pane.add_env_to_layout(&env, &env_file);
+            }
+        }
+        Ok(())
+    }
     fn parse_child_pane_nodes_for_tab(
         &self,
         children: &[KdlNode],
@@ -1186,6 +1424,9 @@ impl<'a> KdlLayoutParser<'a> {
                 )?);
             } else if kdl_name!(child) == "floating_panes" {
                 self.populate_floating_pane_children(child, child_floating_panes)?;
+            } else if kdl_name!(child) == "env" {
+                // parsed separately and applied to all the command panes in the tab
+                continue;
             } else if self.is_a_valid_tab_property(kdl_name!(child)) {
                 return Err(ConfigError::new_layout_kdl_error(
                     format!("Tab property '{}' must be placed on the tab title line and not in the child braces", kdl_name!(child)),
@@ -1291,6 +1532,7 @@ impl<'a> KdlLayoutParser<'a> {
         args: &Option<Vec<String>>,
         close_on_exit: &Option<bool>,
         start_suspended: &Option<bool>,
+        restart_policy: &Option<RestartPolicy>,
         pane_node: &KdlNode,
     ) -> Result<(), ConfigError> {
         if let (None, None, true) = (pane_run, pane_template_run, args.is_some()) {
@@ -1311,14 +1553,51 @@ impl<'a> KdlLayoutParser<'a> {
                 pane_node
             ));
         }
+        if let (None, None, true) = (pane_run, pane_template_run, restart_policy.is_some()) {
+            return Err(kdl_parsing_error!(
+                format!("restart can only be specified if a command was specified either in the pane_template or in the pane"),
+                pane_node
+            ));
+        }
         Ok(())
     }
+    fn assert_env_has_command_or_shell(
+        &self,
+        run: &Option<Run>,
+        env: &Option<BTreeMap<String, String>>,
+        env_file: &Option<PathBuf>,
+        pane_node: &KdlNode,
+    ) -> Result<(), ConfigError> {
+        let runs_command_or_shell = !matches!(run, Some(Run::Plugin(_)) | Some(Run::EditFile(..)));
+        if !runs_command_or_shell && (env.is_some() || env_file.is_some()) {
+            return Err(kdl_parsing_error!(
+                format!("env and env_file can only be specified for panes that run a command or a shell"),
+                pane_node
+            ));
+        }
+        Ok(())
+    }
+    fn assert_pane_dependency_has_command(
+        &self,
+        run: &Option<Run>,
+        depends_on: &Option<PaneDependency>,
+        pane_node: &KdlNode,
+    ) -> Result<(), ConfigError> {
+        match (depends_on, run) {
+            (Some(_), Some(Run::Command(_))) | (None, _) => Ok(()),
+            (Some(_), _) => Err(kdl_parsing_error!(
+                format!("depends_on can only be specified for panes that run a command"),
+                pane_node
+            )),
+        }
+    }
     fn assert_no_bare_attributes_in_pane_node(
         &self,
         command: &Option<PathBuf>,
         args: &Option<Vec<String>>,
         close_on_exit: &Option<bool>,
         start_suspended: &Option<bool>,
+        restart_policy: &Option<RestartPolicy>,
         pane_node: &KdlNode,
     ) -> Result<(), ConfigError> {
         if command.is_none() {
@@ -1336,6 +1615,13 @@ impl<'a> KdlLayoutParser<'a> {
                     pane_node.span().len(),
                 ));
             }
+            if restart_policy.is_some() {
+                return Err(ConfigError::new_layout_kdl_error(
+                    "restart can only be set if a command was specified".into(),
+                    pane_node.span().offset(),
+                    pane_node.span().len(),
+                ));
+            }
             if args.is_some() {
                 return Err(ConfigError::new_layout_kdl_error(
                     "args can only be set if a command was specified".into(),
@@ -1568,6 +1854,7 @@ impl<'a> KdlLayoutParser<'a> {
         if let Some(cwd_prefix) = self.cwd_prefix(tab_cwd.as_ref())? {
             tab_layout.add_cwd_to_layout(&cwd_prefix);
         }
+        self.add_tab_env_to_panes(kdl_node, &mut tab_layout, &mut tab_template_floating_panes)?;
         tab_layout.external_children_index = None;
         Ok((
             is_focused,
@@ -1,7 +1,7 @@
 mod kdl_layout_parser;
 use crate::data::{
-    Direction, InputMode, Key, Palette, PaletteColor, PaneInfo, PaneManifest, PermissionType,
-    Resize, SessionInfo, TabInfo,
+    Direction, InputMode, Key, Palette, PaletteColor, PaneInfo, PaneManifest, PaneProcessInfo,
+    PaneSignal, PermissionType, Resize, SessionInfo, TabInfo,
 };
 use crate::envs::EnvironmentVariables;
 use crate::home::{find_default_config_dir, get_layout_dir};
@@ -12,6 +12,7 @@ use crate::input::options::{Clipboard, OnForceClose, Options};
 use crate::input::permission::{GrantedPermission, PermissionCache};
 use crate::input::plugins::{PluginConfig, PluginTag, PluginType, PluginsConfig};
 use crate::input::theme::{FrameConfig, Theme, Themes, UiConfig};
+use crate::input::workspaces::{Workspace, Workspaces};
 use kdl_layout_parser::KdlLayoutParser;
 use std::collections::{BTreeMap, HashMap, HashSet};
 use strum::IntoEnumIterator;
@@ -24,7 +25,7 @@ use std::path::PathBuf;
 use std::str::FromStr;
 
 use crate::input::actions::{Action, SearchDirection, SearchOption};
-use crate::input::command::RunCommandAction;
+use crate::input::command::{RestartPolicy, RunCommandAction};
 
 #[macro_export]
 macro_rules! parse_kdl_action_arguments {
@@ -364,6 +365,16 @@ pub fn kdl_child_bool_value_for_entry<'a>(
         .and_then(|cwd_value| cwd_value.value().as_bool())
 }
 
+pub fn kdl_child_int_value_for_entry<'a>(
+    command_metadata: &'a KdlDocument,
+    entry_name: &'a str,
+) -> Option<i64> {
+    command_metadata
+        .get(entry_name)
+        .and_then(|cwd| cwd.entries().iter().next())
+        .and_then(|cwd_value| cwd_value.value().as_i64())
+}
+
 impl Action {
     pub fn new_from_bytes(
         action_name: &str,
@@ -453,6 +464,19 @@ impl Action {
                 })?;
                 Ok(Action::MoveFocusOrTab(direction))
             },
+            "MoveTab" => {
+                let direction = Direction::from_str(string.as_str())
+                    .ok()
+                    .filter(|d| matches!(d, Direction::Left | Direction::Right))
+                    .ok_or_else(|| {
+                        ConfigError::new_kdl_error(
+                            format!("Invalid direction: '{}', tabs move left or right", string),
+                            action_node.span().offset(),
+                            action_node.span().len(),
+                        )
+                    })?;
+                Ok(Action::MoveTab(direction))
+            },
             "MovePane" => {
                 if string.is_empty() {
                     return Ok(Action::MovePane(None));
@@ -506,6 +530,16 @@ impl Action {
                 Ok(Action::Search(search_direction))
             },
             "RenameSession" => Ok(Action::RenameSession(string)),
+            "SignalPane" => {
+                let signal = PaneSignal::from_str(string.as_str()).map_err(|e| {
+                    ConfigError::new_kdl_error(
+                        e,
+                        action_node.span().offset(),
+                        action_node.span().len(),
+                    )
+                })?;
+                Ok(Action::SignalPane(None, signal))
+            },
             _ => Err(ConfigError::new_kdl_error(
                 format!("Unsupported action: {}", action_name),
                 action_node.span().offset(),
@@ -743,6 +777,11 @@ impl TryFrom<(&KdlNode, &Options)> for Action {
                 action_arguments,
                 kdl_action
             ),
+            "MoveTab" => parse_kdl_action_char_or_string_arguments!(
+                action_name,
+                action_arguments,
+                kdl_action
+            ),
             "MovePane" => parse_kdl_action_char_or_string_arguments!(
                 action_name,
                 action_arguments,
@@ -896,6 +935,35 @@ impl TryFrom<(&KdlNode, &Options)> for Action {
                 let in_place = command_metadata
                     .and_then(|c_m| kdl_child_bool_value_for_entry(c_m, "in_place"))
                     .unwrap_or(false);
+                let restart_policy = match command_metadata
+                    .and_then(|c_m| kdl_child_string_value_for_entry(c_m, "restart"))
+                {
+                    Some(restart_policy) => {
+                        RestartPolicy::from_str(restart_policy).map_err(|e| {
+                            ConfigError::new_kdl_error(
+                                e,
+                                kdl_action.span().offset(),
+                                kdl_action.span().len(),
+                            )
+                        })?
+                    },
+                    None => RestartPolicy::default(),
+                };
+                let max_restarts = command_metadata
+                    .and_then(|c_m| kdl_child_int_value_for_entry(c_m, "max_restarts"));
+                let restart_backoff_ms = command_metadata
+                    .and_then(|c_m| kdl_child_int_value_for_entry(c_m, "restart_backoff"));
+                if max_restarts.map(|m| m < 0).unwrap_or(false)
+                    || restart_backoff_ms.map(|r| r < 0).unwrap_or(false)
+                {
+                    return Err(ConfigError::new_kdl_error(
+                        "max_restarts and restart_backoff cannot be negative".into(),
+                        kdl_action.span().offset(),
+                        kdl_action.span().len(),
+                    ));
+                }
+                let max_restarts = max_restarts.map(|m| m as usize);
+                let restart_backoff_ms = restart_backoff_ms.map(|r| r as u64);
                 let run_command_action = RunCommandAction {
                     command: PathBuf::from(command),
                     args,
@@ -903,6 +971,10 @@ impl TryFrom<(&KdlNode, &Options)> for Action {
                     direction,
                     hold_on_close,
                     hold_on_start,
+                    restart_policy,
+                    max_restarts,
+                    restart_backoff_ms,
+                    ..Default::default()
                 };
                 if floating {
                     Ok(Action::NewFloatingPane(Some(run_command_action), name))
@@ -1004,6 +1076,11 @@ impl TryFrom<(&KdlNode, &Options)> for Action {
                 action_arguments,
                 kdl_action
             ),
+            "SignalPane" => parse_kdl_action_char_or_string_arguments!(
+                action_name,
+                action_arguments,
+                kdl_action
+            ),
             _ => Err(ConfigError::new_kdl_error(
                 format!("Unsupported action: {}", action_name).into(),
                 kdl_action.span().offset(),
@@ -1449,6 +1526,9 @@ impl Options {
             .map(|(string, _entry)| PathBuf::from(string));
         let mouse_mode =
             kdl_property_first_arg_as_bool_or_error!(kdl_options, "mouse_mode").map(|(v, _)| v);
+        let mouse_hover_events =
+            kdl_property_first_arg_as_bool_or_error!(kdl_options, "mouse_hover_events")
+                .map(|(v, _)| v);
         let scroll_buffer_size =
             kdl_property_first_arg_as_i64_or_error!(kdl_options, "scroll_buffer_size")
                 .map(|(scroll_buffer_size, _entry)| scroll_buffer_size as usize);
@@ -1491,6 +1571,37 @@ impl Options {
         let serialization_interval =
             kdl_property_first_arg_as_i64_or_error!(kdl_options, "serialization_interval")
                 .map(|(scroll_buffer_size, _entry)| scroll_buffer_size as u64);
+        let remote_attach_address =
+            kdl_property_first_arg_as_string_or_error!(kdl_options, "remote_attach_address")
+                .map(|(address, _entry)| address.to_string());
+        let remote_attach_token =
+            kdl_property_first_arg_as_string_or_error!(kdl_options, "remote_attach_token")
+                .map(|(token, _entry)| token.to_string());
+        let remote_attach_cert =
+            kdl_property_first_arg_as_string_or_error!(kdl_options, "remote_attach_cert")
+                .map(|(string, _entry)| PathBuf::from(string));
+        let remote_attach_key =
+            kdl_property_first_arg_as_string_or_error!(kdl_options, "remote_attach_key")
+                .map(|(string, _entry)| PathBuf::from(string));
+        let auto_snapshot_history =
+            kdl_property_first_arg_as_i64_or_error!(kdl_options, "auto_snapshot_history")
+                .map(|(history, _entry)| history as usize);
+        let workspace = kdl_property_first_arg_as_string_or_error!(kdl_options, "workspace")
+            .map(|(workspace, _entry)| workspace.to_string());
+        let idle_session_timeout =
+            kdl_property_first_arg_as_i64_or_error!(kdl_options, "idle_session_timeout")
+                .map(|(timeout, _entry)| timeout as u64);
+        let resurrectable_session_ttl =
+            kdl_property_first_arg_as_i64_or_error!(kdl_options, "resurrectable_session_ttl")
+                .map(|(ttl, _entry)| ttl as u64);
+        let max_resurrectable_sessions =
+            kdl_property_first_arg_as_i64_or_error!(kdl_options, "max_resurrectable_sessions")
+                .map(|(max_sessions, _entry)| max_sessions as usize);
+        let diff_rendering =
+            kdl_property_first_arg_as_bool_or_error!(kdl_options, "diff_rendering").map(|(v, _)| v);
+        let local_echo_prediction =
+            kdl_property_first_arg_as_bool_or_error!(kdl_options, "local_echo_prediction")
+                .map(|(v, _)| v);
         Ok(Options {
             simplified_ui,
             theme,
@@ -1501,6 +1612,7 @@ impl Options {
             layout_dir,
             theme_dir,
             mouse_mode,
+            mouse_hover_events,
             pane_frames,
             mirror_session,
             on_force_close,
@@ -1517,6 +1629,17 @@ impl Options {
             scrollback_lines_to_serialize,
             styled_underlines,
             serialization_interval,
+            remote_attach_address,
+            remote_attach_token,
+            remote_attach_cert,
+            remote_attach_key,
+            auto_snapshot_history,
+            workspace,
+            idle_session_timeout,
+            resurrectable_session_ttl,
+            max_resurrectable_sessions,
+            diff_rendering,
+            local_echo_prediction,
         })
     }
 }
@@ -1785,6 +1908,64 @@ impl Config {
     }
 }
 
+impl Workspaces {
+    pub fn from_kdl(kdl_workspaces: &str, base_config: &Config) -> Result<Self, ConfigError> {
+        let kdl_workspaces: KdlDocument = kdl_workspaces.parse()?;
+        let mut workspaces = BTreeMap::new();
+        for kdl_workspace in kdl_workspaces.nodes() {
+            if kdl_name!(kdl_workspace) != "workspace" {
+                return Err(kdl_parsing_error!(
+                    format!(
+                        "Unknown node: {}, expected a workspace",
+                        kdl_name!(kdl_workspace)
+                    ),
+                    kdl_workspace
+                ));
+            }
+            let name = kdl_first_entry_as_string!(kdl_workspace)
+                .ok_or_else(|| {
+                    kdl_parsing_error!("A workspace must have a name".into(), kdl_workspace)
+                })?
+                .to_owned();
+            if workspaces.contains_key(&name) {
+                return Err(kdl_parsing_error!(
+                    format!("Duplicate workspace: {}", name),
+                    kdl_workspace
+                ));
+            }
+            let mut workspace = Workspace {
+                name: name.clone(),
+                ..Default::default()
+            };
+            if let Some(kdl_workspace_settings) = kdl_workspace.children() {
+                workspace.default_session = kdl_property_first_arg_as_string_or_error!(
+                    kdl_workspace_settings,
+                    "default_session"
+                )
+                .map(|(default_session, _entry)| default_session.to_owned());
+                workspace.layout =
+                    kdl_property_first_arg_as_string_or_error!(kdl_workspace_settings, "layout")
+                        .map(|(layout, _entry)| PathBuf::from(layout));
+                workspace.cwd =
+                    kdl_property_first_arg_as_string_or_error!(kdl_workspace_settings, "cwd")
+                        .map(|(cwd, _entry)| PathBuf::from(cwd));
+                if let Some(kdl_env) = kdl_workspace_settings.get("env") {
+                    workspace.env = EnvironmentVariables::from_kdl(kdl_env)?;
+                }
+                if let Some(kdl_keybinds) = kdl_workspace_settings.get("keybinds") {
+                    workspace.keybinds = Some(Keybinds::from_kdl(
+                        kdl_keybinds,
+                        base_config.keybinds.clone(),
+                        &base_config.options,
+                    )?);
+                }
+            }
+            workspaces.insert(name, workspace);
+        }
+        Ok(Workspaces { workspaces })
+    }
+}
+
 impl PluginsConfig {
     pub fn from_kdl(kdl_plugin_config: &KdlNode) -> Result<Self, ConfigError> {
         let mut plugins: HashMap<PluginTag, PluginConfig> = HashMap::new();
@@ -1826,9 +2007,16 @@ impl UiConfig {
                     .unwrap_or(false);
             let hide_session_name =
                 kdl_get_child_entry_bool_value!(pane_frames, "hide_session_name").unwrap_or(false);
+            let show_foreground_process =
+                kdl_get_child_entry_bool_value!(pane_frames, "show_foreground_process")
+                    .unwrap_or(false);
+            let show_git_branch =
+                kdl_get_child_entry_bool_value!(pane_frames, "show_git_branch").unwrap_or(false);
             let frame_config = FrameConfig {
                 rounded_corners,
                 hide_session_name,
+                show_foreground_process,
+                show_git_branch,
             };
             ui_config.pane_frames = frame_config;
         }
@@ -1986,6 +2174,11 @@ impl SessionInfo {
             .and_then(|p| p.children())
             .map(|p| PaneManifest::decode_from_kdl(p))
             .ok_or("Failed to parse panes")?;
+        let workspace = kdl_document
+            .get("workspace")
+            .and_then(|n| n.entries().iter().next())
+            .and_then(|e| e.value().as_string())
+            .map(|s| s.to_owned());
         let is_current_session = name == current_session_name;
         Ok(SessionInfo {
             name,
@@ -1993,6 +2186,7 @@ impl SessionInfo {
             panes,
             connected_clients,
             is_current_session,
+            workspace,
         })
     }
     pub fn to_string(&self) -> String {
@@ -2021,6 +2215,11 @@ impl SessionInfo {
         kdl_document.nodes_mut().push(tabs);
         kdl_document.nodes_mut().push(panes);
         kdl_document.nodes_mut().push(connected_clients);
+        if let Some(workspace_name) = &self.workspace {
+            let mut workspace = KdlNode::new("workspace");
+            workspace.push(workspace_name.clone());
+            kdl_document.nodes_mut().push(workspace);
+        }
         kdl_document.fmt();
         kdl_document.to_string()
     }
@@ -2087,6 +2286,12 @@ impl TabInfo {
         }
         let active_swap_layout_name = optional_string_node!("active_swap_layout_name");
         let is_swap_layout_dirty = bool_node!("is_swap_layout_dirty");
+        // optional so that session info written by older versions can still be read
+        let is_requesting_attention = kdl_document
+            .get("is_requesting_attention")
+            .and_then(|n| n.entries().iter().next())
+            .and_then(|e| e.value().as_bool())
+            .unwrap_or(false);
         Ok(TabInfo {
             position,
             name,
@@ -2098,6 +2303,7 @@ impl TabInfo {
             other_focused_clients,
             active_swap_layout_name,
             is_swap_layout_dirty,
+            is_requesting_attention,
         })
     }
     pub fn encode_to_kdl(&self) -> KdlDocument {
@@ -2149,6 +2355,12 @@ impl TabInfo {
         is_swap_layout_dirty.push(self.is_swap_layout_dirty);
         kdl_doucment.nodes_mut().push(is_swap_layout_dirty);
 
+        if self.is_requesting_attention {
+            let mut is_requesting_attention = KdlNode::new("is_requesting_attention");
+            is_requesting_attention.push(true);
+            kdl_doucment.nodes_mut().push(is_requesting_attention);
+        }
+
         kdl_doucment
     }
 }
@@ -2276,6 +2488,12 @@ impl PaneInfo {
         let terminal_command = optional_string_node!("terminal_command");
         let plugin_url = optional_string_node!("plugin_url");
         let is_selectable = bool_node!("is_selectable");
+        let restart_count = optional_int_node!("restart_count", usize).unwrap_or(0);
+        let last_exit_status = optional_int_node!("last_exit_status", i32);
+        let process_info = kdl_document
+            .get("process_info")
+            .and_then(|n| n.children())
+            .map(PaneProcessInfo::decode_from_kdl);
 
         let pane_info = PaneInfo {
             id,
@@ -2300,6 +2518,9 @@ impl PaneInfo {
             terminal_command,
             plugin_url,
             is_selectable,
+            restart_count,
+            last_exit_status,
+            process_info,
         };
         Ok((tab_position, pane_info))
     }
@@ -2360,10 +2581,89 @@ impl PaneInfo {
             string_node!("plugin_url", plugin_url.to_string());
         }
         bool_node!("is_selectable", self.is_selectable);
+        if self.restart_count > 0 {
+            int_node!("restart_count", self.restart_count);
+        }
+        if let Some(last_exit_status) = self.last_exit_status {
+            int_node!("last_exit_status", last_exit_status);
+        }
+        if let Some(process_info) = &self.process_info {
+            let mut process_info_node = KdlNode::new("process_info");
+            process_info_node.set_children(process_info.encode_to_kdl());
+            kdl_doucment.nodes_mut().push(process_info_node);
+        }
         kdl_doucment
     }
 }
 
+impl PaneProcessInfo {
+    pub fn decode_from_kdl(kdl_document: &KdlDocument) -> Self {
+        let foreground_process = kdl_document
+            .get("foreground_process")
+            .and_then(|n| n.entries().iter().next())
+            .and_then(|e| e.value().as_string())
+            .map(|s| s.to_owned());
+        let cpu_usage = kdl_document
+            .get("cpu_usage")
+            .and_then(|n| n.entries().iter().next())
+            .and_then(|e| e.value().as_i64())
+            .unwrap_or(0) as u32;
+        let memory_usage = kdl_document
+            .get("memory_usage")
+            .and_then(|n| n.entries().iter().next())
+            .and_then(|e| e.value().as_i64())
+            .unwrap_or(0) as u64;
+        let child_commands = kdl_document
+            .get("child_commands")
+            .map(|n| {
+                n.entries()
+                    .iter()
+                    .filter_map(|e| e.value().as_string().map(|s| s.to_owned()))
+                    .collect()
+            })
+            .unwrap_or_default();
+        let git_branch = kdl_document
+            .get("git_branch")
+            .and_then(|n| n.entries().iter().next())
+            .and_then(|e| e.value().as_string())
+            .map(|s| s.to_owned());
+        PaneProcessInfo {
+            foreground_process,
+            cpu_usage,
+            memory_usage,
+            child_commands,
+            git_branch,
+        }
+    }
+    pub fn encode_to_kdl(&self) -> KdlDocument {
+        let mut kdl_document = KdlDocument::new();
+        if let Some(foreground_process) = &self.foreground_process {
+            let mut foreground_process_node = KdlNode::new("foreground_process");
+            foreground_process_node.push(foreground_process.to_string());
+            kdl_document.nodes_mut().push(foreground_process_node);
+        }
+        let mut cpu_usage = KdlNode::new("cpu_usage");
+        cpu_usage.push(self.cpu_usage as i64);
+        kdl_document.nodes_mut().push(cpu_usage);
+        let mut memory_usage = KdlNode::new("memory_usage");
+        memory_usage.push(self.memory_usage as i64);
+        kdl_document.nodes_mut().push(memory_usage);
+        if !self.child_commands.is_empty() {
+            let mut child_commands = KdlNode::new("child_commands");
+            for child_command in &self.child_commands {
+                child_commands.push(child_command.to_string());
+            }
+            kdl_document.nodes_mut().push(child_commands);
+        }
+        if let Some(git_branch) = &self.git_branch {
+            let mut git_branch_node = KdlNode::new("git_branch");
+            git_branch_node.push(git_branch.to_string());
+            kdl_document.nodes_mut().push(git_branch_node);
+        }
+        kdl_document
+    }
+}
+
 pub fn parse_plugin_user_configuration(
     plugin_block: &KdlNode,
 ) -> Result<BTreeMap<String, String>, ConfigError> {
@@ -2447,6 +2747,14 @@ fn serialize_and_deserialize_session_info_with_data() {
             terminal_command: Some("foo".to_owned()),
             plugin_url: None,
             is_selectable: true,
+            process_info: Some(PaneProcessInfo {
+                foreground_process: Some("vim".to_owned()),
+                cpu_usage: 12,
+                memory_usage: 4096,
+                child_commands: vec!["vim Cargo.toml".to_owned()],
+                git_branch: Some("main".to_owned()),
+            }),
+            ..Default::default()
         },
         PaneInfo {
             id: 1,
@@ -2471,6 +2779,7 @@ fn serialize_and_deserialize_session_info_with_data() {
             terminal_command: None,
             plugin_url: Some("i_am_a_fake_plugin".to_owned()),
             is_selectable: true,
+            ..Default::default()
         },
     ];
     let mut panes = HashMap::new();
@@ -2489,6 +2798,7 @@ fn serialize_and_deserialize_session_info_with_data() {
                 other_focused_clients: vec![2, 3],
                 active_swap_layout_name: Some("BASE".to_owned()),
                 is_swap_layout_dirty: true,
+                is_requesting_attention: false,
             },
             TabInfo {
                 position: 1,
@@ -2501,11 +2811,13 @@ fn serialize_and_deserialize_session_info_with_data() {
                 other_focused_clients: vec![2, 3],
                 active_swap_layout_name: None,
                 is_swap_layout_dirty: false,
+                is_requesting_attention: false,
             },
         ],
         panes: PaneManifest { panes },
         connected_clients: 2,
         is_current_session: false,
+        workspace: Some("my workspace".to_owned()),
     };
     let serialized = session_info.to_string();
     let deserealized = SessionInfo::from_string(&serialized, "not this session").unwrap();
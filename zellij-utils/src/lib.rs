@@ -26,8 +26,8 @@ pub mod logging; // Requires log4rs
 #[cfg(not(target_family = "wasm"))]
 pub use ::{
     anyhow, async_channel, async_std, clap, common_path, humantime, interprocess, lazy_static,
-    libc, miette, nix, notify_debouncer_full, regex, serde, signal_hook, surf, tempfile, termwiz,
-    url, uuid, vte,
+    libc, miette, nix, notify_debouncer_full, regex, serde, serde_json, signal_hook, surf,
+    tempfile, termwiz, url, uuid, vte,
 };
 
 pub use ::prost;
@@ -7,16 +7,17 @@ pub use super::generated_api::api::{
         PaneIdAndShouldFloat, PluginConfiguration as ProtobufPluginConfiguration,
         Position as ProtobufPosition, RunCommandAction as ProtobufRunCommandAction,
         ScrollAtPayload, SearchDirection as ProtobufSearchDirection,
-        SearchOption as ProtobufSearchOption, SwitchToModePayload, WriteCharsPayload, WritePayload,
+        SearchOption as ProtobufSearchOption, SignalPanePayload, SwitchToModePayload,
+        WriteCharsPayload, WritePayload,
     },
     input_mode::InputMode as ProtobufInputMode,
     resize::{Resize as ProtobufResize, ResizeDirection as ProtobufResizeDirection},
 };
-use crate::data::{Direction, InputMode, ResizeStrategy};
+use crate::data::{Direction, InputMode, PaneSignal, ResizeStrategy};
 use crate::errors::prelude::*;
 use crate::input::actions::Action;
 use crate::input::actions::{SearchDirection, SearchOption};
-use crate::input::command::RunCommandAction;
+use crate::input::command::{RestartPolicy, RunCommandAction};
 use crate::input::layout::{PluginUserConfiguration, RunPlugin, RunPluginLocation};
 use crate::position::Position;
 use url::Url;
@@ -24,6 +25,7 @@ use url::Url;
 use std::collections::BTreeMap;
 use std::convert::TryFrom;
 use std::path::PathBuf;
+use std::str::FromStr;
 
 impl TryFrom<ProtobufAction> for Action {
     type Error = &'static str;
@@ -672,6 +674,23 @@ impl TryFrom<ProtobufAction> for Action {
                 },
                 _ => Err("Wrong payload for Action::RenameSession"),
             },
+            Some(ProtobufActionName::SignalPane) => match protobuf_action.optional_payload {
+                Some(OptionalPayload::SignalPanePayload(payload)) => {
+                    let signal = PaneSignal::from_str(&payload.signal)
+                        .map_err(|_| "Invalid signal for Action::SignalPane")?;
+                    Ok(Action::SignalPane(payload.pane_id, signal))
+                },
+                _ => Err("Wrong payload for Action::SignalPane"),
+            },
+            Some(ProtobufActionName::MoveTab) => match protobuf_action.optional_payload {
+                Some(OptionalPayload::MoveTabPayload(move_tab_payload)) => {
+                    let direction: Direction = ProtobufResizeDirection::from_i32(move_tab_payload)
+                        .ok_or("Malformed resize direction for Action::MoveTab")?
+                        .try_into()?;
+                    Ok(Action::MoveTab(direction))
+                },
+                _ => Err("Wrong payload for Action::MoveTab"),
+            },
             _ => Err("Unknown Action"),
         }
     }
@@ -1239,19 +1258,51 @@ impl TryFrom<Action> for ProtobufAction {
                 name: ProtobufActionName::RenameSession as i32,
                 optional_payload: Some(OptionalPayload::RenameSessionPayload(session_name)),
             }),
+            Action::SignalPane(pane_id, signal) => Ok(ProtobufAction {
+                name: ProtobufActionName::SignalPane as i32,
+                optional_payload: Some(OptionalPayload::SignalPanePayload(SignalPanePayload {
+                    pane_id,
+                    signal: signal.to_string(),
+                })),
+            }),
+            Action::MoveTab(direction) => {
+                let direction: ProtobufResizeDirection = direction.try_into()?;
+                Ok(ProtobufAction {
+                    name: ProtobufActionName::MoveTab as i32,
+                    optional_payload: Some(OptionalPayload::MoveTabPayload(direction as i32)),
+                })
+            },
             Action::NoOp
             | Action::Confirm
             | Action::NewInPlacePane(..)
             | Action::NewInPlacePluginPane(..)
             | Action::Deny
             | Action::Copy
+            | Action::MouseMotion(..)
             | Action::DumpLayout
             | Action::CliPipe { .. }
+            | Action::ListClients
+            | Action::KickClient(..)
+            | Action::ListPanes(..)
+            | Action::ListTabs(..)
+            | Action::Subscribe { .. }
+            | Action::SaveSnapshot(..)
+            | Action::Targeted(..)
             | Action::SkipConfirm(..) => Err("Unsupported action"),
         }
     }
 }
 
+impl Action {
+    /// Whether this action arrives unchanged when a plugin runs it: some actions cannot be sent
+    /// to the host at all and others (eg. a new tab with a layout) lose their payload on the way
+    pub fn can_be_run_by_plugins(&self) -> bool {
+        ProtobufAction::try_from(self.clone())
+            .and_then(Action::try_from)
+            .map_or(false, |action| &action == self)
+    }
+}
+
 impl TryFrom<ProtobufSearchOption> for SearchOption {
     type Error = &'static str;
     fn try_from(protobuf_search_option: ProtobufSearchOption) -> Result<Self, &'static str> {
@@ -1308,6 +1359,19 @@ impl TryFrom<ProtobufRunCommandAction> for RunCommandAction {
             .and_then(|d| d.try_into().ok());
         let hold_on_close = protobuf_run_command_action.hold_on_close;
         let hold_on_start = protobuf_run_command_action.hold_on_start;
+        let restart_policy = match protobuf_run_command_action.restart_policy {
+            Some(restart_policy) => RestartPolicy::from_str(&restart_policy)
+                .map_err(|_| "Failed to parse restart policy")?,
+            None => RestartPolicy::default(),
+        };
+        let max_restarts = protobuf_run_command_action.max_restarts.map(|m| m as usize);
+        let restart_backoff_ms = protobuf_run_command_action.restart_backoff_ms;
+        let env = protobuf_run_command_action
+            .env
+            .into_iter()
+            .map(|e| (e.name, e.value))
+            .collect();
+        let env_file = protobuf_run_command_action.env_file.map(PathBuf::from);
         Ok(RunCommandAction {
             command,
             args,
@@ -1315,6 +1379,11 @@ impl TryFrom<ProtobufRunCommandAction> for RunCommandAction {
             direction,
             hold_on_close,
             hold_on_start,
+            restart_policy,
+            max_restarts,
+            restart_backoff_ms,
+            env,
+            env_file,
         })
     }
 }
@@ -1331,6 +1400,18 @@ impl TryFrom<RunCommandAction> for ProtobufRunCommandAction {
         });
         let hold_on_close = run_command_action.hold_on_close;
         let hold_on_start = run_command_action.hold_on_start;
+        let restart_policy = match run_command_action.restart_policy {
+            RestartPolicy::Never => None,
+            restart_policy => Some(restart_policy.to_string()),
+        };
+        let max_restarts = run_command_action.max_restarts.map(|m| m as u32);
+        let restart_backoff_ms = run_command_action.restart_backoff_ms;
+        let env = run_command_action
+            .env
+            .into_iter()
+            .map(|(name, value)| ProtobufNameAndValue { name, value })
+            .collect();
+        let env_file = run_command_action.env_file.map(|e| e.display().to_string());
         Ok(ProtobufRunCommandAction {
             command,
             args,
@@ -1339,6 +1420,11 @@ impl TryFrom<RunCommandAction> for ProtobufRunCommandAction {
             hold_on_close,
             hold_on_start,
             pane_name: None,
+            restart_policy,
+            max_restarts,
+            restart_backoff_ms,
+            env,
+            env_file,
         })
     }
 }
@@ -4,8 +4,9 @@ pub use super::generated_api::api::{
         event::Payload as ProtobufEventPayload, CopyDestination as ProtobufCopyDestination,
         Event as ProtobufEvent, EventNameList as ProtobufEventNameList,
         EventType as ProtobufEventType, InputModeKeybinds as ProtobufInputModeKeybinds,
-        KeyBind as ProtobufKeyBind, ModeUpdatePayload as ProtobufModeUpdatePayload,
-        PaneInfo as ProtobufPaneInfo, PaneManifest as ProtobufPaneManifest,
+        KeyBind as ProtobufKeyBind, LayoutInfo as ProtobufLayoutInfo,
+        ModeUpdatePayload as ProtobufModeUpdatePayload, PaneInfo as ProtobufPaneInfo,
+        PaneManifest as ProtobufPaneManifest, PaneProcessInfo as ProtobufPaneProcessInfo,
         ResurrectableSession as ProtobufResurrectableSession,
         SessionManifest as ProtobufSessionManifest, TabInfo as ProtobufTabInfo, *,
     },
@@ -14,8 +15,9 @@ pub use super::generated_api::api::{
     style::Style as ProtobufStyle,
 };
 use crate::data::{
-    CopyDestination, Event, EventType, InputMode, Key, ModeInfo, Mouse, PaneInfo, PaneManifest,
-    PermissionStatus, PluginCapabilities, SessionInfo, Style, TabInfo,
+    CopyDestination, Event, EventType, InputMode, Key, LayoutInfo, ModeInfo, Mouse, PaneInfo,
+    PaneManifest, PaneProcessInfo, PermissionStatus, PluginCapabilities, SessionInfo, Style,
+    TabInfo,
 };
 
 use crate::errors::prelude::*;
@@ -228,6 +230,46 @@ impl TryFrom<ProtobufEvent> for Event {
                 },
                 _ => Err("Malformed payload for the WebRequestResult Event"),
             },
+            Some(ProtobufEventType::ComponentClicked) => match protobuf_event.payload {
+                Some(ProtobufEventPayload::ComponentEventPayload(component_event_payload)) => {
+                    Ok(Event::ComponentClicked {
+                        id: component_event_payload.id,
+                        row: component_event_payload.row as usize,
+                        col: component_event_payload.col as usize,
+                    })
+                },
+                _ => Err("Malformed payload for the ComponentClicked Event"),
+            },
+            Some(ProtobufEventType::ComponentHovered) => match protobuf_event.payload {
+                Some(ProtobufEventPayload::ComponentEventPayload(component_event_payload)) => {
+                    Ok(Event::ComponentHovered {
+                        id: component_event_payload.id,
+                        row: component_event_payload.row as usize,
+                        col: component_event_payload.col as usize,
+                    })
+                },
+                _ => Err("Malformed payload for the ComponentHovered Event"),
+            },
+            Some(ProtobufEventType::AvailableLayoutInfo) => match protobuf_event.payload {
+                Some(ProtobufEventPayload::AvailableLayoutInfoPayload(
+                    available_layout_info_payload,
+                )) => Ok(Event::AvailableLayoutInfo(
+                    available_layout_info_payload
+                        .available_layouts
+                        .into_iter()
+                        .map(|l| l.into())
+                        .collect(),
+                )),
+                _ => Err("Malformed payload for the AvailableLayoutInfo Event"),
+            },
+            Some(ProtobufEventType::AvailablePluginAliases) => match protobuf_event.payload {
+                Some(ProtobufEventPayload::AvailablePluginAliasesPayload(
+                    available_plugin_aliases_payload,
+                )) => Ok(Event::AvailablePluginAliases(
+                    available_plugin_aliases_payload.aliases,
+                )),
+                _ => Err("Malformed payload for the AvailablePluginAliases Event"),
+            },
             None => Err("Unknown Protobuf Event"),
         }
     }
@@ -425,6 +467,43 @@ impl TryFrom<Event> for ProtobufEvent {
                     )),
                 })
             },
+            Event::ComponentClicked { id, row, col } => Ok(ProtobufEvent {
+                name: ProtobufEventType::ComponentClicked as i32,
+                payload: Some(event::Payload::ComponentEventPayload(
+                    ComponentEventPayload {
+                        id,
+                        row: row as u32,
+                        col: col as u32,
+                    },
+                )),
+            }),
+            Event::ComponentHovered { id, row, col } => Ok(ProtobufEvent {
+                name: ProtobufEventType::ComponentHovered as i32,
+                payload: Some(event::Payload::ComponentEventPayload(
+                    ComponentEventPayload {
+                        id,
+                        row: row as u32,
+                        col: col as u32,
+                    },
+                )),
+            }),
+            Event::AvailableLayoutInfo(available_layouts) => Ok(ProtobufEvent {
+                name: ProtobufEventType::AvailableLayoutInfo as i32,
+                payload: Some(event::Payload::AvailableLayoutInfoPayload(
+                    AvailableLayoutInfoPayload {
+                        available_layouts: available_layouts
+                            .into_iter()
+                            .map(|l| l.into())
+                            .collect(),
+                    },
+                )),
+            }),
+            Event::AvailablePluginAliases(aliases) => Ok(ProtobufEvent {
+                name: ProtobufEventType::AvailablePluginAliases as i32,
+                payload: Some(event::Payload::AvailablePluginAliasesPayload(
+                    AvailablePluginAliasesPayload { aliases },
+                )),
+            }),
         }
     }
 }
@@ -453,6 +532,7 @@ impl TryFrom<SessionInfo> for ProtobufSessionManifest {
                 .collect(),
             connected_clients: session_info.connected_clients as u32,
             is_current_session: session_info.is_current_session,
+            workspace: session_info.workspace,
         })
     }
 }
@@ -485,6 +565,7 @@ impl TryFrom<ProtobufSessionManifest> for SessionInfo {
             panes,
             connected_clients: protobuf_session_manifest.connected_clients as usize,
             is_current_session: protobuf_session_manifest.is_current_session,
+            workspace: protobuf_session_manifest.workspace,
         })
     }
 }
@@ -543,6 +624,14 @@ impl TryFrom<MouseEventPayload> for Mouse {
                     _ => Err("Malformed payload for mouse right click"),
                 }
             },
+            Some(MouseEventName::MouseMiddleClick) => {
+                match mouse_event_payload.mouse_event_payload {
+                    Some(mouse_event_payload::MouseEventPayload::Position(position)) => Ok(
+                        Mouse::MiddleClick(position.line as isize, position.column as usize),
+                    ),
+                    _ => Err("Malformed payload for mouse middle click"),
+                }
+            },
             Some(MouseEventName::MouseHold) => match mouse_event_payload.mouse_event_payload {
                 Some(mouse_event_payload::MouseEventPayload::Position(position)) => Ok(
                     Mouse::Hold(position.line as isize, position.column as usize),
@@ -594,6 +683,15 @@ impl TryFrom<Mouse> for MouseEventPayload {
                     },
                 )),
             }),
+            Mouse::MiddleClick(line, column) => Ok(MouseEventPayload {
+                mouse_event_name: MouseEventName::MouseMiddleClick as i32,
+                mouse_event_payload: Some(mouse_event_payload::MouseEventPayload::Position(
+                    ProtobufPosition {
+                        line: line as i64,
+                        column: column as i64,
+                    },
+                )),
+            }),
             Mouse::Hold(line, column) => Ok(MouseEventPayload {
                 mouse_event_name: MouseEventName::MouseHold as i32,
                 mouse_event_payload: Some(mouse_event_payload::MouseEventPayload::Position(
@@ -644,6 +742,9 @@ impl TryFrom<ProtobufPaneInfo> for PaneInfo {
             terminal_command: protobuf_pane_info.terminal_command,
             plugin_url: protobuf_pane_info.plugin_url,
             is_selectable: protobuf_pane_info.is_selectable,
+            restart_count: protobuf_pane_info.restart_count as usize,
+            last_exit_status: protobuf_pane_info.last_exit_status,
+            process_info: protobuf_pane_info.process_info.map(|p| p.into()),
         })
     }
 }
@@ -679,10 +780,37 @@ impl TryFrom<PaneInfo> for ProtobufPaneInfo {
             terminal_command: pane_info.terminal_command,
             plugin_url: pane_info.plugin_url,
             is_selectable: pane_info.is_selectable,
+            restart_count: pane_info.restart_count as u32,
+            last_exit_status: pane_info.last_exit_status,
+            process_info: pane_info.process_info.map(|p| p.into()),
         })
     }
 }
 
+impl From<ProtobufPaneProcessInfo> for PaneProcessInfo {
+    fn from(protobuf_pane_process_info: ProtobufPaneProcessInfo) -> Self {
+        PaneProcessInfo {
+            foreground_process: protobuf_pane_process_info.foreground_process,
+            cpu_usage: protobuf_pane_process_info.cpu_usage,
+            memory_usage: protobuf_pane_process_info.memory_usage,
+            child_commands: protobuf_pane_process_info.child_commands,
+            git_branch: protobuf_pane_process_info.git_branch,
+        }
+    }
+}
+
+impl From<PaneProcessInfo> for ProtobufPaneProcessInfo {
+    fn from(pane_process_info: PaneProcessInfo) -> Self {
+        ProtobufPaneProcessInfo {
+            foreground_process: pane_process_info.foreground_process,
+            cpu_usage: pane_process_info.cpu_usage,
+            memory_usage: pane_process_info.memory_usage,
+            child_commands: pane_process_info.child_commands,
+            git_branch: pane_process_info.git_branch,
+        }
+    }
+}
+
 impl TryFrom<ProtobufTabInfo> for TabInfo {
     type Error = &'static str;
     fn try_from(protobuf_tab_info: ProtobufTabInfo) -> Result<Self, &'static str> {
@@ -701,6 +829,7 @@ impl TryFrom<ProtobufTabInfo> for TabInfo {
                 .collect(),
             active_swap_layout_name: protobuf_tab_info.active_swap_layout_name,
             is_swap_layout_dirty: protobuf_tab_info.is_swap_layout_dirty,
+            is_requesting_attention: protobuf_tab_info.is_requesting_attention,
         })
     }
 }
@@ -723,6 +852,7 @@ impl TryFrom<TabInfo> for ProtobufTabInfo {
                 .collect(),
             active_swap_layout_name: tab_info.active_swap_layout_name,
             is_swap_layout_dirty: tab_info.is_swap_layout_dirty,
+            is_requesting_attention: tab_info.is_requesting_attention,
         })
     }
 }
@@ -872,6 +1002,10 @@ impl TryFrom<ProtobufEventType> for EventType {
             ProtobufEventType::SessionUpdate => EventType::SessionUpdate,
             ProtobufEventType::RunCommandResult => EventType::RunCommandResult,
             ProtobufEventType::WebRequestResult => EventType::WebRequestResult,
+            ProtobufEventType::ComponentClicked => EventType::ComponentClicked,
+            ProtobufEventType::ComponentHovered => EventType::ComponentHovered,
+            ProtobufEventType::AvailableLayoutInfo => EventType::AvailableLayoutInfo,
+            ProtobufEventType::AvailablePluginAliases => EventType::AvailablePluginAliases,
         })
     }
 }
@@ -899,10 +1033,34 @@ impl TryFrom<EventType> for ProtobufEventType {
             EventType::SessionUpdate => ProtobufEventType::SessionUpdate,
             EventType::RunCommandResult => ProtobufEventType::RunCommandResult,
             EventType::WebRequestResult => ProtobufEventType::WebRequestResult,
+            EventType::ComponentClicked => ProtobufEventType::ComponentClicked,
+            EventType::ComponentHovered => ProtobufEventType::ComponentHovered,
+            EventType::AvailableLayoutInfo => ProtobufEventType::AvailableLayoutInfo,
+            EventType::AvailablePluginAliases => ProtobufEventType::AvailablePluginAliases,
         })
     }
 }
 
+impl From<ProtobufLayoutInfo> for LayoutInfo {
+    fn from(protobuf_layout_info: ProtobufLayoutInfo) -> LayoutInfo {
+        LayoutInfo {
+            name: protobuf_layout_info.name,
+            is_builtin: protobuf_layout_info.is_builtin,
+            stringified_layout: protobuf_layout_info.stringified_layout,
+        }
+    }
+}
+
+impl From<LayoutInfo> for ProtobufLayoutInfo {
+    fn from(layout_info: LayoutInfo) -> ProtobufLayoutInfo {
+        ProtobufLayoutInfo {
+            name: layout_info.name,
+            is_builtin: layout_info.is_builtin,
+            stringified_layout: layout_info.stringified_layout,
+        }
+    }
+}
+
 impl From<ProtobufResurrectableSession> for (String, Duration) {
     fn from(protobuf_resurrectable_session: ProtobufResurrectableSession) -> (String, Duration) {
         (
@@ -995,6 +1153,8 @@ fn serialize_mode_update_event_with_non_default_values() {
             },
             rounded_corners: true,
             hide_session_name: false,
+            show_foreground_process: false,
+            show_git_branch: false,
         },
         capabilities: PluginCapabilities { arrow_fonts: false },
         session_name: Some("my awesome test session".to_owned()),
@@ -1040,6 +1200,7 @@ fn serialize_tab_update_event_with_non_default_values() {
             other_focused_clients: vec![2, 3, 4],
             active_swap_layout_name: Some("my cool swap layout".to_owned()),
             is_swap_layout_dirty: false,
+            is_requesting_attention: false,
         },
         TabInfo {
             position: 1,
@@ -1052,6 +1213,7 @@ fn serialize_tab_update_event_with_non_default_values() {
             other_focused_clients: vec![1, 5, 111],
             active_swap_layout_name: None,
             is_swap_layout_dirty: true,
+            is_requesting_attention: true,
         },
         TabInfo::default(),
     ]);
@@ -1310,6 +1472,7 @@ fn serialize_session_update_event_with_non_default_values() {
             other_focused_clients: vec![2, 3, 4],
             active_swap_layout_name: Some("my cool swap layout".to_owned()),
             is_swap_layout_dirty: false,
+            is_requesting_attention: false,
         },
         TabInfo {
             position: 1,
@@ -1322,6 +1485,7 @@ fn serialize_session_update_event_with_non_default_values() {
             other_focused_clients: vec![1, 5, 111],
             active_swap_layout_name: None,
             is_swap_layout_dirty: true,
+            is_requesting_attention: true,
         },
         TabInfo::default(),
     ];
@@ -1350,6 +1514,14 @@ fn serialize_session_update_event_with_non_default_values() {
             terminal_command: Some("foo".to_owned()),
             plugin_url: None,
             is_selectable: true,
+            process_info: Some(PaneProcessInfo {
+                foreground_process: Some("vim".to_owned()),
+                cpu_usage: 12,
+                memory_usage: 4096,
+                child_commands: vec!["vim Cargo.toml".to_owned()],
+                git_branch: Some("main".to_owned()),
+            }),
+            ..Default::default()
         },
         PaneInfo {
             id: 1,
@@ -1374,6 +1546,7 @@ fn serialize_session_update_event_with_non_default_values() {
             terminal_command: None,
             plugin_url: Some("i_am_a_fake_plugin".to_owned()),
             is_selectable: true,
+            ..Default::default()
         },
     ];
     panes.insert(0, panes_list);
@@ -1383,6 +1556,7 @@ fn serialize_session_update_event_with_non_default_values() {
         panes: PaneManifest { panes },
         connected_clients: 2,
         is_current_session: true,
+        workspace: Some("my workspace".to_owned()),
     };
     let session_info_2 = SessionInfo {
         name: "session 2".to_owned(),
@@ -1392,6 +1566,7 @@ fn serialize_session_update_event_with_non_default_values() {
         },
         connected_clients: 0,
         is_current_session: false,
+        workspace: None,
     };
     let session_infos = vec![session_info_1, session_info_2];
     let resurrectable_sessions = vec![];
@@ -1407,3 +1582,67 @@ fn serialize_session_update_event_with_non_default_values() {
         "Event properly serialized/deserialized without change"
     );
 }
+
+#[test]
+fn serialize_component_clicked_event() {
+    use prost::Message;
+    let component_clicked_event = Event::ComponentClicked {
+        id: "open_button".to_owned(),
+        row: 1,
+        col: 3,
+    };
+    let protobuf_event: ProtobufEvent = component_clicked_event.clone().try_into().unwrap();
+    let serialized_protobuf_event = protobuf_event.encode_to_vec();
+    let deserialized_protobuf_event: ProtobufEvent =
+        Message::decode(serialized_protobuf_event.as_slice()).unwrap();
+    let deserialized_event: Event = deserialized_protobuf_event.try_into().unwrap();
+    assert_eq!(
+        component_clicked_event, deserialized_event,
+        "Event properly serialized/deserialized without change"
+    );
+}
+
+#[test]
+fn serialize_available_layout_info_event() {
+    use prost::Message;
+    let available_layout_info_event = Event::AvailableLayoutInfo(vec![
+        LayoutInfo {
+            name: "compact".to_owned(),
+            is_builtin: true,
+            stringified_layout: "layout {\n    pane\n}\n".to_owned(),
+        },
+        LayoutInfo {
+            name: "my-project".to_owned(),
+            is_builtin: false,
+            stringified_layout: "layout {\n    pane split_direction=\"vertical\" {\n        pane\n        pane\n    }\n}\n".to_owned(),
+        },
+    ]);
+    let protobuf_event: ProtobufEvent = available_layout_info_event.clone().try_into().unwrap();
+    let serialized_protobuf_event = protobuf_event.encode_to_vec();
+    let deserialized_protobuf_event: ProtobufEvent =
+        Message::decode(serialized_protobuf_event.as_slice()).unwrap();
+    let deserialized_event: Event = deserialized_protobuf_event.try_into().unwrap();
+    assert_eq!(
+        available_layout_info_event, deserialized_event,
+        "Event properly serialized/deserialized without change"
+    );
+}
+
+#[test]
+fn serialize_available_plugin_aliases_event() {
+    use prost::Message;
+    let available_plugin_aliases_event = Event::AvailablePluginAliases(vec![
+        "command-palette".to_owned(),
+        "session-manager".to_owned(),
+        "tab-bar".to_owned(),
+    ]);
+    let protobuf_event: ProtobufEvent = available_plugin_aliases_event.clone().try_into().unwrap();
+    let serialized_protobuf_event = protobuf_event.encode_to_vec();
+    let deserialized_protobuf_event: ProtobufEvent =
+        Message::decode(serialized_protobuf_event.as_slice()).unwrap();
+    let deserialized_event: Event = deserialized_protobuf_event.try_into().unwrap();
+    assert_eq!(
+        available_plugin_aliases_event, deserialized_event,
+        "Event properly serialized/deserialized without change"
+    );
+}
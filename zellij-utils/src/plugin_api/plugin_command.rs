@@ -8,22 +8,24 @@ pub use super::generated_api::api::{
         MovePayload, NewPluginArgs as ProtobufNewPluginArgs, OpenCommandPanePayload,
         OpenFilePayload, PaneId as ProtobufPaneId, PaneType as ProtobufPaneType,
         PluginCommand as ProtobufPluginCommand, PluginMessagePayload,
-        RequestPluginPermissionPayload, ResizePayload, RunCommandPayload, SetTimeoutPayload,
-        SubscribePayload, SwitchSessionPayload, SwitchTabToPayload, UnsubscribePayload,
-        WebRequestPayload,
+        RequestPluginPermissionPayload, ResizePayload,
+        ResurrectableSessionLayout as ProtobufResurrectableSessionLayout, RunCommandPayload,
+        SetTimeoutPayload, SignalTerminalPanePayload, SubscribePayload, SwitchSessionPayload,
+        SwitchTabToPayload, UnsubscribePayload, WebRequestPayload,
     },
     plugin_permission::PermissionType as ProtobufPermissionType,
     resize::ResizeAction as ProtobufResizeAction,
 };
 
 use crate::data::{
-    ConnectToSession, HttpVerb, MessageToPlugin, NewPluginArgs, PaneId, PermissionType,
+    ConnectToSession, HttpVerb, MessageToPlugin, NewPluginArgs, PaneId, PaneSignal, PermissionType,
     PluginCommand,
 };
 
 use std::collections::BTreeMap;
 use std::convert::TryFrom;
 use std::path::PathBuf;
+use std::str::FromStr;
 
 impl Into<HttpVerb> for ProtobufHttpVerb {
     fn into(self) -> HttpVerb {
@@ -574,6 +576,13 @@ impl TryFrom<ProtobufPluginCommand> for PluginCommand {
                         name: payload.name,
                         tab_position: payload.tab_position.map(|p| p as usize),
                         pane_id,
+                        layout: payload.layout,
+                        cwd: payload.cwd.map(PathBuf::from),
+                        env: payload
+                            .env_variables
+                            .into_iter()
+                            .map(|e| (e.name, e.value))
+                            .collect(),
                     }))
                 },
                 _ => Err("Mismatched payload for SwitchSession"),
@@ -729,6 +738,38 @@ impl TryFrom<ProtobufPluginCommand> for PluginCommand {
                 },
                 _ => Err("Mismatched payload for PipeOutput"),
             },
+            Some(CommandName::SignalTerminalPane) => match protobuf_plugin_command.payload {
+                Some(Payload::SignalTerminalPanePayload(SignalTerminalPanePayload {
+                    pane_id,
+                    signal,
+                })) => {
+                    let signal = PaneSignal::from_str(&signal)
+                        .map_err(|_| "Invalid signal for SignalTerminalPane")?;
+                    Ok(PluginCommand::SignalTerminalPane(pane_id, signal))
+                },
+                _ => Err("Mismatched payload for SignalTerminalPane"),
+            },
+            Some(CommandName::MoveTab) => match protobuf_plugin_command.payload {
+                Some(Payload::MoveTabPayload(move_payload)) => match move_payload.direction {
+                    Some(direction) => Ok(PluginCommand::MoveTab(direction.try_into()?)),
+                    None => Err("Malformed move tab payload"),
+                },
+                _ => Err("Mismatched payload for MoveTab"),
+            },
+            Some(CommandName::RunAction) => match protobuf_plugin_command.payload {
+                Some(Payload::RunActionPayload(protobuf_action)) => {
+                    Ok(PluginCommand::RunAction(protobuf_action.try_into()?))
+                },
+                _ => Err("Mismatched payload for RunAction"),
+            },
+            Some(CommandName::GetResurrectableSessionLayout) => {
+                match protobuf_plugin_command.payload {
+                    Some(Payload::GetResurrectableSessionLayoutPayload(session_name)) => {
+                        Ok(PluginCommand::GetResurrectableSessionLayout(session_name))
+                    },
+                    _ => Err("Mismatched payload for GetResurrectableSessionLayout"),
+                }
+            },
             None => Err("Unrecognized plugin command"),
         }
     }
@@ -1082,6 +1123,13 @@ impl TryFrom<PluginCommand> for ProtobufPluginCommand {
                     tab_position: switch_to_session.tab_position.map(|t| t as u32),
                     pane_id: switch_to_session.pane_id.map(|p| p.0),
                     pane_id_is_plugin: switch_to_session.pane_id.map(|p| p.1),
+                    layout: switch_to_session.layout,
+                    cwd: switch_to_session.cwd.map(|c| c.display().to_string()),
+                    env_variables: switch_to_session
+                        .env
+                        .into_iter()
+                        .map(|(name, value)| EnvVariable { name, value })
+                        .collect(),
                 })),
             }),
             PluginCommand::OpenTerminalInPlace(cwd) => Ok(ProtobufPluginCommand {
@@ -1205,6 +1253,31 @@ impl TryFrom<PluginCommand> for ProtobufPluginCommand {
                     })),
                 })
             },
+            PluginCommand::SignalTerminalPane(pane_id, signal) => Ok(ProtobufPluginCommand {
+                name: CommandName::SignalTerminalPane as i32,
+                payload: Some(Payload::SignalTerminalPanePayload(
+                    SignalTerminalPanePayload {
+                        pane_id,
+                        signal: signal.to_string(),
+                    },
+                )),
+            }),
+            PluginCommand::MoveTab(direction) => Ok(ProtobufPluginCommand {
+                name: CommandName::MoveTab as i32,
+                payload: Some(Payload::MoveTabPayload(MovePayload {
+                    direction: Some(direction.try_into()?),
+                })),
+            }),
+            PluginCommand::RunAction(action) => Ok(ProtobufPluginCommand {
+                name: CommandName::RunAction as i32,
+                payload: Some(Payload::RunActionPayload(action.try_into()?)),
+            }),
+            PluginCommand::GetResurrectableSessionLayout(session_name) => {
+                Ok(ProtobufPluginCommand {
+                    name: CommandName::GetResurrectableSessionLayout as i32,
+                    payload: Some(Payload::GetResurrectableSessionLayoutPayload(session_name)),
+                })
+            },
         }
     }
 }
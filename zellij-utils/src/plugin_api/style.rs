@@ -18,6 +18,8 @@ impl TryFrom<ProtobufStyle> for Style {
                 .try_into()?,
             rounded_corners: protobuf_style.rounded_corners,
             hide_session_name: protobuf_style.hide_session_name,
+            show_foreground_process: protobuf_style.show_foreground_process,
+            show_git_branch: protobuf_style.show_git_branch,
         })
     }
 }
@@ -29,6 +31,8 @@ impl TryFrom<Style> for ProtobufStyle {
             palette: Some(style.colors.try_into()?),
             rounded_corners: style.rounded_corners,
             hide_session_name: style.hide_session_name,
+            show_foreground_process: style.show_foreground_process,
+            show_git_branch: style.show_git_branch,
         })
     }
 }
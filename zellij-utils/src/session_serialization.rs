@@ -7,10 +7,12 @@
 //! }
 //! ```
 //!
+use kdl::{KdlIdentifier, KdlValue};
 use std::collections::BTreeMap;
 use std::path::PathBuf;
 
 use crate::{
+    input::command::RestartPolicy,
     input::layout::PluginUserConfiguration,
     input::layout::{
         FloatingPaneLayout, Layout, PercentOrFixed, Run, SplitDirection, SplitSize,
@@ -200,11 +202,14 @@ fn kdl_string_from_tiled_pane(
         || layout.external_children_index.is_some()
         || !args.is_empty()
         || plugin.is_some()
-        || command.is_some();
+        || command.is_some()
+        || matches!(layout.run, Some(Run::Shell(_)));
     if has_child_attributes {
         kdl_string.push_str(" {\n");
         stringify_args(args, &mut kdl_string);
         stringify_start_suspended(&command, &mut kdl_string);
+        stringify_restart_policy(&layout.run, &mut kdl_string);
+        stringify_env(&layout.run, &mut kdl_string);
         stringify_plugin(plugin, plugin_config, &mut kdl_string);
         if layout.children.is_empty() && layout.external_children_index.is_some() {
             kdl_string.push_str(&indent(&"children\n", INDENT));
@@ -407,6 +412,47 @@ fn stringify_start_suspended(command: &Option<String>, kdl_string: &mut String)
     }
 }
 
+fn stringify_restart_policy(layout_run: &Option<Run>, kdl_string: &mut String) {
+    if let Some(Run::Command(run_command)) = layout_run {
+        if run_command.restart_policy != RestartPolicy::Never {
+            kdl_string.push_str(&indent(
+                &format!("restart \"{}\"\n", run_command.restart_policy),
+                INDENT,
+            ));
+            if let Some(max_restarts) = run_command.max_restarts {
+                kdl_string.push_str(&indent(&format!("max_restarts {}\n", max_restarts), INDENT));
+            }
+            if let Some(restart_backoff_ms) = run_command.restart_backoff_ms {
+                kdl_string.push_str(&indent(
+                    &format!("restart_backoff {}\n", restart_backoff_ms),
+                    INDENT,
+                ));
+            }
+        }
+    }
+}
+
+fn stringify_env(layout_run: &Option<Run>, kdl_string: &mut String) {
+    let (env, env_file) = match layout_run {
+        Some(Run::Command(run_command)) => (&run_command.env, &run_command.env_file),
+        Some(Run::Shell(run_shell)) => (&run_shell.env, &run_shell.env_file),
+        _ => return,
+    };
+    if let Some(env_file) = env_file {
+        let env_file = KdlValue::String(env_file.display().to_string());
+        kdl_string.push_str(&indent(&format!("env_file {}\n", env_file), INDENT));
+    }
+    if !env.is_empty() {
+        kdl_string.push_str(&indent("env {\n", INDENT));
+        for (name, value) in env {
+            let name = KdlIdentifier::from(name.as_str());
+            let value = KdlValue::String(value.clone());
+            kdl_string.push_str(&indent(&format!("{} {}\n", name, value), DOUBLE_INDENT));
+        }
+        kdl_string.push_str(&indent("}\n", INDENT));
+    }
+}
+
 fn stringify_global_cwd(global_cwd: &Option<PathBuf>, kdl_string: &mut String) {
     if let Some(global_cwd) = global_cwd {
         kdl_string.push_str(&indent(
@@ -566,6 +612,8 @@ fn kdl_string_from_floating_pane(
     );
     kdl_string.push_str(" {\n");
     stringify_start_suspended(&command, &mut kdl_string);
+    stringify_restart_policy(&layout.run, &mut kdl_string);
+    stringify_env(&layout.run, &mut kdl_string);
     stringify_floating_layout_attributes(&layout, &mut kdl_string);
     stringify_args(args, &mut kdl_string);
     stringify_plugin(plugin, plugin_config, &mut kdl_string);
@@ -694,6 +742,7 @@ fn get_floating_panes_layout_from_panegeoms(
                 focus: Some(m.is_focused),
                 already_running: false,
                 pane_initial_contents: m.pane_contents.clone(),
+                depends_on: None,
             }
         })
         .collect()
@@ -920,6 +969,8 @@ fn get_split_sizes(constraints: &Vec<Constraint>) -> Vec<Option<SplitSize>> {
 mod tests {
 
     use super::*;
+    use crate::input::command::RunCommand;
+    use crate::input::layout::RunShell;
     use crate::pane_size::Dimension;
     use expect_test::expect;
     use serde_json::Value;
@@ -1123,6 +1174,54 @@ mod tests {
 }"#]]
         .assert_eq(&kdl.0);
     }
+    #[test]
+    fn env_survives_a_serialization_round_trip() {
+        let env: BTreeMap<String, String> =
+            [("GREETING", "say \"hi\"\nand\\or wave"), ("PORT", "8080")]
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect();
+        let command_run = Run::Command(RunCommand {
+            command: PathBuf::from("api"),
+            env: env.clone(),
+            ..Default::default()
+        });
+        let shell_run = Run::Shell(RunShell {
+            env: env.clone(),
+            env_file: Some(PathBuf::from("my \"quoted\".env")),
+            ..Default::default()
+        });
+        let tiled_panes = PANEGEOMS_JSON[0][..2]
+            .iter()
+            .map(|pg| parse_panegeom_from_json(pg))
+            .zip([command_run.clone(), shell_run.clone()])
+            .map(|(geom, run)| PaneLayoutManifest {
+                geom,
+                run: Some(run),
+                ..Default::default()
+            })
+            .collect();
+        let global_layout_manifest = GlobalLayoutManifest {
+            tabs: vec![(
+                "Tab #1".to_owned(),
+                TabLayoutManifest {
+                    tiled_panes,
+                    ..Default::default()
+                },
+            )],
+            ..Default::default()
+        };
+        let kdl = serialize_session_layout(global_layout_manifest).unwrap();
+        let layout = Layout::from_kdl(&kdl.0, "layout_file_name".into(), None, None).unwrap();
+        let panes = &layout.tabs[0].1.children;
+        match (&panes[0].run, &panes[1].run) {
+            (Some(Run::Shell(parsed_shell)), Some(Run::Command(parsed_command))) => {
+                assert_eq!(Run::Shell(parsed_shell.clone()), shell_run);
+                assert_eq!(parsed_command.env, env);
+            },
+            runs => panic!("unexpected runs after round trip: {:?}", runs),
+        }
+    }
     // utility functions
     fn parse_panegeom_from_json(data_str: &str) -> PaneGeom {
         //
@@ -2,7 +2,7 @@
 use crate::consts::ASSET_MAP;
 use crate::input::theme::Themes;
 use crate::{
-    cli::{CliArgs, Command},
+    cli::{CliArgs, Command, SessionCommand, Sessions},
     consts::{
         FEATURES, SYSTEM_DEFAULT_CONFIG_DIR, SYSTEM_DEFAULT_DATA_DIR_PREFIX, VERSION,
         ZELLIJ_DEFAULT_THEMES, ZELLIJ_PROJ_DIR,
@@ -13,6 +13,7 @@ use crate::{
         config::{Config, ConfigError},
         layout::Layout,
         options::Options,
+        workspaces::Workspaces,
     },
 };
 use clap::{Args, IntoApp};
@@ -355,6 +356,7 @@ impl Setup {
             } else {
                 None
             };
+        let config = Setup::apply_workspace(cli_config_options.as_ref(), config, cli_args)?;
         let (layout, mut config) =
             Setup::parse_layout_and_override_config(cli_config_options.as_ref(), config, cli_args)?;
         let config_options = match cli_config_options {
@@ -643,6 +645,53 @@ impl Setup {
         // that needs to take precedence
         Layout::from_path_or_default(chosen_layout.as_ref(), layout_dir.clone(), config)
     }
+    /// Workspaces are defined next to the configuration file, with `config` as the base their
+    /// keybinds are applied to
+    pub fn workspaces_from_cli_args(
+        cli_args: &CliArgs,
+        config: &Config,
+    ) -> Result<Workspaces, ConfigError> {
+        let config_dir = cli_args
+            .config
+            .as_ref()
+            .and_then(|config_file| config_file.parent().map(|p| p.to_path_buf()))
+            .or_else(|| cli_args.config_dir.clone())
+            .or_else(find_default_config_dir);
+        Workspaces::from_config_dir(config_dir.as_deref(), config)
+    }
+    fn apply_workspace(
+        cli_config_options: Option<&Options>,
+        mut config: Config,
+        cli_args: &CliArgs,
+    ) -> Result<Config, ConfigError> {
+        let attach_options_workspace = match &cli_args.command {
+            Some(Command::Sessions(Sessions::Attach {
+                options: Some(options),
+                ..
+            })) => match options.as_ref() {
+                SessionCommand::Options(options) => options.options.workspace.clone(),
+            },
+            _ => None,
+        };
+        let workspace_name = cli_config_options
+            .and_then(|cli_options| cli_options.workspace.clone())
+            .or(attach_options_workspace)
+            .or_else(|| config.options.workspace.clone());
+        let workspace_name = match workspace_name {
+            Some(workspace_name) => workspace_name,
+            None => return Ok(config),
+        };
+        let workspaces = Setup::workspaces_from_cli_args(cli_args, &config)?;
+        match workspaces.get(&workspace_name) {
+            Some(workspace) => {
+                workspace.apply_to_config(&mut config);
+                Ok(config)
+            },
+            None => Err(ConfigError::Std(
+                format!("Workspace not found: {}", workspace_name).into(),
+            )),
+        }
+    }
     fn handle_setup_commands(cli_args: &CliArgs) {
         if let Some(Command::Setup(ref setup)) = &cli_args.command {
             setup.from_cli().map_or_else(
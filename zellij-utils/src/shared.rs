@@ -33,6 +33,13 @@ pub fn ansi_len(s: &str) -> usize {
     from_utf8(&strip(s).unwrap()).unwrap().width()
 }
 
+pub fn strip_ansi_codes(bytes: &[u8]) -> String {
+    match strip(bytes) {
+        Ok(stripped) => String::from_utf8_lossy(&stripped).to_string(),
+        Err(_) => String::from_utf8_lossy(bytes).to_string(),
+    }
+}
+
 pub fn adjust_to_size(s: &str, rows: usize, columns: usize) -> String {
     s.lines()
         .map(|l| {